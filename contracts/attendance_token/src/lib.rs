@@ -0,0 +1,321 @@
+//! Soulbound PSP34 collection of InkTix attendance tokens.
+//!
+//! The InkTix contract mints an `AttendanceToken` each time a ticket NFT is
+//! used or checked in. This contract is deployed alongside it as a dedicated
+//! PSP34 collection, so wallets and indexers read attendance tokens through the
+//! standard `PSP34`, `PSP34Metadata` and `PSP34Enumerable` selectors. Only the
+//! minter set at instantiation (the InkTix contract) can mint, using the token
+//! IDs InkTix allocates. Attendance tokens are soulbound: approvals and
+//! transfers always fail.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+use ink::prelude::string::String;
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+/// PSP34 token identifier
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub enum Id {
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    Bytes(Vec<u8>),
+}
+
+/// PSP34 error codes
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+pub enum PSP34Error {
+    /// Contract-specific failure, e.g. a soulbound token or a caller that is not the minter
+    Custom(String),
+    SelfApprove,
+    NotApproved,
+    TokenExists,
+    TokenNotExists,
+    SafeTransferCheckFailed(String),
+}
+
+/// PSP34 core interface
+#[ink::trait_definition]
+pub trait PSP34 {
+    /// Identifier of the collection
+    #[ink(message)]
+    fn collection_id(&self) -> Id;
+
+    /// Number of tokens held by `owner`
+    #[ink(message)]
+    fn balance_of(&self, owner: AccountId) -> u32;
+
+    /// Current owner of a token
+    #[ink(message)]
+    fn owner_of(&self, id: Id) -> Option<AccountId>;
+
+    /// Whether `operator` may move `owner`'s token `id`, or all their tokens when `id` is `None`
+    #[ink(message)]
+    fn allowance(&self, owner: AccountId, operator: AccountId, id: Option<Id>) -> bool;
+
+    /// Approve or revoke `operator` for one of the caller's tokens, or for all of them
+    #[ink(message)]
+    fn approve(&mut self, operator: AccountId, id: Option<Id>, approved: bool) -> Result<(), PSP34Error>;
+
+    /// Transfer a token owned by, or approved to, the caller
+    #[ink(message)]
+    fn transfer(&mut self, to: AccountId, id: Id, data: Vec<u8>) -> Result<(), PSP34Error>;
+
+    /// Number of tokens ever minted
+    #[ink(message)]
+    fn total_supply(&self) -> u128;
+}
+
+/// PSP34 metadata extension
+#[ink::trait_definition]
+pub trait PSP34Metadata {
+    /// Attribute `key` of a token, or of the collection when `id` is the collection ID
+    #[ink(message)]
+    fn get_attribute(&self, id: Id, key: Vec<u8>) -> Option<Vec<u8>>;
+}
+
+/// PSP34 enumerable extension
+#[ink::trait_definition]
+pub trait PSP34Enumerable {
+    /// The `index`-th token held by `owner`
+    #[ink(message)]
+    fn owners_token_by_index(&self, owner: AccountId, index: u128) -> Result<Id, PSP34Error>;
+
+    /// The `index`-th token of the collection
+    #[ink(message)]
+    fn token_by_index(&self, index: u128) -> Result<Id, PSP34Error>;
+}
+
+#[ink::contract]
+pub mod attendance_token {
+    use super::{Id, PSP34Enumerable, PSP34Error, PSP34Metadata, PSP34};
+    use ink::prelude::string::ToString;
+    use ink::prelude::vec::Vec;
+    use ink::storage::Mapping;
+
+    /// Proof that the holder attended an event
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Attendance {
+        pub owner: AccountId,
+        pub event_id: u32,
+        pub ticket_id: u64,
+        pub attended_at: u64,
+    }
+
+    /// Soulbound attendance token collection
+    #[ink(storage)]
+    pub struct AttendanceToken {
+        /// Only account allowed to mint: the InkTix contract
+        minter: AccountId,
+        tokens: Mapping<u64, Attendance>,
+        /// Token IDs in mint order
+        minted: Vec<u64>,
+        owned_tokens: Mapping<AccountId, Vec<u64>>,
+    }
+
+    /// Emitted when a token is minted; attendance tokens never move afterwards
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        pub from: Option<AccountId>,
+        #[ink(topic)]
+        pub to: Option<AccountId>,
+        #[ink(topic)]
+        pub id: Id,
+    }
+
+    impl AttendanceToken {
+        /// Create the collection, minted into by `minter`
+        #[ink(constructor)]
+        pub fn new(minter: AccountId) -> Self {
+            Self {
+                minter,
+                tokens: Mapping::default(),
+                minted: Vec::new(),
+                owned_tokens: Mapping::default(),
+            }
+        }
+
+        /// Mint attendance token `token_id` to `to` (minter only)
+        #[ink(message)]
+        pub fn mint_attendance(
+            &mut self,
+            to: AccountId,
+            token_id: u64,
+            event_id: u32,
+            ticket_id: u64,
+            attended_at: u64,
+        ) -> Result<(), PSP34Error> {
+            if self.env().caller() != self.minter {
+                return Err(PSP34Error::Custom("Only the minter can mint attendance tokens".to_string()));
+            }
+            if self.tokens.contains(token_id) {
+                return Err(PSP34Error::TokenExists);
+            }
+            self.tokens.insert(token_id, &Attendance { owner: to, event_id, ticket_id, attended_at });
+            self.minted.push(token_id);
+            let mut owned = self.owned_tokens.get(to).unwrap_or_default();
+            owned.push(token_id);
+            self.owned_tokens.insert(to, &owned);
+            self.env().emit_event(Transfer { from: None, to: Some(to), id: Id::U64(token_id) });
+            Ok(())
+        }
+
+        /// Account allowed to mint
+        #[ink(message)]
+        pub fn minter(&self) -> AccountId {
+            self.minter
+        }
+
+        /// Attendance record behind a token
+        #[ink(message)]
+        pub fn get_attendance(&self, id: Id) -> Option<Attendance> {
+            Self::token_id(&id).and_then(|token_id| self.tokens.get(token_id))
+        }
+
+        /// Convert a PSP34 `Id` into a token ID
+        fn token_id(id: &Id) -> Option<u64> {
+            match id {
+                Id::U8(n) => Some(*n as u64),
+                Id::U16(n) => Some(*n as u64),
+                Id::U32(n) => Some(*n as u64),
+                Id::U64(n) => Some(*n),
+                Id::U128(n) => u64::try_from(*n).ok(),
+                Id::Bytes(_) => None,
+            }
+        }
+
+        fn soulbound() -> Result<(), PSP34Error> {
+            Err(PSP34Error::Custom("Attendance tokens are soulbound".to_string()))
+        }
+    }
+
+    impl PSP34 for AttendanceToken {
+        #[ink(message)]
+        fn collection_id(&self) -> Id {
+            Id::Bytes(b"inktix:attendance".to_vec())
+        }
+
+        #[ink(message)]
+        fn balance_of(&self, owner: AccountId) -> u32 {
+            self.owned_tokens.get(owner).unwrap_or_default().len() as u32
+        }
+
+        #[ink(message)]
+        fn owner_of(&self, id: Id) -> Option<AccountId> {
+            self.get_attendance(id).map(|attendance| attendance.owner)
+        }
+
+        #[ink(message)]
+        fn allowance(&self, _owner: AccountId, _operator: AccountId, _id: Option<Id>) -> bool {
+            false
+        }
+
+        /// Attendance tokens are soulbound, so approvals always fail
+        #[ink(message)]
+        fn approve(&mut self, _operator: AccountId, _id: Option<Id>, _approved: bool) -> Result<(), PSP34Error> {
+            Self::soulbound()
+        }
+
+        /// Attendance tokens are soulbound, so transfers always fail
+        #[ink(message)]
+        fn transfer(&mut self, _to: AccountId, _id: Id, _data: Vec<u8>) -> Result<(), PSP34Error> {
+            Self::soulbound()
+        }
+
+        #[ink(message)]
+        fn total_supply(&self) -> u128 {
+            self.minted.len() as u128
+        }
+    }
+
+    impl PSP34Metadata for AttendanceToken {
+        /// The collection has `name` and `symbol`; tokens have `event_id`, `ticket_id` and `attended_at`
+        #[ink(message)]
+        fn get_attribute(&self, id: Id, key: Vec<u8>) -> Option<Vec<u8>> {
+            if id == self.collection_id() {
+                return match key.as_slice() {
+                    b"name" => Some(b"InkTix Attendance".to_vec()),
+                    b"symbol" => Some(b"POAP".to_vec()),
+                    _ => None,
+                };
+            }
+
+            let attendance = self.get_attendance(id)?;
+            let value = match key.as_slice() {
+                b"event_id" => attendance.event_id.to_string(),
+                b"ticket_id" => attendance.ticket_id.to_string(),
+                b"attended_at" => attendance.attended_at.to_string(),
+                _ => return None,
+            };
+            Some(value.into_bytes())
+        }
+    }
+
+    impl PSP34Enumerable for AttendanceToken {
+        #[ink(message)]
+        fn owners_token_by_index(&self, owner: AccountId, index: u128) -> Result<Id, PSP34Error> {
+            let tokens = self.owned_tokens.get(owner).unwrap_or_default();
+            usize::try_from(index).ok()
+                .and_then(|index| tokens.get(index))
+                .map(|token_id| Id::U64(*token_id))
+                .ok_or(PSP34Error::TokenNotExists)
+        }
+
+        #[ink(message)]
+        fn token_by_index(&self, index: u128) -> Result<Id, PSP34Error> {
+            usize::try_from(index).ok()
+                .and_then(|index| self.minted.get(index))
+                .map(|token_id| Id::U64(*token_id))
+                .ok_or(PSP34Error::TokenNotExists)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn set_caller(caller: AccountId) {
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+        }
+
+        #[ink::test]
+        fn test_only_the_minter_mints_soulbound_tokens() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut collection = AttendanceToken::new(accounts.alice);
+            set_caller(accounts.bob);
+            assert_eq!(
+                collection.mint_attendance(accounts.bob, 7, 1, 3, 1_000),
+                Err(PSP34Error::Custom("Only the minter can mint attendance tokens".to_string()))
+            );
+
+            set_caller(accounts.alice);
+            collection.mint_attendance(accounts.bob, 7, 1, 3, 1_000).unwrap();
+            assert_eq!(collection.mint_attendance(accounts.charlie, 7, 1, 4, 1_000), Err(PSP34Error::TokenExists));
+            let id = Id::U64(7);
+            assert_eq!(collection.balance_of(accounts.bob), 1);
+            assert_eq!(collection.total_supply(), 1);
+            assert_eq!(collection.owner_of(id.clone()), Some(accounts.bob));
+            assert_eq!(collection.token_by_index(0), Ok(id.clone()));
+            assert_eq!(collection.token_by_index(1), Err(PSP34Error::TokenNotExists));
+            assert_eq!(collection.owners_token_by_index(accounts.bob, 0), Ok(id.clone()));
+            assert_eq!(collection.get_attribute(id.clone(), b"event_id".to_vec()), Some(b"1".to_vec()));
+            assert_eq!(collection.get_attribute(collection.collection_id(), b"symbol".to_vec()), Some(b"POAP".to_vec()));
+
+            set_caller(accounts.bob);
+            let soulbound = Err(PSP34Error::Custom("Attendance tokens are soulbound".to_string()));
+            assert_eq!(collection.transfer(accounts.charlie, id.clone(), Vec::new()), soulbound);
+            assert_eq!(collection.approve(accounts.charlie, Some(id.clone()), true), soulbound);
+            assert!(!collection.allowance(accounts.bob, accounts.charlie, None));
+            assert_eq!(collection.owner_of(id), Some(accounts.bob));
+        }
+    }
+}
@@ -38,11 +38,13 @@ pub use types::*;
 pub mod inktix {
     use super::*;
     use crate::logic::core::{
-        anti_scalping, currency_management, event_management, nft_management, pricing, ticket_management, venue_management,
+        access_control, anti_scalping, attestations, auctions, ballots, currency_management, escrow, event_management, group_orders, nft_management, pricing,
+        price_locks, pricing_policy, psp34, purchase_limits, rate_oracle, sale_windows, seat_holds, seat_inventory, ticket_management, venue_management,
+        waitlist,
     };
     #[cfg(feature = "sports")]
     use crate::logic::sports::{
-        advanced_team_loyalty, cross_chain_management, fantasy_sports_management,
+        advanced_team_loyalty, cross_chain_management, fantasy_sports_management, game_result_oracle,
         season_pass_management, team_management, analytics,
     };
     #[cfg(feature = "concert")]
@@ -50,6 +52,7 @@ pub mod inktix {
     use crate::storage::contract_storage::InkTixStorage;
     use crate::types::*;
     use crate::types::core::venue;
+    use ink::env::call::{build_call, ExecutionInput, Selector};
     use ink::prelude::string::String;
     use ink::prelude::string::ToString;
     use ink::prelude::vec::Vec;
@@ -60,6 +63,505 @@ pub mod inktix {
         storage: InkTixStorage,
     }
 
+    // =============================================================================
+    // EVENTS
+    // =============================================================================
+
+    /// Emitted when the owner proposes a new owner
+    #[ink(event)]
+    pub struct OwnershipTransferStarted {
+        #[ink(topic)]
+        pub previous_owner: AccountId,
+        #[ink(topic)]
+        pub new_owner: AccountId,
+    }
+
+    /// Emitted when a proposed owner accepts ownership
+    #[ink(event)]
+    pub struct OwnershipTransferred {
+        #[ink(topic)]
+        pub previous_owner: AccountId,
+        #[ink(topic)]
+        pub new_owner: AccountId,
+    }
+
+    /// Emitted when a role is granted
+    #[ink(event)]
+    pub struct RoleGranted {
+        #[ink(topic)]
+        pub account: AccountId,
+        #[ink(topic)]
+        pub role: Role,
+        pub scope: RoleScope,
+        pub sender: AccountId,
+    }
+
+    /// Emitted when a role is revoked or renounced
+    #[ink(event)]
+    pub struct RoleRevoked {
+        #[ink(topic)]
+        pub account: AccountId,
+        #[ink(topic)]
+        pub role: Role,
+        pub scope: RoleScope,
+        pub sender: AccountId,
+    }
+
+    /// Emitted when a venue is registered
+    #[ink(event)]
+    pub struct VenueRegistered {
+        #[ink(topic)]
+        pub venue_id: u32,
+        pub name: String,
+        pub capacity: u32,
+    }
+
+    /// Emitted when an event is created
+    #[ink(event)]
+    pub struct EventCreated {
+        #[ink(topic)]
+        pub event_id: u32,
+        #[ink(topic)]
+        pub organizer: AccountId,
+        #[ink(topic)]
+        pub venue_id: u32,
+        pub date: u64,
+        pub capacity: u32,
+        pub base_price: u128,
+    }
+
+    /// Emitted when an event's lifecycle status changes
+    #[ink(event)]
+    pub struct EventStatusUpdated {
+        #[ink(topic)]
+        pub event_id: u32,
+        pub status: EventStatus,
+    }
+
+    /// Emitted when an organizer withdraws a completed event's escrowed proceeds
+    #[ink(event)]
+    pub struct EventProceedsWithdrawn {
+        #[ink(topic)]
+        pub event_id: u32,
+        #[ink(topic)]
+        pub organizer: AccountId,
+        pub amount: u128,
+        pub currency: CurrencyId,
+    }
+
+    /// Emitted when a ticket is purchased
+    #[ink(event)]
+    pub struct TicketPurchased {
+        #[ink(topic)]
+        pub event_id: u32,
+        #[ink(topic)]
+        pub buyer: AccountId,
+        pub ticket_id: u64,
+        pub price: u128,
+        pub currency: CurrencyId,
+    }
+
+    /// Emitted when a seat block is put up for auction
+    #[ink(event)]
+    pub struct AuctionCreated {
+        #[ink(topic)]
+        pub auction_id: u32,
+        #[ink(topic)]
+        pub event_id: u32,
+        pub block_index: u32,
+        pub format: AuctionFormat,
+    }
+
+    /// Emitted when a sealed bid is committed
+    #[ink(event)]
+    pub struct AuctionBidCommitted {
+        #[ink(topic)]
+        pub auction_id: u32,
+        #[ink(topic)]
+        pub bidder: AccountId,
+        pub deposit: u128,
+    }
+
+    /// Emitted when an auction ends and its unsold seats return to posted-price sale
+    #[ink(event)]
+    pub struct AuctionSettled {
+        #[ink(topic)]
+        pub auction_id: u32,
+        pub seats_sold: u32,
+        pub clearing_price: Option<u128>,
+    }
+
+    /// Emitted when a ballot is drawn
+    #[ink(event)]
+    pub struct BallotDrawn {
+        #[ink(topic)]
+        pub ballot_id: u32,
+        #[ink(topic)]
+        pub event_id: u32,
+        pub draw_seed: [u8; 32],
+    }
+
+    /// Emitted when released tickets or a returned resale listing are offered to a waitlisted fan
+    #[ink(event)]
+    pub struct WaitlistOffered {
+        #[ink(topic)]
+        pub event_id: u32,
+        #[ink(topic)]
+        pub account: AccountId,
+        pub quantity: u32,
+        pub expires_at: u64,
+        pub listing_id: Option<u64>,
+    }
+
+    /// Emitted when a seat's price is locked for a buyer
+    #[ink(event)]
+    pub struct QuoteLocked {
+        #[ink(topic)]
+        pub quote_id: u64,
+        #[ink(topic)]
+        pub event_id: u32,
+        #[ink(topic)]
+        pub holder: AccountId,
+        pub price: u128,
+        pub expires_at: u64,
+    }
+
+    /// Emitted when seats are held for checkout
+    #[ink(event)]
+    pub struct SeatsHeld {
+        #[ink(topic)]
+        pub hold_id: u64,
+        #[ink(topic)]
+        pub event_id: u32,
+        #[ink(topic)]
+        pub holder: AccountId,
+        pub seat_count: u32,
+        pub expiry: HoldExpiry,
+    }
+
+    /// Emitted when an attester records an attestation
+    #[ink(event)]
+    pub struct AttestationRecorded {
+        #[ink(topic)]
+        pub account: AccountId,
+        pub kind: AttestationKind,
+        #[ink(topic)]
+        pub attester: AccountId,
+        pub expires_at: u64,
+    }
+
+    /// Emitted when an attestation is revoked
+    #[ink(event)]
+    pub struct AttestationRevoked {
+        #[ink(topic)]
+        pub account: AccountId,
+        pub kind: AttestationKind,
+        pub revoked_by: AccountId,
+    }
+
+    /// Emitted when an admin reviews an account's anti-scalping behavior profile
+    #[ink(event)]
+    pub struct BehaviorProfileReviewed {
+        #[ink(topic)]
+        pub account: AccountId,
+        pub status: BlacklistStatus,
+    }
+
+    /// Emitted when a group order reserves its seats
+    #[ink(event)]
+    pub struct GroupOrderCreated {
+        #[ink(topic)]
+        pub order_id: u32,
+        #[ink(topic)]
+        pub event_id: u32,
+        pub organizer: AccountId,
+        pub seats: u32,
+        pub discount_bps: u32,
+    }
+
+    /// Emitted when a group order completes or is cancelled
+    #[ink(event)]
+    pub struct GroupOrderClosed {
+        #[ink(topic)]
+        pub order_id: u32,
+        #[ink(topic)]
+        pub event_id: u32,
+        pub completed: bool,
+    }
+
+    /// Emitted when a seat hold is released before it lapses
+    #[ink(event)]
+    pub struct SeatHoldReleased {
+        #[ink(topic)]
+        pub hold_id: u64,
+        #[ink(topic)]
+        pub holder: AccountId,
+    }
+
+    /// Emitted when a ticket changes owner
+    #[ink(event)]
+    pub struct TicketTransferred {
+        #[ink(topic)]
+        pub ticket_id: u64,
+        #[ink(topic)]
+        pub from: AccountId,
+        #[ink(topic)]
+        pub to: AccountId,
+    }
+
+    /// Emitted when a ticket is listed on the resale marketplace
+    #[ink(event)]
+    pub struct TicketListedForResale {
+        #[ink(topic)]
+        pub listing_id: u64,
+        #[ink(topic)]
+        pub ticket_id: u64,
+        #[ink(topic)]
+        pub seller: AccountId,
+        pub price: u128,
+        pub currency: CurrencyId,
+    }
+
+    /// Emitted when a resale listing is bought
+    #[ink(event)]
+    pub struct ResaleListingPurchased {
+        #[ink(topic)]
+        pub listing_id: u64,
+        #[ink(topic)]
+        pub buyer: AccountId,
+        #[ink(topic)]
+        pub seller: AccountId,
+        pub ticket_id: u64,
+        pub price: u128,
+        pub fee: u128,
+    }
+
+    /// Emitted when a seller cancels a resale listing
+    #[ink(event)]
+    pub struct ResaleListingCancelled {
+        #[ink(topic)]
+        pub listing_id: u64,
+        #[ink(topic)]
+        pub seller: AccountId,
+    }
+
+    /// Emitted when a ticket holder is refunded for a cancelled event
+    #[ink(event)]
+    pub struct RefundClaimed {
+        #[ink(topic)]
+        pub ticket_id: u64,
+        #[ink(topic)]
+        pub event_id: u32,
+        #[ink(topic)]
+        pub owner: AccountId,
+        pub amount: u128,
+        pub currency: CurrencyId,
+    }
+
+    /// Emitted when an account withdraws refunds credited to its balance
+    #[ink(event)]
+    pub struct RefundWithdrawn {
+        #[ink(topic)]
+        pub account: AccountId,
+        pub amount: u128,
+        pub currency: CurrencyId,
+    }
+
+    /// Emitted when a ticket NFT is minted
+    #[ink(event)]
+    pub struct TicketNftMinted {
+        #[ink(topic)]
+        pub token_id: u64,
+        #[ink(topic)]
+        pub event_id: u32,
+        #[ink(topic)]
+        pub owner: AccountId,
+        pub ticket_id: u64,
+    }
+
+    /// Emitted when an admin manually marks a ticket NFT used and an attendance token is issued
+    #[ink(event)]
+    pub struct TicketNftUsed {
+        #[ink(topic)]
+        pub token_id: u64,
+        #[ink(topic)]
+        pub event_id: u32,
+        #[ink(topic)]
+        pub owner: AccountId,
+        pub admin: AccountId,
+        pub attendance_token_id: u64,
+    }
+
+    /// Emitted when the attendance token contract is set or cleared
+    #[ink(event)]
+    pub struct AttendanceTokenContractUpdated {
+        pub contract: Option<AccountId>,
+    }
+
+    /// Emitted when a gate scanner checks a holder in with a signed challenge
+    #[ink(event)]
+    pub struct TicketCheckedIn {
+        #[ink(topic)]
+        pub token_id: u64,
+        #[ink(topic)]
+        pub event_id: u32,
+        #[ink(topic)]
+        pub holder: AccountId,
+        pub scanner: AccountId,
+        pub attendance_token_id: u64,
+    }
+
+    /// Emitted when a ticket NFT changes owner
+    #[ink(event)]
+    pub struct TicketNftTransferred {
+        #[ink(topic)]
+        pub token_id: u64,
+        #[ink(topic)]
+        pub from: AccountId,
+        #[ink(topic)]
+        pub to: AccountId,
+    }
+
+    /// PSP34: emitted when a ticket NFT is minted (no `from`) or changes owner
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        pub from: Option<AccountId>,
+        #[ink(topic)]
+        pub to: Option<AccountId>,
+        #[ink(topic)]
+        pub id: Id,
+    }
+
+    /// PSP34: emitted when an operator is approved or revoked for one token or all of an owner's tokens
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        pub owner: AccountId,
+        #[ink(topic)]
+        pub operator: AccountId,
+        #[ink(topic)]
+        pub id: Option<Id>,
+        pub approved: bool,
+    }
+
+    /// Emitted when a season pass is purchased
+    #[ink(event)]
+    pub struct SeasonPassPurchased {
+        #[ink(topic)]
+        pub pass_id: u32,
+        #[ink(topic)]
+        pub team_id: u32,
+        #[ink(topic)]
+        pub owner: AccountId,
+        pub package_id: u32,
+    }
+
+    /// Emitted when a season pass is renewed into the next season's package
+    #[ink(event)]
+    pub struct SeasonPassRenewed {
+        #[ink(topic)]
+        pub pass_id: u32,
+        #[ink(topic)]
+        pub renewed_pass_id: u32,
+        #[ink(topic)]
+        pub owner: AccountId,
+        pub price: u128,
+    }
+
+    /// Emitted when a season pass is upgraded to a higher tier
+    #[ink(event)]
+    pub struct SeasonPassUpgraded {
+        #[ink(topic)]
+        pub pass_id: u32,
+        #[ink(topic)]
+        pub package_id: u32,
+        pub difference: u128,
+    }
+
+    /// Emitted when a season pass package organizer withdraws its proceeds
+    #[ink(event)]
+    pub struct SeasonPassProceedsWithdrawn {
+        #[ink(topic)]
+        pub package_id: u32,
+        #[ink(topic)]
+        pub organizer: AccountId,
+        pub amount: u128,
+        pub currency: CurrencyId,
+    }
+
+    /// Emitted when a season pass is redeemed for an event ticket
+    #[ink(event)]
+    pub struct SeasonPassUsed {
+        #[ink(topic)]
+        pub pass_id: u32,
+        #[ink(topic)]
+        pub event_id: u32,
+        #[ink(topic)]
+        pub owner: AccountId,
+        pub ticket_id: u64,
+    }
+
+    /// Emitted when tokens are staked on a team
+    #[ink(event)]
+    pub struct TeamStaked {
+        #[ink(topic)]
+        pub stake_id: u32,
+        #[ink(topic)]
+        pub team_id: u32,
+        #[ink(topic)]
+        pub staker: AccountId,
+        pub amount: u128,
+        pub currency: CurrencyId,
+    }
+
+    /// Emitted when a result reporter submits the final score of a sports event
+    #[ink(event)]
+    pub struct GameResultReported {
+        #[ink(topic)]
+        pub event_id: u32,
+        #[ink(topic)]
+        pub reporter: AccountId,
+        pub home_score: u32,
+        pub away_score: u32,
+    }
+
+    /// Emitted when a quorum of reporters agrees on the final score of a sports event
+    #[ink(event)]
+    pub struct GameResultFinalized {
+        #[ink(topic)]
+        pub event_id: u32,
+        pub home_score: u32,
+        pub away_score: u32,
+    }
+
+    /// Emitted when a feeder's report changes a currency's effective exchange rate
+    #[ink(event)]
+    pub struct CurrencyRateUpdated {
+        #[ink(topic)]
+        pub currency: CurrencyId,
+        pub rate: u128,
+    }
+
+    /// Emitted when the PSP22 token contract for a currency is registered or cleared
+    #[ink(event)]
+    pub struct CurrencyTokenUpdated {
+        #[ink(topic)]
+        pub currency: CurrencyId,
+        pub token: Option<AccountId>,
+    }
+
+    /// Emitted when a venue's or an event's pricing policy is set or cleared
+    #[ink(event)]
+    pub struct PricingPolicyUpdated {
+        #[ink(topic)]
+        pub venue_id: Option<u32>,
+        #[ink(topic)]
+        pub event_id: Option<u32>,
+        /// Version assigned to the new policy, 0 when the policy was cleared
+        pub version: u32,
+    }
+
     impl InkTix {
         /// Initialize the contract with default settings
         #[ink(constructor)]
@@ -76,14 +578,199 @@ pub mod inktix {
             self.storage.owner
         }
 
-        /// Check if caller is the owner
-        fn ensure_owner(&self) -> Result<(), String> {
-            if self.env().caller() != self.storage.owner {
-                return Err("Only the owner can call this function".to_string());
+        /// Check that the caller holds a role for a scope (the owner holds every role)
+        fn ensure_role(&self, role: Role, scope: RoleScope) -> Result<(), String> {
+            access_control::AccessControl::ensure_role(&self.storage, self.env().caller(), role, scope)
+        }
+
+        /// Emit `EventCreated` for a freshly stored event
+        fn emit_event_created(&self, event_id: u32) {
+            if let Some(event) = self.storage.events.get(event_id) {
+                self.env().emit_event(EventCreated {
+                    event_id, organizer: event.organizer, venue_id: event.venue_id,
+                    date: event.date, capacity: event.capacity, base_price: event.base_price,
+                });
+            }
+        }
+
+        /// Transfer native value out of the contract, skipping zero amounts
+        fn pay_out(&self, to: AccountId, amount: u128) -> Result<(), String> {
+            if amount == 0 {
+                return Ok(());
+            }
+            self.env().transfer(to, amount).map_err(|_| InkTixError::TransferFailed.into())
+        }
+
+        /// Pay out an amount in any currency: native value for DOT, otherwise the currency's PSP22 token
+        fn pay_out_in(&self, currency: CurrencyId, to: AccountId, amount: u128) -> Result<(), String> {
+            if currency == CurrencyId::DOT {
+                return self.pay_out(to, amount);
+            }
+            if amount == 0 {
+                return Ok(());
+            }
+            let result = build_call::<ink::env::DefaultEnvironment>()
+                .call(self.currency_token(currency)?)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP22::transfer")))
+                        .push_arg(to)
+                        .push_arg(amount)
+                        .push_arg(Vec::<u8>::new()),
+                )
+                .returns::<Result<(), PSP22Error>>()
+                .try_invoke();
+            match result {
+                Ok(Ok(Ok(()))) => Ok(()),
+                _ => Err(InkTixError::TransferFailed.into()),
+            }
+        }
+
+        /// Offer an event's free inventory down its waitlist, refunding the deposits of fans whose offers lapsed
+        fn run_waitlist(&mut self, event_id: u32) -> Result<(), String> {
+            let offered = waitlist::Waitlists::process(&mut self.storage, event_id)?;
+            let entries = self.storage.event_waitlists.get(event_id).unwrap_or_default();
+            for entry in entries.iter().filter(|entry| offered.contains(&entry.account)) {
+                if let Some(offer) = entry.offer {
+                    self.env().emit_event(WaitlistOffered {
+                        event_id, account: entry.account, quantity: offer.quantity, expires_at: offer.expires_at,
+                        listing_id: offer.listing_id,
+                    });
+                }
+            }
+            Ok(())
+        }
+
+        /// Settle a purchase charge: refund native overpayment, or pull a token charge and refund all native value
+        fn settle_payment(&self, payer: AccountId, currency: CurrencyId, charge: u128, payment: u128) -> Result<(), String> {
+            if currency == CurrencyId::DOT {
+                return self.pay_out(payer, payment.saturating_sub(charge));
+            }
+            let result = build_call::<ink::env::DefaultEnvironment>()
+                .call(self.currency_token(currency)?)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP22::transfer_from")))
+                        .push_arg(payer)
+                        .push_arg(self.env().account_id())
+                        .push_arg(charge)
+                        .push_arg(Vec::<u8>::new()),
+                )
+                .returns::<Result<(), PSP22Error>>()
+                .try_invoke();
+            match result {
+                Ok(Ok(Ok(()))) => self.pay_out(payer, payment),
+                _ => Err(InkTixError::TransferFailed.into()),
+            }
+        }
+
+        /// Mint an attendance token into the attendance token contract, if one is configured
+        fn mint_attendance_token(&self, attendance_token_id: u64) -> Result<(), String> {
+            let Some(contract) = self.storage.attendance_token_contract else { return Ok(()) };
+            let token = self.storage.attendance_tokens.get(attendance_token_id).ok_or("Attendance token not found")?;
+            let result = build_call::<ink::env::DefaultEnvironment>()
+                .call(contract)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("mint_attendance")))
+                        .push_arg(token.owner)
+                        .push_arg(token.token_id)
+                        .push_arg(token.event_id)
+                        .push_arg(token.ticket_id)
+                        .push_arg(token.attended_at),
+                )
+                .returns::<Result<(), PSP34Error>>()
+                .try_invoke();
+            match result {
+                Ok(Ok(Ok(()))) => Ok(()),
+                _ => Err("Attendance token mint failed".to_string()),
+            }
+        }
+
+        /// Registered PSP22 token contract for a currency
+        fn currency_token(&self, currency: CurrencyId) -> Result<AccountId, String> {
+            self.storage.currency_tokens.get(currency)
+                .ok_or_else(|| "No token contract registered for this currency".to_string())
+        }
+
+        /// Token ID and current owner of a ticket's NFT, if one was minted
+        fn ticket_nft_owner(&self, ticket_id: u64) -> Option<(u64, AccountId)> {
+            let token_id = self.storage.ticket_to_nft.get(ticket_id)?;
+            self.storage.nft_tickets.get(token_id).map(|nft| (token_id, nft.owner))
+        }
+
+        /// Emit the NFT transfer events for a ticket NFT that moved along with its ticket
+        fn emit_nft_moved(&self, nft_before: Option<(u64, AccountId)>, to: AccountId) {
+            if let Some((token_id, from)) = nft_before {
+                self.env().emit_event(TicketNftTransferred { token_id, from, to });
+                self.env().emit_event(Transfer { from: Some(from), to: Some(to), id: Id::U64(token_id) });
             }
+        }
+
+        // =============================================================================
+        // CORE: ACCESS CONTROL
+        // =============================================================================
+
+        /// Get the account proposed as the next owner, if any
+        #[ink(message)]
+        pub fn get_pending_owner(&self) -> Option<AccountId> {
+            self.storage.pending_owner
+        }
+
+        /// Propose a new owner, who must accept before the transfer takes effect (owner only)
+        #[ink(message)]
+        pub fn transfer_ownership(&mut self, new_owner: AccountId) -> Result<(), String> {
+            let caller = self.env().caller();
+            access_control::AccessControl::transfer_ownership(&mut self.storage, caller, new_owner)?;
+            self.env().emit_event(OwnershipTransferStarted { previous_owner: caller, new_owner });
+            Ok(())
+        }
+
+        /// Accept a pending ownership transfer (pending owner only)
+        #[ink(message)]
+        pub fn accept_ownership(&mut self) -> Result<(), String> {
+            let caller = self.env().caller();
+            let previous_owner = access_control::AccessControl::accept_ownership(&mut self.storage, caller)?;
+            self.env().emit_event(OwnershipTransferred { previous_owner, new_owner: caller });
+            Ok(())
+        }
+
+        /// Grant a role within a scope (admins only; Admin itself is granted by the owner)
+        #[ink(message)]
+        pub fn grant_role(&mut self, account: AccountId, role: Role, scope: RoleScope) -> Result<(), String> {
+            let caller = self.env().caller();
+            access_control::AccessControl::grant_role(&mut self.storage, caller, account, role, scope)?;
+            self.env().emit_event(RoleGranted { account, role, scope, sender: caller });
+            Ok(())
+        }
+
+        /// Revoke a role within a scope (admins only; Admin itself is revoked by the owner)
+        #[ink(message)]
+        pub fn revoke_role(&mut self, account: AccountId, role: Role, scope: RoleScope) -> Result<(), String> {
+            let caller = self.env().caller();
+            access_control::AccessControl::revoke_role(&mut self.storage, caller, account, role, scope)?;
+            self.env().emit_event(RoleRevoked { account, role, scope, sender: caller });
+            Ok(())
+        }
+
+        /// Give up one of the caller's own roles
+        #[ink(message)]
+        pub fn renounce_role(&mut self, role: Role, scope: RoleScope) -> Result<(), String> {
+            let caller = self.env().caller();
+            access_control::AccessControl::renounce_role(&mut self.storage, caller, role, scope)?;
+            self.env().emit_event(RoleRevoked { account: caller, role, scope, sender: caller });
             Ok(())
         }
 
+        /// Check whether an account holds a role for a scope
+        #[ink(message)]
+        pub fn has_role(&self, account: AccountId, role: Role, scope: RoleScope) -> bool {
+            access_control::AccessControl::has_role(&self.storage, account, role, scope)
+        }
+
+        /// Get the roles explicitly granted to an account
+        #[ink(message)]
+        pub fn get_roles(&self, account: AccountId) -> Vec<RoleGrant> {
+            self.storage.account_roles.get(account).unwrap_or_default()
+        }
+
         // =============================================================================
         // CORE: VENUE MANAGEMENT
         // =============================================================================
@@ -93,8 +780,12 @@ pub mod inktix {
         pub fn register_venue(
             &mut self, name: String, capacity: u32, location: String, venue_type: VenueType,
         ) -> Result<u32, String> {
-            self.ensure_owner()?;
-            venue_management::VenueManagement::register_venue(&mut self.storage, name, capacity, location, venue_type)
+            self.ensure_role(Role::VenueOperator, RoleScope::Global)?;
+            let venue_id = venue_management::VenueManagement::register_venue(
+                &mut self.storage, name.clone(), capacity, location, venue_type,
+            )?;
+            self.env().emit_event(VenueRegistered { venue_id, name, capacity });
+            Ok(venue_id)
         }
 
         /// Get venue information
@@ -112,10 +803,17 @@ pub mod inktix {
         /// Update venue capacity
         #[ink(message)]
         pub fn update_venue_capacity(&mut self, venue_id: u32, new_capacity: u32) -> Result<(), String> {
-            self.ensure_owner()?;
+            self.ensure_role(Role::VenueOperator, RoleScope::Venue(venue_id))?;
             venue_management::VenueManagement::update_venue_capacity(&mut self.storage, venue_id, new_capacity)
         }
 
+        /// Add a seat pricing tier to a venue, returning its tier id (venue operators only)
+        #[ink(message)]
+        pub fn add_venue_pricing_tier(&mut self, venue_id: u32, tier: VenuePricingTier) -> Result<u32, String> {
+            self.ensure_role(Role::VenueOperator, RoleScope::Venue(venue_id))?;
+            venue_management::VenueManagement::add_pricing_tier(&mut self.storage, venue_id, tier)
+        }
+
         // =============================================================================
         // CORE: EVENT MANAGEMENT
         // =============================================================================
@@ -126,16 +824,19 @@ pub mod inktix {
             &mut self, name: String, venue_id: u32, date: u64, capacity: u32,
             base_price: u128, category: EventCategory,
         ) -> Result<u32, String> {
-            self.ensure_owner()?;
-            event_management::EventManagement::create_event(
-                &mut self.storage, name, venue_id, date, capacity, base_price, category,
-            )
+            self.ensure_role(Role::EventOrganizer, RoleScope::Venue(venue_id))?;
+            let caller = self.env().caller();
+            let event_id = event_management::EventManagement::create_event(
+                &mut self.storage, caller, name, venue_id, date, capacity, base_price, category,
+            )?;
+            self.emit_event_created(event_id);
+            Ok(event_id)
         }
 
         /// Get event information
         #[ink(message)]
         pub fn get_event(&self, event_id: u32) -> Option<Event> {
-            self.storage.events.get(event_id)
+            event_management::EventManagement::get_event(&self.storage, event_id)
         }
 
         /// Get all events
@@ -147,487 +848,1770 @@ pub mod inktix {
         /// Update event status
         #[ink(message)]
         pub fn update_event_status(&mut self, event_id: u32, status: EventStatus) -> Result<(), String> {
-            self.ensure_owner()?;
-            event_management::EventManagement::update_event_status(&mut self.storage, event_id, status)
+            self.ensure_role(Role::EventOrganizer, RoleScope::Event(event_id))?;
+            event_management::EventManagement::update_event_status(&mut self.storage, event_id, status.clone())?;
+            #[cfg(feature = "sports")]
+            if status == EventStatus::Cancelled {
+                season_pass_management::SeasonPassManagement::refund_cancelled_game(&mut self.storage, event_id);
+            }
+            self.env().emit_event(EventStatusUpdated { event_id, status });
+            Ok(())
         }
 
         // =============================================================================
-        // CORE: TICKET MANAGEMENT
+        // CORE: SEAT INVENTORY
         // =============================================================================
 
-        /// Purchase a ticket
+        /// Set a venue's default seat layout, copied onto events created afterwards (venue operators only)
         #[ink(message)]
-        pub fn purchase_ticket(&mut self, event_id: u32, seat: Seat, currency: CurrencyId) -> Result<u64, String> {
-            let caller = self.env().caller();
-            ticket_management::TicketManagement::purchase_ticket(&mut self.storage, caller, event_id, seat, currency)
+        pub fn set_venue_seat_layout(&mut self, venue_id: u32, blocks: Vec<SeatBlock>) -> Result<(), String> {
+            self.ensure_role(Role::VenueOperator, RoleScope::Venue(venue_id))?;
+            seat_inventory::SeatInventory::set_venue_layout(&mut self.storage, venue_id, blocks)
         }
 
-        /// Get ticket information
+        /// Override an event's seat layout before any tickets are sold (event organizers only)
         #[ink(message)]
-        pub fn get_ticket(&self, ticket_id: u64) -> Option<Ticket> {
-            self.storage.tickets.get(ticket_id)
+        pub fn set_event_seat_layout(&mut self, event_id: u32, blocks: Vec<SeatBlock>) -> Result<(), String> {
+            self.ensure_role(Role::EventOrganizer, RoleScope::Event(event_id))?;
+            seat_inventory::SeatInventory::set_event_layout(&mut self.storage, event_id, blocks)
         }
 
-        /// Get user's tickets
+        /// Get an event's seat layout
         #[ink(message)]
-        pub fn get_user_tickets(&self, user: AccountId) -> Vec<u64> {
-            self.storage.user_tickets.get(user).unwrap_or_default()
+        pub fn get_event_seat_layout(&self, event_id: u32) -> Vec<SeatBlock> {
+            self.storage.event_seat_layouts.get(event_id).unwrap_or_default()
         }
 
-        /// Transfer ticket to another user
+        /// Get every seat in an event's layout with its availability
         #[ink(message)]
-        pub fn transfer_ticket(&mut self, ticket_id: u64, to: AccountId) -> Result<(), String> {
-            let caller = self.env().caller();
-            ticket_management::TicketManagement::transfer_ticket(&mut self.storage, caller, ticket_id, to)
+        pub fn get_seat_map(&self, event_id: u32) -> Vec<SeatAvailability> {
+            seat_inventory::SeatInventory::get_seat_map(&self.storage, event_id)
+        }
+
+        /// Get the availability of a single seat
+        #[ink(message)]
+        pub fn get_seat_status(&self, event_id: u32, section: String, row: String, seat_number: u32) -> SeatStatus {
+            seat_inventory::SeatInventory::seat_status(&self.storage, &(event_id, section, row, seat_number))
         }
 
-        /// Resell ticket
+        /// Hold seats while the caller pays, returning the hold ID
+        ///
+        /// Each seat's price is quoted now and honoured by `confirm_hold` until the
+        /// hold lapses after `duration`.
         #[ink(message)]
-        pub fn resell_ticket(&mut self, ticket_id: u64, price: u128, currency: CurrencyId) -> Result<(), String> {
+        pub fn hold_seats(&mut self, event_id: u32, seats: Vec<Seat>, duration: HoldDuration) -> Result<u64, String> {
             let caller = self.env().caller();
-            ticket_management::TicketManagement::resell_ticket(&mut self.storage, caller, ticket_id, price, currency)
+            let seat_count = seats.len() as u32;
+            let hold_id = seat_holds::SeatHolds::hold_seats(&mut self.storage, caller, event_id, seats, duration)?;
+            let expiry = self.storage.seat_holds.get(hold_id).map(|h| h.expiry).ok_or("Seat hold not found")?;
+            self.env().emit_event(SeatsHeld { hold_id, event_id, holder: caller, seat_count, expiry });
+            Ok(hold_id)
         }
 
-        // =============================================================================
-        // CORE: CURRENCY MANAGEMENT
-        // =============================================================================
+        /// Convert a live hold into tickets, paying the quoted total in `currency`
+        ///
+        /// DOT is paid as native value and any overpayment is refunded; other
+        /// currencies are pulled from the caller's PSP22 allowance. The payment is
+        /// held in the event's escrow.
+        #[ink(message, payable)]
+        pub fn confirm_hold(&mut self, hold_id: u64, currency: CurrencyId) -> Result<Vec<u64>, String> {
+            let caller = self.env().caller();
+            let payment = self.env().transferred_value();
+            let (ticket_ids, total) = seat_holds::SeatHolds::confirm_hold(
+                &mut self.storage, caller, hold_id, currency, payment,
+            )?;
+            self.settle_payment(caller, currency, total, payment)?;
+            for &ticket_id in &ticket_ids {
+                if let Some(ticket) = self.storage.tickets.get(ticket_id) {
+                    self.env().emit_event(TicketPurchased {
+                        event_id: ticket.event_id, buyer: caller, ticket_id, price: ticket.purchase_price, currency,
+                    });
+                }
+            }
+            Ok(ticket_ids)
+        }
 
-        /// Get all supported currency identifiers
+        /// Release one of the caller's seat holds early
         #[ink(message)]
-        pub fn get_supported_currencies(&self) -> Vec<CurrencyId> { self.storage.supported_currencies.clone() }
+        pub fn release_hold(&mut self, hold_id: u64) -> Result<(), String> {
+            let caller = self.env().caller();
+            seat_holds::SeatHolds::release_hold(&mut self.storage, caller, hold_id)?;
+            self.env().emit_event(SeatHoldReleased { hold_id, holder: caller });
+            let event_id = self.storage.seat_holds.get(hold_id).map(|h| h.event_id).ok_or("Seat hold not found")?;
+            self.run_waitlist(event_id)
+        }
 
-        /// Get the exchange rate for a currency
+        /// Get a seat hold
         #[ink(message)]
-        pub fn get_currency_rate(&self, currency: CurrencyId) -> Option<u128> { self.storage.currency_rates.get(currency) }
+        pub fn get_seat_hold(&self, hold_id: u64) -> Option<SeatHold> {
+            self.storage.seat_holds.get(hold_id)
+        }
 
-        /// Update the exchange rate for a currency
+        /// Get an account's live seat hold IDs
         #[ink(message)]
-        pub fn update_currency_rate(&mut self, currency: CurrencyId, rate: u128) -> Result<(), String> {
-            self.ensure_owner()?;
-            currency_management::CurrencyManagement::update_currency_rate(&mut self.storage, currency, rate)
+        pub fn get_active_seat_holds(&self, holder: AccountId) -> Vec<u64> {
+            seat_holds::SeatHolds::active_holds(&self.storage, holder)
         }
 
         // =============================================================================
-        // CORE: ANTI-SCALPING
+        // CORE: GROUP ORDERS
         // =============================================================================
 
-        /// Configure anti-scalping rules for an event
+        /// Set an event's group discount tiers (event organizers only)
         #[ink(message)]
-        pub fn configure_anti_scalping(&mut self, event_id: u32, config: AntiScalpingConfig) -> Result<(), String> {
-            self.ensure_owner()?;
-            anti_scalping::AntiScalping::configure_anti_scalping(&mut self.storage, event_id, config)
+        pub fn set_group_discount_tiers(&mut self, event_id: u32, tiers: Vec<GroupDiscountTier>) -> Result<(), String> {
+            self.ensure_role(Role::EventOrganizer, RoleScope::Event(event_id))?;
+            group_orders::GroupOrders::set_discount_tiers(&mut self.storage, event_id, tiers)
         }
 
-        /// Get the anti-scalping configuration for an event
+        /// Get an event's group discount tiers
         #[ink(message)]
-        pub fn get_anti_scalping_config(&self, event_id: u32) -> Option<AntiScalpingConfig> {
-            self.storage.anti_scalping_configs.get(event_id)
+        pub fn get_group_discount_tiers(&self, event_id: u32) -> Vec<GroupDiscountTier> {
+            self.storage.event_group_discounts.get(event_id).unwrap_or_default()
         }
 
-        // =============================================================================
-        // CORE: DYNAMIC PRICING
-        // =============================================================================
+        /// Reserve a contiguous run of seats from `first_seat` for invited members, returning the order ID
+        ///
+        /// Each member takes the given number of seats and must pay their share by `deadline`.
+        #[ink(message)]
+        pub fn create_group_order(
+            &mut self,
+            event_id: u32,
+            first_seat: Seat,
+            members: Vec<GroupInvite>,
+            deadline: u64,
+        ) -> Result<u32, String> {
+            let caller = self.env().caller();
+            let order_id = group_orders::GroupOrders::create_order(&mut self.storage, caller, event_id, first_seat, members, deadline)?;
+            if let Some(order) = self.storage.group_orders.get(order_id) {
+                self.env().emit_event(GroupOrderCreated {
+                    order_id, event_id, organizer: caller, seats: order.seats.len() as u32, discount_bps: order.discount_bps,
+                });
+            }
+            Ok(order_id)
+        }
 
-        /// Get a price quote for a ticket (does not purchase)
+        /// Pay towards the caller's share of a group order in DOT, refunding any overpayment
+        ///
+        /// The payment that funds the whole order issues every member's tickets.
+        /// Returns whether the order completed.
+        #[ink(message, payable)]
+        pub fn contribute_to_group_order(&mut self, order_id: u32) -> Result<bool, String> {
+            let caller = self.env().caller();
+            let payment = self.env().transferred_value();
+            let (order, excess) = group_orders::GroupOrders::contribute(&mut self.storage, caller, order_id, payment)?;
+            self.pay_out(caller, excess)?;
+            if order.status != GroupOrderStatus::Completed {
+                return Ok(false);
+            }
+            for &ticket_id in &order.ticket_ids {
+                if let Some(ticket) = self.storage.tickets.get(ticket_id) {
+                    self.env().emit_event(TicketPurchased {
+                        event_id: order.event_id, buyer: ticket.owner, ticket_id, price: ticket.purchase_price, currency: CurrencyId::DOT,
+                    });
+                }
+            }
+            self.env().emit_event(GroupOrderClosed { order_id, event_id: order.event_id, completed: true });
+            Ok(true)
+        }
+
+        /// Cancel an unfunded group order, releasing its seats and crediting every contribution back
+        ///
+        /// The organizer can cancel at any time; anyone can once the deadline has
+        /// passed. Members withdraw their contributions with `withdraw_refund`.
         #[ink(message)]
-        pub fn get_price_quote(
-            &self, event_id: u32, seat: Seat, is_season_pass: bool,
-        ) -> Result<PriceQuote, String> {
-            pricing::DynamicPricing::get_price_quote(&self.storage, event_id, &seat, is_season_pass)
+        pub fn cancel_group_order(&mut self, order_id: u32) -> Result<(), String> {
+            let caller = self.env().caller();
+            let order = group_orders::GroupOrders::cancel_order(&mut self.storage, caller, order_id)?;
+            self.env().emit_event(GroupOrderClosed { order_id, event_id: order.event_id, completed: false });
+            self.run_waitlist(order.event_id)
         }
 
-        /// Update team performance data for dynamic pricing (owner only)
+        /// Get a group order
         #[ink(message)]
-        pub fn update_team_performance(
-            &mut self, team_id: u32, wins: u32, losses: u32, streak: i32, playoff_probability: u32,
-        ) -> Result<(), String> {
-            self.ensure_owner()?;
-            pricing::DynamicPricing::update_team_performance(
-                &mut self.storage, team_id, wins, losses, streak, playoff_probability,
-            )
+        pub fn get_group_order(&self, order_id: u32) -> Option<GroupOrder> {
+            self.storage.group_orders.get(order_id)
         }
 
-        /// Toggle dynamic pricing for an event (owner only)
+        /// Get the group orders an account organized or was invited to
         #[ink(message)]
-        pub fn set_dynamic_pricing(&mut self, event_id: u32, enabled: bool) -> Result<(), String> {
-            self.ensure_owner()?;
-            let mut event = self.storage.events.get(event_id).ok_or("Event not found")?;
-            event.dynamic_pricing_enabled = enabled;
-            self.storage.events.insert(event_id, &event);
-            Ok(())
+        pub fn get_user_group_orders(&self, account: AccountId) -> Vec<u32> {
+            self.storage.user_group_orders.get(account).unwrap_or_default()
         }
 
         // =============================================================================
-        // CORE: NFT TICKET MANAGEMENT
+        // CORE: TICKET MANAGEMENT
         // =============================================================================
 
-        /// Mint an NFT for an owned ticket
+        /// Lock the current price of a seat for the caller, returning a quote ID
+        ///
+        /// The quote can be redeemed once through `purchase_ticket` within
+        /// `QUOTE_LOCK_DURATION`. It does not reserve the seat.
         #[ink(message)]
-        pub fn mint_ticket_nft(&mut self, ticket_id: u64) -> Result<u64, String> {
+        pub fn lock_quote(&mut self, event_id: u32, seat: Seat) -> Result<u64, String> {
             let caller = self.env().caller();
-            nft_management::NftManagement::mint_ticket_nft(&mut self.storage, caller, ticket_id)
+            let quote_id = price_locks::PriceLocks::lock_quote(&mut self.storage, caller, event_id, seat)?;
+            if let Some(lock) = self.storage.price_locks.get(quote_id) {
+                self.env().emit_event(QuoteLocked {
+                    quote_id, event_id, holder: caller, price: lock.priced.price, expires_at: lock.expires_at,
+                });
+            }
+            Ok(quote_id)
         }
 
-        /// Verify a ticket NFT
+        /// Get a locked quote
         #[ink(message)]
-        pub fn verify_ticket_nft(&self, token_id: u64) -> Result<TicketVerification, String> {
-            nft_management::NftManagement::verify_ticket_nft(&self.storage, token_id)
+        pub fn get_locked_quote(&self, quote_id: u64) -> Option<PriceLock> {
+            self.storage.price_locks.get(quote_id)
         }
 
-        /// Mark ticket as used at event entry, receive attendance token
+        /// Get an account's unredeemed, unexpired quote IDs
         #[ink(message)]
-        pub fn use_ticket_nft(&mut self, token_id: u64) -> Result<u64, String> {
+        pub fn get_active_quotes(&self, holder: AccountId) -> Vec<u64> {
+            price_locks::PriceLocks::active_locks(&self.storage, holder)
+        }
+
+        /// Purchase a ticket, paid in `currency`
+        ///
+        /// The price is the price locked by `quote_id`, or else the current
+        /// dynamic price; the purchase fails if the amount charged in `currency`
+        /// exceeds `max_price`. DOT is paid as native value and any overpayment
+        /// is refunded; other currencies are charged at the stored rate and
+        /// pulled from the caller's PSP22 allowance. The payment is held in the
+        /// event's escrow.
+        #[ink(message, payable)]
+        pub fn purchase_ticket(
+            &mut self, event_id: u32, seat: Seat, currency: CurrencyId, quote_id: Option<u64>, max_price: Option<u128>,
+        ) -> Result<u64, String> {
             let caller = self.env().caller();
-            nft_management::NftManagement::use_ticket_nft(&mut self.storage, caller, token_id)
+            let payment = self.env().transferred_value();
+            let ticket_id = ticket_management::TicketManagement::purchase_ticket(
+                &mut self.storage, caller, event_id, seat, currency, payment, quote_id, max_price,
+            )?;
+            let price = self.storage.tickets.get(ticket_id).map(|t| t.purchase_price).unwrap_or(0);
+            self.settle_payment(caller, currency, price, payment)?;
+            self.env().emit_event(TicketPurchased { event_id, buyer: caller, ticket_id, price, currency });
+            Ok(ticket_id)
         }
 
-        /// Get all NFT tickets for a user
+        /// Get ticket information
         #[ink(message)]
-        pub fn get_user_nft_tickets(&self, user: AccountId) -> Vec<TicketNft> {
-            nft_management::NftManagement::get_user_nft_tickets(&self.storage, user)
+        pub fn get_ticket(&self, ticket_id: u64) -> Option<Ticket> {
+            self.storage.tickets.get(ticket_id)
         }
 
-        /// Get NFT for a specific ticket
+        /// Get user's tickets
         #[ink(message)]
-        pub fn get_nft_by_ticket(&self, ticket_id: u64) -> Option<TicketNft> {
-            nft_management::NftManagement::get_nft_by_ticket(&self.storage, ticket_id)
+        pub fn get_user_tickets(&self, user: AccountId) -> Vec<u64> {
+            self.storage.user_tickets.get(user).unwrap_or_default()
         }
 
-        /// Transfer NFT to another owner
+        /// Transfer ticket, and its NFT if minted, to another user
         #[ink(message)]
-        pub fn transfer_nft(&mut self, token_id: u64, to: AccountId) -> Result<(), String> {
+        pub fn transfer_ticket(&mut self, ticket_id: u64, to: AccountId) -> Result<(), String> {
             let caller = self.env().caller();
-            nft_management::NftManagement::transfer_nft(&mut self.storage, caller, token_id, to)
+            let nft_before = self.ticket_nft_owner(ticket_id);
+            ticket_management::TicketManagement::transfer_ticket(&mut self.storage, caller, ticket_id, to)?;
+            self.env().emit_event(TicketTransferred { ticket_id, from: caller, to });
+            self.emit_nft_moved(nft_before, to);
+            Ok(())
         }
 
-        // =============================================================================
-        // SPORTS: TEAM MANAGEMENT (feature = "sports")
-        // =============================================================================
-
-        /// Register a new sports team
-        #[cfg(feature = "sports")]
+        /// Get a ticket's transfer history, if it has ever changed hands
         #[ink(message)]
-        pub fn register_team(&mut self, name: String, city: String, sport_type: SportType) -> Result<u32, String> {
-            self.ensure_owner()?;
-            team_management::TeamManagement::register_team(&mut self.storage, name, city, sport_type)
+        pub fn get_ticket_transfer_history(&self, ticket_id: u64) -> Option<TicketTransferHistory> {
+            self.storage.ticket_transfer_history.get(ticket_id)
         }
 
-        /// Get team information by ID
-        #[cfg(feature = "sports")]
+        /// List a ticket for resale, returning the listing ID
         #[ink(message)]
-        pub fn get_team(&self, team_id: u32) -> Option<Team> { self.storage.teams.get(team_id) }
+        pub fn resell_ticket(&mut self, ticket_id: u64, price: u128, currency: CurrencyId) -> Result<u64, String> {
+            let caller = self.env().caller();
+            let listing_id = ticket_management::TicketManagement::resell_ticket(
+                &mut self.storage, caller, ticket_id, price, currency,
+            )?;
+            self.env().emit_event(TicketListedForResale { listing_id, ticket_id, seller: caller, price, currency });
+            Ok(listing_id)
+        }
 
-        /// Get all registered teams
-        #[cfg(feature = "sports")]
-        #[ink(message)]
-        pub fn get_all_teams(&self) -> Vec<Team> { team_management::TeamManagement::get_all_teams(&self.storage) }
+        /// Buy a resale listing, paying at least its asking price
+        ///
+        /// The seller receives the price minus the event's resale fee, and any
+        /// overpayment is refunded.
+        #[ink(message, payable)]
+        pub fn buy_resale_listing(&mut self, listing_id: u64) -> Result<u64, String> {
+            let caller = self.env().caller();
+            let payment = self.env().transferred_value();
+            let nft_before = self.storage.resale_listings.get(listing_id)
+                .and_then(|listing| self.ticket_nft_owner(listing.ticket_id));
+            let (listing, seller_proceeds) = ticket_management::TicketManagement::buy_resale_listing(
+                &mut self.storage, caller, listing_id, payment,
+            )?;
+            self.pay_out(listing.seller, seller_proceeds)?;
+            self.pay_out(caller, payment.saturating_sub(listing.asking_price))?;
+            self.env().emit_event(ResaleListingPurchased {
+                listing_id, buyer: caller, seller: listing.seller, ticket_id: listing.ticket_id,
+                price: listing.asking_price, fee: listing.asking_price - seller_proceeds,
+            });
+            self.emit_nft_moved(nft_before, caller);
+            Ok(listing.ticket_id)
+        }
 
-        /// Update team attributes
-        #[cfg(feature = "sports")]
+        /// Cancel one of the caller's resale listings
+        ///
+        /// While fans wait for the event the listing is offered down its waitlist
+        /// at the asking price instead.
         #[ink(message)]
-        pub fn update_team(&mut self, team_id: u32, name: Option<String>, city: Option<String>, sport_type: Option<SportType>) -> Result<(), String> {
-            self.ensure_owner()?;
-            team_management::TeamManagement::update_team(&mut self.storage, team_id, name, city, sport_type)
+        pub fn cancel_resale_listing(&mut self, listing_id: u64) -> Result<(), String> {
+            let caller = self.env().caller();
+            let event_id = ticket_management::TicketManagement::cancel_resale_listing(&mut self.storage, caller, listing_id)?;
+            self.env().emit_event(ResaleListingCancelled { listing_id, seller: caller });
+            self.run_waitlist(event_id)
         }
 
-        // =============================================================================
-        // SPORTS: SEASON PASS MANAGEMENT
-        // =============================================================================
+        /// Get a resale listing by ID
+        #[ink(message)]
+        pub fn get_resale_listing(&self, listing_id: u64) -> Option<ResaleListing> {
+            self.storage.resale_listings.get(listing_id)
+        }
 
-        /// Create a season pass package for a team and season
-        #[cfg(feature = "sports")]
+        /// Get all unexpired resale listings for an event
         #[ink(message)]
-        pub fn create_season_pass_package(
-            &mut self, name: String, team_id: u32, season_id: u32, price: u128,
-            currency: CurrencyId, staking_requirement: u128, benefits: Vec<SeasonPassBenefits>,
-        ) -> Result<u32, String> {
-            self.ensure_owner()?;
-            season_pass_management::SeasonPassManagement::create_season_pass_package(
-                &mut self.storage, name, team_id, season_id, price, currency, staking_requirement, benefits,
-            )
+        pub fn get_active_listings(&self, event_id: u32) -> Vec<ResaleListing> {
+            ticket_management::TicketManagement::get_active_listings(&self.storage, event_id)
         }
 
-        /// Purchase a season pass from a package
-        #[cfg(feature = "sports")]
+        /// Claim a refund for a ticket to a cancelled event, invalidating the ticket
+        ///
+        /// Returns the refunded amount, paid from the event's escrow in the ticket's purchase currency.
         #[ink(message)]
-        pub fn purchase_season_pass(&mut self, package_id: u32) -> Result<u32, String> {
+        pub fn claim_refund(&mut self, ticket_id: u64) -> Result<u128, String> {
             let caller = self.env().caller();
-            season_pass_management::SeasonPassManagement::purchase_season_pass(&mut self.storage, caller, package_id)
+            let ticket = ticket_management::TicketManagement::claim_refund(&mut self.storage, caller, ticket_id)?;
+            self.pay_out_in(ticket.purchase_currency, caller, ticket.purchase_price)?;
+            self.env().emit_event(RefundClaimed {
+                ticket_id, event_id: ticket.event_id, owner: caller,
+                amount: ticket.purchase_price, currency: ticket.purchase_currency,
+            });
+            Ok(ticket.purchase_price)
         }
 
-        /// Use a season pass to gain entry to an event
-        #[cfg(feature = "sports")]
+        /// Refund a ticket to a live event from its escrow (event organizers only)
+        ///
+        /// The ticket is invalidated, its seat returns to sale and is offered to the
+        /// event's waitlist. Returns the amount credited to the ticket owner's refund
+        /// balance in the ticket's purchase currency, which they collect with
+        /// `withdraw_refund`.
         #[ink(message)]
-        pub fn use_season_pass_for_event(&mut self, season_pass_id: u32, event_id: u32) -> Result<u64, String> {
-            let caller = self.env().caller();
-            season_pass_management::SeasonPassManagement::use_season_pass_for_event(&mut self.storage, caller, season_pass_id, event_id)
+        pub fn refund_ticket(&mut self, ticket_id: u64) -> Result<u128, String> {
+            let event_id = self.storage.tickets.get(ticket_id).map(|t| t.event_id).ok_or("Ticket not found")?;
+            self.ensure_role(Role::EventOrganizer, RoleScope::Event(event_id))?;
+            let ticket = ticket_management::TicketManagement::refund_ticket(&mut self.storage, ticket_id)?;
+            escrow::Escrow::credit_refund(&mut self.storage, ticket.owner, ticket.purchase_currency, ticket.purchase_price);
+            self.env().emit_event(RefundClaimed {
+                ticket_id, event_id, owner: ticket.owner,
+                amount: ticket.purchase_price, currency: ticket.purchase_currency,
+            });
+            self.run_waitlist(event_id)?;
+            Ok(ticket.purchase_price)
         }
 
-        /// Get all season pass IDs for a user
-        #[cfg(feature = "sports")]
+        /// Get the refund claim deadline for a cancelled event
         #[ink(message)]
-        pub fn get_user_season_passes(&self, user: AccountId) -> Vec<u32> {
-            self.storage.user_season_passes.get(user).unwrap_or_default()
+        pub fn get_refund_deadline(&self, event_id: u32) -> Option<u64> {
+            self.storage.refund_deadlines.get(event_id)
         }
 
         // =============================================================================
-        // SPORTS: FANTASY SPORTS
+        // CORE: SETTLEMENT
         // =============================================================================
 
-        /// Create a new fantasy league
-        #[cfg(feature = "sports")]
+        /// Get the native (DOT) ticket proceeds held in escrow for an event
         #[ink(message)]
-        pub fn create_fantasy_league(&mut self, name: String, description: String, max_participants: u32, entry_fee: u128, currency: CurrencyId) -> Result<u32, String> {
-            let caller = self.env().caller();
-            fantasy_sports_management::FantasySportsManagement::create_fantasy_league(&mut self.storage, caller, name, description, max_participants, entry_fee, currency)
+        pub fn get_event_escrow(&self, event_id: u32) -> u128 {
+            escrow::Escrow::balance(&self.storage, event_id, CurrencyId::DOT)
         }
 
-        /// Join an existing fantasy league
-        #[cfg(feature = "sports")]
+        /// Get the ticket proceeds held in escrow for an event in a PSP22 currency
         #[ink(message)]
-        pub fn join_fantasy_league(&mut self, league_id: u32) -> Result<u32, String> {
-            let caller = self.env().caller();
-            fantasy_sports_management::FantasySportsManagement::join_fantasy_league(&mut self.storage, caller, league_id)
+        pub fn get_event_token_escrow(&self, event_id: u32, currency: CurrencyId) -> u128 {
+            escrow::Escrow::balance(&self.storage, event_id, currency)
         }
 
-        /// Create a fantasy team in a league
-        #[cfg(feature = "sports")]
+        /// Withdraw a completed event's native (DOT) escrowed proceeds (event organizer only)
         #[ink(message)]
-        pub fn create_fantasy_team(&mut self, league_id: u32, name: String) -> Result<u32, String> {
+        pub fn withdraw_event_proceeds(&mut self, event_id: u32) -> Result<u128, String> {
+            self.withdraw_proceeds_in(event_id, CurrencyId::DOT)
+        }
+
+        /// Withdraw a completed event's escrowed proceeds in a PSP22 currency (event organizer only)
+        #[ink(message)]
+        pub fn withdraw_event_token_proceeds(&mut self, event_id: u32, currency: CurrencyId) -> Result<u128, String> {
+            self.withdraw_proceeds_in(event_id, currency)
+        }
+
+        /// Withdraw the caller's refund balance in a currency
+        ///
+        /// Refunds owed to an account by someone else's call, such as lapsed
+        /// waitlist deposits, accumulate here instead of being pushed.
+        #[ink(message)]
+        pub fn withdraw_refund(&mut self, currency: CurrencyId) -> Result<u128, String> {
             let caller = self.env().caller();
-            fantasy_sports_management::FantasySportsManagement::create_fantasy_team(&mut self.storage, caller, league_id, name)
+            let amount = escrow::Escrow::withdraw_refund(&mut self.storage, caller, currency)?;
+            self.pay_out_in(currency, caller, amount)?;
+            self.env().emit_event(RefundWithdrawn { account: caller, amount, currency });
+            Ok(amount)
         }
 
-        /// Get the leaderboard for a fantasy league
-        #[cfg(feature = "sports")]
+        /// Get an account's withdrawable refund balance in a currency
         #[ink(message)]
-        pub fn get_fantasy_leaderboard(&self, league_id: u32) -> Option<FantasyLeaderboard> {
-            self.storage.fantasy_leaderboards.get(league_id)
+        pub fn get_refund_balance(&self, account: AccountId, currency: CurrencyId) -> u128 {
+            self.storage.refund_balances.get((account, currency)).unwrap_or(0)
+        }
+
+        fn withdraw_proceeds_in(&mut self, event_id: u32, currency: CurrencyId) -> Result<u128, String> {
+            let caller = self.env().caller();
+            let amount = escrow::Escrow::withdraw_proceeds(&mut self.storage, caller, event_id, currency)?;
+            self.pay_out_in(currency, caller, amount)?;
+            self.env().emit_event(EventProceedsWithdrawn { event_id, organizer: caller, amount, currency });
+            Ok(amount)
         }
 
         // =============================================================================
-        // SPORTS: TEAM LOYALTY
+        // CORE: CURRENCY MANAGEMENT
         // =============================================================================
 
-        /// Create a team loyalty profile for the caller
-        #[cfg(feature = "sports")]
+        /// Get all supported currency identifiers
         #[ink(message)]
-        pub fn create_team_loyalty_profile(&mut self, team_id: u32) -> Result<u32, String> {
-            let caller = self.env().caller();
-            advanced_team_loyalty::AdvancedTeamLoyalty::create_team_loyalty_profile(&mut self.storage, caller, team_id)
-        }
+        pub fn get_supported_currencies(&self) -> Vec<CurrencyId> { self.storage.supported_currencies.clone() }
 
-        /// Stake tokens on a team for loyalty rewards
-        #[cfg(feature = "sports")]
+        /// Get the exchange rate for a currency
         #[ink(message)]
-        pub fn stake_on_team(&mut self, team_id: u32, amount: u128, currency: CurrencyId) -> Result<u32, String> {
+        pub fn get_currency_rate(&self, currency: CurrencyId) -> Option<u128> { self.storage.currency_rates.get(currency) }
+
+        /// Report an exchange rate for a currency as an oracle feeder
+        ///
+        /// The effective rate becomes the median of the fresh feeder reports.
+        #[ink(message)]
+        pub fn update_currency_rate(&mut self, currency: CurrencyId, rate: u128) -> Result<(), String> {
             let caller = self.env().caller();
-            advanced_team_loyalty::AdvancedTeamLoyalty::stake_on_team(&mut self.storage, caller, team_id, amount, currency)
+            let effective = rate_oracle::RateOracle::submit_rate(&mut self.storage, caller, currency, rate)?;
+            self.env().emit_event(CurrencyRateUpdated { currency, rate: effective });
+            Ok(())
         }
 
-        /// Record attendance at a team event for loyalty tracking
-        #[cfg(feature = "sports")]
+        /// Get the effective rate of a currency with its update time and active flag
         #[ink(message)]
-        pub fn record_attendance(&mut self, team_id: u32, event_id: u32) -> Result<u32, String> {
-            let caller = self.env().caller();
-            advanced_team_loyalty::AdvancedTeamLoyalty::record_attendance(&mut self.storage, caller, team_id, event_id)
+        pub fn get_currency_rate_info(&self, currency: CurrencyId) -> Option<CurrencyRates> {
+            self.storage.currency_rate_info.get(currency)
         }
 
-        /// Get the team loyalty profile for a user-team pair
-        #[cfg(feature = "sports")]
+        /// Get the fresh feeder reports behind a currency's effective rate
         #[ink(message)]
-        pub fn get_team_loyalty_profile(&self, user: AccountId, team_id: u32) -> Option<TeamLoyaltyProfile> {
-            self.storage.team_loyalty_profiles.get((user, team_id))
+        pub fn get_rate_submissions(&self, currency: CurrencyId) -> Vec<RateSubmission> {
+            self.storage.rate_submissions.get(currency).unwrap_or_default()
         }
 
-        // =============================================================================
-        // SPORTS: VENUE-SPECIFIC FEATURES
-        // =============================================================================
+        /// Get the recent effective rates of a currency as `(timestamp, rate)` pairs
+        #[ink(message)]
+        pub fn get_rate_history(&self, currency: CurrencyId) -> Vec<RateHistoryEntry> {
+            self.storage.rate_history.get(currency).unwrap_or_default()
+        }
 
-        /// Purchase a parking pass for a venue event
-        #[cfg(feature = "sports")]
+        /// Get the rate oracle's staleness and deviation limits
         #[ink(message)]
-        pub fn purchase_parking_pass(&mut self, venue_id: u32, event_id: u32, pass_type: ParkingPassType) -> Result<u32, String> {
-            let caller = self.env().caller();
-            venue_management::VenueManagement::purchase_parking_pass(
-                &mut self.storage, caller, venue_id, pass_type,
-                ink::env::block_timestamp::<ink::env::DefaultEnvironment>(),
-                ink::env::block_timestamp::<ink::env::DefaultEnvironment>() + 365 * 24 * 60 * 60 * 1000,
-                "Main Lot".to_string(), "DOT".to_string(),
-            )
+        pub fn get_rate_oracle_config(&self) -> RateOracleConfig {
+            self.storage.rate_oracle_config.clone()
         }
 
-        /// Purchase concession credits for a venue
-        #[cfg(feature = "sports")]
+        /// Update the rate oracle's staleness and deviation limits
         #[ink(message)]
-        pub fn purchase_concession_credits(&mut self, venue_id: u32, amount: u128, currency: CurrencyId) -> Result<u32, String> {
-            let caller = self.env().caller();
-            venue_management::VenueManagement::purchase_concession_credits(
-                &mut self.storage, caller, venue_id, amount, venue::ConcessionCreditType::General,
-                ink::env::block_timestamp::<ink::env::DefaultEnvironment>(),
-                ink::env::block_timestamp::<ink::env::DefaultEnvironment>() + 365 * 24 * 60 * 60 * 1000,
-                amount, "DOT".to_string(),
-            )
+        pub fn set_rate_oracle_config(&mut self, config: RateOracleConfig) -> Result<(), String> {
+            self.ensure_role(Role::Admin, RoleScope::Global)?;
+            rate_oracle::RateOracle::set_config(&mut self.storage, config)
         }
 
-        /// Purchase a merchandise bundle from a venue
-        #[cfg(feature = "sports")]
+        /// Activate or deactivate payments in a currency
         #[ink(message)]
-        pub fn purchase_merchandise_bundle(&mut self, venue_id: u32, bundle_id: u32) -> Result<u32, String> {
-            let caller = self.env().caller();
-            venue_management::VenueManagement::purchase_merchandise_bundle(
-                &mut self.storage, caller, venue_id, bundle_id, None, "Main Store".to_string(),
-                ink::env::block_timestamp::<ink::env::DefaultEnvironment>() + 7 * 24 * 60 * 60 * 1000,
-            )
+        pub fn set_currency_active(&mut self, currency: CurrencyId, active: bool) -> Result<(), String> {
+            self.ensure_role(Role::Admin, RoleScope::Global)?;
+            rate_oracle::RateOracle::set_currency_active(&mut self.storage, currency, active)
         }
 
-        // =============================================================================
-        // SPORTS: CROSS-CHAIN
-        // =============================================================================
+        /// Get the PSP22 token contract payments in a currency go through
+        #[ink(message)]
+        pub fn get_currency_token(&self, currency: CurrencyId) -> Option<AccountId> {
+            self.storage.currency_tokens.get(currency)
+        }
 
-        /// Create a cross-chain event on a target network
-        #[cfg(feature = "sports")]
+        /// Register or clear the PSP22 token contract for a non-DOT currency (admins only)
         #[ink(message)]
-        pub fn create_cross_chain_event(&mut self, event_id: u32, target_chain: BlockchainNetwork) -> Result<u32, String> {
-            self.ensure_owner()?;
-            cross_chain_management::CrossChainManagement::create_cross_chain_event(&mut self.storage, event_id, target_chain)
+        pub fn set_currency_token(&mut self, currency: CurrencyId, token: Option<AccountId>) -> Result<(), String> {
+            self.ensure_role(Role::Admin, RoleScope::Global)?;
+            currency_management::CurrencyManagement::set_currency_token(&mut self.storage, currency, token)?;
+            self.env().emit_event(CurrencyTokenUpdated { currency, token });
+            Ok(())
         }
 
-        /// Request a cross-chain ticket purchase
-        #[cfg(feature = "sports")]
+        /// Get the ticket revenue received in a currency
         #[ink(message)]
-        pub fn request_cross_chain_ticket_purchase(&mut self, event_id: u32, target_chain: BlockchainNetwork, seat: Seat, currency: CurrencyId) -> Result<u32, String> {
-            let caller = self.env().caller();
-            cross_chain_management::CrossChainManagement::request_cross_chain_ticket_purchase(&mut self.storage, caller, event_id, target_chain, seat, currency)
+        pub fn get_currency_revenue(&self, currency: CurrencyId) -> u128 {
+            self.storage.currency_revenue.get(currency).unwrap_or(0)
         }
 
-        /// Get connectivity status for a blockchain network
-        #[cfg(feature = "sports")]
+        /// Quote the amount charged in a currency for a DOT price
         #[ink(message)]
-        pub fn get_chain_connectivity(&self, chain: BlockchainNetwork) -> Option<ChainConnectivityStatus> {
-            self.storage.chain_connectivity.get(chain)
+        pub fn convert_from_dot(&self, dot_amount: u128, currency: CurrencyId) -> Result<u128, String> {
+            currency_management::CurrencyManagement::charge_in(&self.storage, dot_amount, currency)
         }
 
         // =============================================================================
-        // SPORTS: ANALYTICS
+        // CORE: ANTI-SCALPING
         // =============================================================================
 
-        /// Get platform-wide statistics
-        #[cfg(feature = "sports")]
+        /// Configure anti-scalping rules for an event
         #[ink(message)]
-        pub fn get_platform_stats(&self) -> PlatformStats { self.storage.platform_stats.clone() }
+        pub fn configure_anti_scalping(&mut self, event_id: u32, config: AntiScalpingConfig) -> Result<(), String> {
+            self.ensure_role(Role::EventOrganizer, RoleScope::Event(event_id))?;
+            anti_scalping::AntiScalping::configure_anti_scalping(&mut self.storage, event_id, config)
+        }
 
-        /// Generate an analytics report for a date range
-        #[cfg(feature = "sports")]
+        /// Get the anti-scalping configuration for an event
         #[ink(message)]
-        pub fn generate_analytics_report(&mut self, report_type: ReportType, start_date: u64, end_date: u64) -> Result<u32, String> {
-            self.ensure_owner()?;
-            analytics::Analytics::generate_analytics_report(&mut self.storage, report_type, start_date, end_date)
+        pub fn get_anti_scalping_config(&self, event_id: u32) -> Option<AntiScalpingConfig> {
+            self.storage.anti_scalping_configs.get(event_id)
         }
 
-        /// Retrieve an analytics report by ID
-        #[cfg(feature = "sports")]
+        /// Get an account's anti-scalping behavior profile
         #[ink(message)]
-        pub fn get_analytics_report(&self, report_id: u32) -> Option<AnalyticsReport> {
-            self.storage.analytics_reports.get(report_id)
+        pub fn get_behavior_profile(&self, account: AccountId) -> Option<UserBehaviorProfile> {
+            anti_scalping::AntiScalping::behavior_profile(&self.storage, account)
         }
 
-        /// Get analytics data for a specific event
-        #[cfg(feature = "sports")]
+        /// Set an account's behavior status after review (admin only)
         #[ink(message)]
-        pub fn get_event_analytics(&self, event_id: u32) -> Option<EventAnalytics> {
-            self.storage.event_analytics.get(event_id)
+        pub fn set_behavior_status(&mut self, account: AccountId, status: BlacklistStatus) -> Result<(), String> {
+            self.ensure_role(Role::Admin, RoleScope::Global)?;
+            anti_scalping::AntiScalping::set_status(&mut self.storage, account, status);
+            self.env().emit_event(BehaviorProfileReviewed { account, status });
+            Ok(())
         }
 
-        // =============================================================================
-        // CONCERT: ARTIST MANAGEMENT (feature = "concert")
-        // =============================================================================
+        /// Reset an account's suspicious activity score and status after review (admin only)
+        #[ink(message)]
+        pub fn clear_behavior_profile(&mut self, account: AccountId) -> Result<(), String> {
+            self.ensure_role(Role::Admin, RoleScope::Global)?;
+            anti_scalping::AntiScalping::clear_profile(&mut self.storage, account)?;
+            self.env().emit_event(BehaviorProfileReviewed { account, status: BlacklistStatus::Clean });
+            Ok(())
+        }
 
-        /// Register a new artist
-        #[cfg(feature = "concert")]
+        /// Set or clear the holding limit for an event (event organizers), or a season or team (admins)
         #[ink(message)]
-        pub fn register_artist(&mut self, name: String) -> Result<u32, String> {
-            let caller = self.env().caller();
-            artist_management::ArtistManagement::register_artist(&mut self.storage, caller, name)
+        pub fn set_purchase_limit(&mut self, scope: PurchaseLimitScope, limit: Option<u32>) -> Result<(), String> {
+            match scope {
+                PurchaseLimitScope::Event(event_id) => self.ensure_role(Role::EventOrganizer, RoleScope::Event(event_id))?,
+                PurchaseLimitScope::Season(_) | PurchaseLimitScope::Team(_) => self.ensure_role(Role::Admin, RoleScope::Global)?,
+            }
+            purchase_limits::PurchaseLimits::set_limit(&mut self.storage, scope, limit)
         }
 
-        /// Verify an artist (owner only)
-        #[cfg(feature = "concert")]
+        /// Get the holding limit set for an event, season or team
         #[ink(message)]
-        pub fn verify_artist(&mut self, artist_id: u32) -> Result<(), String> {
-            self.ensure_owner()?;
-            artist_management::ArtistManagement::verify_artist(&mut self.storage, artist_id)
+        pub fn get_purchase_limit(&self, scope: PurchaseLimitScope) -> Option<u32> {
+            self.storage.purchase_limits.get(scope)
         }
 
-        /// Get artist information
-        #[cfg(feature = "concert")]
+        /// Get the tickets an account and its linked accounts hold within a scope
         #[ink(message)]
-        pub fn get_artist(&self, artist_id: u32) -> Option<Artist> {
-            self.storage.artists.get(artist_id)
+        pub fn get_holdings(&self, account: AccountId, scope: PurchaseLimitScope) -> u32 {
+            let household = purchase_limits::PurchaseLimits::household(&self.storage, account);
+            purchase_limits::PurchaseLimits::holdings(&self.storage, &household, scope)
         }
 
-        /// Create a concert event (convenience method)
-        #[cfg(feature = "concert")]
+        /// Link accounts of one household or entity so they share purchase limits (admin only)
         #[ink(message)]
-        pub fn create_concert_event(
-            &mut self, name: String, artist_id: u32, venue_id: u32,
-            date: u64, capacity: u32, base_price: u128,
-        ) -> Result<u32, String> {
-            self.ensure_owner()?;
-            // Auto-configure anti-scalping with concert preset
-            let category = EventCategory::Concert { artist_id };
-            let event_id = event_management::EventManagement::create_event(
-                &mut self.storage, name, venue_id, date, capacity, base_price, category,
-            )?;
-            // Apply concert anti-scalping preset (4 tickets per user)
-            let config = AntiScalpingConfig::concert_preset(event_id);
-            self.storage.anti_scalping_configs.insert(event_id, &config);
-            Ok(event_id)
+        pub fn link_accounts(&mut self, accounts: Vec<AccountId>) -> Result<u32, String> {
+            self.ensure_role(Role::Admin, RoleScope::Global)?;
+            purchase_limits::PurchaseLimits::link_accounts(&mut self.storage, accounts)
+        }
+
+        /// Remove an account from its link (admin only)
+        #[ink(message)]
+        pub fn unlink_account(&mut self, account: AccountId) -> Result<(), String> {
+            self.ensure_role(Role::Admin, RoleScope::Global)?;
+            purchase_limits::PurchaseLimits::unlink_account(&mut self.storage, account)
+        }
+
+        /// Get an account together with the accounts linked to it
+        #[ink(message)]
+        pub fn get_linked_accounts(&self, account: AccountId) -> Vec<AccountId> {
+            purchase_limits::PurchaseLimits::household(&self.storage, account)
         }
 
         // =============================================================================
-        // UTILITY FUNCTIONS
+        // CORE: ATTESTATIONS
         // =============================================================================
 
-        /// Get total counts
+        /// Record or renew an attestation for an account, storing only a hash of the verified claim (attesters only)
         #[ink(message)]
-        pub fn get_totals(&self) -> (u32, u32, u32, u64, u32, u32) {
-            (
-                self.storage.total_teams,
-                self.storage.total_venues,
-                self.storage.total_events,
-                self.storage.total_tickets,
-                self.storage.total_seasons,
-                self.storage.total_season_passes,
-            )
+        pub fn attest(
+            &mut self,
+            account: AccountId,
+            kind: AttestationKind,
+            claim_hash: [u8; 32],
+            expires_at: u64,
+        ) -> Result<(), String> {
+            self.ensure_role(Role::Attester, RoleScope::Global)?;
+            let attester = self.env().caller();
+            attestations::Attestations::attest(&mut self.storage, attester, account, kind, claim_hash, expires_at)?;
+            self.env().emit_event(AttestationRecorded { account, kind, attester, expires_at });
+            Ok(())
         }
 
-        /// Check if analytics is enabled
+        /// Revoke an account's attestation (its attester or an admin)
         #[ink(message)]
-        pub fn is_analytics_enabled(&self) -> bool { self.storage.analytics_enabled }
+        pub fn revoke_attestation(&mut self, account: AccountId, kind: AttestationKind) -> Result<(), String> {
+            let caller = self.env().caller();
+            attestations::Attestations::revoke(&mut self.storage, caller, account, kind)?;
+            self.env().emit_event(AttestationRevoked { account, kind, revoked_by: caller });
+            Ok(())
+        }
 
-        /// Toggle analytics
+        /// Get an account's latest attestation of a kind, whether or not it is still valid
         #[ink(message)]
-        pub fn toggle_analytics(&mut self) -> Result<(), String> {
-            self.ensure_owner()?;
-            self.storage.analytics_enabled = !self.storage.analytics_enabled;
-            Ok(())
+        pub fn get_attestation(&self, account: AccountId, kind: AttestationKind) -> Option<Attestation> {
+            self.storage.attestations.get((account, kind))
         }
-    }
 
-    impl Default for InkTix {
-        fn default() -> Self { Self::new() }
-    }
+        /// Check whether an account holds a valid attestation of a kind
+        #[ink(message)]
+        pub fn has_valid_attestation(&self, account: AccountId, kind: AttestationKind) -> bool {
+            attestations::Attestations::is_valid(&self.storage, account, kind)
+        }
 
-    // =========================================================================
-    // TESTS
-    // =========================================================================
+        /// Require attestations to buy an event's tickets (event organizers only)
+        #[ink(message)]
+        pub fn set_required_attestations(&mut self, event_id: u32, kinds: Vec<AttestationKind>) -> Result<(), String> {
+            self.ensure_role(Role::EventOrganizer, RoleScope::Event(event_id))?;
+            attestations::Attestations::set_requirements(&mut self.storage, event_id, kinds)
+        }
 
-    #[cfg(test)]
-    mod tests {
-        use super::*;
+        /// Get the attestation kinds an event requires
+        #[ink(message)]
+        pub fn get_required_attestations(&self, event_id: u32) -> Vec<AttestationKind> {
+            self.storage.event_required_attestations.get(event_id).unwrap_or_default()
+        }
 
-        #[ink::test]
+        // =============================================================================
+        // CORE: DYNAMIC PRICING
+        // =============================================================================
+
+        /// Get a price quote for a ticket (does not purchase)
+        #[ink(message)]
+        pub fn get_price_quote(
+            &self, event_id: u32, seat: Seat, is_season_pass: bool,
+        ) -> Result<PriceQuote, String> {
+            let (seat, _, _) = seat_inventory::SeatInventory::apply_layout(&self.storage, event_id, seat)?;
+            pricing::DynamicPricing::get_price_quote(&self.storage, event_id, &seat, is_season_pass)
+        }
+
+        /// Toggle dynamic pricing for an event (event organizers only)
+        #[ink(message)]
+        pub fn set_dynamic_pricing(&mut self, event_id: u32, enabled: bool) -> Result<(), String> {
+            self.ensure_role(Role::EventOrganizer, RoleScope::Event(event_id))?;
+            let mut event = self.storage.events.get(event_id).ok_or("Event not found")?;
+            event.dynamic_pricing_enabled = enabled;
+            self.storage.events.insert(event_id, &event);
+            Ok(())
+        }
+
+        /// Get the pricing policy applied to an event
+        #[ink(message)]
+        pub fn get_pricing_policy(&self, event_id: u32) -> Option<PricingPolicy> {
+            let event = self.storage.events.get(event_id)?;
+            Some(pricing_policy::PricingPolicies::effective(&self.storage, &event))
+        }
+
+        /// Get the pricing policy applied to a venue's events that have no policy of their own
+        #[ink(message)]
+        pub fn get_venue_pricing_policy(&self, venue_id: u32) -> PricingPolicy {
+            pricing_policy::PricingPolicies::for_venue(&self.storage, venue_id)
+        }
+
+        /// Set or clear a venue's pricing policy (venue operators only), returning its version
+        #[ink(message)]
+        pub fn set_venue_pricing_policy(&mut self, venue_id: u32, policy: Option<PricingPolicy>) -> Result<u32, String> {
+            self.ensure_role(Role::VenueOperator, RoleScope::Venue(venue_id))?;
+            let version = pricing_policy::PricingPolicies::set_for_venue(&mut self.storage, venue_id, policy)?;
+            self.env().emit_event(PricingPolicyUpdated { venue_id: Some(venue_id), event_id: None, version });
+            Ok(version)
+        }
+
+        /// Set or clear an event's pricing policy (event organizers only), returning its version
+        #[ink(message)]
+        pub fn set_event_pricing_policy(&mut self, event_id: u32, policy: Option<PricingPolicy>) -> Result<u32, String> {
+            self.ensure_role(Role::EventOrganizer, RoleScope::Event(event_id))?;
+            let version = pricing_policy::PricingPolicies::set_for_event(&mut self.storage, event_id, policy)?;
+            self.env().emit_event(PricingPolicyUpdated { venue_id: None, event_id: Some(event_id), version });
+            Ok(version)
+        }
+
+        // =============================================================================
+        // CORE: AUCTIONS
+        // =============================================================================
+
+        /// Auction a premium block of an event's seat layout (event organizers only)
+        ///
+        /// Courtside and Suite blocks, or any block of a championship game, can be
+        /// auctioned; all of the block's seats must still be free.
+        #[ink(message)]
+        pub fn create_auction(&mut self, event_id: u32, block_index: u32, format: AuctionFormat) -> Result<u32, String> {
+            self.ensure_role(Role::EventOrganizer, RoleScope::Event(event_id))?;
+            let auction_id = auctions::Auctions::create_auction(&mut self.storage, event_id, block_index, format)?;
+            self.env().emit_event(AuctionCreated { auction_id, event_id, block_index, format });
+            Ok(auction_id)
+        }
+
+        /// Get an auction
+        #[ink(message)]
+        pub fn get_auction(&self, auction_id: u32) -> Option<Auction> {
+            self.storage.auctions.get(auction_id)
+        }
+
+        /// Get the IDs of every auction configured for an event
+        #[ink(message)]
+        pub fn get_event_auctions(&self, event_id: u32) -> Vec<u32> {
+            self.storage.event_auctions.get(event_id).unwrap_or_default()
+        }
+
+        /// Get the sale mode of each block in an event's seat layout, in layout order
+        #[ink(message)]
+        pub fn get_event_sale_modes(&self, event_id: u32) -> Vec<SaleMode> {
+            auctions::Auctions::sale_modes(&self.storage, event_id)
+        }
+
+        /// Get the current price of a Dutch auction
+        #[ink(message)]
+        pub fn get_dutch_auction_price(&self, auction_id: u32) -> Result<u128, String> {
+            let auction = self.storage.auctions.get(auction_id).ok_or("Auction not found")?;
+            auctions::Auctions::dutch_price(&auction, self.env().block_timestamp())
+        }
+
+        /// Buy the next free seat of a Dutch auction at its current price in DOT
+        ///
+        /// Any overpayment is refunded and the payment is held in the event's escrow.
+        #[ink(message, payable)]
+        pub fn buy_from_dutch_auction(&mut self, auction_id: u32) -> Result<u64, String> {
+            let caller = self.env().caller();
+            let payment = self.env().transferred_value();
+            let (ticket_id, price) = auctions::Auctions::buy_dutch(&mut self.storage, caller, auction_id, payment)?;
+            self.settle_payment(caller, CurrencyId::DOT, price, payment)?;
+            if let Some(ticket) = self.storage.tickets.get(ticket_id) {
+                self.env().emit_event(TicketPurchased {
+                    event_id: ticket.event_id, buyer: caller, ticket_id, price, currency: CurrencyId::DOT,
+                });
+            }
+            Ok(ticket_id)
+        }
+
+        /// Commit a sealed bid, transferring a DOT deposit that covers it
+        ///
+        /// `commitment` is the Blake2x256 hash of the SCALE-encoded
+        /// `(bidder, price, quantity, salt)` later passed to `reveal_auction_bid`.
+        #[ink(message, payable)]
+        pub fn commit_auction_bid(&mut self, auction_id: u32, commitment: [u8; 32]) -> Result<(), String> {
+            let caller = self.env().caller();
+            let deposit = self.env().transferred_value();
+            auctions::Auctions::commit_bid(&mut self.storage, caller, auction_id, commitment, deposit)?;
+            self.env().emit_event(AuctionBidCommitted { auction_id, bidder: caller, deposit });
+            Ok(())
+        }
+
+        /// Reveal the caller's sealed bid
+        #[ink(message)]
+        pub fn reveal_auction_bid(&mut self, auction_id: u32, price: u128, quantity: u32, salt: [u8; 32]) -> Result<(), String> {
+            let caller = self.env().caller();
+            auctions::Auctions::reveal_bid(&mut self.storage, caller, auction_id, price, quantity, salt)
+        }
+
+        /// Get the bids committed to a sealed-bid auction
+        #[ink(message)]
+        pub fn get_auction_bids(&self, auction_id: u32) -> Vec<SealedBid> {
+            self.storage.auction_bids.get(auction_id).unwrap_or_default()
+        }
+
+        /// Settle an auction once its sale period is over (callable by anyone)
+        ///
+        /// Sealed-bid winners receive their tickets at the clearing price and every
+        /// deposit not spent on a ticket is credited to its bidder's refund balance.
+        #[ink(message)]
+        pub fn settle_auction(&mut self, auction_id: u32) -> Result<(), String> {
+            auctions::Auctions::settle(&mut self.storage, auction_id)?;
+            let auction = self.storage.auctions.get(auction_id).ok_or("Auction not found")?;
+            self.env().emit_event(AuctionSettled {
+                auction_id, seats_sold: auction.seats_sold, clearing_price: auction.clearing_price,
+            });
+            Ok(())
+        }
+
+        // =============================================================================
+        // CORE: BALLOTS
+        // =============================================================================
+
+        /// Open a ballot for an event before any tickets are sold (event organizers only)
+        ///
+        /// `terms.seed_commitment` is the Blake2x256 hash of the seed later revealed
+        /// to `draw_ballot`. The transferred value is the ballot's bond, returned
+        /// when the seed is revealed and forfeited to the entrants if it is withheld.
+        #[ink(message, payable)]
+        pub fn create_ballot(&mut self, event_id: u32, terms: BallotTerms) -> Result<u32, String> {
+            self.ensure_role(Role::EventOrganizer, RoleScope::Event(event_id))?;
+            let caller = self.env().caller();
+            let bond = self.env().transferred_value();
+            ballots::Ballots::create_ballot(&mut self.storage, caller, event_id, terms, bond)
+        }
+
+        /// Register the caller's interest in `quantity` tickets
+        #[ink(message)]
+        pub fn enter_ballot(&mut self, ballot_id: u32, quantity: u32) -> Result<(), String> {
+            let caller = self.env().caller();
+            ballots::Ballots::enter_ballot(&mut self.storage, caller, ballot_id, quantity)
+        }
+
+        /// Reveal the committed seed and draw the ballot (event organizers only)
+        ///
+        /// The seed must be revealed within `DRAW_REVEAL_PERIOD` of registration
+        /// closing, once a randomness beacon round has been published since.
+        #[ink(message)]
+        pub fn draw_ballot(&mut self, ballot_id: u32, seed: [u8; 32]) -> Result<(), String> {
+            let event_id = self.storage.ballots.get(ballot_id).map(|b| b.event_id).ok_or("Ballot not found")?;
+            self.ensure_role(Role::EventOrganizer, RoleScope::Event(event_id))?;
+            let draw_seed = ballots::Ballots::draw(&mut self.storage, ballot_id, seed)?;
+            self.env().emit_event(BallotDrawn { ballot_id, event_id, draw_seed });
+            Ok(())
+        }
+
+        /// Close a ballot whose seed was not revealed in time (callable by anyone), resuming general sale
+        #[ink(message)]
+        pub fn close_undrawn_ballot(&mut self, ballot_id: u32) -> Result<(), String> {
+            ballots::Ballots::close_undrawn(&mut self.storage, ballot_id)
+        }
+
+        /// Publish a randomness beacon round for ballot draws (beacon publishers only), returning its round number
+        #[ink(message)]
+        pub fn publish_beacon_round(&mut self, output: [u8; 32]) -> Result<u32, String> {
+            let caller = self.env().caller();
+            ballots::Ballots::publish_beacon(&mut self.storage, caller, output)
+        }
+
+        /// Get a randomness beacon round
+        #[ink(message)]
+        pub fn get_beacon_round(&self, round: u32) -> Option<BeaconRound> {
+            self.storage.beacon_rounds.get(round)
+        }
+
+        /// Offer lapsed allocations to the ballot's waitlist (callable by anyone), returning the tickets released
+        #[ink(message)]
+        pub fn roll_over_ballot(&mut self, ballot_id: u32) -> Result<u32, String> {
+            ballots::Ballots::roll_over(&mut self.storage, ballot_id)
+        }
+
+        /// Get a ballot
+        #[ink(message)]
+        pub fn get_ballot(&self, ballot_id: u32) -> Option<Ballot> {
+            self.storage.ballots.get(ballot_id)
+        }
+
+        /// Get the ballot selling an event, if any
+        #[ink(message)]
+        pub fn get_event_ballot(&self, event_id: u32) -> Option<u32> {
+            self.storage.event_ballots.get(event_id)
+        }
+
+        /// Get a ballot's entries, in drawn order once drawn
+        #[ink(message)]
+        pub fn get_ballot_entries(&self, ballot_id: u32) -> Vec<BallotEntry> {
+            ballots::Ballots::entries(&self.storage, ballot_id)
+        }
+
+        // =============================================================================
+        // CORE: SALE WINDOWS
+        // =============================================================================
+
+        /// Schedule an event's presale and general on-sale windows (event organizers only)
+        ///
+        /// Windows must ascend without overlapping and end with the general
+        /// on-sale; an empty list returns the event to an unscheduled sale.
+        #[ink(message)]
+        pub fn set_sale_windows(&mut self, event_id: u32, windows: Vec<SaleWindow>) -> Result<(), String> {
+            self.ensure_role(Role::EventOrganizer, RoleScope::Event(event_id))?;
+            sale_windows::SaleWindows::set_windows(&mut self.storage, event_id, windows)
+        }
+
+        /// Get an event's sale windows in order
+        #[ink(message)]
+        pub fn get_sale_windows(&self, event_id: u32) -> Vec<SaleWindow> {
+            self.storage.event_sale_windows.get(event_id).unwrap_or_default()
+        }
+
+        /// Get the sale window open now, with its index
+        #[ink(message)]
+        pub fn get_current_sale_window(&self, event_id: u32) -> Option<(u32, SaleWindow)> {
+            sale_windows::SaleWindows::current_window(&self.storage, event_id, self.env().block_timestamp())
+        }
+
+        /// Add presale access codes issued to specific accounts (event organizers only)
+        ///
+        /// Each hash is the Blake2x256 hash of the SCALE-encoded `(code, account)`,
+        /// so only that account can redeem the code, once.
+        #[ink(message)]
+        pub fn add_access_codes(&mut self, event_id: u32, code_hashes: Vec<[u8; 32]>) -> Result<(), String> {
+            self.ensure_role(Role::EventOrganizer, RoleScope::Event(event_id))?;
+            sale_windows::SaleWindows::add_access_codes(&mut self.storage, event_id, code_hashes)
+        }
+
+        /// Redeem an access code issued to the caller, unlocking the event's access-code presales for them
+        #[ink(message)]
+        pub fn redeem_access_code(&mut self, event_id: u32, code: Vec<u8>) -> Result<(), String> {
+            let caller = self.env().caller();
+            sale_windows::SaleWindows::redeem_access_code(&mut self.storage, caller, event_id, code)
+        }
+
+        /// Get the tickets an account bought in one of an event's sale windows
+        #[ink(message)]
+        pub fn get_sale_window_purchases(&self, event_id: u32, window_index: u32, account: AccountId) -> u32 {
+            self.storage.sale_window_purchases.get((event_id, window_index, account)).unwrap_or(0)
+        }
+
+        // =============================================================================
+        // CORE: WAITLISTS
+        // =============================================================================
+
+        /// Queue for a sold-out event, optionally transferring a DOT deposit put towards the tickets
+        #[ink(message, payable)]
+        pub fn join_waitlist(&mut self, event_id: u32, quantity: u32, max_price: u128) -> Result<(), String> {
+            let caller = self.env().caller();
+            let deposit = self.env().transferred_value();
+            waitlist::Waitlists::join(&mut self.storage, caller, event_id, quantity, max_price, deposit)
+        }
+
+        /// Leave an event's waitlist, refunding the caller's deposit
+        #[ink(message)]
+        pub fn leave_waitlist(&mut self, event_id: u32) -> Result<u128, String> {
+            let caller = self.env().caller();
+            let deposit = waitlist::Waitlists::leave(&mut self.storage, caller, event_id)?;
+            self.pay_out(caller, deposit)?;
+            self.run_waitlist(event_id)?;
+            Ok(deposit)
+        }
+
+        /// Buy tickets offered to the caller at their current dynamic prices in DOT
+        ///
+        /// The deposit counts towards the total and any excess is refunded.
+        #[ink(message, payable)]
+        pub fn claim_waitlist_offer(&mut self, event_id: u32, seats: Vec<Seat>) -> Result<Vec<u64>, String> {
+            let caller = self.env().caller();
+            let payment = self.env().transferred_value();
+            let (ticket_ids, _, refund) = waitlist::Waitlists::claim(&mut self.storage, caller, event_id, seats, payment)?;
+            self.pay_out(caller, refund)?;
+            for &ticket_id in &ticket_ids {
+                if let Some(ticket) = self.storage.tickets.get(ticket_id) {
+                    self.env().emit_event(TicketPurchased {
+                        event_id, buyer: caller, ticket_id, price: ticket.purchase_price, currency: CurrencyId::DOT,
+                    });
+                }
+            }
+            self.run_waitlist(event_id)?;
+            Ok(ticket_ids)
+        }
+
+        /// Buy the returned resale listing offered to the caller at its asking price in DOT
+        ///
+        /// The deposit counts towards the price and any excess is refunded.
+        #[ink(message, payable)]
+        pub fn claim_waitlist_listing(&mut self, event_id: u32) -> Result<u64, String> {
+            let caller = self.env().caller();
+            let payment = self.env().transferred_value();
+            let offered = self.storage.event_waitlists.get(event_id).unwrap_or_default().iter()
+                .find(|entry| entry.account == caller)
+                .and_then(|entry| entry.offer)
+                .and_then(|offer| offer.listing_id);
+            let nft_before = offered
+                .and_then(|listing_id| self.storage.resale_listings.get(listing_id))
+                .and_then(|listing| self.ticket_nft_owner(listing.ticket_id));
+            let (listing, seller_proceeds, refund) = waitlist::Waitlists::claim_listing(&mut self.storage, caller, event_id, payment)?;
+            self.pay_out(listing.seller, seller_proceeds)?;
+            self.pay_out(caller, refund)?;
+            self.env().emit_event(ResaleListingPurchased {
+                listing_id: listing.listing_id, buyer: caller, seller: listing.seller, ticket_id: listing.ticket_id,
+                price: listing.asking_price, fee: listing.asking_price - seller_proceeds,
+            });
+            self.emit_nft_moved(nft_before, caller);
+            self.run_waitlist(event_id)?;
+            Ok(listing.ticket_id)
+        }
+
+        /// Expire lapsed offers and offer returned listings and free inventory down an event's waitlist (callable by anyone)
+        ///
+        /// Picks up inventory released without a call into the contract, such as
+        /// lapsed seat holds and resale listings.
+        #[ink(message)]
+        pub fn process_waitlist(&mut self, event_id: u32) -> Result<(), String> {
+            self.run_waitlist(event_id)
+        }
+
+        /// Get an event's waitlist in FIFO order
+        #[ink(message)]
+        pub fn get_waitlist(&self, event_id: u32) -> Vec<WaitlistEntry> {
+            self.storage.event_waitlists.get(event_id).unwrap_or_default()
+        }
+
+        // =============================================================================
+        // CORE: NFT TICKET MANAGEMENT
+        // =============================================================================
+
+        /// Mint an NFT for an owned ticket
+        #[ink(message)]
+        pub fn mint_ticket_nft(&mut self, ticket_id: u64) -> Result<u64, String> {
+            let caller = self.env().caller();
+            let token_id = nft_management::NftManagement::mint_ticket_nft(&mut self.storage, caller, ticket_id)?;
+            let event_id = self.storage.tickets.get(ticket_id).map(|t| t.event_id).unwrap_or(0);
+            self.env().emit_event(TicketNftMinted { token_id, event_id, owner: caller, ticket_id });
+            self.env().emit_event(Transfer { from: None, to: Some(caller), id: Id::U64(token_id) });
+            Ok(token_id)
+        }
+
+        /// Verify a ticket NFT
+        #[ink(message)]
+        pub fn verify_ticket_nft(&self, token_id: u64) -> Result<TicketVerification, String> {
+            nft_management::NftManagement::verify_ticket_nft(&self.storage, token_id)
+        }
+
+        /// Manually mark a ticket used and issue its attendance token (global admins only)
+        #[ink(message)]
+        pub fn use_ticket_nft(&mut self, token_id: u64) -> Result<u64, String> {
+            let caller = self.env().caller();
+            let attendance_token_id = nft_management::NftManagement::use_ticket_nft(&mut self.storage, caller, token_id)?;
+            self.mint_attendance_token(attendance_token_id)?;
+            if let Some(nft) = self.storage.nft_tickets.get(token_id) {
+                self.env().emit_event(TicketNftUsed {
+                    token_id, event_id: nft.event_id, owner: nft.owner, admin: caller, attendance_token_id,
+                });
+            }
+            Ok(attendance_token_id)
+        }
+
+        /// Get the check-in challenge a holder signs for a ticket NFT
+        #[ink(message)]
+        pub fn get_check_in_message(&self, token_id: u64, issued_at: u64) -> Option<Vec<u8>> {
+            self.storage.nft_tickets.get(token_id).map(|nft| {
+                nft_management::NftManagement::check_in_message(token_id, &nft.verification_hash, issued_at)
+            })
+        }
+
+        /// Check a holder in from a scanned QR code and their signed challenge (gate scanners only)
+        ///
+        /// Returns the attendance token minted for the holder.
+        #[ink(message)]
+        pub fn check_in(
+            &mut self, token_id: u64, verification_hash: [u8; 32], issued_at: u64, signature: CheckInSignature,
+        ) -> Result<u64, String> {
+            let scanner = self.env().caller();
+            let attendance_token_id = nft_management::NftManagement::check_in(
+                &mut self.storage, scanner, token_id, verification_hash, issued_at, signature,
+            )?;
+            self.mint_attendance_token(attendance_token_id)?;
+            if let Some(nft) = self.storage.nft_tickets.get(token_id) {
+                #[cfg(feature = "sports")]
+                advanced_team_loyalty::AdvancedTeamLoyalty::record_check_in(&mut self.storage, nft.owner, nft.event_id);
+                self.env().emit_event(TicketCheckedIn {
+                    token_id, event_id: nft.event_id, holder: nft.owner, scanner, attendance_token_id,
+                });
+            }
+            Ok(attendance_token_id)
+        }
+
+        /// Get all NFT tickets for a user
+        #[ink(message)]
+        pub fn get_user_nft_tickets(&self, user: AccountId) -> Vec<TicketNft> {
+            nft_management::NftManagement::get_user_nft_tickets(&self.storage, user)
+        }
+
+        /// Get NFT for a specific ticket
+        #[ink(message)]
+        pub fn get_nft_by_ticket(&self, ticket_id: u64) -> Option<TicketNft> {
+            nft_management::NftManagement::get_nft_by_ticket(&self.storage, ticket_id)
+        }
+
+        /// Transfer NFT, and its ticket, to another owner
+        #[ink(message)]
+        pub fn transfer_nft(&mut self, token_id: u64, to: AccountId) -> Result<(), String> {
+            let caller = self.env().caller();
+            nft_management::NftManagement::transfer_nft(&mut self.storage, caller, token_id, to)?;
+            if let Some(nft) = self.storage.nft_tickets.get(token_id) {
+                self.env().emit_event(TicketTransferred { ticket_id: nft.ticket_id, from: caller, to });
+            }
+            self.emit_nft_moved(Some((token_id, caller)), to);
+            Ok(())
+        }
+
+        // =============================================================================
+        // CORE: ATTENDANCE TOKENS
+        // =============================================================================
+
+        /// Set or clear the soulbound PSP34 contract attendance tokens are minted into (admins only)
+        ///
+        /// The contract is an `attendance_token` instance whose minter is this contract.
+        /// Once set, using or checking in a ticket NFT fails unless the mint succeeds.
+        #[ink(message)]
+        pub fn set_attendance_token_contract(&mut self, contract: Option<AccountId>) -> Result<(), String> {
+            self.ensure_role(Role::Admin, RoleScope::Global)?;
+            self.storage.attendance_token_contract = contract;
+            self.env().emit_event(AttendanceTokenContractUpdated { contract });
+            Ok(())
+        }
+
+        /// Get the soulbound PSP34 contract attendance tokens are minted into
+        #[ink(message)]
+        pub fn get_attendance_token_contract(&self) -> Option<AccountId> {
+            self.storage.attendance_token_contract
+        }
+
+        /// Get an attendance token
+        #[ink(message)]
+        pub fn get_attendance_token(&self, attendance_token_id: u64) -> Option<AttendanceToken> {
+            self.storage.attendance_tokens.get(attendance_token_id)
+        }
+
+        // =============================================================================
+        // SPORTS: TEAM MANAGEMENT (feature = "sports")
+        // =============================================================================
+
+        /// Register a new sports team
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn register_team(&mut self, name: String, city: String, sport_type: SportType) -> Result<u32, String> {
+            self.ensure_role(Role::Admin, RoleScope::Global)?;
+            team_management::TeamManagement::register_team(&mut self.storage, name, city, sport_type)
+        }
+
+        /// Get team information by ID
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn get_team(&self, team_id: u32) -> Option<Team> { self.storage.teams.get(team_id) }
+
+        /// Get all registered teams
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn get_all_teams(&self) -> Vec<Team> { team_management::TeamManagement::get_all_teams(&self.storage) }
+
+        /// Update team attributes
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn update_team(&mut self, team_id: u32, name: Option<String>, city: Option<String>, sport_type: Option<SportType>) -> Result<(), String> {
+            self.ensure_role(Role::Admin, RoleScope::Global)?;
+            team_management::TeamManagement::update_team(&mut self.storage, team_id, name, city, sport_type)
+        }
+
+        // =============================================================================
+        // SPORTS: GAME RESULTS
+        // =============================================================================
+
+        /// Report the final score of a played sports event (result reporters only)
+        ///
+        /// The result is finalized once `get_result_quorum` reporters agree on a score.
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn report_game_result(&mut self, event_id: u32, home_score: u32, away_score: u32) -> Result<(), String> {
+            self.ensure_role(Role::ResultReporter, RoleScope::Event(event_id))?;
+            let caller = self.env().caller();
+            let finalized = game_result_oracle::GameResultOracle::report_result(
+                &mut self.storage, caller, event_id, home_score, away_score,
+            )?;
+            self.env().emit_event(GameResultReported { event_id, reporter: caller, home_score, away_score });
+            if finalized.is_some() {
+                self.env().emit_event(GameResultFinalized { event_id, home_score, away_score });
+            }
+            Ok(())
+        }
+
+        /// Get the finalized result of a sports event
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn get_game_result(&self, event_id: u32) -> Option<GameResult> {
+            self.storage.game_results.get(event_id)
+        }
+
+        /// Get the reports for a sports event whose result is not yet finalized
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn get_game_result_reports(&self, event_id: u32) -> Vec<GameResultReport> {
+            self.storage.game_result_reports.get(event_id).unwrap_or_default()
+        }
+
+        /// Get a team's record for a season
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn get_team_performance(&self, team_id: u32, season_id: u32) -> Option<TeamPerformance> {
+            self.storage.team_performance.get((team_id, season_id))
+        }
+
+        /// Get the loyalty rewards a team's results have earned
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn get_team_performance_rewards(&self, team_id: u32) -> Vec<TeamPerformanceReward> {
+            self.storage.team_performance_reward_ids.get(team_id).unwrap_or_default()
+                .into_iter()
+                .filter_map(|id| self.storage.team_performance_rewards.get(id))
+                .collect()
+        }
+
+        /// Get how many matching reports finalize a game result
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn get_result_quorum(&self) -> u32 { self.storage.result_quorum }
+
+        /// Set how many matching reports finalize a game result
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn set_result_quorum(&mut self, quorum: u32) -> Result<(), String> {
+            self.ensure_role(Role::Admin, RoleScope::Global)?;
+            game_result_oracle::GameResultOracle::set_quorum(&mut self.storage, quorum)
+        }
+
+        // =============================================================================
+        // SPORTS: SEASON PASS MANAGEMENT
+        // =============================================================================
+
+        /// Create a season pass package for a team and season
+        ///
+        /// Flex passes cover any `n` of `game_ids` (or of the season's games when
+        /// empty); other pass types cover every listed game.
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        #[allow(clippy::too_many_arguments)]
+        pub fn create_season_pass_package(
+            &mut self, name: String, team_id: u32, season_id: u32, pass_type: SeasonPassType, game_ids: Vec<u32>,
+            price: u128, currency: CurrencyId, staking_requirement: u128, benefits: Vec<SeasonPassBenefits>,
+        ) -> Result<u32, String> {
+            self.ensure_role(Role::EventOrganizer, RoleScope::Global)?;
+            let caller = self.env().caller();
+            season_pass_management::SeasonPassManagement::create_season_pass_package(
+                &mut self.storage, caller, name, team_id, season_id, pass_type, game_ids, price, currency, staking_requirement, benefits,
+            )
+        }
+
+        /// Purchase a season pass from a package, paying its price in the package currency
+        ///
+        /// `seat` is reserved for the pass at every game it covers.
+        #[cfg(feature = "sports")]
+        #[ink(message, payable)]
+        pub fn purchase_season_pass(&mut self, package_id: u32, seat: Seat) -> Result<u32, String> {
+            let caller = self.env().caller();
+            let payment = self.env().transferred_value();
+            let pass_id = season_pass_management::SeasonPassManagement::purchase_season_pass(
+                &mut self.storage, caller, package_id, seat, payment,
+            )?;
+            let pass = self.storage.season_passes.get(pass_id).ok_or("Season pass not found")?;
+            self.settle_payment(caller, pass.purchase_currency, pass.purchase_price, payment)?;
+            self.env().emit_event(SeasonPassPurchased { pass_id, team_id: pass.team_id, owner: caller, package_id });
+            Ok(pass_id)
+        }
+
+        /// Give holders of a package a priority window to renew into a later season's package
+        ///
+        /// Holders renew at the lower of what they paid and the new package's
+        /// price; the new package's general sale starts when the window ends.
+        /// The caller must organize both packages.
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn open_season_pass_renewals(
+            &mut self, from_package_id: u32, renewal_package_id: u32, window_ends_at: u64,
+        ) -> Result<(), String> {
+            self.ensure_role(Role::EventOrganizer, RoleScope::Global)?;
+            let caller = self.env().caller();
+            season_pass_management::SeasonPassManagement::open_renewals(
+                &mut self.storage, caller, from_package_id, renewal_package_id, window_ends_at,
+            )
+        }
+
+        /// Get the renewal offered for a season pass
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn get_renewal_option(&self, pass_id: u32) -> Option<RenewalOption> {
+            season_pass_management::SeasonPassManagement::renewal_option(&self.storage, pass_id)
+        }
+
+        /// Renew a season pass at its locked price, carrying over its loyalty points and staking
+        #[cfg(feature = "sports")]
+        #[ink(message, payable)]
+        pub fn renew_season_pass(&mut self, pass_id: u32) -> Result<u32, String> {
+            let caller = self.env().caller();
+            let payment = self.env().transferred_value();
+            let (renewed_pass_id, price) = season_pass_management::SeasonPassManagement::renew_season_pass(
+                &mut self.storage, caller, pass_id, payment,
+            )?;
+            let currency = self.storage.season_passes.get(renewed_pass_id).map(|p| p.purchase_currency).unwrap_or(CurrencyId::DOT);
+            self.settle_payment(caller, currency, price, payment)?;
+            self.env().emit_event(SeasonPassRenewed { pass_id, renewed_pass_id, owner: caller, price });
+            Ok(renewed_pass_id)
+        }
+
+        /// Upgrade a season pass to a higher-priced package of its team and season, paying the difference
+        #[cfg(feature = "sports")]
+        #[ink(message, payable)]
+        pub fn upgrade_season_pass(&mut self, pass_id: u32, package_id: u32) -> Result<u128, String> {
+            let caller = self.env().caller();
+            let payment = self.env().transferred_value();
+            let difference = season_pass_management::SeasonPassManagement::upgrade_season_pass(
+                &mut self.storage, caller, pass_id, package_id, payment,
+            )?;
+            let currency = self.storage.season_passes.get(pass_id).map(|p| p.purchase_currency).unwrap_or(CurrencyId::DOT);
+            self.settle_payment(caller, currency, difference, payment)?;
+            self.env().emit_event(SeasonPassUpgraded { pass_id, package_id, difference });
+            Ok(difference)
+        }
+
+        /// Claim a game covered by a season pass, issuing a ticket for the pass's seat
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn use_season_pass_for_event(&mut self, season_pass_id: u32, event_id: u32) -> Result<u64, String> {
+            let caller = self.env().caller();
+            let ticket_id = season_pass_management::SeasonPassManagement::use_season_pass_for_event(
+                &mut self.storage, caller, season_pass_id, event_id,
+            )?;
+            self.env().emit_event(SeasonPassUsed { pass_id: season_pass_id, event_id, owner: caller, ticket_id });
+            Ok(ticket_id)
+        }
+
+        /// Withdraw the proceeds released by a season pass package's completed games (package organizer only)
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn withdraw_season_pass_proceeds(&mut self, package_id: u32) -> Result<u128, String> {
+            let caller = self.env().caller();
+            let (amount, currency) = season_pass_management::SeasonPassManagement::withdraw_proceeds(
+                &mut self.storage, caller, package_id,
+            )?;
+            self.pay_out_in(currency, caller, amount)?;
+            self.env().emit_event(SeasonPassProceedsWithdrawn { package_id, organizer: caller, amount, currency });
+            Ok(amount)
+        }
+
+        /// Get a season pass
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn get_season_pass(&self, pass_id: u32) -> Option<SeasonPass> {
+            self.storage.season_passes.get(pass_id)
+        }
+
+        /// Get the scheduled games a season pass covers
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn get_season_pass_games(&self, pass_id: u32) -> Vec<u32> {
+            self.storage.season_passes.get(pass_id)
+                .map(|pass| season_pass_management::SeasonPassManagement::covered_events(&self.storage, &pass))
+                .unwrap_or_default()
+        }
+
+        /// Get a season pass's claim of a game, if it was claimed
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn get_season_pass_usage(&self, pass_id: u32, event_id: u32) -> Option<SeasonPassUsage> {
+            self.storage.season_pass_usage.get((pass_id, event_id))
+        }
+
+        /// Get all season pass IDs for a user
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn get_user_season_passes(&self, user: AccountId) -> Vec<u32> {
+            self.storage.user_season_passes.get(user).unwrap_or_default()
+        }
+
+        // =============================================================================
+        // SPORTS: FANTASY SPORTS
+        // =============================================================================
+
+        /// Create a new fantasy league
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn create_fantasy_league(&mut self, name: String, description: String, max_participants: u32, entry_fee: u128, currency: CurrencyId) -> Result<u32, String> {
+            let caller = self.env().caller();
+            fantasy_sports_management::FantasySportsManagement::create_fantasy_league(&mut self.storage, caller, name, description, max_participants, entry_fee, currency)
+        }
+
+        /// Join an existing fantasy league
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn join_fantasy_league(&mut self, league_id: u32) -> Result<u32, String> {
+            let caller = self.env().caller();
+            fantasy_sports_management::FantasySportsManagement::join_fantasy_league(&mut self.storage, caller, league_id)
+        }
+
+        /// Create a fantasy team in a league
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn create_fantasy_team(&mut self, league_id: u32, name: String) -> Result<u32, String> {
+            let caller = self.env().caller();
+            fantasy_sports_management::FantasySportsManagement::create_fantasy_team(&mut self.storage, caller, league_id, name)
+        }
+
+        /// Get the leaderboard for a fantasy league
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn get_fantasy_leaderboard(&self, league_id: u32) -> Option<FantasyLeaderboard> {
+            self.storage.fantasy_leaderboards.get(league_id)
+        }
+
+        /// Register a fantasy player on a real team
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn register_fantasy_player(&mut self, name: String, position: PlayerPosition, team_id: u32) -> Result<u32, String> {
+            self.ensure_role(Role::Admin, RoleScope::Global)?;
+            fantasy_sports_management::FantasySportsManagement::register_player(&mut self.storage, name, position, team_id)
+        }
+
+        /// Get a fantasy player's stats
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn get_fantasy_player(&self, player_id: u32) -> Option<PlayerStats> {
+            self.storage.fantasy_players.get(player_id)
+        }
+
+        /// Add a player to one of the caller's fantasy teams
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn add_fantasy_player(&mut self, fantasy_team_id: u32, player_id: u32) -> Result<(), String> {
+            let caller = self.env().caller();
+            fantasy_sports_management::FantasySportsManagement::add_player_to_team(&mut self.storage, caller, fantasy_team_id, player_id)
+        }
+
+        /// Recompute a fantasy league's standings from its rosters' scores
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn refresh_fantasy_leaderboard(&mut self, league_id: u32) -> Result<FantasyLeaderboard, String> {
+            fantasy_sports_management::FantasySportsManagement::refresh_leaderboard(&mut self.storage, league_id)
+        }
+
+        // =============================================================================
+        // SPORTS: TEAM LOYALTY
+        // =============================================================================
+
+        /// Create a team loyalty profile for the caller
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn create_team_loyalty_profile(&mut self, team_id: u32) -> Result<u32, String> {
+            let caller = self.env().caller();
+            advanced_team_loyalty::AdvancedTeamLoyalty::create_team_loyalty_profile(&mut self.storage, caller, team_id)
+        }
+
+        /// Stake tokens on a team for loyalty rewards
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn stake_on_team(&mut self, team_id: u32, amount: u128, currency: CurrencyId) -> Result<u32, String> {
+            let caller = self.env().caller();
+            let stake_id = advanced_team_loyalty::AdvancedTeamLoyalty::stake_on_team(&mut self.storage, caller, team_id, amount, currency)?;
+            self.env().emit_event(TeamStaked { stake_id, team_id, staker: caller, amount, currency });
+            Ok(stake_id)
+        }
+
+        /// Record a ticket holder's attendance at a team's game for loyalty tracking (gate scanners only)
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn record_attendance(&mut self, user: AccountId, team_id: u32, event_id: u32) -> Result<u32, String> {
+            let caller = self.env().caller();
+            advanced_team_loyalty::AdvancedTeamLoyalty::record_attendance(&mut self.storage, caller, user, team_id, event_id)
+        }
+
+        /// Get the team loyalty profile for a user-team pair
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn get_team_loyalty_profile(&self, user: AccountId, team_id: u32) -> Option<TeamLoyaltyProfile> {
+            self.storage.team_loyalty_profiles.get((user, team_id))
+        }
+
+        // =============================================================================
+        // SPORTS: VENUE-SPECIFIC FEATURES
+        // =============================================================================
+
+        /// Purchase a parking pass for a venue event
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn purchase_parking_pass(&mut self, venue_id: u32, event_id: u32, pass_type: ParkingPassType) -> Result<u32, String> {
+            let caller = self.env().caller();
+            venue_management::VenueManagement::purchase_parking_pass(
+                &mut self.storage, caller, venue_id, pass_type,
+                ink::env::block_timestamp::<ink::env::DefaultEnvironment>(),
+                ink::env::block_timestamp::<ink::env::DefaultEnvironment>() + 365 * 24 * 60 * 60 * 1000,
+                "Main Lot".to_string(), "DOT".to_string(),
+            )
+        }
+
+        /// Purchase concession credits for a venue
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn purchase_concession_credits(&mut self, venue_id: u32, amount: u128, currency: CurrencyId) -> Result<u32, String> {
+            let caller = self.env().caller();
+            venue_management::VenueManagement::purchase_concession_credits(
+                &mut self.storage, caller, venue_id, amount, venue::ConcessionCreditType::General,
+                ink::env::block_timestamp::<ink::env::DefaultEnvironment>(),
+                ink::env::block_timestamp::<ink::env::DefaultEnvironment>() + 365 * 24 * 60 * 60 * 1000,
+                amount, "DOT".to_string(),
+            )
+        }
+
+        /// Purchase a merchandise bundle from a venue
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn purchase_merchandise_bundle(&mut self, venue_id: u32, bundle_id: u32) -> Result<u32, String> {
+            let caller = self.env().caller();
+            venue_management::VenueManagement::purchase_merchandise_bundle(
+                &mut self.storage, caller, venue_id, bundle_id, None, "Main Store".to_string(),
+                ink::env::block_timestamp::<ink::env::DefaultEnvironment>() + 7 * 24 * 60 * 60 * 1000,
+            )
+        }
+
+        // =============================================================================
+        // SPORTS: CROSS-CHAIN
+        // =============================================================================
+
+        /// Create a cross-chain event on a target network
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn create_cross_chain_event(&mut self, event_id: u32, target_chain: BlockchainNetwork) -> Result<u32, String> {
+            self.ensure_role(Role::EventOrganizer, RoleScope::Event(event_id))?;
+            cross_chain_management::CrossChainManagement::create_cross_chain_event(&mut self.storage, event_id, target_chain)
+        }
+
+        /// Request a cross-chain ticket purchase
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn request_cross_chain_ticket_purchase(&mut self, event_id: u32, target_chain: BlockchainNetwork, seat: Seat, currency: CurrencyId) -> Result<u32, String> {
+            let caller = self.env().caller();
+            cross_chain_management::CrossChainManagement::request_cross_chain_ticket_purchase(&mut self.storage, caller, event_id, target_chain, seat, currency)
+        }
+
+        /// Get connectivity status for a blockchain network
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn get_chain_connectivity(&self, chain: BlockchainNetwork) -> Option<ChainConnectivityStatus> {
+            self.storage.chain_connectivity.get(chain)
+        }
+
+        // =============================================================================
+        // SPORTS: ANALYTICS
+        // =============================================================================
+
+        /// Get platform-wide statistics
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn get_platform_stats(&self) -> PlatformStats { self.storage.platform_stats.clone() }
+
+        /// Generate an analytics report for a date range
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn generate_analytics_report(&mut self, report_type: ReportType, start_date: u64, end_date: u64) -> Result<u32, String> {
+            self.ensure_role(Role::Admin, RoleScope::Global)?;
+            analytics::Analytics::generate_analytics_report(&mut self.storage, report_type, start_date, end_date)
+        }
+
+        /// Retrieve an analytics report by ID
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn get_analytics_report(&self, report_id: u32) -> Option<AnalyticsReport> {
+            self.storage.analytics_reports.get(report_id)
+        }
+
+        /// Get analytics data for a specific event
+        #[cfg(feature = "sports")]
+        #[ink(message)]
+        pub fn get_event_analytics(&self, event_id: u32) -> Option<EventAnalytics> {
+            self.storage.event_analytics.get(event_id)
+        }
+
+        // =============================================================================
+        // CONCERT: ARTIST MANAGEMENT (feature = "concert")
+        // =============================================================================
+
+        /// Register a new artist
+        #[cfg(feature = "concert")]
+        #[ink(message)]
+        pub fn register_artist(&mut self, name: String) -> Result<u32, String> {
+            let caller = self.env().caller();
+            artist_management::ArtistManagement::register_artist(&mut self.storage, caller, name)
+        }
+
+        /// Verify an artist (artist managers only)
+        #[cfg(feature = "concert")]
+        #[ink(message)]
+        pub fn verify_artist(&mut self, artist_id: u32) -> Result<(), String> {
+            self.ensure_role(Role::ArtistManager, RoleScope::Global)?;
+            artist_management::ArtistManagement::verify_artist(&mut self.storage, artist_id)
+        }
+
+        /// Get artist information
+        #[cfg(feature = "concert")]
+        #[ink(message)]
+        pub fn get_artist(&self, artist_id: u32) -> Option<Artist> {
+            self.storage.artists.get(artist_id)
+        }
+
+        /// Create a concert event (convenience method)
+        #[cfg(feature = "concert")]
+        #[ink(message)]
+        pub fn create_concert_event(
+            &mut self, name: String, artist_id: u32, venue_id: u32,
+            date: u64, capacity: u32, base_price: u128,
+        ) -> Result<u32, String> {
+            self.ensure_role(Role::EventOrganizer, RoleScope::Venue(venue_id))?;
+            // Auto-configure anti-scalping with concert preset
+            let category = EventCategory::Concert { artist_id };
+            let caller = self.env().caller();
+            let event_id = event_management::EventManagement::create_event(
+                &mut self.storage, caller, name, venue_id, date, capacity, base_price, category,
+            )?;
+            // Apply concert anti-scalping preset (4 tickets per user)
+            let config = AntiScalpingConfig::concert_preset(event_id);
+            self.storage.anti_scalping_configs.insert(event_id, &config);
+            self.emit_event_created(event_id);
+            Ok(event_id)
+        }
+
+        // =============================================================================
+        // UTILITY FUNCTIONS
+        // =============================================================================
+
+        /// Get total counts
+        #[ink(message)]
+        pub fn get_totals(&self) -> (u32, u32, u32, u64, u32, u32) {
+            (
+                self.storage.total_teams,
+                self.storage.total_venues,
+                self.storage.total_events,
+                self.storage.total_tickets,
+                self.storage.total_seasons,
+                self.storage.total_season_passes,
+            )
+        }
+
+        /// Check if analytics is enabled
+        #[ink(message)]
+        pub fn is_analytics_enabled(&self) -> bool { self.storage.analytics_enabled }
+
+        /// Toggle analytics
+        #[ink(message)]
+        pub fn toggle_analytics(&mut self) -> Result<(), String> {
+            self.ensure_role(Role::Admin, RoleScope::Global)?;
+            self.storage.analytics_enabled = !self.storage.analytics_enabled;
+            Ok(())
+        }
+    }
+
+    // =========================================================================
+    // PSP34: TICKET NFT COLLECTION
+    // =========================================================================
+
+    impl PSP34 for InkTix {
+        #[ink(message)]
+        fn collection_id(&self) -> Id {
+            psp34::TicketCollection::collection_id()
+        }
+
+        #[ink(message)]
+        fn balance_of(&self, owner: AccountId) -> u32 {
+            psp34::TicketCollection::balance_of(&self.storage, owner)
+        }
+
+        #[ink(message)]
+        fn owner_of(&self, id: Id) -> Option<AccountId> {
+            psp34::TicketCollection::owner_of(&self.storage, &id)
+        }
+
+        #[ink(message)]
+        fn allowance(&self, owner: AccountId, operator: AccountId, id: Option<Id>) -> bool {
+            psp34::TicketCollection::allowance(&self.storage, owner, operator, id.as_ref())
+        }
+
+        #[ink(message)]
+        fn approve(&mut self, operator: AccountId, id: Option<Id>, approved: bool) -> Result<(), PSP34Error> {
+            let caller = self.env().caller();
+            psp34::TicketCollection::approve(&mut self.storage, caller, operator, id.as_ref(), approved)?;
+            self.env().emit_event(Approval { owner: caller, operator, id, approved });
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn transfer(&mut self, to: AccountId, id: Id, _data: Vec<u8>) -> Result<(), PSP34Error> {
+            let caller = self.env().caller();
+            let from = psp34::TicketCollection::transfer(&mut self.storage, caller, to, &id)?;
+            if let Some(nft) = psp34::token_id(&id).and_then(|token_id| self.storage.nft_tickets.get(token_id)) {
+                self.env().emit_event(TicketTransferred { ticket_id: nft.ticket_id, from, to });
+                self.emit_nft_moved(Some((nft.token_id, from)), to);
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn total_supply(&self) -> u128 {
+            psp34::TicketCollection::total_supply(&self.storage)
+        }
+    }
+
+    impl PSP34Metadata for InkTix {
+        #[ink(message)]
+        fn get_attribute(&self, id: Id, key: Vec<u8>) -> Option<Vec<u8>> {
+            psp34::TicketCollection::get_attribute(&self.storage, &id, &key)
+        }
+    }
+
+    impl PSP34Enumerable for InkTix {
+        #[ink(message)]
+        fn owners_token_by_index(&self, owner: AccountId, index: u128) -> Result<Id, PSP34Error> {
+            psp34::TicketCollection::owners_token_by_index(&self.storage, owner, index)
+        }
+
+        #[ink(message)]
+        fn token_by_index(&self, index: u128) -> Result<Id, PSP34Error> {
+            psp34::TicketCollection::token_by_index(&self.storage, index)
+        }
+    }
+
+    impl Default for InkTix {
+        fn default() -> Self { Self::new() }
+    }
+
+    // =========================================================================
+    // TESTS
+    // =========================================================================
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn default_accounts() -> ink::env::test::DefaultAccounts<ink::env::DefaultEnvironment> {
+            ink::env::test::default_accounts::<ink::env::DefaultEnvironment>()
+        }
+
+        /// Give the contract its own account so payouts aren't self-transfers to the default callee
+        fn use_contract_account() {
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(AccountId::from([0xC0; 32]));
+        }
+
+        fn recorded_events() -> Vec<ink::env::test::EmittedEvent> {
+            ink::env::test::recorded_events().collect()
+        }
+
+        fn decode_event<E: ink::scale::Decode>(event: &ink::env::test::EmittedEvent) -> E {
+            <E as ink::scale::Decode>::decode(&mut &event.data[..]).expect("invalid event data")
+        }
+
+        /// Purchase a ticket paying exactly the current quoted price
+        fn buy_ticket(contract: &mut InkTix, event_id: u32, seat: Seat) -> Result<u64, String> {
+            let price = contract.get_price_quote(event_id, seat.clone(), false)?.final_price;
+            ink::env::pay_with_call!(contract.purchase_ticket(event_id, seat, CurrencyId::DOT, None, None), price)
+        }
+
+        #[ink::test]
         fn test_new_contract() {
             let contract = InkTix::new();
             let totals = contract.get_totals();
@@ -636,275 +2620,3119 @@ pub mod inktix {
         }
 
         #[ink::test]
-        fn test_register_venue() {
+        fn test_register_venue() {
+            let mut contract = InkTix::new();
+            let venue_id = contract.register_venue(
+                "Staples Center".to_string(), 20000, "Los Angeles".to_string(), VenueType::Arena,
+            ).unwrap();
+            assert_eq!(venue_id, 1);
+            let venue = contract.get_venue(venue_id).unwrap();
+            assert_eq!(venue.name, "Staples Center");
+            assert_eq!(venue.capacity, 20000);
+        }
+
+        #[cfg(feature = "sports")]
+        #[ink::test]
+        fn test_register_team() {
+            let mut contract = InkTix::new();
+            let team_id = contract.register_team(
+                "Lakers".to_string(), "Los Angeles".to_string(), SportType::Basketball,
+            ).unwrap();
+            assert_eq!(team_id, 1);
+            let team = contract.get_team(team_id).unwrap();
+            assert_eq!(team.name, "Lakers");
+            assert_eq!(team.city, "Los Angeles");
+            assert_eq!(team.sport_type, SportType::Basketball);
+        }
+
+        #[cfg(feature = "sports")]
+        #[ink::test]
+        fn test_create_sports_event() {
+            let mut contract = InkTix::new();
+
+            let team1_id = contract.register_team("Lakers".to_string(), "Los Angeles".to_string(), SportType::Basketball).unwrap();
+            let team2_id = contract.register_team("Warriors".to_string(), "San Francisco".to_string(), SportType::Basketball).unwrap();
+            let venue_id = contract.register_venue("Staples Center".to_string(), 20000, "Los Angeles".to_string(), VenueType::Arena).unwrap();
+
+            // Create a season (directly in storage for test)
+            let season = crate::types::sports::season::Season {
+                id: 1, name: "2024 Season".to_string(), sport_type: SportType::Basketball,
+                start_date: 1000000000, end_date: 2000000000, regular_season_games: 82,
+                active: true, season_pass_base_price: 1000000000000000000,
+                early_bird_discount: 20, early_bird_deadline: 999999000,
+            };
+            contract.storage.seasons.insert(1, &season);
+            contract.storage.total_seasons = 1;
+
+            let event_id = contract.create_event(
+                "Lakers vs Warriors".to_string(), venue_id, 1640995200, 20000,
+                1000000000000000000,
+                EventCategory::Sports {
+                    home_team_id: team1_id, away_team_id: team2_id, season_id: 1,
+                    game_type: GameType::RegularSeason, sport_type: SportType::Basketball,
+                },
+            ).unwrap();
+
+            assert_eq!(event_id, 1);
+            let event = contract.get_event(event_id).unwrap();
+            assert_eq!(event.name, "Lakers vs Warriors");
+            match &event.category {
+                EventCategory::Sports { home_team_id, away_team_id, .. } => {
+                    assert_eq!(*home_team_id, team1_id);
+                    assert_eq!(*away_team_id, team2_id);
+                }
+                _ => panic!("Expected Sports event category"),
+            }
+        }
+
+        #[cfg(feature = "concert")]
+        #[ink::test]
+        fn test_register_artist() {
+            let mut contract = InkTix::new();
+            let artist_id = contract.register_artist("Taylor Swift".to_string()).unwrap();
+            assert_eq!(artist_id, 1);
+
+            let artist = contract.get_artist(artist_id).unwrap();
+            assert_eq!(artist.name, "Taylor Swift");
+            assert!(!artist.verified);
+        }
+
+        #[cfg(feature = "concert")]
+        #[ink::test]
+        fn test_verify_artist() {
+            let mut contract = InkTix::new();
+            let artist_id = contract.register_artist("Taylor Swift".to_string()).unwrap();
+            contract.verify_artist(artist_id).unwrap();
+
+            let artist = contract.get_artist(artist_id).unwrap();
+            assert!(artist.verified);
+        }
+
+        #[cfg(feature = "concert")]
+        #[ink::test]
+        fn test_create_concert_event() {
+            let mut contract = InkTix::new();
+            let artist_id = contract.register_artist("Taylor Swift".to_string()).unwrap();
+            let venue_id = contract.register_venue(
+                "Madison Square Garden".to_string(), 20000, "New York".to_string(), VenueType::ConcertHall,
+            ).unwrap();
+
+            let event_id = contract.create_concert_event(
+                "Eras Tour NYC".to_string(), artist_id, venue_id, 1640995200, 20000, 150000000000000000,
+            ).unwrap();
+
+            assert_eq!(event_id, 1);
+            let event = contract.get_event(event_id).unwrap();
+            assert_eq!(event.name, "Eras Tour NYC");
+            match &event.category {
+                EventCategory::Concert { artist_id: aid } => assert_eq!(*aid, artist_id),
+                _ => panic!("Expected Concert event category"),
+            }
+
+            // Verify anti-scalping was auto-configured
+            let config = contract.get_anti_scalping_config(event_id).unwrap();
+            assert_eq!(config.max_tickets_per_user, 4);
+            assert!(config.anti_bot_measures);
+        }
+
+        #[cfg(feature = "concert")]
+        #[ink::test]
+        fn test_concert_purchase_limit() {
+            let mut contract = InkTix::new();
+            let artist_id = contract.register_artist("Artist".to_string()).unwrap();
+            let venue_id = contract.register_venue("Venue".to_string(), 100, "City".to_string(), VenueType::ConcertHall).unwrap();
+            let event_id = contract.create_concert_event("Concert".to_string(), artist_id, venue_id, 1, 100, 10).unwrap();
+
+            let seat = Seat {
+                section: "A".to_string(), row: "1".to_string(), seat_number: "1".to_string(),
+                seat_type: SeatType::GeneralAdmission, access_level: AccessLevel::Standard, price_multiplier: 10000,
+            };
+
+            // Should be able to purchase 4 tickets
+            for _ in 0..4 {
+                buy_ticket(&mut contract, event_id, seat.clone()).unwrap();
+            }
+
+            // 5th ticket should fail
+            let result = buy_ticket(&mut contract, event_id, seat.clone());
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err(), "Purchase limit reached");
+        }
+
+        #[ink::test]
+        fn test_mint_ticket_nft() {
+            let mut contract = InkTix::new();
+            // Setup: create venue and event
+            let venue_id = contract.register_venue("Arena".to_string(), 1000, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Game".to_string(), venue_id, 1000, 1000, 100, EventCategory::Generic).unwrap();
+            // Purchase ticket
+            let ticket_id = buy_ticket(&mut contract, event_id, Seat {
+                seat_number: "1".to_string(), section: "A".to_string(), row: "1".to_string(),
+                seat_type: SeatType::GeneralAdmission, access_level: AccessLevel::Standard,
+                price_multiplier: 10000,
+            }).unwrap();
+            // Mint NFT
+            let token_id = contract.mint_ticket_nft(ticket_id).unwrap();
+            assert_eq!(token_id, 1);
+            // Verify
+            let verification = contract.verify_ticket_nft(token_id).unwrap();
+            assert!(verification.is_valid);
+            assert!(!verification.is_used);
+            // Can't mint again
+            assert!(contract.mint_ticket_nft(ticket_id).is_err());
+        }
+
+        #[ink::test]
+        fn test_use_ticket_nft() {
+            let mut contract = InkTix::new();
+            let venue_id = contract.register_venue("Arena".to_string(), 1000, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Game".to_string(), venue_id, 1000, 1000, 100, EventCategory::Generic).unwrap();
+            let ticket_id = buy_ticket(&mut contract, event_id, Seat {
+                seat_number: "1".to_string(), section: "A".to_string(), row: "1".to_string(),
+                seat_type: SeatType::GeneralAdmission, access_level: AccessLevel::Standard,
+                price_multiplier: 10000,
+            }).unwrap();
+            let token_id = contract.mint_ticket_nft(ticket_id).unwrap();
+            // Gate scanners must check holders in; only admins can override
+            let accounts = default_accounts();
+            contract.grant_role(accounts.bob, Role::GateScanner, RoleScope::Event(event_id)).unwrap();
+            set_caller(accounts.bob);
+            assert_eq!(contract.use_ticket_nft(token_id), Err(String::from(InkTixError::MissingRole)));
+            set_caller(accounts.alice);
+            // Use ticket
+            let attendance_id = contract.use_ticket_nft(token_id).unwrap();
+            assert_eq!(attendance_id, 1);
+            // Verify shows used
+            let verification = contract.verify_ticket_nft(token_id).unwrap();
+            assert!(verification.is_used);
+            // Can't use again
+            assert!(contract.use_ticket_nft(token_id).is_err());
+        }
+
+        #[ink::test]
+        fn test_currency_management() {
+            let contract = InkTix::new();
+            let currencies = contract.get_supported_currencies();
+            assert!(currencies.contains(&CurrencyId::DOT));
+            let dot_rate = contract.get_currency_rate(CurrencyId::DOT).unwrap();
+            assert_eq!(dot_rate, 1_000_000_000_000_000_000);
+        }
+
+        #[ink::test]
+        fn test_generic_event() {
+            let mut contract = InkTix::new();
+            let venue_id = contract.register_venue("Hall".to_string(), 500, "City".to_string(), VenueType::ConventionCenter).unwrap();
+            let event_id = contract.create_event(
+                "Conference".to_string(), venue_id, 1640995200, 500, 50000000000000000, EventCategory::Generic,
+            ).unwrap();
+            let event = contract.get_event(event_id).unwrap();
+            assert_eq!(event.category, EventCategory::Generic);
+        }
+
+        #[ink::test]
+        fn test_dynamic_pricing() {
+            let mut contract = InkTix::new();
+            let venue_id = contract.register_venue(
+                "Arena".to_string(), 100, "LA".to_string(), VenueType::Arena,
+            ).unwrap();
+            let event_id = contract.create_event(
+                "Game".to_string(), venue_id, 1640995200, 100, 1000, EventCategory::Generic,
+            ).unwrap();
+
+            // Enable dynamic pricing
+            contract.set_dynamic_pricing(event_id, true).unwrap();
+
+            // Get price quote for general admission
+            let seat = Seat {
+                section: "A".to_string(), row: "1".to_string(), seat_number: "1".to_string(),
+                seat_type: SeatType::GeneralAdmission, access_level: AccessLevel::Standard,
+                price_multiplier: 10000,
+            };
+            let quote = contract.get_price_quote(event_id, seat.clone(), false).unwrap();
+            assert!(quote.final_price > 0);
+            assert_eq!(quote.base_price, 1000);
+
+            // Without a layout every seat is general admission, whatever the caller asks for
+            let vip_seat = Seat {
+                section: "VIP".to_string(), row: "1".to_string(), seat_number: "1".to_string(),
+                seat_type: SeatType::Courtside, access_level: AccessLevel::VIP,
+                price_multiplier: 50000,
+            };
+            let unseated_quote = contract.get_price_quote(event_id, vip_seat.clone(), false).unwrap();
+            assert_eq!((unseated_quote.final_price, unseated_quote.seat_multiplier), (quote.final_price, quote.seat_multiplier));
+
+            // A premium seat in a layout should cost more
+            let seated_id = contract.create_event(
+                "Game".to_string(), venue_id, 1640995200, 100, 1000, EventCategory::Generic,
+            ).unwrap();
+            let courtside = SeatBlock { seat_type: SeatType::Courtside, ..seat_block("VIP", "1", 1, 2) };
+            contract.set_event_seat_layout(seated_id, vec![courtside]).unwrap();
+            contract.set_dynamic_pricing(seated_id, true).unwrap();
+            let vip_quote = contract.get_price_quote(seated_id, vip_seat, false).unwrap();
+            assert!(vip_quote.final_price > quote.final_price);
+            assert!(vip_quote.seat_multiplier > quote.seat_multiplier);
+
+            // Purchase a ticket and verify dynamic price was applied
+            let ticket_id = buy_ticket(&mut contract, event_id, seat).unwrap();
+            let ticket = contract.get_ticket(ticket_id).unwrap();
+            assert_eq!(ticket.dynamic_price_paid, ticket.purchase_price);
+            assert!(ticket.performance_multiplier_applied > 0);
+        }
+
+        #[ink::test]
+        fn test_dynamic_pricing_demand_surge() {
+            let mut contract = InkTix::new();
+            let venue_id = contract.register_venue(
+                "Arena".to_string(), 10, "LA".to_string(), VenueType::Arena,
+            ).unwrap();
+            let event_id = contract.create_event(
+                "Game".to_string(), venue_id, 1640995200, 10, 1000, EventCategory::Generic,
+            ).unwrap();
+            contract.set_dynamic_pricing(event_id, true).unwrap();
+
+            let seat = Seat {
+                section: "A".to_string(), row: "1".to_string(), seat_number: "1".to_string(),
+                seat_type: SeatType::GeneralAdmission, access_level: AccessLevel::Standard,
+                price_multiplier: 10000,
+            };
+
+            // Buy 9 of 10 tickets to create high demand (90%)
+            for _ in 0..9 {
+                buy_ticket(&mut contract, event_id, seat.clone()).unwrap();
+            }
+
+            // Price quote at 90% should show demand surge
+            let quote = contract.get_price_quote(event_id, seat, false).unwrap();
+            assert!(quote.demand_multiplier > 10000, "Expected demand surge at 90% capacity");
+            assert_eq!(quote.demand_percentage, 90);
+        }
+
+        #[ink::test]
+        fn test_purchase_requires_payment() {
+            let mut contract = InkTix::new();
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Game".to_string(), venue_id, 1000, 100, 1000, EventCategory::Generic).unwrap();
+            let seat = Seat {
+                section: "A".to_string(), row: "1".to_string(), seat_number: "1".to_string(),
+                seat_type: SeatType::GeneralAdmission, access_level: AccessLevel::Standard, price_multiplier: 10000,
+            };
+            let price = contract.get_price_quote(event_id, seat.clone(), false).unwrap().final_price;
+
+            // Unpaid and underpaid purchases are rejected
+            let result = contract.purchase_ticket(event_id, seat.clone(), CurrencyId::DOT, None, None);
+            assert_eq!(result, Err(String::from(InkTixError::InsufficientPayment)));
+            let result = ink::env::pay_with_call!(contract.purchase_ticket(event_id, seat.clone(), CurrencyId::DOT, None, None), price - 1);
+            assert_eq!(result, Err(String::from(InkTixError::InsufficientPayment)));
+            assert_eq!(contract.get_event(event_id).unwrap().sold_tickets, 0);
+            assert_eq!(contract.get_event_escrow(event_id), 0);
+        }
+
+        #[ink::test]
+        fn test_purchase_refunds_overpayment_into_escrow() {
+            let accounts = default_accounts();
+            use_contract_account();
+            let mut contract = InkTix::new();
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Game".to_string(), venue_id, 1000, 100, 1000, EventCategory::Generic).unwrap();
+            let seat = Seat {
+                section: "A".to_string(), row: "1".to_string(), seat_number: "1".to_string(),
+                seat_type: SeatType::GeneralAdmission, access_level: AccessLevel::Standard, price_multiplier: 10000,
+            };
+            let price = contract.get_price_quote(event_id, seat.clone(), false).unwrap().final_price;
+
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.bob, 1_000_000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let balance_before = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob).unwrap();
+            let ticket_id = ink::env::pay_with_call!(contract.purchase_ticket(event_id, seat, CurrencyId::DOT, None, None), price + 500).unwrap();
+            let balance_after = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob).unwrap();
+
+            assert_eq!(balance_before - balance_after, price);
+            assert_eq!(contract.get_ticket(ticket_id).unwrap().purchase_price, price);
+            assert_eq!(contract.get_event_escrow(event_id), price);
+        }
+
+        #[ink::test]
+        fn test_withdraw_event_proceeds_after_completion() {
+            let accounts = default_accounts();
+            use_contract_account();
+            let mut contract = InkTix::new();
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Game".to_string(), venue_id, 1000, 100, 1000, EventCategory::Generic).unwrap();
+            let seat = Seat {
+                section: "A".to_string(), row: "1".to_string(), seat_number: "1".to_string(),
+                seat_type: SeatType::GeneralAdmission, access_level: AccessLevel::Standard, price_multiplier: 10000,
+            };
+
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.bob, 1_000_000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let ticket_id = buy_ticket(&mut contract, event_id, seat).unwrap();
+            let price = contract.get_ticket(ticket_id).unwrap().purchase_price;
+
+            // Proceeds stay locked until the event completes, which can't happen before its date
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(contract.withdraw_event_proceeds(event_id).is_err());
+            assert_eq!(
+                contract.update_event_status(event_id, EventStatus::Completed),
+                Err("Event has not taken place yet".to_string()),
+            );
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+            contract.update_event_status(event_id, EventStatus::Completed).unwrap();
+            assert_eq!(contract.get_event(event_id).unwrap().status, EventStatus::Completed);
+
+            // Only the organizer can withdraw
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(contract.withdraw_event_proceeds(event_id).is_err());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let balance_before = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.alice).unwrap();
+            assert_eq!(contract.withdraw_event_proceeds(event_id), Ok(price));
+            let balance_after = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.alice).unwrap();
+            assert_eq!(balance_after - balance_before, price);
+            assert_eq!(contract.get_event_escrow(event_id), 0);
+            assert!(contract.withdraw_event_proceeds(event_id).is_err());
+
+            // A completed event is final, so a drained escrow can't be turned into open refunds
+            for status in [EventStatus::Cancelled, EventStatus::OnSale] {
+                assert_eq!(
+                    contract.update_event_status(event_id, status),
+                    Err("Completed events cannot change status".to_string()),
+                );
+            }
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(contract.claim_refund(ticket_id).is_err());
+        }
+
+        #[ink::test]
+        fn test_event_status_follows_allowed_transitions() {
+            let mut contract = InkTix::new();
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Game".to_string(), venue_id, 1000, 100, 1000, EventCategory::Generic).unwrap();
+            let invalid = Err("Invalid event status transition".to_string());
+            assert_eq!(contract.update_event_status(event_id, EventStatus::Scheduled), invalid);
+            contract.update_event_status(event_id, EventStatus::SoldOut).unwrap();
+            contract.update_event_status(event_id, EventStatus::OnSale).unwrap();
+            assert!(contract.get_event(event_id).unwrap().active);
+
+            // A started event can't go back on sale
+            contract.update_event_status(event_id, EventStatus::InProgress).unwrap();
+            assert!(!contract.get_event(event_id).unwrap().active);
+            assert_eq!(contract.update_event_status(event_id, EventStatus::OnSale), invalid);
+            assert_eq!(contract.update_event_status(event_id, EventStatus::SoldOut), invalid);
+
+            // Cancelling can't open refunds that withdrawn proceeds no longer fund
+            contract.storage.proceeds_withdrawn.insert(event_id, &());
+            assert_eq!(
+                contract.update_event_status(event_id, EventStatus::Cancelled),
+                Err("Proceeds have already been withdrawn".to_string()),
+            );
+        }
+
+        #[ink::test]
+        fn test_venue_and_event_creation_emit_events() {
+            let accounts = default_accounts();
+            let mut contract = InkTix::new();
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Game".to_string(), venue_id, 1000, 100, 1000, EventCategory::Generic).unwrap();
+            contract.update_event_status(event_id, EventStatus::SoldOut).unwrap();
+
+            let events = recorded_events();
+            assert_eq!(events.len(), 3);
+            let venue: VenueRegistered = decode_event(&events[0]);
+            assert_eq!(venue.venue_id, venue_id);
+            assert_eq!(venue.name, "Arena");
+            let created: EventCreated = decode_event(&events[1]);
+            assert_eq!(created.event_id, event_id);
+            assert_eq!(created.organizer, accounts.alice);
+            assert_eq!(created.venue_id, venue_id);
+            // Signature topic plus event, organizer and venue topics
+            assert_eq!(events[1].topics.len(), 4);
+            assert_eq!(events[1].topics[2], AsRef::<[u8]>::as_ref(&accounts.alice).to_vec());
+            let status: EventStatusUpdated = decode_event(&events[2]);
+            assert_eq!(status.status, EventStatus::SoldOut);
+        }
+
+        #[ink::test]
+        fn test_ticket_lifecycle_emits_events() {
+            let accounts = default_accounts();
+            let mut contract = InkTix::new();
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Game".to_string(), venue_id, 1000, 100, 1000, EventCategory::Generic).unwrap();
+            let seat = Seat {
+                section: "A".to_string(), row: "1".to_string(), seat_number: "1".to_string(),
+                seat_type: SeatType::GeneralAdmission, access_level: AccessLevel::Standard, price_multiplier: 10000,
+            };
+            let first = buy_ticket(&mut contract, event_id, seat.clone()).unwrap();
+            let second = buy_ticket(&mut contract, event_id, seat).unwrap();
+            contract.transfer_ticket(first, accounts.bob).unwrap();
+            let listing_id = contract.resell_ticket(second, 2000, CurrencyId::DOT).unwrap();
+            let token_id = contract.mint_ticket_nft(second).unwrap();
+            let attendance_token_id = contract.use_ticket_nft(token_id).unwrap();
+            contract.cancel_resale_listing(listing_id).unwrap();
+            contract.transfer_nft(token_id, accounts.charlie).unwrap();
+
+            let events = recorded_events();
+            assert_eq!(events.len(), 13);
+            let purchased: TicketPurchased = decode_event(&events[2]);
+            assert_eq!((purchased.event_id, purchased.ticket_id), (event_id, first));
+            assert_eq!(purchased.buyer, accounts.alice);
+            assert_eq!(purchased.price, contract.get_ticket(first).unwrap().purchase_price);
+            let transferred: TicketTransferred = decode_event(&events[4]);
+            assert_eq!((transferred.ticket_id, transferred.from, transferred.to), (first, accounts.alice, accounts.bob));
+            assert_eq!(events[4].topics[3], AsRef::<[u8]>::as_ref(&accounts.bob).to_vec());
+            let listed: TicketListedForResale = decode_event(&events[5]);
+            assert_eq!((listed.ticket_id, listed.price), (second, 2000));
+            let minted: TicketNftMinted = decode_event(&events[6]);
+            assert_eq!((minted.token_id, minted.event_id, minted.ticket_id), (token_id, event_id, second));
+            let mint_transfer: Transfer = decode_event(&events[7]);
+            assert_eq!((mint_transfer.from, mint_transfer.to, mint_transfer.id), (None, Some(accounts.alice), Id::U64(token_id)));
+            let used: TicketNftUsed = decode_event(&events[8]);
+            assert_eq!((used.token_id, used.owner, used.attendance_token_id), (token_id, accounts.alice, attendance_token_id));
+            assert_eq!(used.admin, accounts.alice);
+            // Transferring the NFT moves its ticket too
+            let ticket_moved: TicketTransferred = decode_event(&events[10]);
+            assert_eq!((ticket_moved.ticket_id, ticket_moved.to), (second, accounts.charlie));
+            let nft_transferred: TicketNftTransferred = decode_event(&events[11]);
+            assert_eq!(nft_transferred.to, accounts.charlie);
+            let psp34_transfer: Transfer = decode_event(&events[12]);
+            assert_eq!((psp34_transfer.from, psp34_transfer.to), (Some(accounts.alice), Some(accounts.charlie)));
+        }
+
+        #[ink::test]
+        fn test_currency_rate_update_emits_event() {
+            let mut contract = InkTix::new();
+            contract.update_currency_rate(CurrencyId::KSM, 20_000).unwrap();
+            let events = recorded_events();
+            assert_eq!(events.len(), 1);
+            let updated: CurrencyRateUpdated = decode_event(&events[0]);
+            assert_eq!((updated.currency, updated.rate), (CurrencyId::KSM, 20_000));
+        }
+
+        /// Register a home and an away team and insert the given seasons, returning the team IDs
+        #[cfg(feature = "sports")]
+        fn season_teams(contract: &mut InkTix, season_ids: &[u32]) -> (u32, u32) {
+            let home = contract.register_team("Lakers".to_string(), "Los Angeles".to_string(), SportType::Basketball).unwrap();
+            let away = contract.register_team("Warriors".to_string(), "San Francisco".to_string(), SportType::Basketball).unwrap();
+            for &id in season_ids {
+                contract.storage.seasons.insert(id, &crate::types::sports::season::Season {
+                    id, name: "Season".to_string(), sport_type: SportType::Basketball,
+                    start_date: 0, end_date: 2_000_000_000, regular_season_games: 82, active: true,
+                    season_pass_base_price: 1000, early_bird_discount: 0, early_bird_deadline: 0,
+                });
+            }
+            (home, away)
+        }
+
+        #[cfg(feature = "sports")]
+        fn home_game(home_team_id: u32, away_team_id: u32, season_id: u32) -> EventCategory {
+            EventCategory::Sports {
+                home_team_id, away_team_id, season_id, game_type: GameType::RegularSeason, sport_type: SportType::Basketball,
+            }
+        }
+
+        #[cfg(feature = "sports")]
+        #[ink::test]
+        fn test_season_pass_and_staking_emit_events() {
+            let accounts = default_accounts();
+            let mut contract = InkTix::new();
+            let (team_id, away_id) = season_teams(&mut contract, &[1]);
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Game".to_string(), venue_id, 1000, 100, 1000, home_game(team_id, away_id, 1)).unwrap();
+            let package_id = contract.create_season_pass_package(
+                "Full Season".to_string(), team_id, 1, SeasonPassType::FullSeason, vec![event_id],
+                1000, CurrencyId::DOT, 0, vec![SeasonPassBenefits::default()],
+            ).unwrap();
+            let pass_id = ink::env::pay_with_call!(contract.purchase_season_pass(package_id, layout_seat("A", "1", 1)), 1000).unwrap();
+            let ticket_id = contract.use_season_pass_for_event(pass_id, event_id).unwrap();
+            let stake_id = contract.stake_on_team(team_id, 5000, CurrencyId::DOT).unwrap();
+
+            let events = recorded_events();
+            assert_eq!(events.len(), 5);
+            let purchased: SeasonPassPurchased = decode_event(&events[2]);
+            assert_eq!((purchased.pass_id, purchased.team_id, purchased.package_id), (pass_id, team_id, package_id));
+            assert_eq!(purchased.owner, accounts.alice);
+            let used: SeasonPassUsed = decode_event(&events[3]);
+            assert_eq!((used.pass_id, used.event_id, used.ticket_id), (pass_id, event_id, ticket_id));
+            let staked: TeamStaked = decode_event(&events[4]);
+            assert_eq!((staked.stake_id, staked.team_id, staked.amount), (stake_id, team_id, 5000));
+        }
+
+        fn resale_config(event_id: u32) -> AntiScalpingConfig {
+            AntiScalpingConfig {
+                event_id, transfer_restricted: false, max_tickets_per_user: 10,
+                resale_allowed: true, max_resale_price_multiplier: 150, resale_fee_percentage: 10,
+                transfer_lock_period: 0, blacklisted_addresses: Vec::new(), whitelisted_addresses: Vec::new(),
+                dynamic_pricing_enabled: false, anti_bot_measures: false,
+            }
+        }
+
+        #[ink::test]
+        fn test_resale_purchase_settles_seller_fee_and_nft() {
+            let accounts = default_accounts();
+            use_contract_account();
+            let mut contract = InkTix::new();
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Game".to_string(), venue_id, 1_000_000_000, 100, 1000, EventCategory::Generic).unwrap();
+            contract.set_dynamic_pricing(event_id, false).unwrap();
+            let invalid = [
+                (resale_config(event_id + 1), "Config is for a different event"),
+                (AntiScalpingConfig { max_resale_price_multiplier: 90, ..resale_config(event_id) }, "Resale price cap cannot be below face value"),
+                (AntiScalpingConfig { resale_fee_percentage: 101, ..resale_config(event_id) }, "Resale fee cannot exceed 100%"),
+            ];
+            for (config, error) in invalid {
+                assert_eq!(contract.configure_anti_scalping(event_id, config), Err(error.to_string()));
+            }
+            contract.configure_anti_scalping(event_id, resale_config(event_id)).unwrap();
+            let seat = Seat {
+                section: "A".to_string(), row: "1".to_string(), seat_number: "1".to_string(),
+                seat_type: SeatType::GeneralAdmission, access_level: AccessLevel::Standard, price_multiplier: 10000,
+            };
+            let ticket_id = buy_ticket(&mut contract, event_id, seat).unwrap();
+            let token_id = contract.mint_ticket_nft(ticket_id).unwrap();
+            let escrow_before = contract.get_event_escrow(event_id);
+
+            // The marketplace (this contract) must be approved to move the minted NFT
+            let marketplace = ink::env::test::callee::<ink::env::DefaultEnvironment>();
+            assert_eq!(
+                contract.resell_ticket(ticket_id, 1500, CurrencyId::DOT),
+                Err("Marketplace is not approved to transfer the ticket NFT".to_string()),
+            );
+            contract.approve(marketplace, Some(Id::U64(token_id)), true).unwrap();
+
+            // Listing above 150% of the original price is rejected
+            assert_eq!(contract.resell_ticket(ticket_id, 1501, CurrencyId::DOT), Err("Resale price exceeds the allowed cap".to_string()));
+            let listing_id = contract.resell_ticket(ticket_id, 1500, CurrencyId::DOT).unwrap();
+            let listing = contract.get_resale_listing(listing_id).unwrap();
+            assert!(listing.is_active && listing.approved);
+            assert_eq!(contract.get_active_listings(event_id).len(), 1);
+            // A listed ticket can't be transferred or listed twice
+            assert!(contract.transfer_ticket(ticket_id, accounts.django).is_err());
+            assert!(contract.resell_ticket(ticket_id, 1200, CurrencyId::DOT).is_err());
+
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.bob, 1_000_000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let underpaid = ink::env::pay_with_call!(contract.buy_resale_listing(listing_id), 1499);
+            assert_eq!(underpaid, Err(String::from(InkTixError::InsufficientPayment)));
+
+            let seller_before = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.alice).unwrap();
+            let bought = ink::env::pay_with_call!(contract.buy_resale_listing(listing_id), 1600);
+            assert_eq!(bought, Ok(ticket_id));
+            let seller_after = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.alice).unwrap();
+
+            assert_eq!(seller_after - seller_before, 1350);
+            assert_eq!(contract.get_event_escrow(event_id), escrow_before + 150);
+            assert_eq!(contract.get_ticket(ticket_id).unwrap().owner, accounts.bob);
+            assert_eq!(contract.get_nft_by_ticket(ticket_id).unwrap().owner, accounts.bob);
+            assert_eq!(contract.get_user_nft_tickets(accounts.bob)[0].token_id, token_id);
+            assert!(!contract.allowance(accounts.bob, marketplace, Some(Id::U64(token_id))));
+            assert!(contract.get_user_tickets(accounts.alice).is_empty());
+            assert!(!contract.get_resale_listing(listing_id).unwrap().is_active);
+            assert!(contract.get_active_listings(event_id).is_empty());
+        }
+
+        #[ink::test]
+        fn test_resale_cancel_and_expiry() {
+            let accounts = default_accounts();
+            let mut contract = InkTix::new();
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Game".to_string(), venue_id, 1_000_000_000, 100, 1000, EventCategory::Generic).unwrap();
+            let seat = Seat {
+                section: "A".to_string(), row: "1".to_string(), seat_number: "1".to_string(),
+                seat_type: SeatType::GeneralAdmission, access_level: AccessLevel::Standard, price_multiplier: 10000,
+            };
+            let ticket_id = buy_ticket(&mut contract, event_id, seat).unwrap();
+            let listing_id = contract.resell_ticket(ticket_id, 900, CurrencyId::DOT).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(contract.cancel_resale_listing(listing_id).is_err());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.cancel_resale_listing(listing_id).unwrap();
+            assert!(contract.get_active_listings(event_id).is_empty());
+
+            // A relisted ticket expires after the listing window
+            let relisted = contract.resell_ticket(ticket_id, 900, CurrencyId::DOT).unwrap();
+            assert_ne!(relisted, listing_id);
+            let expiry = contract.get_resale_listing(relisted).unwrap().expiry_time;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(expiry);
+            assert!(contract.get_active_listings(event_id).is_empty());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(ink::env::pay_with_call!(contract.buy_resale_listing(relisted), 900).is_err());
+            assert_eq!(contract.get_ticket(ticket_id).unwrap().owner, accounts.alice);
+        }
+
+        #[cfg(feature = "concert")]
+        #[ink::test]
+        fn test_resale_blocked_by_concert_preset() {
+            let mut contract = InkTix::new();
+            let artist_id = contract.register_artist("Artist".to_string()).unwrap();
+            let venue_id = contract.register_venue("Venue".to_string(), 100, "City".to_string(), VenueType::ConcertHall).unwrap();
+            let event_id = contract.create_concert_event("Concert".to_string(), artist_id, venue_id, 1_000_000_000, 100, 10).unwrap();
+            let seat = Seat {
+                section: "A".to_string(), row: "1".to_string(), seat_number: "1".to_string(),
+                seat_type: SeatType::GeneralAdmission, access_level: AccessLevel::Standard, price_multiplier: 10000,
+            };
+            let ticket_id = buy_ticket(&mut contract, event_id, seat).unwrap();
+            assert_eq!(
+                contract.resell_ticket(ticket_id, 10, CurrencyId::DOT),
+                Err("Resale is not allowed for this event".to_string()),
+            );
+        }
+
+        fn seat_block(section: &str, row: &str, first_seat: u32, seat_count: u32) -> SeatBlock {
+            SeatBlock {
+                section: section.to_string(), row: row.to_string(), first_seat, seat_count,
+                seat_type: SeatType::Reserved, access_level: AccessLevel::Standard, pricing_tier_id: None,
+            }
+        }
+
+        fn layout_seat(section: &str, row: &str, seat_number: u32) -> Seat {
+            Seat {
+                section: section.to_string(), row: row.to_string(), seat_number: seat_number.to_string(),
+                seat_type: SeatType::GeneralAdmission, access_level: AccessLevel::Standard, price_multiplier: 0,
+            }
+        }
+
+        #[ink::test]
+        fn test_seat_layout_prevents_double_booking() {
+            let mut contract = InkTix::new();
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let tier_id = contract.add_venue_pricing_tier(venue_id, VenuePricingTier {
+                id: 0, name: "Club".to_string(), description: String::new(), base_price_multiplier: 20000,
+                amenities_included: vec![], parking_included: false, concession_credits_included: 0,
+                merchandise_discount: 0, active: true,
+            }).unwrap();
+            let mut club = seat_block("CLUB", "A", 1, 2);
+            club.seat_type = SeatType::Club;
+            club.pricing_tier_id = Some(tier_id);
+            contract.set_venue_seat_layout(venue_id, vec![seat_block("101", "A", 1, 10), club]).unwrap();
+            let event_id = contract.create_event("Game".to_string(), venue_id, 1000, 100, 1000, EventCategory::Generic).unwrap();
+            assert_eq!(contract.get_seat_map(event_id).len(), 12);
+
+            // The layout, not the caller, decides the seat type and tier pricing
+            let ticket_id = buy_ticket(&mut contract, event_id, layout_seat("CLUB", "A", 2)).unwrap();
+            let ticket = contract.get_ticket(ticket_id).unwrap();
+            assert_eq!(ticket.seat_number, 2);
+            assert_eq!(ticket.seat_type, SeatType::Club);
+            assert_eq!(
+                contract.get_seat_status(event_id, "CLUB".to_string(), "A".to_string(), 2),
+                SeatStatus::Sold { ticket_id },
+            );
+
+            // The same seat can't be sold twice, and seats outside the layout don't exist
+            assert_eq!(
+                ink::env::pay_with_call!(contract.purchase_ticket(event_id, layout_seat("CLUB", "A", 2), CurrencyId::DOT, None, None), 1_000_000),
+                Err(String::from(InkTixError::SoldOut)),
+            );
+            assert_eq!(
+                buy_ticket(&mut contract, event_id, layout_seat("101", "A", 11)),
+                Err("Seat not found in event layout".to_string()),
+            );
+            assert_eq!(contract.get_event(event_id).unwrap().sold_tickets, 1);
+
+            // Layouts are frozen once sales start
+            assert!(contract.set_event_seat_layout(event_id, vec![seat_block("101", "A", 1, 5)]).is_err());
+        }
+
+        #[ink::test]
+        fn test_seat_layout_validation() {
+            let mut contract = InkTix::new();
+            let venue_id = contract.register_venue("Arena".to_string(), 10, "LA".to_string(), VenueType::Arena).unwrap();
+            assert_eq!(
+                contract.set_venue_seat_layout(venue_id, vec![seat_block("101", "A", 1, 5), seat_block("101", "A", 5, 2)]),
+                Err("Seat blocks overlap".to_string()),
+            );
+            assert_eq!(
+                contract.set_venue_seat_layout(venue_id, vec![seat_block("101", "A", 1, 11)]),
+                Err("Seat layout exceeds venue capacity".to_string()),
+            );
+            let mut tiered = seat_block("101", "A", 1, 5);
+            tiered.pricing_tier_id = Some(7);
+            assert_eq!(contract.set_venue_seat_layout(venue_id, vec![tiered]), Err("Pricing tier not found".to_string()));
+            assert!(contract.set_venue_seat_layout(venue_id, vec![seat_block("101", "A", 1, 5), seat_block("101", "B", 1, 5)]).is_ok());
+        }
+
+        #[ink::test]
+        fn test_general_admission_sells_out_at_capacity() {
+            let mut contract = InkTix::new();
+            let venue_id = contract.register_venue("Club".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Show".to_string(), venue_id, 1000, 1, 1000, EventCategory::Generic).unwrap();
+            let seat = Seat {
+                section: "GA".to_string(), row: "1".to_string(), seat_number: "1".to_string(),
+                seat_type: SeatType::GeneralAdmission, access_level: AccessLevel::Standard, price_multiplier: 10000,
+            };
+            buy_ticket(&mut contract, event_id, seat.clone()).unwrap();
+            assert_eq!(buy_ticket(&mut contract, event_id, seat), Err(String::from(InkTixError::SoldOut)));
+        }
+
+        #[ink::test]
+        fn test_cancelled_event_refunds_ticket_holders() {
+            let accounts = default_accounts();
+            use_contract_account();
+            let mut contract = InkTix::new();
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Game".to_string(), venue_id, 1000, 100, 1000, EventCategory::Generic).unwrap();
+            let seat = Seat {
+                section: "A".to_string(), row: "1".to_string(), seat_number: "1".to_string(),
+                seat_type: SeatType::GeneralAdmission, access_level: AccessLevel::Standard, price_multiplier: 10000,
+            };
+
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.bob, 1_000_000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let ticket_id = buy_ticket(&mut contract, event_id, seat).unwrap();
+            let token_id = contract.mint_ticket_nft(ticket_id).unwrap();
+            let price = contract.get_ticket(ticket_id).unwrap().purchase_price;
+            assert!(contract.claim_refund(ticket_id).is_err());
+
+            // Cancellation opens the refund window and is final
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.update_event_status(event_id, EventStatus::Cancelled).unwrap();
+            assert_eq!(contract.get_event(event_id).unwrap().status, EventStatus::Cancelled);
+            assert!(contract.update_event_status(event_id, EventStatus::OnSale).is_err());
+            assert_eq!(contract.withdraw_event_proceeds(event_id), Err("Refund window is still open".to_string()));
+            assert!(contract.claim_refund(ticket_id).is_err());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let balance_before = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob).unwrap();
+            assert_eq!(contract.claim_refund(ticket_id), Ok(price));
+            let balance_after = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob).unwrap();
+            assert_eq!(balance_after - balance_before, price);
+            assert_eq!(contract.get_event_escrow(event_id), 0);
+
+            let refunded: RefundClaimed = decode_event(recorded_events().last().unwrap());
+            assert_eq!((refunded.ticket_id, refunded.event_id, refunded.amount), (ticket_id, event_id, price));
+            assert_eq!(refunded.owner, accounts.bob);
+
+            // The ticket and its NFT are no longer valid
+            let ticket = contract.get_ticket(ticket_id).unwrap();
+            assert!(ticket.refunded && !ticket.transferable);
+            assert!(!contract.verify_ticket_nft(token_id).unwrap().is_valid);
+            assert_eq!(contract.claim_refund(ticket_id), Err("Ticket already refunded".to_string()));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(contract.use_ticket_nft(token_id), Err("Ticket has been refunded".to_string()));
+        }
+
+        #[ink::test]
+        fn test_refund_window_closes() {
+            let accounts = default_accounts();
+            use_contract_account();
+            let mut contract = InkTix::new();
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Game".to_string(), venue_id, 1000, 100, 1000, EventCategory::Generic).unwrap();
+            let seat = Seat {
+                section: "A".to_string(), row: "1".to_string(), seat_number: "1".to_string(),
+                seat_type: SeatType::GeneralAdmission, access_level: AccessLevel::Standard, price_multiplier: 10000,
+            };
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.bob, 1_000_000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let ticket_id = buy_ticket(&mut contract, event_id, seat).unwrap();
+            let price = contract.get_ticket(ticket_id).unwrap().purchase_price;
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.update_event_status(event_id, EventStatus::Cancelled).unwrap();
+            let deadline = contract.get_refund_deadline(event_id).unwrap();
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(deadline);
+
+            // Unclaimed refunds go back to the organizer once the window closes
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.claim_refund(ticket_id), Err("Refund window has closed".to_string()));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(contract.withdraw_event_proceeds(event_id), Ok(price));
+        }
+
+        #[cfg(feature = "sports")]
+        #[ink::test]
+        fn test_season_pass_refund_credits_flex_game_back_or_per_game_share() {
+            let accounts = default_accounts();
+            let mut contract = InkTix::new();
+            let (team_id, away_id) = season_teams(&mut contract, &[1]);
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Game".to_string(), venue_id, 1000, 100, 1000, home_game(team_id, away_id, 1)).unwrap();
+            let package_id = contract.create_season_pass_package(
+                "Full Season".to_string(), team_id, 1, SeasonPassType::FullSeason, vec![event_id],
+                1000, CurrencyId::DOT, 0, vec![SeasonPassBenefits::default()],
+            ).unwrap();
+            let flex_id = contract.create_season_pass_package(
+                "Flex".to_string(), team_id, 1, SeasonPassType::Flex(1), Vec::new(),
+                600, CurrencyId::DOT, 0, vec![SeasonPassBenefits::default()],
+            ).unwrap();
+            let pass_id = ink::env::pay_with_call!(contract.purchase_season_pass(package_id, layout_seat("A", "1", 1)), 1000).unwrap();
+            let flex_pass = ink::env::pay_with_call!(contract.purchase_season_pass(flex_id, layout_seat("A", "1", 2)), 600).unwrap();
+            let games_before = contract.storage.season_passes.get(pass_id).unwrap().games_remaining;
+            let ticket_id = contract.use_season_pass_for_event(pass_id, event_id).unwrap();
+            let flex_ticket = contract.use_season_pass_for_event(flex_pass, event_id).unwrap();
+            assert_eq!(contract.storage.season_passes.get(pass_id).unwrap().games_remaining, games_before - 1);
+
+            // A flex pass can use its game on another fixture
+            contract.update_event_status(event_id, EventStatus::Cancelled).unwrap();
+            assert_eq!(contract.claim_refund(flex_ticket), Ok(0));
+            let pass = contract.storage.season_passes.get(flex_pass).unwrap();
+            assert_eq!((pass.games_attended, pass.games_remaining), (0, 1));
+            assert_eq!(contract.get_refund_balance(accounts.alice, CurrencyId::DOT), 0);
+
+            // Other passes only covered the cancelled game, so its share is refunded
+            assert_eq!(contract.claim_refund(ticket_id), Ok(0));
+            let pass = contract.storage.season_passes.get(pass_id).unwrap();
+            assert_eq!((pass.games_attended, pass.games_remaining), (0, games_before - 1));
+            assert_eq!(contract.get_refund_balance(accounts.alice, CurrencyId::DOT), 1000);
+        }
+
+        #[cfg(feature = "sports")]
+        #[ink::test]
+        fn test_season_pass_claims_count_against_capacity() {
+            let accounts = default_accounts();
+            use_contract_account();
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.alice, 100_000_000);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.bob, 100_000_000);
+            let mut contract = InkTix::new();
+            let (team_id, away_id) = season_teams(&mut contract, &[1]);
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Game".to_string(), venue_id, 1000, 2, 1000, home_game(team_id, away_id, 1)).unwrap();
+            let package_id = contract.create_season_pass_package(
+                "Full Season".to_string(), team_id, 1, SeasonPassType::FullSeason, vec![event_id],
+                1000, CurrencyId::DOT, 0, vec![SeasonPassBenefits::default()],
+            ).unwrap();
+            let first = ink::env::pay_with_call!(contract.purchase_season_pass(package_id, layout_seat("A", "1", 1)), 1000).unwrap();
+            let second = ink::env::pay_with_call!(contract.purchase_season_pass(package_id, layout_seat("A", "1", 2)), 1000).unwrap();
+
+            set_caller(accounts.bob);
+            buy_ticket(&mut contract, event_id, layout_seat("GA", "1", 3)).unwrap();
+            set_caller(accounts.alice);
+            let ticket_id = contract.use_season_pass_for_event(first, event_id).unwrap();
+            assert_eq!(contract.get_event(event_id).unwrap().sold_tickets, 2);
+            assert_eq!(contract.use_season_pass_for_event(second, event_id), Err(InkTixError::SoldOut.into()));
+
+            // Refunding a claimed game frees its capacity again
+            contract.update_event_status(event_id, EventStatus::Cancelled).unwrap();
+            contract.claim_refund(ticket_id).unwrap();
+            assert_eq!(contract.get_event(event_id).unwrap().sold_tickets, 1);
+        }
+
+        fn layout_event(contract: &mut InkTix, seats: u32) -> u32 {
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            contract.set_venue_seat_layout(venue_id, vec![seat_block("101", "A", 1, seats)]).unwrap();
+            contract.create_event("Game".to_string(), venue_id, 1000, 100, 1000, EventCategory::Generic).unwrap()
+        }
+
+        #[ink::test]
+        fn test_seat_hold_confirms_at_quoted_price() {
+            let accounts = default_accounts();
+            use_contract_account();
+            let mut contract = InkTix::new();
+            let event_id = layout_event(&mut contract, 10);
+
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.bob, 1_000_000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let seats = vec![layout_seat("101", "A", 1), layout_seat("101", "A", 2)];
+            let hold_id = contract.hold_seats(event_id, seats, HoldDuration::Milliseconds(60_000)).unwrap();
+            let hold = contract.get_seat_hold(hold_id).unwrap();
+            let total: u128 = hold.seats.iter().map(|s| s.price).sum();
+            assert_eq!(contract.get_active_seat_holds(accounts.bob), vec![hold_id]);
+            assert_eq!(
+                contract.get_seat_status(event_id, "101".to_string(), "A".to_string(), 1),
+                SeatStatus::Held { hold_id },
+            );
+
+            // Held seats can't be bought or held by anyone else
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(buy_ticket(&mut contract, event_id, layout_seat("101", "A", 1)), Err("Seat is currently held".to_string()));
+            assert!(contract.hold_seats(event_id, vec![layout_seat("101", "A", 2)], HoldDuration::Blocks(10)).is_err());
+
+            // A price rise after the hold doesn't affect the quoted total
+            let mut event = contract.get_event(event_id).unwrap();
+            event.base_price *= 2;
+            contract.storage.events.insert(event_id, &event);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(ink::env::pay_with_call!(contract.confirm_hold(hold_id, CurrencyId::DOT), total - 1).is_err());
+            let ticket_ids = ink::env::pay_with_call!(contract.confirm_hold(hold_id, CurrencyId::DOT), total).unwrap();
+            assert_eq!(ticket_ids.len(), 2);
+            let paid: u128 = ticket_ids.iter().map(|id| contract.get_ticket(*id).unwrap().purchase_price).sum();
+            assert_eq!(paid, total);
+            assert_eq!(contract.get_event_escrow(event_id), total);
+            assert_eq!(
+                contract.get_seat_status(event_id, "101".to_string(), "A".to_string(), 2),
+                SeatStatus::Sold { ticket_id: ticket_ids[1] },
+            );
+            assert!(contract.get_active_seat_holds(accounts.bob).is_empty());
+            assert!(ink::env::pay_with_call!(contract.confirm_hold(hold_id, CurrencyId::DOT), total).is_err());
+        }
+
+        #[ink::test]
+        fn test_seat_holds_expire_and_are_limited_per_account() {
+            let accounts = default_accounts();
+            let mut contract = InkTix::new();
+            let event_id = layout_event(&mut contract, 10);
+            assert_eq!(
+                contract.hold_seats(event_id, vec![layout_seat("101", "A", 1)], HoldDuration::Blocks(10_000)),
+                Err("Invalid hold duration".to_string()),
+            );
+
+            assert_eq!(contract.storage.event_available_seats.get(event_id), Some(10));
+
+            // A lapsed hold frees its seat without any further call
+            let hold_id = contract.hold_seats(event_id, vec![layout_seat("101", "A", 1)], HoldDuration::Blocks(2)).unwrap();
+            assert_eq!(contract.storage.event_available_seats.get(event_id), Some(9));
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            assert_eq!(
+                contract.get_seat_status(event_id, "101".to_string(), "A".to_string(), 1),
+                SeatStatus::Available,
+            );
+            assert_eq!(
+                ink::env::pay_with_call!(contract.confirm_hold(hold_id, CurrencyId::DOT), 1_000_000),
+                Err("Seat hold has expired".to_string()),
+            );
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.bob, 1_000_000);
+            assert!(buy_ticket(&mut contract, event_id, layout_seat("101", "A", 1)).is_ok());
+            assert_eq!(contract.storage.event_available_seats.get(event_id), Some(9));
+
+            // Each account has a cap on concurrent holds
+            let mut holds = Vec::new();
+            for seat in 2..5 {
+                holds.push(contract.hold_seats(event_id, vec![layout_seat("101", "A", seat)], HoldDuration::Blocks(10)).unwrap());
+            }
+            assert_eq!(
+                contract.hold_seats(event_id, vec![layout_seat("101", "A", 5)], HoldDuration::Blocks(10)),
+                Err("Too many active seat holds".to_string()),
+            );
+            contract.release_hold(holds[0]).unwrap();
+            assert_eq!(
+                contract.get_seat_status(event_id, "101".to_string(), "A".to_string(), 2),
+                SeatStatus::Available,
+            );
+            assert_eq!(contract.storage.event_available_seats.get(event_id), Some(7));
+            assert!(contract.hold_seats(event_id, vec![layout_seat("101", "A", 5)], HoldDuration::Blocks(10)).is_ok());
+        }
+
+        fn set_caller(account: AccountId) {
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(account);
+        }
+
+        #[ink::test]
+        fn test_scoped_roles_gate_privileged_messages() {
+            let accounts = default_accounts();
+            let mut contract = InkTix::new();
+            let venue_a = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let venue_b = contract.register_venue("Stadium".to_string(), 100, "NY".to_string(), VenueType::Stadium).unwrap();
+            let missing_role = String::from(InkTixError::MissingRole);
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.register_venue("Club".to_string(), 10, "SF".to_string(), VenueType::Arena), Err(missing_role.clone()));
+            assert_eq!(contract.grant_role(accounts.bob, Role::Admin, RoleScope::Global), Err(String::from(InkTixError::NotOwner)));
+            assert_eq!(contract.grant_role(accounts.bob, Role::VenueOperator, RoleScope::Global), Err(missing_role.clone()));
+
+            // Venue-scoped roles only cover their own venue and its events
+            set_caller(accounts.alice);
+            contract.grant_role(accounts.bob, Role::VenueOperator, RoleScope::Venue(venue_a)).unwrap();
+            contract.grant_role(accounts.charlie, Role::EventOrganizer, RoleScope::Venue(venue_a)).unwrap();
+            assert!(contract.has_role(accounts.bob, Role::VenueOperator, RoleScope::Venue(venue_a)));
+            assert!(!contract.has_role(accounts.bob, Role::VenueOperator, RoleScope::Venue(venue_b)));
+
+            set_caller(accounts.bob);
+            assert!(contract.set_venue_seat_layout(venue_a, vec![seat_block("101", "A", 1, 10)]).is_ok());
+            assert_eq!(contract.set_venue_seat_layout(venue_b, vec![seat_block("101", "A", 1, 10)]), Err(missing_role.clone()));
+
+            set_caller(accounts.charlie);
+            let event_id = contract.create_event("Game".to_string(), venue_a, 1000, 100, 1000, EventCategory::Generic).unwrap();
+            assert_eq!(contract.get_event(event_id).unwrap().organizer, accounts.charlie);
+            assert_eq!(contract.create_event("Game".to_string(), venue_b, 1000, 100, 1000, EventCategory::Generic), Err(missing_role.clone()));
+            assert!(contract.set_dynamic_pricing(event_id, false).is_ok());
+            assert_eq!(contract.update_currency_rate(CurrencyId::KSM, 20_000), Err(missing_role.clone()));
+
+            // Manually using a ticket is an admin override, not a gate scanner action
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.charlie, 1_000_000);
+            let ticket_id = buy_ticket(&mut contract, event_id, layout_seat("101", "A", 1)).unwrap();
+            let token_id = contract.mint_ticket_nft(ticket_id).unwrap();
+            assert_eq!(contract.use_ticket_nft(token_id), Err(missing_role.clone()));
+            set_caller(accounts.alice);
+            contract.grant_role(accounts.django, Role::GateScanner, RoleScope::Event(event_id)).unwrap();
+            set_caller(accounts.django);
+            assert_eq!(contract.use_ticket_nft(token_id), Err(missing_role.clone()));
+            set_caller(accounts.alice);
+            contract.grant_role(accounts.django, Role::Admin, RoleScope::Global).unwrap();
+            set_caller(accounts.django);
+            assert!(contract.use_ticket_nft(token_id).is_ok());
+
+            // Revoked and renounced roles stop working
+            set_caller(accounts.alice);
+            contract.revoke_role(accounts.charlie, Role::EventOrganizer, RoleScope::Venue(venue_a)).unwrap();
+            set_caller(accounts.charlie);
+            assert_eq!(contract.update_event_status(event_id, EventStatus::SoldOut), Err(missing_role.clone()));
+            set_caller(accounts.bob);
+            contract.renounce_role(Role::VenueOperator, RoleScope::Venue(venue_a)).unwrap();
+            assert!(contract.get_roles(accounts.bob).is_empty());
+            assert_eq!(contract.update_venue_capacity(venue_a, 50), Err(missing_role.clone()));
+        }
+
+        #[ink::test]
+        fn test_admins_manage_roles_but_not_admins() {
+            let accounts = default_accounts();
+            let mut contract = InkTix::new();
+            assert_eq!(
+                contract.grant_role(accounts.bob, Role::Admin, RoleScope::Venue(1)),
+                Err("Admin role must be granted globally".to_string()),
+            );
+            contract.grant_role(accounts.bob, Role::Admin, RoleScope::Global).unwrap();
+
+            set_caller(accounts.bob);
+            assert!(contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).is_ok());
+            contract.grant_role(accounts.charlie, Role::OracleFeeder, RoleScope::Global).unwrap();
+            assert_eq!(
+                contract.grant_role(accounts.charlie, Role::OracleFeeder, RoleScope::Global),
+                Err("Role already granted".to_string()),
+            );
+            assert_eq!(contract.grant_role(accounts.charlie, Role::Admin, RoleScope::Global), Err(String::from(InkTixError::NotOwner)));
+
+            set_caller(accounts.charlie);
+            assert!(contract.update_currency_rate(CurrencyId::KSM, 20_000).is_ok());
+
+            let events = recorded_events();
+            let granted: RoleGranted = decode_event(&events[2]);
+            assert_eq!((granted.account, granted.role, granted.sender), (accounts.charlie, Role::OracleFeeder, accounts.bob));
+        }
+
+        #[ink::test]
+        fn test_two_step_ownership_transfer() {
+            let accounts = default_accounts();
+            let mut contract = InkTix::new();
+            set_caller(accounts.bob);
+            assert_eq!(contract.transfer_ownership(accounts.bob), Err(String::from(InkTixError::NotOwner)));
+
+            set_caller(accounts.alice);
+            contract.transfer_ownership(accounts.bob).unwrap();
+            assert_eq!(contract.get_pending_owner(), Some(accounts.bob));
+            assert_eq!(contract.get_owner(), accounts.alice);
+
+            set_caller(accounts.charlie);
+            assert_eq!(contract.accept_ownership(), Err("Caller is not the pending owner".to_string()));
+            set_caller(accounts.bob);
+            contract.accept_ownership().unwrap();
+            assert_eq!(contract.get_owner(), accounts.bob);
+            assert_eq!(contract.get_pending_owner(), None);
+
+            // The previous owner loses its implicit roles
+            set_caller(accounts.alice);
+            assert_eq!(
+                contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena),
+                Err(String::from(InkTixError::MissingRole)),
+            );
+            let transferred: OwnershipTransferred = decode_event(recorded_events().last().unwrap());
+            assert_eq!((transferred.previous_owner, transferred.new_owner), (accounts.alice, accounts.bob));
+        }
+
+        const CHECK_IN_EVENT_DATE: u64 = 10 * 60 * 60 * 1000;
+
+        fn sr25519_keypair(seed: u8) -> schnorrkel::Keypair {
+            schnorrkel::MiniSecretKey::from_bytes(&[seed; 32]).unwrap()
+                .expand_to_keypair(schnorrkel::ExpansionMode::Ed25519)
+        }
+
+        /// Secret key and the Substrate account (Blake2 hash of the compressed public key) for an ECDSA holder
+        fn ecdsa_holder(seed: u8) -> (secp256k1::SecretKey, AccountId) {
+            let secret = secp256k1::SecretKey::from_slice(&[seed; 32]).unwrap();
+            let public = secp256k1::PublicKey::from_secret_key_global(&secret).serialize();
+            let mut account = [0u8; 32];
+            ink::env::hash_bytes::<ink::env::hash::Blake2x256>(&public, &mut account);
+            (secret, AccountId::from(account))
+        }
+
+        fn ecdsa_sign(secret: &secp256k1::SecretKey, message: &[u8]) -> [u8; 65] {
+            let mut hash = [0u8; 32];
+            ink::env::hash_bytes::<ink::env::hash::Blake2x256>(message, &mut hash);
+            let (recovery_id, compact) = secp256k1::SECP256K1
+                .sign_ecdsa_recoverable(&secp256k1::Message::from_digest(hash), secret)
+                .serialize_compact();
+            let mut signature = [0u8; 65];
+            signature[..64].copy_from_slice(&compact);
+            signature[64] = recovery_id.to_i32() as u8;
+            signature
+        }
+
+        /// Event with a gate scanner (django) for its venue, plus a minted NFT held by `holder`
+        fn check_in_setup(contract: &mut InkTix, holder: AccountId) -> (u32, u64) {
+            let accounts = default_accounts();
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event(
+                "Game".to_string(), venue_id, CHECK_IN_EVENT_DATE, 100, 1000, EventCategory::Generic,
+            ).unwrap();
+            contract.grant_role(accounts.django, Role::GateScanner, RoleScope::Venue(venue_id)).unwrap();
+
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(holder, 1_000_000);
+            set_caller(holder);
+            let seat = Seat {
+                section: "A".to_string(), row: "1".to_string(), seat_number: "1".to_string(),
+                seat_type: SeatType::GeneralAdmission, access_level: AccessLevel::Standard, price_multiplier: 10000,
+            };
+            let ticket_id = buy_ticket(contract, event_id, seat).unwrap();
+            let token_id = contract.mint_ticket_nft(ticket_id).unwrap();
+            (event_id, token_id)
+        }
+
+        #[ink::test]
+        fn test_check_in_with_sr25519_signature() {
+            let accounts = default_accounts();
+            let keypair = sr25519_keypair(7);
+            let holder = AccountId::from(keypair.public.to_bytes());
+            let mut contract = InkTix::new();
+            let (event_id, token_id) = check_in_setup(&mut contract, holder);
+            let hash = contract.get_nft_by_ticket(1).unwrap().verification_hash;
+            let sign = |contract: &InkTix, issued_at: u64| {
+                let message = contract.get_check_in_message(token_id, issued_at).unwrap();
+                CheckInSignature::Sr25519(keypair.sign_simple(b"substrate", &message).to_bytes())
+            };
+
+            // Too early: the check-in window opens a few hours before the event
+            set_caller(accounts.django);
+            assert_eq!(contract.check_in(token_id, hash, 0, sign(&contract, 0)), Err("Check-in has not opened".to_string()));
+
+            let now = CHECK_IN_EVENT_DATE - 1000;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(now);
+            let signature = sign(&contract, now);
+            let stale_at = now - nft_management::CHECK_IN_CHALLENGE_TTL - 1;
+            assert_eq!(
+                contract.check_in(token_id, hash, stale_at, sign(&contract, stale_at)),
+                Err("Check-in challenge has expired".to_string()),
+            );
+            assert_eq!(
+                contract.check_in(token_id, [0u8; 32], now, signature.clone()),
+                Err("Verification hash does not match".to_string()),
+            );
+            let forged = sr25519_keypair(8).sign_simple(b"substrate", &contract.get_check_in_message(token_id, now).unwrap());
+            assert_eq!(
+                contract.check_in(token_id, hash, now, CheckInSignature::Sr25519(forged.to_bytes())),
+                Err("Invalid check-in signature".to_string()),
+            );
+            set_caller(accounts.bob);
+            assert_eq!(
+                contract.check_in(token_id, hash, now, signature.clone()),
+                Err(String::from(InkTixError::MissingRole)),
+            );
+
+            set_caller(accounts.django);
+            let attendance_token_id = contract.check_in(token_id, hash, now, signature.clone()).unwrap();
+            assert!(contract.verify_ticket_nft(token_id).unwrap().is_used);
+            assert_eq!(contract.check_in(token_id, hash, now, signature), Err("Ticket already used".to_string()));
+
+            let checked_in: TicketCheckedIn = decode_event(recorded_events().last().unwrap());
+            assert_eq!((checked_in.event_id, checked_in.holder, checked_in.scanner), (event_id, holder, accounts.django));
+            assert_eq!(checked_in.attendance_token_id, attendance_token_id);
+        }
+
+        #[cfg(feature = "sports")]
+        #[ink::test]
+        fn test_check_in_records_team_attendance() {
+            let accounts = default_accounts();
+            let keypair = sr25519_keypair(8);
+            let holder = AccountId::from(keypair.public.to_bytes());
+            let mut contract = InkTix::new();
+            let (home, away) = season_teams(&mut contract, &[1]);
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event(
+                "Game".to_string(), venue_id, CHECK_IN_EVENT_DATE, 100, 1000, home_game(home, away, 1),
+            ).unwrap();
+            contract.grant_role(accounts.django, Role::GateScanner, RoleScope::Event(event_id)).unwrap();
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(holder, 1_000_000);
+            set_caller(holder);
+            contract.create_team_loyalty_profile(home).unwrap();
+            let ticket_id = buy_ticket(&mut contract, event_id, layout_seat("A", "1", 1)).unwrap();
+            let token_id = contract.mint_ticket_nft(ticket_id).unwrap();
+            let hash = contract.get_nft_by_ticket(ticket_id).unwrap().verification_hash;
+
+            // Checking in credits the teams the holder follows, once
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(CHECK_IN_EVENT_DATE);
+            let message = contract.get_check_in_message(token_id, CHECK_IN_EVENT_DATE).unwrap();
+            let signature = CheckInSignature::Sr25519(keypair.sign_simple(b"substrate", &message).to_bytes());
+            set_caller(accounts.django);
+            contract.check_in(token_id, hash, CHECK_IN_EVENT_DATE, signature).unwrap();
+            let profile = contract.get_team_loyalty_profile(holder, home).unwrap();
+            assert_eq!((profile.total_events_attended, profile.loyalty_points), (1, 100));
+            assert!(contract.get_team_loyalty_profile(holder, away).is_none());
+            assert_eq!(contract.record_attendance(holder, home, event_id), Err("Attendance already recorded".to_string()));
+        }
+
+        #[ink::test]
+        fn test_check_in_with_ecdsa_signature_and_stale_tickets() {
+            let accounts = default_accounts();
+            let (secret, holder) = ecdsa_holder(9);
+            let mut contract = InkTix::new();
+            let (event_id, token_id) = check_in_setup(&mut contract, holder);
+            let hash = contract.get_nft_by_ticket(1).unwrap().verification_hash;
+
+            // A ticket transferred after minting gets a new hash, so old codes stop scanning
+            contract.transfer_ticket(1, accounts.eve).unwrap();
+            let new_hash = contract.get_nft_by_ticket(1).unwrap().verification_hash;
+            assert_ne!(hash, new_hash);
+            assert!(contract.verify_ticket_nft(token_id).unwrap().is_valid);
+            set_caller(accounts.eve);
+            contract.transfer_ticket(1, holder).unwrap();
+            let hash = contract.get_nft_by_ticket(1).unwrap().verification_hash;
+
+            let now = CHECK_IN_EVENT_DATE;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(now);
+            let signature = CheckInSignature::Ecdsa(ecdsa_sign(&secret, &contract.get_check_in_message(token_id, now).unwrap()));
+            set_caller(accounts.django);
+            assert_eq!(contract.check_in(token_id, new_hash, now, signature.clone()), Err("Verification hash does not match".to_string()));
+
+            // Moving the NFT moves its ticket, so the pair stays valid and scans for the holder
+            set_caller(holder);
+            contract.transfer_nft(token_id, accounts.eve).unwrap();
+            assert_eq!(contract.get_ticket(1).unwrap().owner, accounts.eve);
+            assert!(contract.verify_ticket_nft(token_id).unwrap().is_valid);
+            set_caller(accounts.eve);
+            contract.transfer_nft(token_id, holder).unwrap();
+            set_caller(accounts.django);
+            assert!(contract.check_in(token_id, hash, now, signature).is_ok());
+
+            // A ticket for a cancelled event is no longer valid
+            set_caller(accounts.alice);
+            contract.update_event_status(event_id, EventStatus::Cancelled).unwrap();
+            assert_eq!(
+                contract.verify_ticket_nft(token_id).unwrap().invalid_reason,
+                Some("Event has been cancelled".to_string()),
+            );
+        }
+
+        /// Event with two minted ticket NFTs held by alice
+        fn psp34_setup(contract: &mut InkTix) -> u32 {
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Game".to_string(), venue_id, 1_000_000_000, 100, 1000, EventCategory::Generic).unwrap();
+            let seat = Seat {
+                section: "A".to_string(), row: "1".to_string(), seat_number: "1".to_string(),
+                seat_type: SeatType::GeneralAdmission, access_level: AccessLevel::Standard, price_multiplier: 10000,
+            };
+            for _ in 0..2 {
+                let ticket_id = buy_ticket(contract, event_id, seat.clone()).unwrap();
+                contract.mint_ticket_nft(ticket_id).unwrap();
+            }
+            event_id
+        }
+
+        #[ink::test]
+        fn test_psp34_ticket_collection() {
+            let accounts = default_accounts();
+            let mut contract = InkTix::new();
+            let event_id = psp34_setup(&mut contract);
+
+            assert_eq!((contract.balance_of(accounts.alice), contract.total_supply()), (2, 2));
+            assert_eq!(contract.owner_of(Id::U32(1)), Some(accounts.alice));
+            assert_eq!(contract.owner_of(Id::Bytes(vec![1])), None);
+            assert_eq!(contract.token_by_index(1), Ok(Id::U64(2)));
+            assert_eq!(contract.owners_token_by_index(accounts.alice, 0), Ok(Id::U64(1)));
+            assert_eq!(contract.owners_token_by_index(accounts.alice, 2), Err(PSP34Error::TokenNotExists));
+            assert_eq!(contract.get_attribute(contract.collection_id(), b"symbol".to_vec()), Some(b"TIX".to_vec()));
+            assert_eq!(contract.get_attribute(Id::U64(1), b"event_id".to_vec()), Some(event_id.to_string().into_bytes()));
+            assert_eq!(contract.get_attribute(Id::U64(1), b"uri".to_vec()), None);
+
+            // Only the owner or an approved operator can move a token
+            assert_eq!(contract.approve(accounts.alice, None, true), Err(PSP34Error::SelfApprove));
+            set_caller(accounts.bob);
+            assert_eq!(contract.transfer(accounts.bob, Id::U64(1), Vec::new()), Err(PSP34Error::NotApproved));
+            assert_eq!(contract.approve(accounts.charlie, Some(Id::U64(1)), true), Err(PSP34Error::NotApproved));
+
+            set_caller(accounts.alice);
+            contract.approve(accounts.bob, Some(Id::U64(1)), true).unwrap();
+            assert!(contract.allowance(accounts.alice, accounts.bob, Some(Id::U64(1))));
+            assert!(!contract.allowance(accounts.alice, accounts.bob, Some(Id::U64(2))));
+            set_caller(accounts.bob);
+            contract.transfer(accounts.charlie, Id::U64(1), vec![0xAB]).unwrap();
+            assert_eq!(contract.owner_of(Id::U64(1)), Some(accounts.charlie));
+            assert!(!contract.allowance(accounts.charlie, accounts.bob, Some(Id::U64(1))));
+            assert_eq!(contract.transfer(accounts.bob, Id::U64(2), Vec::new()), Err(PSP34Error::NotApproved));
+
+            // Operator approval covers all of the owner's tokens until revoked
+            set_caller(accounts.alice);
+            contract.approve(accounts.bob, None, true).unwrap();
+            set_caller(accounts.bob);
+            contract.transfer(accounts.django, Id::U64(2), Vec::new()).unwrap();
+            assert_eq!(contract.balance_of(accounts.alice), 0);
+            assert_eq!(contract.transfer(accounts.bob, Id::U64(3), Vec::new()), Err(PSP34Error::TokenNotExists));
+            set_caller(accounts.alice);
+            contract.approve(accounts.bob, None, false).unwrap();
+            assert!(!contract.allowance(accounts.alice, accounts.bob, None));
+
+            let events = recorded_events();
+            let approval: Approval = decode_event(&events[events.len() - 1]);
+            assert_eq!((approval.operator, approval.id, approval.approved), (accounts.bob, None, false));
+            let transfer: Transfer = decode_event(&events[events.len() - 2]);
+            assert_eq!((transfer.from, transfer.to, transfer.id), (Some(accounts.alice), Some(accounts.django), Id::U64(2)));
+        }
+
+        #[ink::test]
+        fn test_attendance_tokens_stay_out_of_the_ticket_collection() {
+            let accounts = default_accounts();
+            let mut contract = InkTix::new();
+            let event_id = psp34_setup(&mut contract);
+            let supply = contract.total_supply();
+            let attendance_token_id = contract.use_ticket_nft(1).unwrap();
+
+            let token = contract.get_attendance_token(attendance_token_id).unwrap();
+            assert_eq!((token.owner, token.event_id, token.ticket_id), (accounts.alice, event_id, 1));
+            assert_eq!(contract.total_supply(), supply);
+
+            // Only admins choose the soulbound collection tokens are minted into
+            set_caller(accounts.bob);
+            assert_eq!(
+                contract.set_attendance_token_contract(Some(accounts.django)),
+                Err(String::from(InkTixError::MissingRole))
+            );
+            set_caller(accounts.alice);
+            contract.set_attendance_token_contract(Some(accounts.django)).unwrap();
+            assert_eq!(contract.get_attendance_token_contract(), Some(accounts.django));
+            contract.set_attendance_token_contract(None).unwrap();
+            assert_eq!(contract.get_attendance_token_contract(), None);
+        }
+
+        #[ink::test]
+        fn test_ticket_and_nft_transfers_stay_in_sync() {
+            let accounts = default_accounts();
+            let mut contract = InkTix::new();
+            use_contract_account();
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Game".to_string(), venue_id, 1_000_000_000, 100, 1000, EventCategory::Generic).unwrap();
+            contract.set_dynamic_pricing(event_id, false).unwrap();
+            contract.configure_anti_scalping(event_id, AntiScalpingConfig {
+                transfer_restricted: true, transfer_lock_period: 60_000, ..resale_config(event_id)
+            }).unwrap();
+            let seat = Seat {
+                section: "A".to_string(), row: "1".to_string(), seat_number: "1".to_string(),
+                seat_type: SeatType::GeneralAdmission, access_level: AccessLevel::Standard, price_multiplier: 10000,
+            };
+            let ticket_id = buy_ticket(&mut contract, event_id, seat).unwrap();
+            let token_id = contract.mint_ticket_nft(ticket_id).unwrap();
+
+            // Restricted events only allow transfers through the resale marketplace, after the lock period
+            let restricted = "Transfers are restricted for this event".to_string();
+            assert_eq!(contract.transfer_ticket(ticket_id, accounts.bob), Err(restricted.clone()));
+            assert_eq!(contract.transfer_nft(token_id, accounts.bob), Err(restricted.clone()));
+            assert_eq!(contract.transfer(accounts.bob, Id::U64(token_id), Vec::new()), Err(PSP34Error::Custom(restricted)));
+            contract.approve(ink::env::test::callee::<ink::env::DefaultEnvironment>(), Some(Id::U64(token_id)), true).unwrap();
+            assert_eq!(
+                contract.resell_ticket(ticket_id, 1200, CurrencyId::DOT),
+                Err("Ticket is within its transfer lock period".to_string()),
+            );
+            assert_eq!(contract.get_ticket_transfer_history(ticket_id), None);
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(60_000);
+            let listing_id = contract.resell_ticket(ticket_id, 1200, CurrencyId::DOT).unwrap();
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.bob, 1_000_000);
+            set_caller(accounts.bob);
+            ink::env::pay_with_call!(contract.buy_resale_listing(listing_id), 1200).unwrap();
+            assert_eq!(contract.get_nft_by_ticket(ticket_id).unwrap().owner, accounts.bob);
+            assert_eq!((contract.balance_of(accounts.alice), contract.balance_of(accounts.bob)), (0, 1));
+
+            // Once the event lifts the restriction, moving the NFT moves the ticket too
+            set_caller(accounts.alice);
+            contract.configure_anti_scalping(event_id, resale_config(event_id)).unwrap();
+            set_caller(accounts.bob);
+            contract.transfer_nft(token_id, accounts.charlie).unwrap();
+            assert_eq!(contract.get_ticket(ticket_id).unwrap().owner, accounts.charlie);
+            assert!(contract.get_user_tickets(accounts.bob).is_empty());
+            assert_eq!(contract.get_user_tickets(accounts.charlie), vec![ticket_id]);
+            assert_eq!(contract.owner_of(Id::U64(token_id)), Some(accounts.charlie));
+            assert!(contract.verify_ticket_nft(token_id).unwrap().is_valid);
+
+            let history = contract.get_ticket_transfer_history(ticket_id).unwrap();
+            assert_eq!((history.original_owner, history.current_owner), (accounts.alice, accounts.charlie));
+            assert_eq!(history.transfer_count, 2);
+            assert_eq!(history.transfer_reasons, vec![TransferReason::Resale, TransferReason::Gift]);
+            assert_eq!(history.price_history, vec![(60_000, 1200)]);
+        }
+
+        #[ink::test]
+        fn test_multi_currency_charges_and_token_registry() {
+            let accounts = default_accounts();
+            let mut contract = InkTix::new();
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Game".to_string(), venue_id, 1_000_000_000, 100, 1000, EventCategory::Generic).unwrap();
+            contract.set_dynamic_pricing(event_id, false).unwrap();
+            let seat = Seat {
+                section: "A".to_string(), row: "1".to_string(), seat_number: "1".to_string(),
+                seat_type: SeatType::GeneralAdmission, access_level: AccessLevel::Standard, price_multiplier: 10000,
+            };
+
+            // Rates are the DOT value of one unit, scaled by 10^18: 1 ACA = 0.05 DOT, 1 KSM = 15 DOT
+            contract.update_currency_rate(CurrencyId::ACA, 50_000_000_000_000_000).unwrap();
+            contract.update_currency_rate(CurrencyId::KSM, 15_000_000_000_000_000_000).unwrap();
+            assert_eq!(contract.convert_from_dot(1000, CurrencyId::DOT), Ok(1000));
+            assert_eq!(contract.convert_from_dot(1000, CurrencyId::ACA), Ok(20_000));
+            assert_eq!(contract.convert_from_dot(1500, CurrencyId::KSM), Ok(100));
+            assert_eq!(contract.storage.convert_to_dot(20_000, CurrencyId::ACA), Ok(1000));
+            assert_eq!(contract.storage.get_exchange_rate(CurrencyId::KSM), Ok(15_000_000_000_000_000_000));
+
+            // Charges that don't convert evenly round up in the platform's favour
+            assert_eq!(contract.convert_from_dot(1000, CurrencyId::KSM), Ok(67));
+            assert_eq!(contract.convert_from_dot(1501, CurrencyId::KSM), Ok(101));
+
+            // Large prices convert exactly, and overflowing ones fail instead of saturating
+            let large_price = 500 * 1_000_000_000_000_000_000;
+            assert_eq!(contract.convert_from_dot(large_price, CurrencyId::ACA), Ok(large_price * 20));
+            assert_eq!(contract.storage.convert_to_dot(large_price * 20, CurrencyId::ACA), Ok(large_price));
+            let conversion_failed = Err(String::from(InkTixError::CurrencyConversionFailed));
+            assert_eq!(contract.convert_from_dot(u128::MAX / 2, CurrencyId::ACA), conversion_failed);
+            assert_eq!(contract.storage.convert_to_dot(u128::MAX, CurrencyId::KSM), conversion_failed);
+
+            // Native DOT purchases record the DOT equivalent and revenue
+            let ticket_id = buy_ticket(&mut contract, event_id, seat.clone()).unwrap();
+            let ticket = contract.get_ticket(ticket_id).unwrap();
+            assert_eq!(ticket.purchase_currency, CurrencyId::DOT);
+            assert_eq!(ticket.dot_equivalent_paid, ticket.purchase_price);
+            assert_eq!(contract.get_currency_revenue(CurrencyId::DOT), ticket.purchase_price);
+
+            // Token currencies need a registered PSP22 contract
+            assert_eq!(
+                ink::env::pay_with_call!(contract.purchase_ticket(event_id, seat, CurrencyId::ACA, None, None), 0),
+                Err("No token contract registered for this currency".to_string()),
+            );
+            assert_eq!(contract.get_currency_revenue(CurrencyId::ACA), 0);
+            assert_eq!(contract.get_user_tickets(accounts.alice), vec![ticket_id]);
+
+            assert_eq!(contract.set_currency_token(CurrencyId::DOT, Some(accounts.frank)), Err("DOT is paid in native value".to_string()));
+            set_caller(accounts.bob);
+            assert_eq!(
+                contract.set_currency_token(CurrencyId::ACA, Some(accounts.frank)),
+                Err(String::from(InkTixError::MissingRole)),
+            );
+            set_caller(accounts.alice);
+            contract.set_currency_token(CurrencyId::ACA, Some(accounts.frank)).unwrap();
+            assert_eq!(contract.get_currency_token(CurrencyId::ACA), Some(accounts.frank));
+            let updated: CurrencyTokenUpdated = decode_event(recorded_events().last().unwrap());
+            assert_eq!((updated.currency, updated.token), (CurrencyId::ACA, Some(accounts.frank)));
+            contract.set_currency_token(CurrencyId::ACA, None).unwrap();
+            assert_eq!(contract.get_currency_token(CurrencyId::ACA), None);
+
+            // Resale stays in DOT
+            assert_eq!(
+                contract.resell_ticket(ticket_id, 1000, CurrencyId::KSM),
+                Err("Resale listings are settled in DOT".to_string()),
+            );
+        }
+
+        #[ink::test]
+        fn test_rate_oracle_median_staleness_and_deviation() {
+            let accounts = default_accounts();
+            let mut contract = InkTix::new();
+            let rate = 1_000_000_000_000_000_000u128;
+            contract.grant_role(accounts.bob, Role::OracleFeeder, RoleScope::Global).unwrap();
+            contract.grant_role(accounts.charlie, Role::OracleFeeder, RoleScope::Global).unwrap();
+
+            // Token payments wait for a reported rate; DOT's rate is fixed
+            assert_eq!(contract.convert_from_dot(1000, CurrencyId::AUSD), Err("Currency rate has not been reported".to_string()));
+            assert_eq!(contract.update_currency_rate(CurrencyId::DOT, rate), Err("DOT rate is fixed".to_string()));
+            set_caller(accounts.django);
+            assert_eq!(contract.update_currency_rate(CurrencyId::AUSD, rate), Err(String::from(InkTixError::MissingRole)));
+
+            // The effective rate is the median of the feeders' latest reports
+            set_caller(accounts.alice);
+            contract.update_currency_rate(CurrencyId::AUSD, rate).unwrap();
+            set_caller(accounts.bob);
+            contract.update_currency_rate(CurrencyId::AUSD, rate * 104 / 100).unwrap();
+            set_caller(accounts.charlie);
+            contract.update_currency_rate(CurrencyId::AUSD, rate * 98 / 100).unwrap();
+            assert_eq!(contract.get_currency_rate(CurrencyId::AUSD), Some(rate));
+            assert_eq!(contract.get_rate_submissions(CurrencyId::AUSD).len(), 3);
+            assert_eq!(contract.convert_from_dot(1000, CurrencyId::AUSD), Ok(1000));
+            let updated: CurrencyRateUpdated = decode_event(recorded_events().last().unwrap());
+            assert_eq!((updated.currency, updated.rate), (CurrencyId::AUSD, rate));
+
+            // A feeder's new report replaces its old one, within the deviation limit
+            assert_eq!(
+                contract.update_currency_rate(CurrencyId::AUSD, rate * 120 / 100),
+                Err("Rate deviates too far from the current rate".to_string()),
+            );
+            contract.update_currency_rate(CurrencyId::AUSD, rate * 106 / 100).unwrap();
+            assert_eq!(contract.get_currency_rate(CurrencyId::AUSD), Some(rate * 104 / 100));
+            assert_eq!(contract.get_rate_submissions(CurrencyId::AUSD).len(), 3);
+            assert_eq!(contract.get_rate_history(CurrencyId::AUSD).len(), 4);
+
+            // Stale rates stop token payments until a fresh report arrives
+            let max_age = contract.get_rate_oracle_config().max_rate_age;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(max_age + 1);
+            assert_eq!(contract.convert_from_dot(1000, CurrencyId::AUSD), Err("Currency rate is stale".to_string()));
+            set_caller(accounts.bob);
+            contract.update_currency_rate(CurrencyId::AUSD, rate * 150 / 100).unwrap();
+            assert_eq!(contract.get_rate_submissions(CurrencyId::AUSD).len(), 1);
+            assert_eq!(contract.convert_from_dot(1500, CurrencyId::AUSD), Ok(1000));
+
+            // Admins can deactivate a currency and tune the limits
+            assert_eq!(contract.set_currency_active(CurrencyId::AUSD, false), Err(String::from(InkTixError::MissingRole)));
+            set_caller(accounts.alice);
+            contract.set_currency_active(CurrencyId::AUSD, false).unwrap();
+            assert_eq!(contract.convert_from_dot(1000, CurrencyId::AUSD), Err("Currency is not active".to_string()));
+            assert_eq!(contract.set_currency_active(CurrencyId::DOT, false), Err("DOT cannot be deactivated".to_string()));
+            contract.set_currency_active(CurrencyId::AUSD, true).unwrap();
+            assert!(contract.get_currency_rate_info(CurrencyId::AUSD).unwrap().is_active);
+            assert_eq!(
+                contract.set_rate_oracle_config(RateOracleConfig { max_rate_age: 0, max_deviation_bps: 500 }),
+                Err("Rate oracle limits must be greater than 0".to_string()),
+            );
+            contract.set_rate_oracle_config(RateOracleConfig { max_rate_age: max_age, max_deviation_bps: 500 }).unwrap();
+            assert_eq!(contract.get_rate_oracle_config().max_deviation_bps, 500);
+        }
+
+        #[cfg(feature = "sports")]
+        #[ink::test]
+        fn test_game_results_reach_quorum_and_feed_pricing_loyalty_and_fantasy() {
+            let accounts = default_accounts();
+            let mut contract = InkTix::new();
+            let lakers = contract.register_team("Lakers".to_string(), "Los Angeles".to_string(), SportType::Basketball).unwrap();
+            let warriors = contract.register_team("Warriors".to_string(), "San Francisco".to_string(), SportType::Basketball).unwrap();
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            contract.storage.seasons.insert(1, &crate::types::sports::season::Season {
+                id: 1, name: "2024 Season".to_string(), sport_type: SportType::Basketball,
+                start_date: 0, end_date: 2_000_000_000, regular_season_games: 82, active: true,
+                season_pass_base_price: 1000, early_bird_discount: 20, early_bird_deadline: 0,
+            });
+            let game = |home_team_id, away_team_id, game_type| EventCategory::Sports {
+                home_team_id, away_team_id, season_id: 1, game_type, sport_type: SportType::Basketball,
+            };
+            let final_id = contract.create_event("Finals".to_string(), venue_id, 1000, 100, 1000, game(lakers, warriors, GameType::Championship)).unwrap();
+            let generic_id = contract.create_event("Show".to_string(), venue_id, 1000, 100, 1000, EventCategory::Generic).unwrap();
+
+            let player_id = contract.register_fantasy_player("Guard".to_string(), PlayerPosition::Flex, lakers).unwrap();
+            let league_id = contract.create_fantasy_league("League".to_string(), "".to_string(), 10, 0, CurrencyId::DOT).unwrap();
+            let fantasy_team_id = contract.create_fantasy_team(league_id, "Showtime".to_string()).unwrap();
+            contract.add_fantasy_player(fantasy_team_id, player_id).unwrap();
+            assert_eq!(contract.add_fantasy_player(fantasy_team_id, player_id), Err("Player already on roster".to_string()));
+            contract.create_team_loyalty_profile(lakers).unwrap();
+
+            contract.grant_role(accounts.bob, Role::ResultReporter, RoleScope::Global).unwrap();
+            contract.grant_role(accounts.charlie, Role::ResultReporter, RoleScope::Event(final_id)).unwrap();
+            assert_eq!(contract.report_game_result(final_id, 110, 100), Err("Game has not been played yet".to_string()));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(2000);
+            assert_eq!(contract.report_game_result(generic_id, 1, 0), Err("Event is not a sports event".to_string()));
+            let cancelled_id = contract.create_event("Rained out".to_string(), venue_id, 1000, 100, 1000, game(lakers, warriors, GameType::RegularSeason)).unwrap();
+            contract.update_event_status(cancelled_id, EventStatus::Cancelled).unwrap();
+            assert_eq!(contract.report_game_result(cancelled_id, 1, 0), Err("Event is not active or completed".to_string()));
+            set_caller(accounts.django);
+            assert_eq!(contract.report_game_result(final_id, 110, 100), Err(String::from(InkTixError::MissingRole)));
+
+            // Disputed reports wait until a quorum agrees on one score
+            set_caller(accounts.bob);
+            contract.report_game_result(final_id, 100, 110).unwrap();
+            contract.report_game_result(final_id, 110, 100).unwrap();
+            let reports = contract.get_game_result_reports(final_id);
+            assert_eq!((reports.len(), reports[0].home_score, reports[0].away_score), (1, 110, 100));
+            set_caller(accounts.charlie);
+            contract.report_game_result(final_id, 100, 110).unwrap();
+            assert_eq!(contract.get_game_result(final_id), None);
+            assert_eq!(contract.get_game_result_reports(final_id).len(), 2);
+
+            // Reports from reporters whose role was revoked no longer count
+            set_caller(accounts.alice);
+            contract.revoke_role(accounts.bob, Role::ResultReporter, RoleScope::Global).unwrap();
+            contract.grant_role(accounts.django, Role::ResultReporter, RoleScope::Event(final_id)).unwrap();
+            set_caller(accounts.django);
+            contract.report_game_result(final_id, 110, 100).unwrap();
+            assert_eq!(contract.get_game_result(final_id), None);
+            let reporters: Vec<AccountId> = contract.get_game_result_reports(final_id).iter().map(|r| r.reporter).collect();
+            assert_eq!(reporters, vec![accounts.charlie, accounts.django]);
+            set_caller(accounts.alice);
+            contract.report_game_result(final_id, 110, 100).unwrap();
+            let result = contract.get_game_result(final_id).unwrap();
+            assert_eq!((result.home_score, result.away_score, result.confirmations), (110, 100, 2));
+            assert!(contract.get_game_result_reports(final_id).is_empty());
+            let finalized: GameResultFinalized = decode_event(recorded_events().last().unwrap());
+            assert_eq!((finalized.event_id, finalized.home_score, finalized.away_score), (final_id, 110, 100));
+            set_caller(accounts.django);
+            assert_eq!(contract.report_game_result(final_id, 110, 100), Err("Game result already finalized".to_string()));
+
+            // Season records are derived from the result
+            let home = contract.get_team_performance(lakers, 1).unwrap();
+            assert_eq!((home.wins, home.losses, home.win_percentage, home.streak), (1, 0, 1000, 1));
+            assert_eq!((home.home_record_wins, home.points_scored_avg, home.points_allowed_avg), (1, 110, 100));
+            assert_eq!((home.performance_rank, home.playoff_probability), (1, 100));
+            let away = contract.get_team_performance(warriors, 1).unwrap();
+            assert_eq!((away.wins, away.losses, away.streak, away.performance_rank), (0, 1, -1, 2));
+
+            // Pricing reads the home team's season record
+            set_caller(accounts.alice);
+            let lakers_home = contract.create_event("Game 2".to_string(), venue_id, 3000, 100, 1000, game(lakers, warriors, GameType::RegularSeason)).unwrap();
+            let warriors_home = contract.create_event("Game 3".to_string(), venue_id, 3000, 100, 1000, game(warriors, lakers, GameType::RegularSeason)).unwrap();
+            let seat = layout_seat("A", "1", 1);
+            let lakers_quote = contract.get_price_quote(lakers_home, seat.clone(), false).unwrap();
+            let warriors_quote = contract.get_price_quote(warriors_home, seat, false).unwrap();
+            assert_eq!(lakers_quote.final_price, warriors_quote.final_price * 13500 / 10000);
+
+            // The championship win boosts loyalty points for attendance
+            let rewards = contract.get_team_performance_rewards(lakers);
+            assert_eq!(rewards.len(), 1);
+            assert_eq!((rewards[0].reward_type.clone(), rewards[0].points_multiplier), (TeamPerformanceRewardType::ChampionshipWin, 20000));
+            assert!(contract.get_team_performance_rewards(warriors).is_empty());
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.alice, 100_000_000);
+            assert_eq!(contract.record_attendance(accounts.alice, lakers, lakers_home), Err("No ticket for this event".to_string()));
+            buy_ticket(&mut contract, lakers_home, layout_seat("A", "1", 2)).unwrap();
+            set_caller(accounts.bob);
+            assert_eq!(contract.record_attendance(accounts.alice, lakers, lakers_home), Err(String::from(InkTixError::MissingRole)));
+            set_caller(accounts.alice);
+            assert_eq!(contract.record_attendance(accounts.alice, lakers, generic_id), Err("Team is not playing this event".to_string()));
+            contract.record_attendance(accounts.alice, lakers, lakers_home).unwrap();
+            assert_eq!(contract.record_attendance(accounts.alice, lakers, lakers_home), Err("Attendance already recorded".to_string()));
+            assert_eq!(contract.get_team_loyalty_profile(accounts.alice, lakers).unwrap().loyalty_points, 200);
+
+            // Fantasy players score from their team's results
+            let player = contract.get_fantasy_player(player_id).unwrap();
+            assert_eq!((player.points, player.games_played), (14, 1));
+            let leaderboard = contract.refresh_fantasy_leaderboard(league_id).unwrap();
+            assert_eq!((leaderboard.entries[0].total_points, leaderboard.entries[0].rank), (14, 1));
+
+            assert_eq!(contract.set_result_quorum(0), Err("Result quorum must be greater than 0".to_string()));
+            contract.set_result_quorum(3).unwrap();
+            assert_eq!(contract.get_result_quorum(), 3);
+        }
+
+        #[ink::test]
+        fn test_pricing_policies_are_validated_versioned_and_scoped() {
+            let accounts = default_accounts();
+            let mut contract = InkTix::new();
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Game".to_string(), venue_id, 1640995200, 100, 1000, EventCategory::Generic).unwrap();
+            let seat = layout_seat("A", "1", 1);
+
+            // The default policy reproduces the original curves: 0% sold is 0.8x, 19 days out is 1.0x
+            let quote = contract.get_price_quote(event_id, seat.clone(), false).unwrap();
+            assert_eq!((quote.final_price, quote.demand_multiplier, quote.time_multiplier, quote.policy_version), (800, 8000, 10000, 0));
+            assert_eq!(contract.get_pricing_policy(event_id), Some(PricingPolicy::default()));
+
+            let flat = PricingPolicy {
+                demand_bands: vec![(0, 10000)],
+                time_bands: vec![(0, 10000)],
+                seat_multipliers: vec![(SeatType::GeneralAdmission, 12000)],
+                ..PricingPolicy::default()
+            };
+            let invalid = [
+                (PricingPolicy { demand_bands: vec![(10, 10000)], ..flat.clone() }, "Pricing bands must start at 0 and ascend"),
+                (PricingPolicy { demand_bands: vec![(0, 10000), (50, 9000)], ..flat.clone() }, "Demand multipliers must not decrease as demand rises"),
+                (PricingPolicy { time_bands: vec![(0, 10000), (7, 12000)], ..flat.clone() }, "Time multipliers must not increase further from the event"),
+                (PricingPolicy { time_bands: vec![(0, 10000), (0, 9000)], ..flat.clone() }, "Pricing bands must start at 0 and ascend"),
+                (
+                    PricingPolicy { seat_multipliers: vec![(SeatType::Club, 15000), (SeatType::Club, 16000)], ..flat.clone() },
+                    "Seat type listed more than once",
+                ),
+                (PricingPolicy { floor_bps: 12000, ..flat.clone() }, "Price floor cannot exceed the base price"),
+                (PricingPolicy { cap_bps: 9000, ..flat.clone() }, "Price cap cannot be below the base price"),
+                (PricingPolicy { rivalries: vec![(1, 1, 15000)], ..flat.clone() }, "A rivalry needs two different teams"),
+                (PricingPolicy { rivalries: vec![(1, 2, 15000), (2, 1, 12000)], ..flat.clone() }, "Rivalry listed more than once"),
+            ];
+            for (policy, error) in invalid {
+                assert_eq!(contract.set_venue_pricing_policy(venue_id, Some(policy)), Err(error.to_string()));
+            }
+
+            // Venue policies apply to the venue's events; event policies override them
+            assert_eq!(contract.set_venue_pricing_policy(venue_id, Some(flat.clone())), Ok(1));
+            let quote = contract.get_price_quote(event_id, seat.clone(), false).unwrap();
+            assert_eq!((quote.final_price, quote.seat_multiplier, quote.policy_version), (1200, 12000, 1));
+            assert_eq!(contract.set_event_pricing_policy(event_id, Some(PricingPolicy { cap_bps: 11000, ..flat.clone() })), Ok(2));
+            let quote = contract.get_price_quote(event_id, seat.clone(), false).unwrap();
+            assert_eq!((quote.final_price, quote.policy_version), (1100, 2));
+            let updated: PricingPolicyUpdated = decode_event(recorded_events().last().unwrap());
+            assert_eq!((updated.venue_id, updated.event_id, updated.version), (None, Some(event_id), 2));
+
+            assert_eq!(contract.set_event_pricing_policy(event_id, None), Ok(0));
+            assert_eq!(contract.get_price_quote(event_id, seat.clone(), false).unwrap().policy_version, 1);
+            assert_eq!(contract.get_venue_pricing_policy(venue_id).version, 1);
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.set_event_pricing_policy(event_id, Some(flat.clone())), Err(String::from(InkTixError::MissingRole)));
+            assert_eq!(contract.set_venue_pricing_policy(venue_id, None), Err(String::from(InkTixError::MissingRole)));
+
+            // Rivalries are keyed by team IDs in either order
+            #[cfg(feature = "sports")]
+            {
+                set_caller(accounts.alice);
+                let home = contract.register_team("Home".to_string(), "A".to_string(), SportType::Basketball).unwrap();
+                let away = contract.register_team("Away".to_string(), "B".to_string(), SportType::Basketball).unwrap();
+                contract.storage.seasons.insert(1, &crate::types::sports::season::Season {
+                    id: 1, name: "Season".to_string(), sport_type: SportType::Basketball, start_date: 0, end_date: 0,
+                    regular_season_games: 82, active: true, season_pass_base_price: 0, early_bird_discount: 0, early_bird_deadline: 0,
+                });
+                contract.set_venue_pricing_policy(venue_id, Some(PricingPolicy { rivalries: vec![(away, home, 15000)], ..flat.clone() })).unwrap();
+                let game_id = contract.create_event("Derby".to_string(), venue_id, 1640995200, 100, 1000, EventCategory::Sports {
+                    home_team_id: home, away_team_id: away, season_id: 1, game_type: GameType::RegularSeason, sport_type: SportType::Basketball,
+                }).unwrap();
+                assert_eq!(contract.get_event(game_id).unwrap().rivalry_multiplier, 15000);
+                let quote = contract.get_price_quote(game_id, seat, false).unwrap();
+                assert_eq!((quote.rivalry_multiplier, quote.final_price), (15000, 1800));
+
+                // The event reports the rivalry of its current policy
+                contract.set_event_pricing_policy(game_id, Some(PricingPolicy { rivalries: vec![(home, away, 12000)], ..flat })).unwrap();
+                assert_eq!(contract.get_event(game_id).unwrap().rivalry_multiplier, 12000);
+            }
+        }
+
+        #[ink::test]
+        fn test_locked_quotes_hold_price_and_max_price_guards_purchases() {
+            let accounts = default_accounts();
+            use_contract_account();
+            let mut contract = InkTix::new();
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.alice, 100_000_000);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.bob, 100_000_000);
+            let venue_id = contract.register_venue("Arena".to_string(), 10, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Game".to_string(), venue_id, 1000, 10, 1000, EventCategory::Generic).unwrap();
+            let seat = Seat {
+                section: "A".to_string(), row: "1".to_string(), seat_number: "1".to_string(),
+                seat_type: SeatType::GeneralAdmission, access_level: AccessLevel::Standard, price_multiplier: 10000,
+            };
+
+            // Lock the price before demand rises
+            let quote_id = contract.lock_quote(event_id, seat.clone()).unwrap();
+            let locked = contract.get_locked_quote(quote_id).unwrap();
+            assert_eq!(locked.holder, accounts.alice);
+            assert_eq!(contract.get_active_quotes(accounts.alice), vec![quote_id]);
+            let emitted = recorded_events();
+            let quote_event: QuoteLocked = decode_event(emitted.last().unwrap());
+            assert_eq!(quote_event.price, locked.priced.price);
+            assert_eq!(quote_event.expires_at, locked.expires_at);
+
+            for _ in 0..8 {
+                buy_ticket(&mut contract, event_id, seat.clone()).unwrap();
+            }
+            let current = contract.get_price_quote(event_id, seat.clone(), false).unwrap().final_price;
+            assert!(current > locked.priced.price);
+
+            // Only the holder can redeem, and only for the quoted seat
+            set_caller(accounts.bob);
+            let result = ink::env::pay_with_call!(
+                contract.purchase_ticket(event_id, seat.clone(), CurrencyId::DOT, Some(quote_id), None), current
+            );
+            assert_eq!(result, Err("Only the holder can redeem a quote".to_string()));
+            set_caller(accounts.alice);
+            let other_seat = Seat { section: "B".to_string(), ..seat.clone() };
+            let result = ink::env::pay_with_call!(
+                contract.purchase_ticket(event_id, other_seat, CurrencyId::DOT, Some(quote_id), None), current
+            );
+            assert_eq!(result, Err("Quote was issued for a different seat".to_string()));
+
+            // The max price guard applies to the locked price
+            let result = ink::env::pay_with_call!(
+                contract.purchase_ticket(event_id, seat.clone(), CurrencyId::DOT, Some(quote_id), Some(locked.priced.price - 1)),
+                locked.priced.price
+            );
+            assert_eq!(result, Err("Price exceeds the maximum price".to_string()));
+
+            // Redeeming pays the locked price, once
+            let ticket_id = ink::env::pay_with_call!(
+                contract.purchase_ticket(event_id, seat.clone(), CurrencyId::DOT, Some(quote_id), Some(locked.priced.price)),
+                locked.priced.price
+            ).unwrap();
+            assert_eq!(contract.get_ticket(ticket_id).unwrap().purchase_price, locked.priced.price);
+            assert!(contract.get_locked_quote(quote_id).unwrap().redeemed);
+            assert!(contract.get_active_quotes(accounts.alice).is_empty());
+            let result = ink::env::pay_with_call!(
+                contract.purchase_ticket(event_id, seat.clone(), CurrencyId::DOT, Some(quote_id), None), current
+            );
+            assert_eq!(result, Err("Quote has already been redeemed".to_string()));
+
+            // Without a quote the max price guard rejects the dynamic price
+            let current = contract.get_price_quote(event_id, seat.clone(), false).unwrap().final_price;
+            let result = ink::env::pay_with_call!(
+                contract.purchase_ticket(event_id, seat.clone(), CurrencyId::DOT, None, Some(current - 1)), current
+            );
+            assert_eq!(result, Err("Price exceeds the maximum price".to_string()));
+
+            // Quotes expire
+            let quote_id = contract.lock_quote(event_id, seat.clone()).unwrap();
+            let expires_at = contract.get_locked_quote(quote_id).unwrap().expires_at;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(expires_at);
+            assert!(contract.get_active_quotes(accounts.alice).is_empty());
+            let result = ink::env::pay_with_call!(
+                contract.purchase_ticket(event_id, seat, CurrencyId::DOT, Some(quote_id), None), current
+            );
+            assert_eq!(result, Err("Quote has expired".to_string()));
+        }
+
+        #[ink::test]
+        fn test_premium_blocks_sell_by_dutch_and_sealed_bid_auctions() {
+            let accounts = default_accounts();
+            use_contract_account();
+            let mut contract = InkTix::new();
+            for account in [accounts.bob, accounts.charlie, accounts.django, accounts.eve] {
+                ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(account, 100_000_000);
+            }
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Final".to_string(), venue_id, 1_000_000, 100, 1000, EventCategory::Generic).unwrap();
+            let courtside = SeatBlock { seat_type: SeatType::Courtside, ..seat_block("CS", "A", 1, 3) };
+            let suite = SeatBlock { seat_type: SeatType::Suite, ..seat_block("S", "1", 1, 2) };
+            contract.set_event_seat_layout(event_id, vec![courtside, suite, seat_block("R", "A", 1, 5)]).unwrap();
+
+            let dutch = AuctionFormat::Dutch { start_price: 10_000, floor_price: 4_000, starts_at: 100, ends_at: 1_100 };
+            assert_eq!(contract.create_auction(event_id, 2, dutch), Err("Only premium seats can be auctioned".to_string()));
+            let dutch_id = contract.create_auction(event_id, 0, dutch).unwrap();
+            let sealed = AuctionFormat::SealedBid { reserve_price: 1_000, commit_ends_at: 2_000, reveal_ends_at: 3_000 };
+            let sealed_id = contract.create_auction(event_id, 1, sealed).unwrap();
+            assert_eq!(contract.create_auction(event_id, 1, sealed), Err("Seat block is already being auctioned".to_string()));
+            assert_eq!(contract.get_event_sale_modes(event_id), vec![
+                SaleMode::Auction { auction_id: dutch_id, format: dutch },
+                SaleMode::Auction { auction_id: sealed_id, format: sealed },
+                SaleMode::PostedPrice,
+            ]);
+
+            // Auctioned seats cannot be bought or held at the posted price
+            assert_eq!(buy_ticket(&mut contract, event_id, layout_seat("CS", "A", 1)), Err("Seat is sold by auction".to_string()));
+            assert_eq!(
+                contract.hold_seats(event_id, vec![layout_seat("S", "1", 1)], HoldDuration::Milliseconds(1000)),
+                Err("Seat is sold by auction".to_string())
+            );
+
+            // The Dutch price falls linearly and buyers get the next free seat
+            assert_eq!(contract.get_dutch_auction_price(dutch_id), Err("Auction has not started".to_string()));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(600);
+            assert_eq!(contract.get_dutch_auction_price(dutch_id), Ok(7_000));
+            set_caller(accounts.bob);
+            let ticket_id = ink::env::pay_with_call!(contract.buy_from_dutch_auction(dutch_id), 7_500).unwrap();
+            let ticket = contract.get_ticket(ticket_id).unwrap();
+            assert_eq!((ticket.section.as_str(), ticket.seat_number, ticket.purchase_price), ("CS", 1, 7_000));
+            assert_eq!(ticket.seat_type, SeatType::Courtside);
+
+            // Sealed bids are committed with a covering deposit, then revealed
+            let salt = [7u8; 32];
+            let bids = [
+                (accounts.bob, 5_000, 1, 5_000),
+                (accounts.charlie, 3_000, 2, 6_000),
+                (accounts.django, 2_000, 1, 2_000),
+                (accounts.eve, 4_000, 1, 4_000),
+            ];
+            for (bidder, price, quantity, deposit) in bids {
+                set_caller(bidder);
+                let commitment = auctions::Auctions::bid_commitment(bidder, price, quantity, salt);
+                ink::env::pay_with_call!(contract.commit_auction_bid(sealed_id, commitment), deposit).unwrap();
+            }
+            assert_eq!(
+                ink::env::pay_with_call!(contract.commit_auction_bid(sealed_id, [0u8; 32]), 1),
+                Err("Bid already committed".to_string())
+            );
+            assert_eq!(contract.reveal_auction_bid(sealed_id, 4_000, 1, salt), Err("Reveal period is not open".to_string()));
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(2_000);
+            set_caller(accounts.django);
+            assert_eq!(
+                contract.reveal_auction_bid(sealed_id, 2_500, 1, salt),
+                Err("Revealed bid does not match the commitment".to_string())
+            );
+            for (bidder, price, quantity, _) in &bids[..3] {
+                set_caller(*bidder);
+                contract.reveal_auction_bid(sealed_id, *price, *quantity, salt).unwrap();
+            }
+            assert_eq!(contract.settle_auction(sealed_id), Err("Auction has not ended".to_string()));
+
+            // Winners pay the uniform clearing price; everything else is refunded
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(3_000);
+            contract.settle_auction(sealed_id).unwrap();
+            let auction = contract.get_auction(sealed_id).unwrap();
+            assert_eq!((auction.seats_sold, auction.clearing_price, auction.settled), (2, Some(3_000), true));
+            let refunds: Vec<u128> = bids.iter().map(|(bidder, ..)| contract.get_refund_balance(*bidder, CurrencyId::DOT)).collect();
+            assert_eq!(refunds, vec![2_000, 3_000, 2_000, 4_000]);
+            let settled: AuctionSettled = decode_event(recorded_events().last().unwrap());
+            assert_eq!((settled.seats_sold, settled.clearing_price), (2, Some(3_000)));
+            let balance = |account| ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(account).unwrap();
+            let before = balance(accounts.eve);
+            set_caller(accounts.eve);
+            assert_eq!(contract.withdraw_refund(CurrencyId::DOT), Ok(4_000));
+            assert_eq!(balance(accounts.eve) - before, 4_000);
+            for (owner, seat_number) in [(accounts.bob, 1), (accounts.charlie, 2)] {
+                let ticket_id = *contract.get_user_tickets(owner).last().unwrap();
+                let ticket = contract.get_ticket(ticket_id).unwrap();
+                assert_eq!((ticket.section.as_str(), ticket.seat_number, ticket.purchase_price), ("S", seat_number, 3_000));
+            }
+            assert_eq!(contract.get_event_escrow(event_id), 7_000 + 6_000);
+            assert_eq!(contract.settle_auction(sealed_id), Err("Auction already settled".to_string()));
+
+            // Once the Dutch auction settles its unsold seats return to posted-price sale
+            assert_eq!(contract.get_dutch_auction_price(dutch_id), Err("Auction has ended".to_string()));
+            contract.settle_auction(dutch_id).unwrap();
+            assert_eq!(contract.get_event_sale_modes(event_id), vec![SaleMode::PostedPrice; 3]);
+            set_caller(accounts.alice);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.alice, 100_000_000);
+            assert!(buy_ticket(&mut contract, event_id, layout_seat("CS", "A", 2)).is_ok());
+            assert_eq!(contract.get_event_auctions(event_id), vec![dutch_id, sealed_id]);
+        }
+
+        #[ink::test]
+        fn test_ballot_draw_is_verifiable_and_rolls_unclaimed_allocations() {
+            let accounts = default_accounts();
+            use_contract_account();
+            let mut contract = InkTix::new();
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Tour".to_string(), venue_id, 1_000_000, 10, 1000, EventCategory::Generic).unwrap();
+            let seed = [42u8; 32];
+            let mut seed_commitment = [0u8; 32];
+            ink::env::hash_bytes::<ink::env::hash::Blake2x256>(&seed, &mut seed_commitment);
+            let terms = BallotTerms {
+                tickets: 2, max_per_entrant: 1, registration_ends_at: 1_000, claim_window: 500,
+                required_attestation: Some(AttestationKind::SocialProof), seed_commitment,
+            };
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.alice, 100_000_000_000);
+            assert_eq!(
+                ink::env::pay_with_call!(contract.create_ballot(event_id, terms.clone()), ballots::MIN_BALLOT_BOND - 1),
+                Err("Ballot bond is below the minimum".to_string())
+            );
+            let ballot_id = ink::env::pay_with_call!(contract.create_ballot(event_id, terms.clone()), ballots::MIN_BALLOT_BOND).unwrap();
+            assert_eq!(
+                ink::env::pay_with_call!(contract.create_ballot(event_id, terms), ballots::MIN_BALLOT_BOND),
+                Err("Event already has a ballot".to_string())
+            );
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+
+            // Only attested fans can register, once each
+            let fans = vec![accounts.bob, accounts.charlie, accounts.django, accounts.eve];
+            contract.grant_role(accounts.alice, Role::Attester, RoleScope::Global).unwrap();
+            for &fan in &fans {
+                contract.attest(fan, AttestationKind::SocialProof, [7; 32], 100_000).unwrap();
+                ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(fan, 100_000_000);
+            }
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.frank, 100_000_000);
+            set_caller(accounts.frank);
+            assert_eq!(contract.enter_ballot(ballot_id, 1), Err("Only attested fans can enter this ballot".to_string()));
+            for &fan in &fans {
+                set_caller(fan);
+                assert_eq!(contract.enter_ballot(ballot_id, 2), Err("Invalid ballot quantity".to_string()));
+                contract.enter_ballot(ballot_id, 1).unwrap();
+            }
+            assert_eq!(contract.enter_ballot(ballot_id, 1), Err("Already entered this ballot".to_string()));
+
+            // Nothing can be bought or held before the draw
+            let seat = layout_seat("GA", "1", 1);
+            assert_eq!(buy_ticket(&mut contract, event_id, seat.clone()), Err("Tickets are sold by ballot".to_string()));
+            assert_eq!(
+                contract.hold_seats(event_id, vec![seat.clone()], HoldDuration::Milliseconds(1000)),
+                Err("Tickets are sold by ballot".to_string())
+            );
+
+            set_caller(accounts.alice);
+            assert_eq!(contract.draw_ballot(ballot_id, seed), Err("Ballot registration is still open".to_string()));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1_000);
+            assert_eq!(contract.draw_ballot(ballot_id, [0u8; 32]), Err("Seed does not match the commitment".to_string()));
+
+            // The draw waits for a beacon round published after registration closed
+            contract.grant_role(accounts.frank, Role::RandomnessBeacon, RoleScope::Global).unwrap();
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(999);
+            set_caller(accounts.frank);
+            assert_eq!(contract.publish_beacon_round([8u8; 32]), Ok(1));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1_000);
+            set_caller(accounts.alice);
+            assert_eq!(contract.draw_ballot(ballot_id, seed), Err("No beacon round since registration closed".to_string()));
+            set_caller(accounts.charlie);
+            assert_eq!(contract.publish_beacon_round([9u8; 32]), Err(String::from(InkTixError::MissingRole)));
+            set_caller(accounts.frank);
+            assert_eq!(contract.publish_beacon_round([9u8; 32]), Ok(2));
+            set_caller(accounts.alice);
+            contract.draw_ballot(ballot_id, seed).unwrap();
+            assert_eq!(contract.get_ballot(ballot_id).unwrap().beacon_round, Some(2));
+            assert_eq!(contract.get_refund_balance(accounts.alice, CurrencyId::DOT), ballots::MIN_BALLOT_BOND);
+
+            // Anyone can recompute the entropy from the entries, then the drawn order from it and the seed
+            fn blake2<T: ink::scale::Encode>(value: &T) -> [u8; 32] {
+                let mut output = [0u8; 32];
+                ink::env::hash_encoded::<ink::env::hash::Blake2x256, _>(value, &mut output);
+                output
+            }
+            let entries = contract.get_ballot_entries(ballot_id);
+            let entropy = fans.iter().fold([0u8; 32], |entropy, fan| {
+                let entry = entries.iter().find(|entry| entry.entrant == *fan).unwrap();
+                blake2(&(entropy, *fan, entry.requested, entry.entered_block))
+            });
+            assert_eq!(contract.get_ballot(ballot_id).unwrap().entropy, entropy);
+            let draw_seed = blake2(&(seed, entropy, [9u8; 32]));
+            let mut expected = fans.clone();
+            for i in (1..expected.len()).rev() {
+                let digest = blake2(&(draw_seed, i as u32));
+                let r = u64::from_le_bytes(digest[..8].try_into().unwrap());
+                expected.swap(i, (r % (i as u64 + 1)) as usize);
+            }
+            let drawn: BallotDrawn = decode_event(recorded_events().last().unwrap());
+            assert_eq!(drawn.draw_seed, draw_seed);
+            assert_eq!(entries.iter().map(|e| e.entrant).collect::<Vec<_>>(), expected);
+            assert_eq!(entries.iter().map(|e| e.allocated).collect::<Vec<_>>(), vec![1, 1, 0, 0]);
+
+            // Winners buy within their window; others cannot
+            set_caller(expected[3]);
+            assert_eq!(buy_ticket(&mut contract, event_id, seat.clone()), Err("No live ballot allocation".to_string()));
+            set_caller(expected[0]);
+            buy_ticket(&mut contract, event_id, seat.clone()).unwrap();
+            assert_eq!(buy_ticket(&mut contract, event_id, seat.clone()), Err("No live ballot allocation".to_string()));
+
+            // A lapsed allocation rolls to the next fan in drawn order
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1_500);
+            set_caller(expected[1]);
+            assert_eq!(buy_ticket(&mut contract, event_id, seat.clone()), Err("No live ballot allocation".to_string()));
+            assert_eq!(contract.roll_over_ballot(ballot_id), Ok(1));
+            let entries = contract.get_ballot_entries(ballot_id);
+            assert_eq!(entries.iter().map(|e| e.allocated).collect::<Vec<_>>(), vec![1, 0, 1, 0]);
+            assert_eq!(entries[2].claim_deadline, 2_000);
+            set_caller(expected[2]);
+            buy_ticket(&mut contract, event_id, seat.clone()).unwrap();
+
+            // Every allocation is claimed, so the ballot closes and general sale resumes
+            assert!(contract.get_ballot(ballot_id).unwrap().closed);
+            set_caller(accounts.frank);
+            buy_ticket(&mut contract, event_id, seat).unwrap();
+            assert_eq!(contract.get_event(event_id).unwrap().sold_tickets, 3);
+        }
+
+        #[ink::test]
+        fn test_waitlist_offers_released_tickets_in_fifo_order() {
+            let accounts = default_accounts();
+            use_contract_account();
+            let mut contract = InkTix::new();
+            for account in [accounts.bob, accounts.charlie, accounts.django, accounts.eve, accounts.frank] {
+                ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(account, 100_000_000);
+            }
+            let balance = |account| ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(account).unwrap();
+            let venue_id = contract.register_venue("Club".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Gig".to_string(), venue_id, 1_000_000, 2, 1000, EventCategory::Generic).unwrap();
+            let seat = layout_seat("GA", "1", 1);
+
+            set_caller(accounts.frank);
+            assert_eq!(contract.join_waitlist(event_id, 1, 1_000_000), Err("Event is not sold out".to_string()));
+            set_caller(accounts.bob);
+            let refunded_ticket = buy_ticket(&mut contract, event_id, seat.clone()).unwrap();
+            set_caller(accounts.charlie);
+            buy_ticket(&mut contract, event_id, seat.clone()).unwrap();
+
+            // Fans queue with an optional deposit
+            set_caller(accounts.django);
+            ink::env::pay_with_call!(contract.join_waitlist(event_id, 1, 1_000_000), 500).unwrap();
+            assert_eq!(contract.join_waitlist(event_id, 1, 1_000_000), Err("Already on the waitlist".to_string()));
+            set_caller(accounts.eve);
+            assert_eq!(
+                ink::env::pay_with_call!(contract.join_waitlist(event_id, 1, 300), 400),
+                Err("Deposit exceeds the maximum total".to_string())
+            );
+            ink::env::pay_with_call!(contract.join_waitlist(event_id, 1, 1_000_000), 400).unwrap();
+
+            // A refund returns the seat to sale and offers it to the head of the queue
+            set_caller(accounts.alice);
+            let amount = contract.refund_ticket(refunded_ticket).unwrap();
+            assert_eq!(contract.get_refund_balance(accounts.bob, CurrencyId::DOT), amount);
+            assert!(contract.get_ticket(refunded_ticket).unwrap().refunded);
+            assert_eq!(contract.get_event(event_id).unwrap().sold_tickets, 1);
+            let offered: WaitlistOffered = decode_event(recorded_events().last().unwrap());
+            assert_eq!((offered.account, offered.quantity), (accounts.django, 1));
+            let waitlist = contract.get_waitlist(event_id);
+            assert!(waitlist[0].offer.is_some() && waitlist[1].offer.is_none());
+
+            // Offered tickets are reserved for the waitlist
+            set_caller(accounts.frank);
+            assert_eq!(
+                buy_ticket(&mut contract, event_id, seat.clone()),
+                Err("Remaining tickets are offered to the waitlist".to_string())
+            );
+            set_caller(accounts.eve);
+            assert_eq!(contract.claim_waitlist_offer(event_id, vec![seat.clone()]), Err("No live waitlist offer".to_string()));
+
+            // An unclaimed offer lapses, crediting the deposit back and moving to the next fan
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(waitlist::WAITLIST_CLAIM_WINDOW);
+            contract.process_waitlist(event_id).unwrap();
+            assert_eq!(contract.get_refund_balance(accounts.django, CurrencyId::DOT), 500);
+            set_caller(accounts.django);
+            let django_before = balance(accounts.django);
+            assert_eq!(contract.withdraw_refund(CurrencyId::DOT), Ok(500));
+            assert_eq!(balance(accounts.django) - django_before, 500);
+            assert_eq!(contract.withdraw_refund(CurrencyId::DOT), Err("No refund balance to withdraw".to_string()));
+            set_caller(accounts.bob);
+            let bob_before = balance(accounts.bob);
+            assert_eq!(contract.withdraw_refund(CurrencyId::DOT), Ok(amount));
+            assert_eq!(balance(accounts.bob) - bob_before, amount);
+            set_caller(accounts.eve);
+            let waitlist = contract.get_waitlist(event_id);
+            assert_eq!(waitlist.len(), 1);
+            assert_eq!(waitlist[0].account, accounts.eve);
+
+            // The deposit counts towards the claimed tickets and the excess is refunded
+            let price = contract.get_price_quote(event_id, seat.clone(), false).unwrap().final_price;
+            let eve_before = balance(accounts.eve);
+            let ticket_ids = ink::env::pay_with_call!(contract.claim_waitlist_offer(event_id, vec![seat]), price).unwrap();
+            assert_eq!(eve_before - balance(accounts.eve), price - 400);
+            assert_eq!(contract.get_ticket(ticket_ids[0]).unwrap().owner, accounts.eve);
+            assert!(contract.get_waitlist(event_id).is_empty());
+            assert_eq!(contract.get_event(event_id).unwrap().sold_tickets, 2);
+
+            // The queue is bounded
+            for i in 0..waitlist::MAX_WAITLIST_ENTRIES {
+                set_caller(AccountId::from([i as u8 + 100; 32]));
+                contract.join_waitlist(event_id, 1, 1_000_000).unwrap();
+            }
+            set_caller(accounts.frank);
+            assert_eq!(contract.join_waitlist(event_id, 1, 1_000_000), Err("Waitlist is full".to_string()));
+        }
+
+        #[ink::test]
+        fn test_returned_resale_listing_is_offered_to_the_waitlist() {
+            let accounts = default_accounts();
+            use_contract_account();
+            let mut contract = InkTix::new();
+            for account in [accounts.bob, accounts.charlie, accounts.django] {
+                ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(account, 100_000_000);
+            }
+            let balance = |account| ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(account).unwrap();
+            let venue_id = contract.register_venue("Club".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Gig".to_string(), venue_id, 1_000_000_000, 1, 1000, EventCategory::Generic).unwrap();
+            set_caller(accounts.bob);
+            let ticket_id = buy_ticket(&mut contract, event_id, layout_seat("GA", "1", 1)).unwrap();
+            let listing_id = contract.resell_ticket(ticket_id, 900, CurrencyId::DOT).unwrap();
+            set_caller(accounts.charlie);
+            ink::env::pay_with_call!(contract.join_waitlist(event_id, 1, 500), 0).unwrap();
+            set_caller(accounts.django);
+            ink::env::pay_with_call!(contract.join_waitlist(event_id, 1, 1_000_000), 400).unwrap();
+
+            // A cancelled listing goes to the first fan whose maximum price covers it
+            set_caller(accounts.bob);
+            contract.cancel_resale_listing(listing_id).unwrap();
+            let offered: WaitlistOffered = decode_event(recorded_events().last().unwrap());
+            assert_eq!((offered.account, offered.listing_id), (accounts.django, Some(listing_id)));
+            set_caller(accounts.charlie);
+            assert_eq!(contract.claim_waitlist_listing(event_id), Err("No live resale listing offer".to_string()));
+            set_caller(accounts.django);
+            assert_eq!(
+                contract.claim_waitlist_offer(event_id, vec![layout_seat("GA", "1", 1)]),
+                Err("Offer is for a resale listing".to_string())
+            );
+            let bob_before = balance(accounts.bob);
+            assert_eq!(ink::env::pay_with_call!(contract.claim_waitlist_listing(event_id), 500), Ok(ticket_id));
+            assert_eq!(balance(accounts.bob) - bob_before, 900);
+            assert_eq!(contract.get_ticket(ticket_id).unwrap().owner, accounts.django);
+            assert_eq!(contract.get_waitlist(event_id).len(), 1);
+
+            // A listing that lapses unsold is returned on the next run
+            let relisted = contract.resell_ticket(ticket_id, 500, CurrencyId::DOT).unwrap();
+            let expiry = contract.get_resale_listing(relisted).unwrap().expiry_time;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(expiry);
+            contract.process_waitlist(event_id).unwrap();
+            let offered: WaitlistOffered = decode_event(recorded_events().last().unwrap());
+            assert_eq!((offered.account, offered.listing_id), (accounts.charlie, Some(relisted)));
+            set_caller(accounts.charlie);
+            ink::env::pay_with_call!(contract.claim_waitlist_listing(event_id), 500).unwrap();
+            assert_eq!(contract.get_ticket(ticket_id).unwrap().owner, accounts.charlie);
+            assert!(contract.get_waitlist(event_id).is_empty());
+        }
+
+        #[ink::test]
+        fn test_lapsed_seat_hold_is_offered_to_the_waitlist() {
+            let accounts = default_accounts();
+            use_contract_account();
+            let mut contract = InkTix::new();
+            for account in [accounts.bob, accounts.charlie, accounts.django] {
+                ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(account, 100_000_000);
+            }
+            let event_id = layout_event(&mut contract, 2);
+            set_caller(accounts.bob);
+            buy_ticket(&mut contract, event_id, layout_seat("101", "A", 1)).unwrap();
+            set_caller(accounts.charlie);
+            contract.hold_seats(event_id, vec![layout_seat("101", "A", 2)], HoldDuration::Milliseconds(1000)).unwrap();
+            set_caller(accounts.django);
+            contract.join_waitlist(event_id, 1, 1_000_000).unwrap();
+
+            // The hold lapses with nothing else touching the event, and the next run offers its seat
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+            contract.process_waitlist(event_id).unwrap();
+            let offered: WaitlistOffered = decode_event(recorded_events().last().unwrap());
+            assert_eq!((offered.account, offered.quantity), (accounts.django, 1));
+            set_caller(accounts.bob);
+            assert_eq!(
+                buy_ticket(&mut contract, event_id, layout_seat("101", "A", 2)),
+                Err("Remaining tickets are offered to the waitlist".to_string())
+            );
+        }
+
+        #[ink::test]
+        fn test_sale_windows_gate_purchases_by_tier_and_access_code() {
+            let accounts = default_accounts();
+            use_contract_account();
+            let mut contract = InkTix::new();
+            for account in [accounts.bob, accounts.charlie, accounts.django, accounts.eve] {
+                ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(account, 100_000_000);
+            }
+            let venue_id = contract.register_venue("Club".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Gig".to_string(), venue_id, 1_000_000, 10, 1000, EventCategory::Generic).unwrap();
+            let seat = layout_seat("GA", "1", 1);
+            let window = |access, starts_at, ends_at, per_user_limit| SaleWindow { access, starts_at, ends_at, per_user_limit };
+
+            assert_eq!(
+                contract.set_sale_windows(event_id, vec![window(SaleWindowAccess::General, 100, 200, 0), window(SaleWindowAccess::AccessCode, 200, 300, 2)]),
+                Err("The general on-sale must be the last window".to_string())
+            );
+            contract.set_sale_windows(event_id, vec![
+                window(SaleWindowAccess::LoyaltyTier(LoyaltyTier::Gold), 100, 200, 1),
+                window(SaleWindowAccess::AccessCode, 200, 300, 2),
+                window(SaleWindowAccess::General, 300, 900_000, 0),
+            ]).unwrap();
+            let code_hash = sale_windows::SaleWindows::access_code_hash(b"FANCLUB", accounts.charlie);
+            contract.add_access_codes(event_id, vec![code_hash]).unwrap();
+            for (account, tier) in [(accounts.bob, LoyaltyTier::Platinum), (accounts.charlie, LoyaltyTier::Silver)] {
+                contract.storage.loyalty_profiles.insert(account, &LoyaltyProfile {
+                    user_id: account, total_points: 0, current_tier: tier, points_earned_this_month: 0,
+                    points_earned_this_year: 0, total_tickets_purchased: 0, total_spent: 0, join_date: 0,
+                    last_activity: 0, streak_days: 0, referral_count: 0, referral_points: 0, fantasy_sports_points: 0,
+                });
+            }
+
+            set_caller(accounts.bob);
+            assert_eq!(buy_ticket(&mut contract, event_id, seat.clone()), Err("No sale window is open".to_string()));
+
+            // Fan-club presale admits Gold and above, one ticket each
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(100);
+            assert_eq!(contract.get_current_sale_window(event_id).map(|(index, _)| index), Some(0));
+            buy_ticket(&mut contract, event_id, seat.clone()).unwrap();
+            assert_eq!(buy_ticket(&mut contract, event_id, seat.clone()), Err("Sale window purchase limit reached".to_string()));
+            set_caller(accounts.charlie);
+            assert_eq!(buy_ticket(&mut contract, event_id, seat.clone()), Err("Not eligible for the current sale window".to_string()));
+            set_caller(accounts.alice);
+            assert_eq!(
+                contract.set_sale_windows(event_id, Vec::new()),
+                Err("Sale windows cannot change after sales have started".to_string())
+            );
+
+            // An access code unlocks the code presale only for the fan it was issued to
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(200);
+            set_caller(accounts.django);
+            assert_eq!(contract.redeem_access_code(event_id, b"FANCLUB".to_vec()), Err("Invalid access code".to_string()));
+            set_caller(accounts.charlie);
+            assert_eq!(contract.redeem_access_code(event_id, b"WRONG".to_vec()), Err("Invalid access code".to_string()));
+            contract.redeem_access_code(event_id, b"FANCLUB".to_vec()).unwrap();
+            assert_eq!(contract.redeem_access_code(event_id, b"FANCLUB".to_vec()), Err("Presale access already unlocked".to_string()));
+            buy_ticket(&mut contract, event_id, seat.clone()).unwrap();
+            buy_ticket(&mut contract, event_id, seat.clone()).unwrap();
+            assert_eq!(contract.get_sale_window_purchases(event_id, 1, accounts.charlie), 2);
+            set_caller(accounts.django);
+            assert_eq!(contract.redeem_access_code(event_id, b"FANCLUB".to_vec()), Err("Invalid access code".to_string()));
+            assert_eq!(buy_ticket(&mut contract, event_id, seat.clone()), Err("Not eligible for the current sale window".to_string()));
+
+            // The general on-sale is open to everyone
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(300);
+            buy_ticket(&mut contract, event_id, seat.clone()).unwrap();
+            set_caller(accounts.eve);
+            buy_ticket(&mut contract, event_id, seat).unwrap();
+            assert_eq!(contract.get_event(event_id).unwrap().sold_tickets, 5);
+        }
+
+        #[ink::test]
+        fn test_group_orders_complete_when_funded_and_refund_when_not() {
+            let accounts = default_accounts();
+            use_contract_account();
             let mut contract = InkTix::new();
-            let venue_id = contract.register_venue(
-                "Staples Center".to_string(), 20000, "Los Angeles".to_string(), VenueType::Arena,
+            for account in [accounts.bob, accounts.charlie, accounts.django, accounts.eve] {
+                ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(account, 100_000_000);
+            }
+            let balance = |account| ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(account).unwrap();
+            let venue_id = contract.register_venue("Club".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Gig".to_string(), venue_id, 1_000_000, 10, 1000, EventCategory::Generic).unwrap();
+            contract.set_event_seat_layout(event_id, vec![seat_block("A", "1", 1, 6)]).unwrap();
+            let tier = |min_seats, discount_bps| GroupDiscountTier { min_seats, discount_bps };
+            assert_eq!(
+                contract.set_group_discount_tiers(event_id, vec![tier(5, 1000), tier(3, 500)]),
+                Err("Group discount tiers must ascend by size and discount".to_string())
+            );
+            contract.set_group_discount_tiers(event_id, vec![tier(3, 500), tier(5, 1000)]).unwrap();
+            let quote = contract.get_price_quote(event_id, layout_seat("A", "1", 1), false).unwrap().final_price;
+
+            // The organizer reserves three seats for themselves and a friend at the group price
+            set_caller(accounts.bob);
+            let order_id = contract.create_group_order(
+                event_id, layout_seat("A", "1", 1), vec![(accounts.bob, 2), (accounts.charlie, 1)], 5000
             ).unwrap();
-            assert_eq!(venue_id, 1);
-            let venue = contract.get_venue(venue_id).unwrap();
-            assert_eq!(venue.name, "Staples Center");
-            assert_eq!(venue.capacity, 20000);
+            let order = contract.get_group_order(order_id).unwrap();
+            assert_eq!(order.discount_bps, 500);
+            assert_eq!(order.seats[0].price, quote * 9500 / 10000);
+            assert_eq!(order.members[0].share, order.seats[0].price * 2);
+            assert_eq!(contract.get_user_group_orders(accounts.charlie), vec![order_id]);
+            set_caller(accounts.django);
+            assert_eq!(
+                buy_ticket(&mut contract, event_id, layout_seat("A", "1", 2)),
+                Err("Seat is reserved for a group order".to_string())
+            );
+            assert_eq!(contract.contribute_to_group_order(order_id), Err("Not a member of this group order".to_string()));
+
+            // Overpayment is refunded; the order completes once every share is paid
+            set_caller(accounts.bob);
+            let bob_before = balance(accounts.bob);
+            assert_eq!(ink::env::pay_with_call!(contract.contribute_to_group_order(order_id), order.members[0].share + 100), Ok(false));
+            assert_eq!(bob_before - balance(accounts.bob), order.members[0].share);
+            set_caller(accounts.charlie);
+            assert_eq!(ink::env::pay_with_call!(contract.contribute_to_group_order(order_id), 1), Ok(false));
+            assert_eq!(contract.get_event(event_id).unwrap().sold_tickets, 0);
+            assert_eq!(ink::env::pay_with_call!(contract.contribute_to_group_order(order_id), order.members[1].share - 1), Ok(true));
+            let order = contract.get_group_order(order_id).unwrap();
+            assert_eq!(order.status, GroupOrderStatus::Completed);
+            let owners: Vec<AccountId> = order.ticket_ids.iter().map(|id| contract.get_ticket(*id).unwrap().owner).collect();
+            assert_eq!(owners, vec![accounts.bob, accounts.bob, accounts.charlie]);
+            assert_eq!(contract.get_event(event_id).unwrap().sold_tickets, 3);
+
+            // An unfunded order lapses at its deadline and anyone can release it
+            set_caller(accounts.django);
+            let order_id = contract.create_group_order(
+                event_id, layout_seat("A", "1", 4), vec![(accounts.django, 1), (accounts.eve, 1)], 5000
+            ).unwrap();
+            let share = contract.get_group_order(order_id).unwrap().members[0].share;
+            let django_before = balance(accounts.django);
+            ink::env::pay_with_call!(contract.contribute_to_group_order(order_id), share).unwrap();
+            set_caller(accounts.eve);
+            assert_eq!(
+                contract.cancel_group_order(order_id),
+                Err("Only the organizer can cancel a group order before its deadline".to_string())
+            );
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(5000);
+            assert_eq!(
+                ink::env::pay_with_call!(contract.contribute_to_group_order(order_id), share),
+                Err("Group order is not open".to_string())
+            );
+            contract.cancel_group_order(order_id).unwrap();
+            assert_eq!(contract.get_refund_balance(accounts.django, CurrencyId::DOT), share);
+            set_caller(accounts.django);
+            assert_eq!(contract.withdraw_refund(CurrencyId::DOT), Ok(share));
+            assert_eq!(balance(accounts.django), django_before);
+            assert_eq!(contract.get_group_order(order_id).unwrap().status, GroupOrderStatus::Cancelled);
+            assert_eq!(contract.get_seat_map(event_id)[3].status, SeatStatus::Available);
         }
 
-        #[cfg(feature = "sports")]
         #[ink::test]
-        fn test_register_team() {
+        fn test_behavior_scoring_escalates_and_lists_are_enforced() {
+            let accounts = default_accounts();
+            use_contract_account();
             let mut contract = InkTix::new();
-            let team_id = contract.register_team(
-                "Lakers".to_string(), "Los Angeles".to_string(), SportType::Basketball,
-            ).unwrap();
-            assert_eq!(team_id, 1);
-            let team = contract.get_team(team_id).unwrap();
-            assert_eq!(team.name, "Lakers");
-            assert_eq!(team.city, "Los Angeles");
-            assert_eq!(team.sport_type, SportType::Basketball);
+            for account in [accounts.bob, accounts.charlie, accounts.django, accounts.eve] {
+                ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(account, 100_000_000);
+            }
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Game".to_string(), venue_id, 1_000_000_000, 100, 1000, EventCategory::Generic).unwrap();
+            contract.set_dynamic_pricing(event_id, false).unwrap();
+            contract.configure_anti_scalping(event_id, AntiScalpingConfig {
+                blacklisted_addresses: vec![accounts.eve], anti_bot_measures: true, ..resale_config(event_id)
+            }).unwrap();
+            let seat = layout_seat("A", "1", 1);
+            let set_time = |t| ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(t);
+
+            set_caller(accounts.eve);
+            assert_eq!(buy_ticket(&mut contract, event_id, seat.clone()), Err("Account is blacklisted for this event".to_string()));
+
+            // A long run of rapid repeat purchases on an anti-bot event raises the score to a warning
+            set_caller(accounts.bob);
+            let mut tickets = Vec::new();
+            for t in 0..9 {
+                set_time(t * 1000);
+                tickets.push(buy_ticket(&mut contract, event_id, seat.clone()).unwrap());
+            }
+            let profile = contract.get_behavior_profile(accounts.bob).unwrap();
+            assert_eq!((profile.suspicious_activity_score, profile.blacklist_status), (30, BlacklistStatus::Warning));
+            assert_eq!(contract.transfer_ticket(tickets[3], accounts.eve), Err("Account is blacklisted for this event".to_string()));
+
+            // Flipping tickets at a markup right after purchase leads to a suspension
+            let price = contract.get_ticket(tickets[0]).unwrap().dot_equivalent_paid * 3 / 2;
+            for &ticket_id in &tickets[..2] {
+                set_caller(accounts.bob);
+                let listing_id = contract.resell_ticket(ticket_id, price, CurrencyId::DOT).unwrap();
+                set_caller(accounts.charlie);
+                ink::env::pay_with_call!(contract.buy_resale_listing(listing_id), price).unwrap();
+            }
+            let profile = contract.get_behavior_profile(accounts.bob).unwrap();
+            assert_eq!((profile.suspicious_activity_score, profile.blacklist_status), (70, BlacklistStatus::Suspended));
+            assert_eq!((profile.total_tickets_resold, profile.warning_count), (2, 2));
+            set_caller(accounts.bob);
+            assert_eq!(buy_ticket(&mut contract, event_id, seat.clone()), Err("Account is suspended".to_string()));
+            assert_eq!(contract.resell_ticket(tickets[2], price, CurrencyId::DOT), Err("Account is suspended".to_string()));
+            assert_eq!(contract.transfer_ticket(tickets[2], accounts.django), Err("Account is suspended".to_string()));
+
+            // Admins review profiles: clearing restores access, a ban blocks it
+            assert!(contract.clear_behavior_profile(accounts.bob).is_err());
+            set_caller(accounts.alice);
+            contract.clear_behavior_profile(accounts.bob).unwrap();
+            assert_eq!(contract.get_behavior_profile(accounts.bob).unwrap().blacklist_status, BlacklistStatus::Clean);
+            set_caller(accounts.bob);
+            contract.transfer_ticket(tickets[2], accounts.django).unwrap();
+            set_caller(accounts.alice);
+            contract.set_behavior_status(accounts.bob, BlacklistStatus::Banned).unwrap();
+            set_caller(accounts.bob);
+            assert_eq!(contract.transfer_ticket(tickets[3], accounts.django), Err("Account is banned".to_string()));
+
+            // A whitelist limits the event to listed accounts
+            set_caller(accounts.alice);
+            contract.configure_anti_scalping(event_id, AntiScalpingConfig {
+                whitelisted_addresses: vec![accounts.charlie], ..resale_config(event_id)
+            }).unwrap();
+            set_caller(accounts.django);
+            assert_eq!(buy_ticket(&mut contract, event_id, seat.clone()), Err("Account is not whitelisted for this event".to_string()));
+            set_caller(accounts.charlie);
+            buy_ticket(&mut contract, event_id, seat).unwrap();
         }
 
         #[cfg(feature = "sports")]
         #[ink::test]
-        fn test_create_sports_event() {
+        fn test_holding_limits_span_categories_seasons_teams_and_linked_accounts() {
+            let accounts = default_accounts();
+            use_contract_account();
             let mut contract = InkTix::new();
-
-            let team1_id = contract.register_team("Lakers".to_string(), "Los Angeles".to_string(), SportType::Basketball).unwrap();
-            let team2_id = contract.register_team("Warriors".to_string(), "San Francisco".to_string(), SportType::Basketball).unwrap();
-            let venue_id = contract.register_venue("Staples Center".to_string(), 20000, "Los Angeles".to_string(), VenueType::Arena).unwrap();
-
-            // Create a season (directly in storage for test)
-            let season = crate::types::sports::season::Season {
+            for account in [accounts.bob, accounts.django] {
+                ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(account, 100_000_000);
+            }
+            let lakers = contract.register_team("Lakers".to_string(), "Los Angeles".to_string(), SportType::Basketball).unwrap();
+            let warriors = contract.register_team("Warriors".to_string(), "San Francisco".to_string(), SportType::Basketball).unwrap();
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            contract.storage.seasons.insert(1, &crate::types::sports::season::Season {
                 id: 1, name: "2024 Season".to_string(), sport_type: SportType::Basketball,
-                start_date: 1000000000, end_date: 2000000000, regular_season_games: 82,
-                active: true, season_pass_base_price: 1000000000000000000,
-                early_bird_discount: 20, early_bird_deadline: 999999000,
+                start_date: 0, end_date: 2_000_000_000, regular_season_games: 82, active: true,
+                season_pass_base_price: 1000, early_bird_discount: 20, early_bird_deadline: 0,
+            });
+            let game = |home_team_id, away_team_id| EventCategory::Sports {
+                home_team_id, away_team_id, season_id: 1, game_type: GameType::RegularSeason, sport_type: SportType::Basketball,
             };
-            contract.storage.seasons.insert(1, &season);
-            contract.storage.total_seasons = 1;
+            let game1 = contract.create_event("G1".to_string(), venue_id, 1_000_000, 100, 1000, game(lakers, warriors)).unwrap();
+            let game2 = contract.create_event("G2".to_string(), venue_id, 1_000_000, 100, 1000, game(lakers, warriors)).unwrap();
+            let show = contract.create_event("Show".to_string(), venue_id, 1_000_000, 100, 1000, EventCategory::Generic).unwrap();
+            let seat = layout_seat("A", "1", 1);
 
-            let event_id = contract.create_event(
-                "Lakers vs Warriors".to_string(), venue_id, 1640995200, 20000,
-                1000000000000000000,
-                EventCategory::Sports {
-                    home_team_id: team1_id, away_team_id: team2_id, season_id: 1,
-                    game_type: GameType::RegularSeason, sport_type: SportType::Basketball,
-                },
-            ).unwrap();
+            // Generic events are limited too, and limits count holdings, not purchases
+            contract.configure_anti_scalping(show, AntiScalpingConfig { max_tickets_per_user: 2, ..resale_config(show) }).unwrap();
+            set_caller(accounts.bob);
+            let first = buy_ticket(&mut contract, show, seat.clone()).unwrap();
+            let second = buy_ticket(&mut contract, show, seat.clone()).unwrap();
+            assert_eq!(buy_ticket(&mut contract, show, seat.clone()), Err("Purchase limit reached".to_string()));
+            contract.transfer_ticket(first, accounts.charlie).unwrap();
+            let third = buy_ticket(&mut contract, show, seat.clone()).unwrap();
+            contract.transfer_ticket(second, accounts.charlie).unwrap();
+            assert_eq!(contract.get_holdings(accounts.charlie, PurchaseLimitScope::Event(show)), 2);
+            assert_eq!(contract.transfer_ticket(third, accounts.charlie), Err("Purchase limit reached".to_string()));
 
-            assert_eq!(event_id, 1);
-            let event = contract.get_event(event_id).unwrap();
-            assert_eq!(event.name, "Lakers vs Warriors");
-            match &event.category {
-                EventCategory::Sports { home_team_id, away_team_id, .. } => {
-                    assert_eq!(*home_team_id, team1_id);
-                    assert_eq!(*away_team_id, team2_id);
-                }
-                _ => panic!("Expected Sports event category"),
-            }
+            // Season and team limits count every game in scope
+            set_caller(accounts.alice);
+            contract.set_purchase_limit(PurchaseLimitScope::Season(1), Some(3)).unwrap();
+            set_caller(accounts.bob);
+            assert!(contract.set_purchase_limit(PurchaseLimitScope::Team(lakers), Some(3)).is_err());
+            buy_ticket(&mut contract, game1, seat.clone()).unwrap();
+            buy_ticket(&mut contract, game1, seat.clone()).unwrap();
+            buy_ticket(&mut contract, game2, seat.clone()).unwrap();
+            assert_eq!(buy_ticket(&mut contract, game2, seat.clone()), Err("Season purchase limit reached".to_string()));
+            set_caller(accounts.alice);
+            contract.set_purchase_limit(PurchaseLimitScope::Season(1), None).unwrap();
+            contract.set_purchase_limit(PurchaseLimitScope::Team(lakers), Some(3)).unwrap();
+            set_caller(accounts.bob);
+            assert_eq!(buy_ticket(&mut contract, game2, seat.clone()), Err("Team purchase limit reached".to_string()));
+
+            // Linked accounts share their limits
+            set_caller(accounts.alice);
+            contract.link_accounts(vec![accounts.bob, accounts.django]).unwrap();
+            assert_eq!(contract.get_linked_accounts(accounts.django), vec![accounts.bob, accounts.django]);
+            assert_eq!(contract.get_holdings(accounts.django, PurchaseLimitScope::Team(lakers)), 3);
+            set_caller(accounts.django);
+            assert_eq!(buy_ticket(&mut contract, game1, seat.clone()), Err("Team purchase limit reached".to_string()));
+            set_caller(accounts.alice);
+            contract.unlink_account(accounts.django).unwrap();
+            assert_eq!(contract.get_linked_accounts(accounts.bob), vec![accounts.bob]);
+            set_caller(accounts.django);
+            buy_ticket(&mut contract, game1, seat).unwrap();
         }
 
-        #[cfg(feature = "concert")]
         #[ink::test]
-        fn test_register_artist() {
+        fn test_events_require_live_attestations_from_trusted_attesters() {
+            let accounts = default_accounts();
+            use_contract_account();
             let mut contract = InkTix::new();
-            let artist_id = contract.register_artist("Taylor Swift".to_string()).unwrap();
-            assert_eq!(artist_id, 1);
+            for account in [accounts.bob, accounts.charlie] {
+                ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(account, 100_000_000);
+            }
+            let venue_id = contract.register_venue("Club".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Gig".to_string(), venue_id, 1_000_000, 10, 1000, EventCategory::Generic).unwrap();
+            let seat = layout_seat("GA", "1", 1);
+            contract.set_required_attestations(event_id, vec![AttestationKind::KycLite, AttestationKind::Phone]).unwrap();
+            contract.grant_role(accounts.django, Role::Attester, RoleScope::Global).unwrap();
 
-            let artist = contract.get_artist(artist_id).unwrap();
-            assert_eq!(artist.name, "Taylor Swift");
-            assert!(!artist.verified);
+            // Only attesters can attest, and buyers need every required kind
+            set_caller(accounts.bob);
+            assert_eq!(contract.attest(accounts.bob, AttestationKind::KycLite, [1; 32], 500), Err(String::from(InkTixError::MissingRole)));
+            set_caller(accounts.django);
+            contract.attest(accounts.bob, AttestationKind::KycLite, [1; 32], 500).unwrap();
+            let recorded: AttestationRecorded = decode_event(recorded_events().last().unwrap());
+            assert_eq!((recorded.account, recorded.attester), (accounts.bob, accounts.django));
+            set_caller(accounts.bob);
+            assert_eq!(buy_ticket(&mut contract, event_id, seat.clone()), Err("Missing required attestation".to_string()));
+            set_caller(accounts.django);
+            contract.attest(accounts.bob, AttestationKind::Phone, [2; 32], 500).unwrap();
+            assert_eq!(contract.get_attestation(accounts.bob, AttestationKind::Phone).unwrap().claim_hash, [2; 32]);
+            set_caller(accounts.bob);
+            buy_ticket(&mut contract, event_id, seat.clone()).unwrap();
+
+            // Attestations lapse at expiry until renewed
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(500);
+            assert!(!contract.has_valid_attestation(accounts.bob, AttestationKind::Phone));
+            assert_eq!(buy_ticket(&mut contract, event_id, seat.clone()), Err("Missing required attestation".to_string()));
+            set_caller(accounts.django);
+            contract.attest(accounts.bob, AttestationKind::KycLite, [1; 32], 1000).unwrap();
+            contract.attest(accounts.bob, AttestationKind::Phone, [2; 32], 1000).unwrap();
+
+            // Only the attester or an admin can revoke
+            set_caller(accounts.charlie);
+            assert_eq!(
+                contract.revoke_attestation(accounts.bob, AttestationKind::Phone),
+                Err("Only the attester or an admin can revoke an attestation".to_string())
+            );
+            set_caller(accounts.django);
+            contract.revoke_attestation(accounts.bob, AttestationKind::Phone).unwrap();
+            set_caller(accounts.bob);
+            assert_eq!(buy_ticket(&mut contract, event_id, seat.clone()), Err("Missing required attestation".to_string()));
+            set_caller(accounts.django);
+            contract.attest(accounts.bob, AttestationKind::Phone, [2; 32], 1000).unwrap();
+            set_caller(accounts.bob);
+            buy_ticket(&mut contract, event_id, seat.clone()).unwrap();
+
+            // Removing an attester withdraws its attestations
+            set_caller(accounts.alice);
+            contract.revoke_role(accounts.django, Role::Attester, RoleScope::Global).unwrap();
+            assert!(!contract.has_valid_attestation(accounts.bob, AttestationKind::KycLite));
+            set_caller(accounts.bob);
+            assert_eq!(buy_ticket(&mut contract, event_id, seat), Err("Missing required attestation".to_string()));
         }
 
-        #[cfg(feature = "concert")]
+        #[cfg(feature = "sports")]
         #[ink::test]
-        fn test_verify_artist() {
+        fn test_season_pass_upgrade_and_priority_renewal() {
+            let accounts = default_accounts();
+            use_contract_account();
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.alice, 100_000_000);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.bob, 100_000_000);
             let mut contract = InkTix::new();
-            let artist_id = contract.register_artist("Taylor Swift".to_string()).unwrap();
-            contract.verify_artist(artist_id).unwrap();
+            let (team_id, away_id) = season_teams(&mut contract, &[1, 2]);
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            contract.set_venue_seat_layout(venue_id, vec![seat_block("A", "1", 1, 10)]).unwrap();
+            let games: Vec<u32> = [1, 1, 2].iter()
+                .map(|&season_id| contract.create_event("Game".to_string(), venue_id, 1000, 100, 1000, home_game(team_id, away_id, season_id)).unwrap())
+                .collect();
+            let seat = layout_seat("A", "1", 1);
+            let benefits = vec![SeasonPassBenefits::default()];
+            let flex = contract.create_season_pass_package(
+                "Flex".to_string(), team_id, 1, SeasonPassType::Flex(3), vec![games[0], games[1]], 500, CurrencyId::DOT, 0, benefits.clone(),
+            );
+            assert_eq!(flex, Err("Flex pass covers more games than listed".to_string()));
+            let half = contract.create_season_pass_package(
+                "Half".to_string(), team_id, 1, SeasonPassType::HalfSeason, vec![games[0]], 600, CurrencyId::DOT, 0, benefits.clone(),
+            ).unwrap();
+            let full = contract.create_season_pass_package(
+                "Full".to_string(), team_id, 1, SeasonPassType::FullSeason, vec![games[0], games[1]], 1000, CurrencyId::DOT, 0, benefits.clone(),
+            ).unwrap();
 
-            let artist = contract.get_artist(artist_id).unwrap();
-            assert!(artist.verified);
+            let pass_id = ink::env::pay_with_call!(contract.purchase_season_pass(half, seat.clone()), 600).unwrap();
+            let underpaid = ink::env::pay_with_call!(contract.upgrade_season_pass(pass_id, full), 399);
+            assert_eq!(underpaid, Err(InkTixError::InsufficientPayment.into()));
+            assert_eq!(ink::env::pay_with_call!(contract.upgrade_season_pass(pass_id, full), 400), Ok(400));
+            assert_eq!(contract.upgrade_season_pass(pass_id, half), Err("Package is not a higher tier".to_string()));
+            let pass = contract.get_season_pass(pass_id).unwrap();
+            assert_eq!((pass.package_id, pass.pass_type, pass.total_games, pass.purchase_price), (full, SeasonPassType::FullSeason, 2, 1000));
+
+            contract.use_season_pass_for_event(pass_id, games[0]).unwrap();
+            let mut pass = contract.get_season_pass(pass_id).unwrap();
+            assert_eq!((pass.games_remaining, pass.loyalty_points), (1, SEASON_PASS_GAME_POINTS));
+            pass.staking_amount = 5000;
+            contract.storage.season_passes.insert(pass_id, &pass);
+
+            let next = contract.create_season_pass_package(
+                "Next Full".to_string(), team_id, 2, SeasonPassType::FullSeason, vec![games[2]], 1200, CurrencyId::DOT, 0, benefits,
+            ).unwrap();
+            let window_ends_at = ink::env::block_timestamp::<ink::env::DefaultEnvironment>() + 1000;
+            let ksm = contract.create_season_pass_package(
+                "Next KSM".to_string(), team_id, 2, SeasonPassType::FullSeason, vec![games[2]], 1200, CurrencyId::KSM, 0,
+                vec![SeasonPassBenefits::default()],
+            ).unwrap();
+            // Renewals can only move holders between the caller's own packages
+            contract.grant_role(accounts.django, Role::EventOrganizer, RoleScope::Global).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            let rival = contract.create_season_pass_package(
+                "Rival".to_string(), team_id, 2, SeasonPassType::FullSeason, vec![games[2]], 900, CurrencyId::DOT, 0,
+                vec![SeasonPassBenefits::default()],
+            ).unwrap();
+            let not_organizer = Err("Only the organizer of both packages can open renewals".to_string());
+            assert_eq!(contract.open_season_pass_renewals(full, rival, window_ends_at), not_organizer);
+            assert_eq!(contract.open_season_pass_renewals(full, next, window_ends_at), not_organizer);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(contract.open_season_pass_renewals(full, rival, window_ends_at), not_organizer);
+            let cross_currency = contract.open_season_pass_renewals(full, ksm, window_ends_at);
+            assert_eq!(cross_currency, Err("Renewal package must be in the same currency".to_string()));
+            assert_eq!(contract.open_season_pass_renewals(full, next, window_ends_at), Ok(()));
+            assert_eq!(contract.get_renewal_option(pass_id).map(|option| option.locked_price), Some(1000));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let early = ink::env::pay_with_call!(contract.purchase_season_pass(next, layout_seat("A", "1", 2)), 1200);
+            assert_eq!(early, Err("Season pass sale has not started".to_string()));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let renewed_id = ink::env::pay_with_call!(contract.renew_season_pass(pass_id), 1000).unwrap();
+            let renewed = contract.get_season_pass(renewed_id).unwrap();
+            assert_eq!((renewed.season_id, renewed.package_id, renewed.purchase_price), (2, next, 1000));
+            assert_eq!((renewed.loyalty_points, renewed.staking_amount), (SEASON_PASS_GAME_POINTS, 5000));
+            let old = contract.get_season_pass(pass_id).unwrap();
+            assert_eq!((old.status, old.loyalty_points, old.staking_amount), (SeasonPassStatus::Active, 0, 0));
+            let again = ink::env::pay_with_call!(contract.renew_season_pass(pass_id), 1000);
+            assert_eq!(again, Err("No renewal offer for this season pass".to_string()));
+            let renewed_event: SeasonPassRenewed = decode_event(recorded_events().last().unwrap());
+            assert_eq!((renewed_event.pass_id, renewed_event.renewed_pass_id, renewed_event.price), (pass_id, renewed_id, 1000));
+
+            // The renewed pass still covers the rest of its season and keeps its seat there
+            assert_eq!(
+                contract.get_seat_status(games[1], "A".to_string(), "1".to_string(), 1),
+                SeatStatus::SeasonPassReserved { pass_id },
+            );
+            contract.use_season_pass_for_event(pass_id, games[1]).unwrap();
+            assert_eq!(contract.storage.renewed_season_passes.get(pass_id), Some(renewed_id));
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(window_ends_at);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(ink::env::pay_with_call!(contract.purchase_season_pass(next, layout_seat("A", "1", 2)), 1200).is_ok());
+
+            // Payments are held per package and released to its organizer as its games complete
+            let organizer_only = contract.withdraw_season_pass_proceeds(next);
+            assert_eq!(organizer_only, Err("Only the package organizer can withdraw proceeds".to_string()));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let no_games = Err("No games of the package have completed".to_string());
+            assert_eq!(contract.withdraw_season_pass_proceeds(half), no_games);
+            contract.update_event_status(games[0], EventStatus::Completed).unwrap();
+            assert_eq!(contract.withdraw_season_pass_proceeds(half), Ok(600));
+            assert_eq!(contract.withdraw_season_pass_proceeds(full), Ok(200));
+            assert_eq!(contract.withdraw_season_pass_proceeds(full), Err("No proceeds to withdraw".to_string()));
+            contract.update_event_status(games[1], EventStatus::Completed).unwrap();
+            assert_eq!(contract.withdraw_season_pass_proceeds(full), Ok(200));
+            assert_eq!(contract.withdraw_season_pass_proceeds(next), no_games);
+            contract.update_event_status(games[2], EventStatus::Completed).unwrap();
+            assert_eq!(contract.withdraw_season_pass_proceeds(next), Ok(2200));
+            assert_eq!(contract.withdraw_season_pass_proceeds(next), Err("No proceeds to withdraw".to_string()));
         }
 
-        #[cfg(feature = "concert")]
+        #[cfg(feature = "sports")]
         #[ink::test]
-        fn test_create_concert_event() {
+        fn test_season_pass_cancelled_game_shares_and_open_flex_proceeds() {
+            let accounts = default_accounts();
+            use_contract_account();
+            for account in [accounts.alice, accounts.bob, accounts.charlie] {
+                ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(account, 100_000_000);
+            }
             let mut contract = InkTix::new();
-            let artist_id = contract.register_artist("Taylor Swift".to_string()).unwrap();
-            let venue_id = contract.register_venue(
-                "Madison Square Garden".to_string(), 20000, "New York".to_string(), VenueType::ConcertHall,
+            let (team_id, away_id) = season_teams(&mut contract, &[1]);
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let games: Vec<u32> = (0..2)
+                .map(|_| contract.create_event("Game".to_string(), venue_id, 1000, 100, 1000, home_game(team_id, away_id, 1)).unwrap())
+                .collect();
+            let benefits = vec![SeasonPassBenefits::default()];
+            let full = contract.create_season_pass_package(
+                "Full".to_string(), team_id, 1, SeasonPassType::FullSeason, games.clone(), 1000, CurrencyId::DOT, 0, benefits.clone(),
             ).unwrap();
-
-            let event_id = contract.create_concert_event(
-                "Eras Tour NYC".to_string(), artist_id, venue_id, 1640995200, 20000, 150000000000000000,
+            let flex = contract.create_season_pass_package(
+                "Flex".to_string(), team_id, 1, SeasonPassType::Flex(1), Vec::new(), 600, CurrencyId::DOT, 0, benefits,
             ).unwrap();
+            set_caller(accounts.bob);
+            let claimed_pass = ink::env::pay_with_call!(contract.purchase_season_pass(full, layout_seat("A", "1", 1)), 1000).unwrap();
+            let claimed_ticket = contract.use_season_pass_for_event(claimed_pass, games[1]).unwrap();
+            set_caller(accounts.charlie);
+            ink::env::pay_with_call!(contract.purchase_season_pass(full, layout_seat("A", "1", 2)), 1000).unwrap();
+            ink::env::pay_with_call!(contract.purchase_season_pass(flex, layout_seat("A", "1", 3)), 600).unwrap();
 
-            assert_eq!(event_id, 1);
-            let event = contract.get_event(event_id).unwrap();
-            assert_eq!(event.name, "Eras Tour NYC");
-            match &event.category {
-                EventCategory::Concert { artist_id: aid } => assert_eq!(*aid, artist_id),
-                _ => panic!("Expected Concert event category"),
-            }
+            // Holders that have not claimed a cancelled game are credited its share of their pass
+            set_caller(accounts.alice);
+            contract.update_event_status(games[1], EventStatus::Cancelled).unwrap();
+            assert_eq!(contract.get_refund_balance(accounts.charlie, CurrencyId::DOT), 500);
+            assert_eq!(contract.get_refund_balance(accounts.bob, CurrencyId::DOT), 0);
 
-            // Verify anti-scalping was auto-configured
-            let config = contract.get_anti_scalping_config(event_id).unwrap();
-            assert_eq!(config.max_tickets_per_user, 4);
-            assert!(config.anti_bot_measures);
+            // A claimed game is refunded through its ticket, which credits the same share
+            set_caller(accounts.bob);
+            contract.claim_refund(claimed_ticket).unwrap();
+            assert_eq!(contract.get_refund_balance(accounts.bob, CurrencyId::DOT), 500);
+            let pass = contract.get_season_pass(claimed_pass).unwrap();
+            assert_eq!((pass.games_attended, pass.games_remaining), (0, 1));
+            set_caller(accounts.alice);
+
+            // Games created later do not shrink an open flex package's released share
+            contract.create_event("Later".to_string(), venue_id, 1000, 100, 1000, home_game(team_id, away_id, 1)).unwrap();
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+            contract.update_event_status(games[0], EventStatus::Completed).unwrap();
+            assert_eq!(contract.withdraw_season_pass_proceeds(flex), Ok(300));
+            assert_eq!(contract.withdraw_season_pass_proceeds(full), Ok(1000));
         }
 
-        #[cfg(feature = "concert")]
+        #[cfg(feature = "sports")]
         #[ink::test]
-        fn test_concert_purchase_limit() {
+        fn test_season_pass_reserves_seat_and_claims_each_game_once() {
+            let accounts = default_accounts();
+            use_contract_account();
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.alice, 100_000_000);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.bob, 100_000_000);
             let mut contract = InkTix::new();
-            let artist_id = contract.register_artist("Artist".to_string()).unwrap();
-            let venue_id = contract.register_venue("Venue".to_string(), 100, "City".to_string(), VenueType::ConcertHall).unwrap();
-            let event_id = contract.create_concert_event("Concert".to_string(), artist_id, venue_id, 1, 100, 10).unwrap();
+            let (team_id, away_id) = season_teams(&mut contract, &[1]);
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            contract.set_venue_seat_layout(venue_id, vec![seat_block("101", "A", 1, 10)]).unwrap();
+            let game = contract.create_event("Game".to_string(), venue_id, 1000, 100, 1000, home_game(team_id, away_id, 1)).unwrap();
+            let away_game = contract.create_event("Away".to_string(), venue_id, 1000, 100, 1000, home_game(away_id, team_id, 1)).unwrap();
+            let benefits = vec![SeasonPassBenefits::default()];
+            let mixed = contract.create_season_pass_package(
+                "Mixed".to_string(), team_id, 1, SeasonPassType::FullSeason, vec![game, away_game], 1000, CurrencyId::DOT, 0, benefits.clone(),
+            );
+            assert_eq!(mixed, Err("Game is not a home game of the team's season".to_string()));
+            let full = contract.create_season_pass_package(
+                "Full".to_string(), team_id, 1, SeasonPassType::FullSeason, vec![game], 1000, CurrencyId::DOT, 0, benefits.clone(),
+            ).unwrap();
+            let flex = contract.create_season_pass_package(
+                "Flex".to_string(), team_id, 1, SeasonPassType::Flex(2), Vec::new(), 1000, CurrencyId::DOT, 0, benefits,
+            ).unwrap();
 
-            let seat = Seat {
-                section: "A".to_string(), row: "1".to_string(), seat_number: "1".to_string(),
-                seat_type: SeatType::GeneralAdmission, access_level: AccessLevel::Standard, price_multiplier: 10000,
+            let seat = layout_seat("101", "A", 3);
+            let flex_seat = layout_seat("101", "A", 5);
+            let pass_id = ink::env::pay_with_call!(contract.purchase_season_pass(full, seat.clone()), 1000).unwrap();
+            let flex_pass = ink::env::pay_with_call!(contract.purchase_season_pass(flex, flex_seat.clone()), 1000).unwrap();
+            let later = contract.create_event("Later".to_string(), venue_id, 1000, 100, 1000, home_game(team_id, away_id, 1)).unwrap();
+            assert_eq!(contract.get_season_pass_games(pass_id), vec![game]);
+            assert_eq!(contract.get_season_pass_games(flex_pass), vec![game, later]);
+            let status = |contract: &InkTix, event_id, seat_number| {
+                contract.get_seat_status(event_id, "101".to_string(), "A".to_string(), seat_number)
             };
-
-            // Should be able to purchase 4 tickets
-            for _ in 0..4 {
-                contract.purchase_ticket(event_id, seat.clone(), CurrencyId::DOT).unwrap();
+            assert_eq!(status(&contract, game, 3), SeatStatus::SeasonPassReserved { pass_id });
+            assert_eq!(status(&contract, later, 3), SeatStatus::Available);
+            assert_eq!(status(&contract, away_game, 3), SeatStatus::Available);
+            // A flex pass does not hold its seat, so it can sell before the holder claims it
+            for event_id in [game, later] {
+                assert_eq!(status(&contract, event_id, 5), SeatStatus::Available);
             }
 
-            // 5th ticket should fail
-            let result = contract.purchase_ticket(event_id, seat.clone(), CurrencyId::DOT);
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), "Purchase limit reached");
+            set_caller(accounts.bob);
+            assert_eq!(buy_ticket(&mut contract, game, seat.clone()), Err("Seat is reserved for a season pass holder".to_string()));
+            let taken = ink::env::pay_with_call!(contract.purchase_season_pass(full, seat.clone()), 1000);
+            assert_eq!(taken, Err("Seat is assigned to another season pass".to_string()));
+            buy_ticket(&mut contract, later, flex_seat).unwrap();
+            set_caller(accounts.alice);
+            assert_eq!(contract.use_season_pass_for_event(flex_pass, later), Err(InkTixError::SoldOut.into()));
+
+            set_caller(accounts.alice);
+            assert_eq!(contract.use_season_pass_for_event(pass_id, away_game), Err("Event is not covered by this season pass".to_string()));
+            assert_eq!(contract.use_season_pass_for_event(pass_id, 99), Err("Event not found".to_string()));
+            let ticket_id = contract.use_season_pass_for_event(pass_id, game).unwrap();
+            let ticket = contract.get_ticket(ticket_id).unwrap();
+            assert_eq!((ticket.section.as_str(), ticket.row.as_str(), ticket.seat_number), ("101", "A", 3));
+            assert_eq!(contract.get_seat_status(game, "101".to_string(), "A".to_string(), 3), SeatStatus::Sold { ticket_id });
+            let usage = contract.get_season_pass_usage(pass_id, game).unwrap();
+            assert_eq!((usage.event_id, usage.loyalty_points_earned), (game, SEASON_PASS_GAME_POINTS));
+            assert_eq!(contract.use_season_pass_for_event(pass_id, game), Err("No games remaining on this season pass".to_string()));
+            contract.use_season_pass_for_event(flex_pass, game).unwrap();
+            assert_eq!(contract.use_season_pass_for_event(flex_pass, game), Err("Game already claimed with this season pass".to_string()));
         }
 
         #[ink::test]
-        fn test_mint_ticket_nft() {
+        fn test_sealed_bid_auction_skips_over_limit_winners_and_caps_bids() {
+            let accounts = default_accounts();
+            use_contract_account();
             let mut contract = InkTix::new();
-            // Setup: create venue and event
-            let venue_id = contract.register_venue("Arena".to_string(), 1000, "LA".to_string(), VenueType::Arena).unwrap();
-            let event_id = contract.create_event("Game".to_string(), venue_id, 1000, 1000, 100, EventCategory::Generic).unwrap();
-            // Purchase ticket
-            let ticket_id = contract.purchase_ticket(event_id, Seat {
-                seat_number: "1".to_string(), section: "A".to_string(), row: "1".to_string(),
-                seat_type: SeatType::GeneralAdmission, access_level: AccessLevel::Standard,
-                price_multiplier: 10000,
-            }, CurrencyId::DOT).unwrap();
-            // Mint NFT
-            let token_id = contract.mint_ticket_nft(ticket_id).unwrap();
-            assert_eq!(token_id, 1);
-            // Verify
-            let verification = contract.verify_ticket_nft(token_id).unwrap();
-            assert!(verification.is_valid);
-            assert!(!verification.is_used);
-            // Can't mint again
-            assert!(contract.mint_ticket_nft(ticket_id).is_err());
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Final".to_string(), venue_id, 1_000_000, 100, 1000, EventCategory::Generic).unwrap();
+            let suite = SeatBlock { seat_type: SeatType::Suite, ..seat_block("S", "1", 1, 4) };
+            contract.set_event_seat_layout(event_id, vec![suite]).unwrap();
+            contract.configure_anti_scalping(event_id, AntiScalpingConfig { max_tickets_per_user: 2, ..resale_config(event_id) }).unwrap();
+            let sealed = AuctionFormat::SealedBid { reserve_price: 1_000, commit_ends_at: 2_000, reveal_ends_at: 3_000 };
+            let auction_id = contract.create_auction(event_id, 0, sealed).unwrap();
+
+            // Deposits must cover the reserve price and each auction takes a bounded number of bids
+            let salt = [7u8; 32];
+            let bids = [(accounts.bob, 5_000, 3, 15_000), (accounts.charlie, 3_000, 2, 6_000)];
+            for (bidder, price, quantity, deposit) in bids {
+                ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(bidder, 100_000_000);
+                set_caller(bidder);
+                let commitment = auctions::Auctions::bid_commitment(bidder, price, quantity, salt);
+                ink::env::pay_with_call!(contract.commit_auction_bid(auction_id, commitment), deposit).unwrap();
+            }
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.django, 100_000_000);
+            set_caller(accounts.django);
+            assert_eq!(
+                ink::env::pay_with_call!(contract.commit_auction_bid(auction_id, [0u8; 32]), 500),
+                Err("Deposit is below the reserve price".to_string())
+            );
+            for i in bids.len()..auctions::MAX_SEALED_BIDS {
+                let bidder = AccountId::from([i as u8 + 10; 32]);
+                ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(bidder, 100_000_000);
+                set_caller(bidder);
+                ink::env::pay_with_call!(contract.commit_auction_bid(auction_id, [1u8; 32]), 1_000).unwrap();
+            }
+            set_caller(accounts.django);
+            assert_eq!(
+                ink::env::pay_with_call!(contract.commit_auction_bid(auction_id, [0u8; 32]), 1_000),
+                Err("Auction has reached its bid limit".to_string())
+            );
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(2_000);
+            for (bidder, price, quantity, _) in bids {
+                set_caller(bidder);
+                contract.reveal_auction_bid(auction_id, price, quantity, salt).unwrap();
+            }
+
+            // Bob's award would exceed the event's limit, so he is skipped and fully refunded
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(3_000);
+            contract.settle_auction(auction_id).unwrap();
+            let refunds: Vec<u128> = bids.iter().map(|(bidder, ..)| contract.get_refund_balance(*bidder, CurrencyId::DOT)).collect();
+            assert_eq!(refunds, vec![15_000, 0]);
+            let auction = contract.get_auction(auction_id).unwrap();
+            assert_eq!((auction.seats_sold, auction.clearing_price), (2, Some(3_000)));
+            assert!(contract.get_user_tickets(accounts.bob).is_empty());
+            assert_eq!(contract.get_user_tickets(accounts.charlie).len(), 2);
         }
 
         #[ink::test]
-        fn test_use_ticket_nft() {
+        fn test_sealed_bid_settlement_counts_household_awards_and_skips_cancelled_events() {
+            let accounts = default_accounts();
+            use_contract_account();
             let mut contract = InkTix::new();
-            let venue_id = contract.register_venue("Arena".to_string(), 1000, "LA".to_string(), VenueType::Arena).unwrap();
-            let event_id = contract.create_event("Game".to_string(), venue_id, 1000, 1000, 100, EventCategory::Generic).unwrap();
-            let ticket_id = contract.purchase_ticket(event_id, Seat {
-                seat_number: "1".to_string(), section: "A".to_string(), row: "1".to_string(),
-                seat_type: SeatType::GeneralAdmission, access_level: AccessLevel::Standard,
-                price_multiplier: 10000,
-            }, CurrencyId::DOT).unwrap();
-            let token_id = contract.mint_ticket_nft(ticket_id).unwrap();
-            // Use ticket
-            let attendance_id = contract.use_ticket_nft(token_id).unwrap();
-            assert_eq!(attendance_id, 1);
-            // Verify shows used
-            let verification = contract.verify_ticket_nft(token_id).unwrap();
-            assert!(verification.is_used);
-            // Can't use again
-            assert!(contract.use_ticket_nft(token_id).is_err());
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let suite = SeatBlock { seat_type: SeatType::Suite, ..seat_block("S", "1", 1, 4) };
+            let sealed = AuctionFormat::SealedBid { reserve_price: 1_000, commit_ends_at: 2_000, reveal_ends_at: 3_000 };
+            let salt = [7u8; 32];
+            let mut auction_ids = Vec::new();
+            for _ in 0..2 {
+                let event_id = contract.create_event("Final".to_string(), venue_id, 1_000_000, 100, 1000, EventCategory::Generic).unwrap();
+                contract.set_event_seat_layout(event_id, vec![suite.clone()]).unwrap();
+                contract.configure_anti_scalping(event_id, AntiScalpingConfig { max_tickets_per_user: 2, ..resale_config(event_id) }).unwrap();
+                auction_ids.push((event_id, contract.create_auction(event_id, 0, sealed).unwrap()));
+            }
+            contract.link_accounts(vec![accounts.bob, accounts.charlie]).unwrap();
+            let bids = [(accounts.bob, 5_000, 2, 10_000), (accounts.charlie, 4_000, 2, 8_000), (accounts.django, 3_000, 1, 3_000)];
+            for (bidder, price, quantity, deposit) in bids {
+                ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(bidder, 100_000_000);
+                set_caller(bidder);
+                let commitment = auctions::Auctions::bid_commitment(bidder, price, quantity, salt);
+                for &(_, auction_id) in &auction_ids {
+                    ink::env::pay_with_call!(contract.commit_auction_bid(auction_id, commitment), deposit).unwrap();
+                }
+            }
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(2_000);
+            for (bidder, price, quantity, _) in bids {
+                set_caller(bidder);
+                for &(_, auction_id) in &auction_ids {
+                    contract.reveal_auction_bid(auction_id, price, quantity, salt).unwrap();
+                }
+            }
+            set_caller(accounts.alice);
+            contract.update_event_status(auction_ids[1].0, EventStatus::Cancelled).unwrap();
+
+            // Charlie shares Bob's limit, so Bob's award leaves no room for Charlie's
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(3_000);
+            contract.settle_auction(auction_ids[0].1).unwrap();
+            let refunds: Vec<u128> = bids.iter().map(|(bidder, ..)| contract.get_refund_balance(*bidder, CurrencyId::DOT)).collect();
+            assert_eq!(refunds, vec![4_000, 8_000, 0]);
+            let auction = contract.get_auction(auction_ids[0].1).unwrap();
+            assert_eq!((auction.seats_sold, auction.clearing_price), (3, Some(3_000)));
+            assert!(contract.get_user_tickets(accounts.charlie).is_empty());
+
+            // Nothing is awarded for a cancelled event and every deposit is refunded
+            contract.settle_auction(auction_ids[1].1).unwrap();
+            let refunds: Vec<u128> = bids.iter().map(|(bidder, ..)| contract.get_refund_balance(*bidder, CurrencyId::DOT)).collect();
+            assert_eq!(refunds, vec![14_000, 16_000, 3_000]);
+            assert_eq!(contract.get_auction(auction_ids[1].1).unwrap().seats_sold, 0);
+            assert_eq!(contract.get_user_tickets(accounts.bob).len(), 2);
         }
 
         #[ink::test]
-        fn test_currency_management() {
-            let contract = InkTix::new();
-            let currencies = contract.get_supported_currencies();
-            assert!(currencies.contains(&CurrencyId::DOT));
-            let dot_rate = contract.get_currency_rate(CurrencyId::DOT).unwrap();
-            assert_eq!(dot_rate, 1_000_000_000_000_000_000);
+        fn test_behavior_score_decays_and_lock_period_follows_the_holder() {
+            let accounts = default_accounts();
+            use_contract_account();
+            let mut contract = InkTix::new();
+            for account in [accounts.bob, accounts.charlie] {
+                ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(account, 100_000_000);
+            }
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Game".to_string(), venue_id, 1_000_000_000, 100, 1000, EventCategory::Generic).unwrap();
+            let other_event = contract.create_event("Show".to_string(), venue_id, 1_000_000_000, 100, 1000, EventCategory::Generic).unwrap();
+            contract.configure_anti_scalping(event_id, AntiScalpingConfig {
+                transfer_lock_period: 60_000, ..resale_config(event_id)
+            }).unwrap();
+            let set_time = |t| ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(t);
+
+            // Seven seats bought one by one only score the purchase beyond the allowance
+            set_caller(accounts.bob);
+            let mut tickets = Vec::new();
+            for t in 1..=7 {
+                set_time(t * 1000);
+                tickets.push(buy_ticket(&mut contract, event_id, layout_seat("A", "1", 1)).unwrap());
+            }
+            // A rapid purchase for another event starts its own run
+            set_time(8_000);
+            buy_ticket(&mut contract, other_event, layout_seat("A", "1", 1)).unwrap();
+            let profile = contract.get_behavior_profile(accounts.bob).unwrap();
+            assert_eq!((profile.suspicious_activity_score, profile.blacklist_status), (5, BlacklistStatus::Clean));
+
+            // The score decays a point per interval
+            set_time(7_000 + 3 * anti_scalping::SCORE_DECAY_INTERVAL);
+            assert_eq!(contract.get_behavior_profile(accounts.bob).unwrap().suspicious_activity_score, 2);
+
+            // The transfer lock runs from when the current holder got the ticket
+            contract.transfer_ticket(tickets[0], accounts.charlie).unwrap();
+            set_caller(accounts.charlie);
+            assert_eq!(
+                contract.transfer_ticket(tickets[0], accounts.django),
+                Err("Ticket is within its transfer lock period".to_string())
+            );
+            set_time(67_000 + 3 * anti_scalping::SCORE_DECAY_INTERVAL);
+            contract.transfer_ticket(tickets[0], accounts.django).unwrap();
+            assert_eq!(contract.get_ticket(tickets[0]).unwrap().purchase_date, 1_000);
         }
 
         #[ink::test]
-        fn test_generic_event() {
+        fn test_group_orders_are_bounded_and_recheck_limits_on_completion() {
+            let accounts = default_accounts();
+            use_contract_account();
             let mut contract = InkTix::new();
-            let venue_id = contract.register_venue("Hall".to_string(), 500, "City".to_string(), VenueType::ConventionCenter).unwrap();
-            let event_id = contract.create_event(
-                "Conference".to_string(), venue_id, 1640995200, 500, 50000000000000000, EventCategory::Generic,
-            ).unwrap();
-            let event = contract.get_event(event_id).unwrap();
-            assert_eq!(event.category, EventCategory::Generic);
+            for account in [accounts.bob, accounts.charlie] {
+                ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(account, 100_000_000);
+            }
+            let venue_id = contract.register_venue("Club".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Gig".to_string(), venue_id, 1_000_000_000, 20, 1000, EventCategory::Generic).unwrap();
+            contract.set_event_seat_layout(event_id, vec![seat_block("A", "1", 1, 10)]).unwrap();
+            contract.configure_anti_scalping(event_id, AntiScalpingConfig { max_tickets_per_user: 2, ..resale_config(event_id) }).unwrap();
+
+            // Deadlines are capped and each organizer has a bounded number of live orders
+            set_caller(accounts.bob);
+            let members = vec![(accounts.bob, 1), (accounts.charlie, 1)];
+            let too_late = group_orders::MAX_GROUP_ORDER_DURATION_MS + 1;
+            assert_eq!(
+                contract.create_group_order(event_id, layout_seat("A", "1", 1), members.clone(), too_late),
+                Err("Invalid group order deadline".to_string())
+            );
+            let mut orders = Vec::new();
+            for first_seat in [1, 3, 5] {
+                orders.push(contract.create_group_order(event_id, layout_seat("A", "1", first_seat), members.clone(), 5000).unwrap());
+            }
+            assert_eq!(
+                contract.create_group_order(event_id, layout_seat("A", "1", 7), members.clone(), 5000),
+                Err("Too many live group orders".to_string())
+            );
+
+            // A member who reached the event limit meanwhile blocks completion
+            set_caller(accounts.charlie);
+            for seat_number in [9, 10] {
+                buy_ticket(&mut contract, event_id, layout_seat("A", "1", seat_number)).unwrap();
+            }
+            let order = contract.get_group_order(orders[0]).unwrap();
+            set_caller(accounts.bob);
+            ink::env::pay_with_call!(contract.contribute_to_group_order(orders[0]), order.members[0].share).unwrap();
+            set_caller(accounts.charlie);
+            assert_eq!(
+                ink::env::pay_with_call!(contract.contribute_to_group_order(orders[0]), order.members[1].share),
+                Err(InkTixError::PurchaseLimitReached.into())
+            );
+            assert_eq!(contract.get_group_order(orders[0]).unwrap().status, GroupOrderStatus::Funding);
         }
 
         #[ink::test]
-        fn test_dynamic_pricing() {
+        fn test_dutch_auction_purchases_respect_sale_windows() {
+            let accounts = default_accounts();
+            use_contract_account();
             let mut contract = InkTix::new();
-            let venue_id = contract.register_venue(
-                "Arena".to_string(), 100, "LA".to_string(), VenueType::Arena,
-            ).unwrap();
-            let event_id = contract.create_event(
-                "Game".to_string(), venue_id, 1640995200, 100, 1000, EventCategory::Generic,
-            ).unwrap();
+            for account in [accounts.bob, accounts.charlie] {
+                ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(account, 100_000_000);
+            }
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Final".to_string(), venue_id, 1_000_000, 100, 1000, EventCategory::Generic).unwrap();
+            let courtside = SeatBlock { seat_type: SeatType::Courtside, ..seat_block("CS", "A", 1, 3) };
+            contract.set_event_seat_layout(event_id, vec![courtside]).unwrap();
+            contract.set_sale_windows(event_id, vec![
+                SaleWindow { access: SaleWindowAccess::LoyaltyTier(LoyaltyTier::Gold), starts_at: 100, ends_at: 200, per_user_limit: 1 },
+                SaleWindow { access: SaleWindowAccess::General, starts_at: 200, ends_at: 900_000, per_user_limit: 0 },
+            ]).unwrap();
+            contract.storage.loyalty_profiles.insert(accounts.bob, &LoyaltyProfile {
+                user_id: accounts.bob, total_points: 0, current_tier: LoyaltyTier::Platinum, points_earned_this_month: 0,
+                points_earned_this_year: 0, total_tickets_purchased: 0, total_spent: 0, join_date: 0,
+                last_activity: 0, streak_days: 0, referral_count: 0, referral_points: 0, fantasy_sports_points: 0,
+            });
+            let dutch = AuctionFormat::Dutch { start_price: 10_000, floor_price: 4_000, starts_at: 100, ends_at: 1_100 };
+            let auction_id = contract.create_auction(event_id, 0, dutch).unwrap();
 
-            // Enable dynamic pricing
-            contract.set_dynamic_pricing(event_id, true).unwrap();
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(150);
+            set_caller(accounts.charlie);
+            assert_eq!(
+                ink::env::pay_with_call!(contract.buy_from_dutch_auction(auction_id), 10_000),
+                Err("Not eligible for the current sale window".to_string())
+            );
+            set_caller(accounts.bob);
+            ink::env::pay_with_call!(contract.buy_from_dutch_auction(auction_id), 10_000).unwrap();
+            assert_eq!(
+                ink::env::pay_with_call!(contract.buy_from_dutch_auction(auction_id), 10_000),
+                Err("Sale window purchase limit reached".to_string())
+            );
+            assert_eq!(contract.get_sale_window_purchases(event_id, 0, accounts.bob), 1);
 
-            // Get price quote for general admission
-            let seat = Seat {
-                section: "A".to_string(), row: "1".to_string(), seat_number: "1".to_string(),
-                seat_type: SeatType::GeneralAdmission, access_level: AccessLevel::Standard,
-                price_multiplier: 10000,
-            };
-            let quote = contract.get_price_quote(event_id, seat.clone(), false).unwrap();
-            assert!(quote.final_price > 0);
-            assert_eq!(quote.base_price, 1000);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(200);
+            set_caller(accounts.charlie);
+            ink::env::pay_with_call!(contract.buy_from_dutch_auction(auction_id), 10_000).unwrap();
+        }
 
-            // Premium seat should cost more
-            let vip_seat = Seat {
-                section: "VIP".to_string(), row: "1".to_string(), seat_number: "1".to_string(),
-                seat_type: SeatType::Courtside, access_level: AccessLevel::VIP,
-                price_multiplier: 0, // Let algorithm decide
+        #[ink::test]
+        fn test_dutch_auction_purchases_respect_ballots() {
+            let accounts = default_accounts();
+            use_contract_account();
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.bob, 100_000_000);
+            let mut contract = InkTix::new();
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Final".to_string(), venue_id, 1_000_000, 100, 1000, EventCategory::Generic).unwrap();
+            let courtside = SeatBlock { seat_type: SeatType::Courtside, ..seat_block("CS", "A", 1, 3) };
+            contract.set_event_seat_layout(event_id, vec![courtside]).unwrap();
+            let dutch = AuctionFormat::Dutch { start_price: 10_000, floor_price: 4_000, starts_at: 100, ends_at: 1_100 };
+            let auction_id = contract.create_auction(event_id, 0, dutch).unwrap();
+            let terms = BallotTerms {
+                tickets: 2, max_per_entrant: 1, registration_ends_at: 1_000, claim_window: 500,
+                required_attestation: None, seed_commitment: [0u8; 32],
             };
-            let vip_quote = contract.get_price_quote(event_id, vip_seat, false).unwrap();
-            assert!(vip_quote.final_price > quote.final_price);
-            assert!(vip_quote.seat_multiplier > quote.seat_multiplier);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.alice, 100_000_000_000);
+            ink::env::pay_with_call!(contract.create_ballot(event_id, terms), ballots::MIN_BALLOT_BOND).unwrap();
 
-            // Purchase a ticket and verify dynamic price was applied
-            let ticket_id = contract.purchase_ticket(event_id, seat, CurrencyId::DOT).unwrap();
-            let ticket = contract.get_ticket(ticket_id).unwrap();
-            assert_eq!(ticket.dynamic_price_paid, ticket.purchase_price);
-            assert!(ticket.performance_multiplier_applied > 0);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(150);
+            set_caller(accounts.bob);
+            assert_eq!(
+                ink::env::pay_with_call!(contract.buy_from_dutch_auction(auction_id), 10_000),
+                Err("Tickets are sold by ballot".to_string())
+            );
         }
 
         #[ink::test]
-        fn test_dynamic_pricing_demand_surge() {
+        fn test_undrawn_ballot_closes_after_reveal_deadline() {
+            let accounts = default_accounts();
+            use_contract_account();
             let mut contract = InkTix::new();
-            let venue_id = contract.register_venue(
-                "Arena".to_string(), 10, "LA".to_string(), VenueType::Arena,
-            ).unwrap();
-            let event_id = contract.create_event(
-                "Game".to_string(), venue_id, 1640995200, 10, 1000, EventCategory::Generic,
-            ).unwrap();
-            contract.set_dynamic_pricing(event_id, true).unwrap();
-
-            let seat = Seat {
-                section: "A".to_string(), row: "1".to_string(), seat_number: "1".to_string(),
-                seat_type: SeatType::GeneralAdmission, access_level: AccessLevel::Standard,
-                price_multiplier: 10000,
+            let venue_id = contract.register_venue("Arena".to_string(), 100, "LA".to_string(), VenueType::Arena).unwrap();
+            let event_id = contract.create_event("Tour".to_string(), venue_id, 1_000_000_000, 10, 1000, EventCategory::Generic).unwrap();
+            let seed = [42u8; 32];
+            let mut seed_commitment = [0u8; 32];
+            ink::env::hash_bytes::<ink::env::hash::Blake2x256>(&seed, &mut seed_commitment);
+            let terms = BallotTerms {
+                tickets: 2, max_per_entrant: 1, registration_ends_at: 1_000, claim_window: 500,
+                required_attestation: None, seed_commitment,
             };
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.alice, 100_000_000_000);
+            let ballot_id = ink::env::pay_with_call!(contract.create_ballot(event_id, terms), ballots::MIN_BALLOT_BOND).unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.bob, 100_000_000);
+            set_caller(accounts.bob);
+            contract.enter_ballot(ballot_id, 1).unwrap();
+            let mut ballot = contract.get_ballot(ballot_id).unwrap();
+            ballot.entries = ballots::MAX_BALLOT_ENTRIES;
+            contract.storage.ballots.insert(ballot_id, &ballot);
+            set_caller(accounts.charlie);
+            assert_eq!(contract.enter_ballot(ballot_id, 1), Err("Ballot is full".to_string()));
+            ballot.entries = 1;
+            contract.storage.ballots.insert(ballot_id, &ballot);
 
-            // Buy 9 of 10 tickets to create high demand (90%)
-            for _ in 0..9 {
-                contract.purchase_ticket(event_id, seat.clone(), CurrencyId::DOT).unwrap();
-            }
+            // A beacon round after registration closed leaves the organizer able to draw
+            set_caller(accounts.alice);
+            contract.grant_role(accounts.frank, Role::RandomnessBeacon, RoleScope::Global).unwrap();
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1_000);
+            set_caller(accounts.frank);
+            contract.publish_beacon_round([9u8; 32]).unwrap();
 
-            // Price quote at 90% should show demand surge
-            let quote = contract.get_price_quote(event_id, seat, false).unwrap();
-            assert!(quote.demand_multiplier > 10000, "Expected demand surge at 90% capacity");
-            assert_eq!(quote.demand_percentage, 90);
+            // Until the reveal deadline only the organizer's draw can open sales
+            let deadline = 1_000 + ballots::DRAW_REVEAL_PERIOD;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(deadline - 1);
+            assert_eq!(contract.close_undrawn_ballot(ballot_id), Err("Ballot draw deadline has not passed".to_string()));
+
+            // Once it passes the seed can no longer be revealed and anyone can close the ballot
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(deadline);
+            set_caller(accounts.alice);
+            assert_eq!(contract.draw_ballot(ballot_id, seed), Err("Ballot draw deadline has passed".to_string()));
+            set_caller(accounts.bob);
+            contract.close_undrawn_ballot(ballot_id).unwrap();
+            assert!(contract.get_ballot(ballot_id).unwrap().closed);
+
+            // Withholding the seed forfeits the bond to the entrants
+            assert_eq!(contract.get_refund_balance(accounts.alice, CurrencyId::DOT), 0);
+            assert_eq!(contract.get_refund_balance(accounts.bob, CurrencyId::DOT), ballots::MIN_BALLOT_BOND);
+            assert_eq!(contract.close_undrawn_ballot(ballot_id), Err("Ballot is closed".to_string()));
+            set_caller(accounts.charlie);
+            assert_eq!(contract.enter_ballot(ballot_id, 1), Err("Ballot registration has closed".to_string()));
+            set_caller(accounts.bob);
+            buy_ticket(&mut contract, event_id, layout_seat("GA", "1", 1)).unwrap();
         }
     }
 }
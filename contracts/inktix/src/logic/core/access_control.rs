@@ -0,0 +1,129 @@
+//! Role-based access control and ownership transfer.
+//!
+//! The contract owner and global Admins pass every role check. Other roles are
+//! granted per scope: a global grant covers everything, a venue grant covers the
+//! venue and every event held there, and an event grant covers a single event.
+//! Ownership moves in two steps so it can't be handed to a mistyped account.
+//!
+//! # Functions
+//! - `has_role` -- whether an account holds a role for a scope
+//! - `ensure_role` -- rejects callers without a role for a scope
+//! - `grant_role` -- grants a scoped role (Admin grants are owner only)
+//! - `revoke_role` -- revokes a scoped role (Admin revocations are owner only)
+//! - `renounce_role` -- drops one of the caller's own roles
+//! - `transfer_ownership` -- proposes a new owner
+//! - `accept_ownership` -- completes a proposed ownership transfer
+
+use crate::storage::*;
+use crate::types::*;
+use ink::prelude::string::String;
+use ink::prelude::string::ToString;
+use ink::primitives::AccountId;
+
+/// Access control logic
+pub struct AccessControl;
+
+impl AccessControl {
+    /// Whether an account holds a role for a scope
+    pub fn has_role(storage: &InkTixStorage, account: AccountId, role: Role, scope: RoleScope) -> bool {
+        if account == storage.owner { return true; }
+        let grants = storage.account_roles.get(account).unwrap_or_default();
+        let event_venue = match scope {
+            RoleScope::Event(event_id) => storage.events.get(event_id).map(|e| e.venue_id),
+            _ => None,
+        };
+        grants.iter().any(|grant| {
+            if grant.role == Role::Admin && grant.scope == RoleScope::Global { return true; }
+            grant.role == role && match grant.scope {
+                RoleScope::Global => true,
+                RoleScope::Venue(venue_id) => scope == grant.scope || event_venue == Some(venue_id),
+                RoleScope::Event(_) => scope == grant.scope,
+            }
+        })
+    }
+
+    /// Reject a caller without a role for a scope
+    pub fn ensure_role(storage: &InkTixStorage, caller: AccountId, role: Role, scope: RoleScope) -> Result<(), String> {
+        if Self::has_role(storage, caller, role, scope) {
+            Ok(())
+        } else {
+            Err(InkTixError::MissingRole.into())
+        }
+    }
+
+    /// Grant a role to an account within a scope
+    pub fn grant_role(
+        storage: &mut InkTixStorage,
+        caller: AccountId,
+        account: AccountId,
+        role: Role,
+        scope: RoleScope,
+    ) -> Result<(), String> {
+        Self::ensure_can_administer(storage, caller, role)?;
+        match scope {
+            RoleScope::Global => {}
+            _ if role == Role::Admin => return Err("Admin role must be granted globally".to_string()),
+            RoleScope::Venue(venue_id) => { storage.venues.get(venue_id).ok_or("Venue not found")?; }
+            RoleScope::Event(event_id) => { storage.events.get(event_id).ok_or("Event not found")?; }
+        }
+        let grant = RoleGrant { role, scope };
+        let mut grants = storage.account_roles.get(account).unwrap_or_default();
+        if grants.contains(&grant) { return Err("Role already granted".to_string()); }
+        grants.push(grant);
+        storage.account_roles.insert(account, &grants);
+        Ok(())
+    }
+
+    /// Revoke a role from an account within a scope
+    pub fn revoke_role(
+        storage: &mut InkTixStorage,
+        caller: AccountId,
+        account: AccountId,
+        role: Role,
+        scope: RoleScope,
+    ) -> Result<(), String> {
+        Self::ensure_can_administer(storage, caller, role)?;
+        Self::remove_grant(storage, account, RoleGrant { role, scope })
+    }
+
+    /// Drop one of the caller's own roles
+    pub fn renounce_role(storage: &mut InkTixStorage, caller: AccountId, role: Role, scope: RoleScope) -> Result<(), String> {
+        Self::remove_grant(storage, caller, RoleGrant { role, scope })
+    }
+
+    /// Propose a new owner, who must call `accept_ownership` (owner only)
+    pub fn transfer_ownership(storage: &mut InkTixStorage, caller: AccountId, new_owner: AccountId) -> Result<(), String> {
+        if caller != storage.owner { return Err(InkTixError::NotOwner.into()); }
+        if new_owner == storage.owner { return Err("Account is already the owner".to_string()); }
+        storage.pending_owner = Some(new_owner);
+        Ok(())
+    }
+
+    /// Complete an ownership transfer, returning the previous owner (pending owner only)
+    pub fn accept_ownership(storage: &mut InkTixStorage, caller: AccountId) -> Result<AccountId, String> {
+        if storage.pending_owner != Some(caller) {
+            return Err("Caller is not the pending owner".to_string());
+        }
+        let previous_owner = storage.owner;
+        storage.owner = caller;
+        storage.pending_owner = None;
+        Ok(previous_owner)
+    }
+
+    fn ensure_can_administer(storage: &InkTixStorage, caller: AccountId, role: Role) -> Result<(), String> {
+        if role == Role::Admin {
+            if caller != storage.owner { return Err(InkTixError::NotOwner.into()); }
+            return Ok(());
+        }
+        Self::ensure_role(storage, caller, Role::Admin, RoleScope::Global)
+    }
+
+    fn remove_grant(storage: &mut InkTixStorage, account: AccountId, grant: RoleGrant) -> Result<(), String> {
+        let mut grants = storage.account_roles.get(account).unwrap_or_default();
+        let before = grants.len();
+        grants.retain(|g| *g != grant);
+        if grants.len() == before { return Err("Role not granted".to_string()); }
+        storage.account_roles.insert(account, &grants);
+        Ok(())
+    }
+}
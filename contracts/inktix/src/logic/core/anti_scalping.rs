@@ -1,27 +1,215 @@
-//! Anti-scalping configuration logic.
+//! Anti-scalping configuration, enforcement and behavior scoring.
 //!
-//! Allows the contract owner to configure per-event anti-scalping rules
-//! such as purchase limits, transfer restrictions, and bot-detection flags.
+//! Event organizers configure per-event anti-scalping rules such as purchase
+//! limits, transfer restrictions, blacklists and whitelists, and bot-detection
+//! flags. A blacklisted account cannot buy, receive, transfer or resell the
+//! event's tickets; a non-empty whitelist limits those to listed accounts.
+//!
+//! Every purchase, gift and resale also feeds the account's
+//! `UserBehaviorProfile`. Signals raise its `suspicious_activity_score` (0-100):
+//! runs of more than `RAPID_PURCHASE_ALLOWANCE` quick repeat purchases for one
+//! event (weighted up on events with `anti_bot_measures`), tickets flipped or
+//! gifted soon after their holder acquired them, resale markup over face value,
+//! and a high share of purchases resold. The score decays by one point every
+//! `SCORE_DECAY_INTERVAL` and escalates the profile to Warning, Suspended and
+//! Banned; suspended and banned accounts are blocked like blacklisted ones on
+//! every event. Statuses never relax on their own; admins review profiles and
+//! clear or override them.
 //!
 //! # Functions
 //! - `configure_anti_scalping` -- stores an anti-scalping config for a specific event
+//! - `ensure_allowed` -- rejects blacklisted, non-whitelisted, suspended or banned accounts
+//! - `behavior_profile` -- an account's profile with its score decayed to now
+//! - `record_purchase` / `record_transfer` / `record_resale` -- score activity and escalate status
+//! - `set_status` / `clear_profile` -- admin review of a behavior profile
 
 use crate::storage::*;
 use crate::types::*;
+use ink::env::DefaultEnvironment;
 use ink::prelude::string::String;
+use ink::prelude::string::ToString;
+use ink::primitives::AccountId;
+
+/// Purchases within this many milliseconds of the previous one count as rapid
+pub const RAPID_PURCHASE_INTERVAL: u64 = 60 * 1000;
+/// Rapid purchases for one event allowed in a run before each further one scores
+pub const RAPID_PURCHASE_ALLOWANCE: u32 = 6;
+/// Milliseconds for the suspicious activity score to decay by one point
+pub const SCORE_DECAY_INTERVAL: u64 = 60 * 60 * 1000;
+/// Tickets resold or gifted within this many milliseconds of purchase count as flipped
+pub const QUICK_FLIP_PERIOD: u64 = 24 * 60 * 60 * 1000;
+/// Score at which a profile is flagged with a warning
+pub const WARNING_SCORE: u8 = 30;
+/// Score at which a profile is suspended
+pub const SUSPENSION_SCORE: u8 = 60;
+/// Score at which a profile is banned
+pub const BAN_SCORE: u8 = 90;
 
 /// Anti-scalping functionality
 pub struct AntiScalping;
 
+#[allow(clippy::arithmetic_side_effects)]
+#[allow(clippy::cast_possible_truncation)]
 impl AntiScalping {
     /// Configure anti-scalping for event
+    ///
+    /// The config must name the event, cap resale at or above face value and
+    /// take a resale fee of at most 100%.
     pub fn configure_anti_scalping(
         storage: &mut InkTixStorage,
         event_id: u32,
         config: AntiScalpingConfig,
     ) -> Result<(), String> {
         let _event = storage.events.get(event_id).ok_or("Event not found")?;
+        if config.event_id != event_id { return Err("Config is for a different event".to_string()); }
+        if config.max_resale_price_multiplier < 100 {
+            return Err("Resale price cap cannot be below face value".to_string());
+        }
+        if config.resale_fee_percentage > 100 { return Err("Resale fee cannot exceed 100%".to_string()); }
         storage.anti_scalping_configs.insert(event_id, &config);
         Ok(())
     }
+
+    /// Check an account may buy, receive, transfer or resell an event's tickets
+    pub fn ensure_allowed(storage: &InkTixStorage, account: AccountId, event_id: u32) -> Result<(), String> {
+        if let Some(config) = storage.anti_scalping_configs.get(event_id) {
+            if config.blacklisted_addresses.contains(&account) {
+                return Err("Account is blacklisted for this event".to_string());
+            }
+            if !config.whitelisted_addresses.is_empty() && !config.whitelisted_addresses.contains(&account) {
+                return Err("Account is not whitelisted for this event".to_string());
+            }
+        }
+        match storage.user_behavior_profiles.get(account).map(|profile| profile.blacklist_status) {
+            Some(BlacklistStatus::Suspended) => Err("Account is suspended".to_string()),
+            Some(BlacklistStatus::Banned) => Err("Account is banned".to_string()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Get an account's behavior profile with its score decayed to now
+    pub fn behavior_profile(storage: &InkTixStorage, account: AccountId) -> Option<UserBehaviorProfile> {
+        storage.user_behavior_profiles.get(account).map(Self::decay)
+    }
+
+    /// Score a ticket purchase; tickets issued at the same time count as one purchase
+    ///
+    /// Purchases for the event within `RAPID_PURCHASE_INTERVAL` of the previous
+    /// one extend the buyer's run; each beyond `RAPID_PURCHASE_ALLOWANCE` scores.
+    pub fn record_purchase(storage: &mut InkTixStorage, buyer: AccountId, event_id: u32) {
+        let now = ink::env::block_timestamp::<DefaultEnvironment>();
+        let mut profile = Self::profile(storage, buyer);
+        let mut score = 0;
+        let mut burst = storage.purchase_bursts.get((buyer, event_id))
+            .unwrap_or(PurchaseBurst { purchases: 0, last_purchase_time: 0 });
+        if burst.purchases == 0 || now.saturating_sub(burst.last_purchase_time) >= RAPID_PURCHASE_INTERVAL {
+            burst.purchases = 1;
+        } else if now > burst.last_purchase_time {
+            burst.purchases += 1;
+            if burst.purchases > RAPID_PURCHASE_ALLOWANCE {
+                let anti_bot = storage.anti_scalping_configs.get(event_id).is_some_and(|config| config.anti_bot_measures);
+                score += if anti_bot { 10 } else { 5 };
+            }
+        }
+        burst.last_purchase_time = now;
+        storage.purchase_bursts.insert((buyer, event_id), &burst);
+        profile.total_tickets_purchased += 1;
+        profile.last_purchase_time = now;
+        Self::escalate(storage, profile, score);
+    }
+
+    /// Score a gift of a ticket by its previous owner
+    pub fn record_transfer(storage: &mut InkTixStorage, from: AccountId, ticket: &Ticket) {
+        let held = Self::hold_time(ticket);
+        let profile = Self::profile(storage, from);
+        Self::escalate(storage, profile, if held < QUICK_FLIP_PERIOD { 5 } else { 0 });
+    }
+
+    /// Score a completed resale by the seller, from hold time, markup and resale frequency
+    pub fn record_resale(storage: &mut InkTixStorage, seller: AccountId, ticket: &Ticket, price: u128) {
+        let held = Self::hold_time(ticket);
+        let mut profile = Self::profile(storage, seller);
+        let resold = profile.total_tickets_resold as u64;
+        profile.average_hold_time = (profile.average_hold_time * resold + held) / (resold + 1);
+        profile.total_tickets_resold += 1;
+        profile.last_resale_time = ink::env::block_timestamp::<DefaultEnvironment>();
+
+        let mut score: u32 = 0;
+        if held < QUICK_FLIP_PERIOD {
+            score += 15;
+        }
+        if ticket.dot_equivalent_paid > 0 && price > ticket.dot_equivalent_paid {
+            let markup_percent = (price - ticket.dot_equivalent_paid) * 100 / ticket.dot_equivalent_paid;
+            score += (markup_percent / 10).min(20) as u32;
+        }
+        if profile.total_tickets_resold >= 3 && profile.total_tickets_resold * 2 > profile.total_tickets_purchased {
+            score += 10;
+        }
+        Self::escalate(storage, profile, score.min(u8::MAX as u32) as u8);
+    }
+
+    /// Override a profile's status after review
+    pub fn set_status(storage: &mut InkTixStorage, account: AccountId, status: BlacklistStatus) {
+        let mut profile = Self::profile(storage, account);
+        profile.blacklist_status = status;
+        storage.user_behavior_profiles.insert(account, &profile);
+    }
+
+    /// Clear a profile's score and status after review, keeping its activity counters
+    pub fn clear_profile(storage: &mut InkTixStorage, account: AccountId) -> Result<(), String> {
+        let mut profile = storage.user_behavior_profiles.get(account).ok_or("Behavior profile not found")?;
+        profile.suspicious_activity_score = 0;
+        profile.blacklist_status = BlacklistStatus::Clean;
+        storage.user_behavior_profiles.insert(account, &profile);
+        Ok(())
+    }
+
+    /// Add `score` to a profile and escalate its status to match, never relaxing it
+    fn escalate(storage: &mut InkTixStorage, mut profile: UserBehaviorProfile, score: u8) {
+        profile.suspicious_activity_score = profile.suspicious_activity_score.saturating_add(score).min(100);
+        if score > 0 {
+            profile.score_updated_at = ink::env::block_timestamp::<DefaultEnvironment>();
+        }
+        let status = match profile.suspicious_activity_score {
+            s if s >= BAN_SCORE => BlacklistStatus::Banned,
+            s if s >= SUSPENSION_SCORE => BlacklistStatus::Suspended,
+            s if s >= WARNING_SCORE => BlacklistStatus::Warning,
+            _ => BlacklistStatus::Clean,
+        };
+        if status > profile.blacklist_status {
+            profile.blacklist_status = status;
+            profile.warning_count = profile.warning_count.saturating_add(1);
+        }
+        storage.user_behavior_profiles.insert(profile.user_id, &profile);
+    }
+
+    /// Milliseconds since the current owner acquired the ticket
+    fn hold_time(ticket: &Ticket) -> u64 {
+        ink::env::block_timestamp::<DefaultEnvironment>().saturating_sub(ticket.acquired_at)
+    }
+
+    /// Take one point off the score per elapsed `SCORE_DECAY_INTERVAL`, carrying the remainder
+    fn decay(mut profile: UserBehaviorProfile) -> UserBehaviorProfile {
+        let now = ink::env::block_timestamp::<DefaultEnvironment>();
+        let steps = now.saturating_sub(profile.score_updated_at) / SCORE_DECAY_INTERVAL;
+        let score = profile.suspicious_activity_score.saturating_sub(steps.min(u8::MAX as u64) as u8);
+        profile.score_updated_at = if score == 0 { now } else { profile.score_updated_at + steps * SCORE_DECAY_INTERVAL };
+        profile.suspicious_activity_score = score;
+        profile
+    }
+
+    fn profile(storage: &InkTixStorage, account: AccountId) -> UserBehaviorProfile {
+        Self::behavior_profile(storage, account).unwrap_or(UserBehaviorProfile {
+            user_id: account,
+            total_tickets_purchased: 0,
+            total_tickets_resold: 0,
+            average_hold_time: 0,
+            suspicious_activity_score: 0,
+            last_purchase_time: 0,
+            last_resale_time: 0,
+            score_updated_at: 0,
+            blacklist_status: BlacklistStatus::Clean,
+            warning_count: 0,
+        })
+    }
 }
@@ -0,0 +1,101 @@
+//! Identity attestation registry.
+//!
+//! Accounts holding the global Attester role record time-limited verifications
+//! against other accounts, such as KYC-lite, social proof or phone checks. Each
+//! attestation stores only a hash of the verified claim. An attestation counts
+//! while it is unexpired, unrevoked and its attester still holds the role, so
+//! removing a compromised attester withdraws everything it attested. Recording
+//! the same kind again replaces the previous attestation.
+//!
+//! Event organizers can require attestation kinds for buying an event's tickets.
+//!
+//! # Functions
+//! - `attest` -- records or renews an attestation for an account
+//! - `revoke` -- revokes an attestation (its attester or an admin)
+//! - `is_valid` -- whether an account holds a live attestation of a kind
+//! - `set_requirements` -- sets the attestation kinds an event requires
+//! - `ensure_attested` -- rejects buyers missing a required attestation
+
+use crate::storage::*;
+use crate::types::*;
+use ink::env::DefaultEnvironment;
+use ink::prelude::string::String;
+use ink::prelude::string::ToString;
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+use super::access_control::AccessControl;
+
+/// Attestation registry
+pub struct Attestations;
+
+impl Attestations {
+    /// Record an attestation of `kind` for `account`, valid until `expires_at`
+    pub fn attest(
+        storage: &mut InkTixStorage,
+        attester: AccountId,
+        account: AccountId,
+        kind: AttestationKind,
+        claim_hash: [u8; 32],
+        expires_at: u64,
+    ) -> Result<(), String> {
+        let now = ink::env::block_timestamp::<DefaultEnvironment>();
+        if expires_at <= now { return Err("Attestation expiry must be in the future".to_string()); }
+        if claim_hash == [0u8; 32] { return Err("Claim hash cannot be empty".to_string()); }
+        storage.attestations.insert((account, kind), &Attestation {
+            kind,
+            attester,
+            claim_hash,
+            issued_at: now,
+            expires_at,
+            revoked: false,
+        });
+        Ok(())
+    }
+
+    /// Revoke an account's attestation of `kind`; only its attester or an admin can
+    pub fn revoke(storage: &mut InkTixStorage, caller: AccountId, account: AccountId, kind: AttestationKind) -> Result<(), String> {
+        let mut attestation = storage.attestations.get((account, kind)).ok_or("Attestation not found")?;
+        if attestation.attester != caller && !AccessControl::has_role(storage, caller, Role::Admin, RoleScope::Global) {
+            return Err("Only the attester or an admin can revoke an attestation".to_string());
+        }
+        if attestation.revoked { return Err("Attestation already revoked".to_string()); }
+        attestation.revoked = true;
+        storage.attestations.insert((account, kind), &attestation);
+        Ok(())
+    }
+
+    /// Whether an account holds an unexpired, unrevoked attestation from a current attester
+    pub fn is_valid(storage: &InkTixStorage, account: AccountId, kind: AttestationKind) -> bool {
+        storage.attestations.get((account, kind)).is_some_and(|attestation| {
+            !attestation.revoked
+                && ink::env::block_timestamp::<DefaultEnvironment>() < attestation.expires_at
+                && AccessControl::has_role(storage, attestation.attester, Role::Attester, RoleScope::Global)
+        })
+    }
+
+    /// Set the attestation kinds buyers of an event need (an empty list removes the requirement)
+    pub fn set_requirements(storage: &mut InkTixStorage, event_id: u32, kinds: Vec<AttestationKind>) -> Result<(), String> {
+        if !storage.events.contains(event_id) {
+            return Err(InkTixError::EventNotFound.into());
+        }
+        if kinds.iter().enumerate().any(|(i, kind)| kinds[..i].contains(kind)) {
+            return Err("Attestation kind listed more than once".to_string());
+        }
+        if kinds.is_empty() {
+            storage.event_required_attestations.remove(event_id);
+        } else {
+            storage.event_required_attestations.insert(event_id, &kinds);
+        }
+        Ok(())
+    }
+
+    /// Reject an account missing any attestation the event requires
+    pub fn ensure_attested(storage: &InkTixStorage, account: AccountId, event_id: u32) -> Result<(), String> {
+        let required = storage.event_required_attestations.get(event_id).unwrap_or_default();
+        if required.iter().any(|kind| !Self::is_valid(storage, account, *kind)) {
+            return Err("Missing required attestation".to_string());
+        }
+        Ok(())
+    }
+}
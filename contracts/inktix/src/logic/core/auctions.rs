@@ -0,0 +1,376 @@
+//! Dutch and sealed-bid auctions for premium seat blocks.
+//!
+//! Every block in an event's seat layout is sold at the posted dynamic price
+//! unless an auction is configured for it. Only premium inventory can be
+//! auctioned: Courtside and Suite blocks, or any block of a championship game.
+//! While an auction is unsettled its seats cannot be bought, held or quoted at
+//! the posted price; once it settles, unsold seats return to posted-price sale.
+//!
+//! A Dutch auction's price falls linearly from its start price to its floor;
+//! each buyer pays the price at the moment they buy and receives the next free
+//! seat in the block. A sealed-bid auction collects up to `MAX_SEALED_BIDS`
+//! commitments, each with a deposit of at least the reserve price, then reveals.
+//! At settlement revealed bids are filled from the highest price down, skipping
+//! bidders whose award would break the event's purchase limits or who are
+//! banned, every winner pays the lowest winning price, and all other deposits
+//! (losing, skipped, unrevealed or excess) are credited to the bidders' refund
+//! balances for them to withdraw. Auctions settle in DOT.
+//!
+//! # Functions
+//! - `create_auction` -- puts a premium block of an event's layout up for auction
+//! - `sale_modes` -- returns the sale mode of each block in an event's layout
+//! - `ensure_posted_price` -- rejects seats in a block under an unsettled auction
+//! - `dutch_price` -- current price of a Dutch auction
+//! - `buy_dutch` -- buys the next free seat of a Dutch auction at its current price
+//! - `commit_bid` / `reveal_bid` -- sealed-bid commit and reveal
+//! - `bid_commitment` -- hash a sealed bid is committed as
+//! - `settle` -- ends an auction, issuing sealed-bid tickets and crediting refunds
+
+use crate::storage::*;
+use crate::types::*;
+use ink::env::DefaultEnvironment;
+use ink::prelude::string::String;
+use ink::prelude::string::ToString;
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+use super::currency_management::CurrencyManagement;
+use super::seat_inventory::SeatInventory;
+use super::ticket_management::TicketManagement;
+
+/// Most sealed bids an auction accepts, keeping its bid list within one storage cell
+pub const MAX_SEALED_BIDS: usize = 100;
+
+/// Auction management
+pub struct Auctions;
+
+#[allow(clippy::arithmetic_side_effects)]
+#[allow(clippy::cast_possible_truncation)]
+impl Auctions {
+    /// Put a block of an event's seat layout up for auction, returning the auction ID
+    pub fn create_auction(
+        storage: &mut InkTixStorage,
+        event_id: u32,
+        block_index: u32,
+        format: AuctionFormat,
+    ) -> Result<u32, String> {
+        let event = storage.events.get(event_id).ok_or("Event not found")?;
+        if !event.active { return Err("Event is not active".to_string()); }
+        let block = Self::block(storage, event_id, block_index)?;
+        let championship = matches!(event.category, EventCategory::Sports { game_type: GameType::Championship, .. });
+        if !championship && !matches!(block.seat_type, SeatType::Courtside | SeatType::Suite) {
+            return Err("Only premium seats can be auctioned".to_string());
+        }
+        if storage.block_auctions.contains((event_id, block_index)) {
+            return Err("Seat block is already being auctioned".to_string());
+        }
+        if Self::free_seats(storage, event_id, &block).len() as u32 != block.seat_count {
+            return Err("Seat block has seats already held or sold".to_string());
+        }
+
+        let now = ink::env::block_timestamp::<DefaultEnvironment>();
+        let (opens_at, closes_at) = match format {
+            AuctionFormat::Dutch { start_price, floor_price, starts_at, ends_at } => {
+                if floor_price == 0 || start_price <= floor_price {
+                    return Err("Dutch auction must fall from its start price to a non-zero floor".to_string());
+                }
+                (starts_at, ends_at)
+            }
+            AuctionFormat::SealedBid { reserve_price, commit_ends_at, reveal_ends_at } => {
+                if reserve_price == 0 {
+                    return Err("Reserve price must be greater than 0".to_string());
+                }
+                (commit_ends_at, reveal_ends_at)
+            }
+        };
+        if closes_at <= now || opens_at >= closes_at || closes_at > event.date {
+            return Err("Invalid auction schedule".to_string());
+        }
+
+        let auction_id = storage.get_next_id("auction");
+        storage.auctions.insert(auction_id, &Auction {
+            id: auction_id,
+            event_id,
+            block_index,
+            format,
+            seats_sold: 0,
+            clearing_price: None,
+            settled: false,
+        });
+        let mut event_auctions = storage.event_auctions.get(event_id).unwrap_or_default();
+        event_auctions.push(auction_id);
+        storage.event_auctions.insert(event_id, &event_auctions);
+        storage.block_auctions.insert((event_id, block_index), &auction_id);
+        Ok(auction_id)
+    }
+
+    /// Sale mode of each block in an event's seat layout, in layout order
+    pub fn sale_modes(storage: &InkTixStorage, event_id: u32) -> Vec<SaleMode> {
+        let blocks = storage.event_seat_layouts.get(event_id).unwrap_or_default();
+        (0..blocks.len() as u32)
+            .map(|block_index| {
+                storage.block_auctions.get((event_id, block_index))
+                    .and_then(|auction_id| storage.auctions.get(auction_id))
+                    .map(|auction| SaleMode::Auction { auction_id: auction.id, format: auction.format })
+                    .unwrap_or(SaleMode::PostedPrice)
+            })
+            .collect()
+    }
+
+    /// Reject a seat whose block is under an unsettled auction
+    pub fn ensure_posted_price(storage: &InkTixStorage, key: &SeatKey) -> Result<(), String> {
+        let (event_id, section, row, seat_number) = key;
+        let blocks = storage.event_seat_layouts.get(*event_id).unwrap_or_default();
+        let block_index = blocks.iter().position(|b| {
+            &b.section == section && &b.row == row
+                && *seat_number >= b.first_seat && *seat_number < b.first_seat + b.seat_count
+        });
+        match block_index {
+            Some(index) if storage.block_auctions.contains((*event_id, index as u32)) => {
+                Err("Seat is sold by auction".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Current price of a Dutch auction, falling linearly to its floor at `ends_at`
+    pub fn dutch_price(auction: &Auction, now: u64) -> Result<u128, String> {
+        let AuctionFormat::Dutch { start_price, floor_price, starts_at, ends_at } = auction.format else {
+            return Err("Not a Dutch auction".to_string());
+        };
+        if now < starts_at { return Err("Auction has not started".to_string()); }
+        if auction.settled || now >= ends_at { return Err("Auction has ended".to_string()); }
+        let elapsed = (now - starts_at) as u128;
+        let span = (ends_at - starts_at) as u128;
+        Ok(start_price - (start_price - floor_price) * elapsed / span)
+    }
+
+    /// Buy the next free seat of a Dutch auction at its current price
+    ///
+    /// The purchase goes through the same waitlist, purchase limit, ballot and
+    /// sale window checks as a posted-price sale. `payment` is native value and
+    /// must cover the price; refunding any excess is left to the caller.
+    /// Returns the ticket ID and the price charged.
+    pub fn buy_dutch(
+        storage: &mut InkTixStorage,
+        buyer: AccountId,
+        auction_id: u32,
+        payment: u128,
+    ) -> Result<(u64, u128), String> {
+        let mut auction = storage.auctions.get(auction_id).ok_or("Auction not found")?;
+        let price = Self::dutch_price(&auction, ink::env::block_timestamp::<DefaultEnvironment>())?;
+        let event = storage.events.get(auction.event_id).ok_or("Event not found")?;
+        if !event.active { return Err("Event is not active".to_string()); }
+        if event.sold_tickets >= event.capacity {
+            return Err(InkTixError::SoldOut.into());
+        }
+        super::waitlist::Waitlists::ensure_unreserved(storage, &event, 1)?;
+        let block = Self::block(storage, auction.event_id, auction.block_index)?;
+        let free = Self::free_seats(storage, auction.event_id, &block);
+        let seat_number = *free.first().ok_or(InkTixError::SoldOut)?;
+        TicketManagement::check_purchase_limit(storage, buyer, &event, 1)?;
+        let ballot_allocation = super::ballots::Ballots::check_purchase(storage, buyer, auction.event_id)?;
+        let sale_window = super::sale_windows::SaleWindows::check(storage, buyer, &event, 1)?;
+        CurrencyManagement::ensure_payable(storage, CurrencyId::DOT, price, payment)?;
+
+        let priced = Self::priced_seat(storage, auction.event_id, &block, seat_number, price)?;
+        let ticket_id = TicketManagement::issue_ticket(storage, buyer, auction.event_id, &priced, CurrencyId::DOT, price);
+        if let Some((ballot_id, position)) = ballot_allocation {
+            super::ballots::Ballots::record_purchase(storage, ballot_id, position);
+        }
+        if let Some(window_index) = sale_window {
+            super::sale_windows::SaleWindows::record(storage, auction.event_id, window_index, buyer, 1);
+        }
+        auction.seats_sold += 1;
+        if free.len() == 1 {
+            auction.settled = true;
+            storage.block_auctions.remove((auction.event_id, auction.block_index));
+        }
+        storage.auctions.insert(auction_id, &auction);
+        Ok((ticket_id, price))
+    }
+
+    /// Commit a sealed bid with a native deposit that must cover the bid when revealed
+    pub fn commit_bid(
+        storage: &mut InkTixStorage,
+        bidder: AccountId,
+        auction_id: u32,
+        commitment: [u8; 32],
+        deposit: u128,
+    ) -> Result<(), String> {
+        let auction = storage.auctions.get(auction_id).ok_or("Auction not found")?;
+        let AuctionFormat::SealedBid { reserve_price, commit_ends_at, .. } = auction.format else {
+            return Err("Not a sealed-bid auction".to_string());
+        };
+        if auction.settled || ink::env::block_timestamp::<DefaultEnvironment>() >= commit_ends_at {
+            return Err("Bidding has closed".to_string());
+        }
+        if deposit == 0 { return Err("A bid deposit is required".to_string()); }
+        super::anti_scalping::AntiScalping::ensure_allowed(storage, bidder, auction.event_id)?;
+        let mut bids = storage.auction_bids.get(auction_id).unwrap_or_default();
+        if bids.iter().any(|bid| bid.bidder == bidder) {
+            return Err("Bid already committed".to_string());
+        }
+        if bids.len() >= MAX_SEALED_BIDS { return Err("Auction has reached its bid limit".to_string()); }
+        if deposit < reserve_price { return Err("Deposit is below the reserve price".to_string()); }
+        bids.push(SealedBid { bidder, commitment, deposit, price: None, quantity: 0 });
+        storage.auction_bids.insert(auction_id, &bids);
+        Ok(())
+    }
+
+    /// Reveal a committed bid's price per seat and quantity
+    pub fn reveal_bid(
+        storage: &mut InkTixStorage,
+        bidder: AccountId,
+        auction_id: u32,
+        price: u128,
+        quantity: u32,
+        salt: [u8; 32],
+    ) -> Result<(), String> {
+        let auction = storage.auctions.get(auction_id).ok_or("Auction not found")?;
+        let AuctionFormat::SealedBid { reserve_price, commit_ends_at, reveal_ends_at } = auction.format else {
+            return Err("Not a sealed-bid auction".to_string());
+        };
+        let now = ink::env::block_timestamp::<DefaultEnvironment>();
+        if now < commit_ends_at || now >= reveal_ends_at {
+            return Err("Reveal period is not open".to_string());
+        }
+        let mut bids = storage.auction_bids.get(auction_id).unwrap_or_default();
+        let bid = bids.iter_mut().find(|bid| bid.bidder == bidder).ok_or("Bid not found")?;
+        if bid.price.is_some() { return Err("Bid already revealed".to_string()); }
+        if Self::bid_commitment(bidder, price, quantity, salt) != bid.commitment {
+            return Err("Revealed bid does not match the commitment".to_string());
+        }
+        let block = Self::block(storage, auction.event_id, auction.block_index)?;
+        if quantity == 0 || quantity > block.seat_count {
+            return Err("Invalid bid quantity".to_string());
+        }
+        if price < reserve_price { return Err("Bid is below the reserve price".to_string()); }
+        if price.checked_mul(quantity as u128).is_none_or(|total| total > bid.deposit) {
+            return Err("Deposit does not cover the bid".to_string());
+        }
+        bid.price = Some(price);
+        bid.quantity = quantity;
+        storage.auction_bids.insert(auction_id, &bids);
+        Ok(())
+    }
+
+    /// Blake2x256 hash of the SCALE-encoded `(bidder, price, quantity, salt)`
+    pub fn bid_commitment(bidder: AccountId, price: u128, quantity: u32, salt: [u8; 32]) -> [u8; 32] {
+        let mut commitment = [0u8; 32];
+        ink::env::hash_encoded::<ink::env::hash::Blake2x256, _>(&(bidder, price, quantity, salt), &mut commitment);
+        commitment
+    }
+
+    /// End an auction whose sale period is over
+    ///
+    /// A sealed-bid auction fills revealed bids from the highest price down (ties
+    /// in commit order), issuing tickets at the lowest winning price. Bidders
+    /// whose award, together with the awards already made to their household,
+    /// fails the event's purchase checks are skipped. Nothing is awarded for an
+    /// inactive or cancelled event. Deposits not spent on tickets are credited
+    /// to the bidders' refund balances. The block's unsold seats return to
+    /// posted-price sale.
+    pub fn settle(storage: &mut InkTixStorage, auction_id: u32) -> Result<(), String> {
+        let mut auction = storage.auctions.get(auction_id).ok_or("Auction not found")?;
+        if auction.settled { return Err("Auction already settled".to_string()); }
+        let now = ink::env::block_timestamp::<DefaultEnvironment>();
+        let closes_at = match auction.format {
+            AuctionFormat::Dutch { ends_at, .. } => ends_at,
+            AuctionFormat::SealedBid { reveal_ends_at, .. } => reveal_ends_at,
+        };
+        if now < closes_at { return Err("Auction has not ended".to_string()); }
+
+        if let AuctionFormat::SealedBid { .. } = auction.format {
+            let event = storage.events.get(auction.event_id).ok_or("Event not found")?;
+            let block = Self::block(storage, auction.event_id, auction.block_index)?;
+            let mut free = Self::free_seats(storage, auction.event_id, &block);
+            free.truncate(event.capacity.saturating_sub(event.sold_tickets) as usize);
+            if !event.active || event.status == EventStatus::Cancelled {
+                free.clear();
+            }
+
+            let bids = storage.auction_bids.get(auction_id).unwrap_or_default();
+            let mut ranked: Vec<&SealedBid> = bids.iter().filter(|bid| bid.price.is_some()).collect();
+            ranked.sort_by_key(|bid| ::core::cmp::Reverse(bid.price));
+            let mut remaining = free.len() as u32;
+            let mut awards: Vec<(AccountId, u32)> = Vec::new();
+            let mut clearing_price = None;
+            for bid in ranked {
+                if remaining == 0 { break; }
+                let awarded = bid.quantity.min(remaining);
+                let household = super::purchase_limits::PurchaseLimits::household(storage, bid.bidder);
+                let household_awarded: u32 = awards.iter()
+                    .filter(|(bidder, _)| household.contains(bidder))
+                    .map(|(_, n)| n)
+                    .sum();
+                if TicketManagement::check_purchase_limit(storage, bid.bidder, &event, household_awarded + awarded).is_err() {
+                    continue;
+                }
+                remaining -= awarded;
+                awards.push((bid.bidder, awarded));
+                clearing_price = bid.price;
+            }
+
+            let mut seats = free.into_iter();
+            for (bidder, awarded) in &awards {
+                for seat_number in seats.by_ref().take(*awarded as usize) {
+                    let price = clearing_price.unwrap_or_default();
+                    let priced = Self::priced_seat(storage, auction.event_id, &block, seat_number, price)?;
+                    TicketManagement::issue_ticket(storage, *bidder, auction.event_id, &priced, CurrencyId::DOT, price);
+                }
+            }
+            for bid in &bids {
+                let awarded = awards.iter().find(|(bidder, _)| *bidder == bid.bidder).map_or(0, |(_, n)| *n);
+                let paid = clearing_price.unwrap_or_default() * awarded as u128;
+                if bid.deposit > paid {
+                    super::escrow::Escrow::credit_refund(storage, bid.bidder, CurrencyId::DOT, bid.deposit - paid);
+                }
+            }
+            auction.seats_sold = awards.iter().map(|(_, n)| n).sum();
+            auction.clearing_price = clearing_price;
+        }
+
+        auction.settled = true;
+        storage.auctions.insert(auction_id, &auction);
+        storage.block_auctions.remove((auction.event_id, auction.block_index));
+        Ok(())
+    }
+
+    /// Block at an index of an event's seat layout
+    fn block(storage: &InkTixStorage, event_id: u32, block_index: u32) -> Result<SeatBlock, String> {
+        storage.event_seat_layouts.get(event_id)
+            .and_then(|blocks| blocks.into_iter().nth(block_index as usize))
+            .ok_or_else(|| "Seat block not found".to_string())
+    }
+
+    /// Seat numbers in a block that are neither held nor sold
+    fn free_seats(storage: &InkTixStorage, event_id: u32, block: &SeatBlock) -> Vec<u32> {
+        (block.first_seat..block.first_seat + block.seat_count)
+            .filter(|&seat_number| {
+                let key = (event_id, block.section.clone(), block.row.clone(), seat_number);
+                SeatInventory::seat_status(storage, &key) == SeatStatus::Available
+            })
+            .collect()
+    }
+
+    /// A block seat resolved against the layout, priced at the auction price
+    fn priced_seat(
+        storage: &InkTixStorage,
+        event_id: u32,
+        block: &SeatBlock,
+        seat_number: u32,
+        price: u128,
+    ) -> Result<HeldSeat, String> {
+        let requested = Seat {
+            section: block.section.clone(),
+            row: block.row.clone(),
+            seat_number: seat_number.to_string(),
+            seat_type: block.seat_type,
+            access_level: block.access_level,
+            price_multiplier: 0,
+        };
+        let (seat, seat_number, _) = SeatInventory::apply_layout(storage, event_id, requested)?;
+        Ok(HeldSeat { seat, seat_number, price, performance_multiplier: 10000 })
+    }
+}
@@ -0,0 +1,361 @@
+//! Ballot sales for oversubscribed events.
+//!
+//! Instead of a first-come sale, an organizer can open a ballot before any
+//! tickets are sold, posting a bond of at least `MIN_BALLOT_BOND`. Fans
+//! (optionally only those holding a valid attestation of a required kind)
+//! register interest for up to `max_per_entrant` tickets until registration
+//! closes or the ballot holds `MAX_BALLOT_ENTRIES` entries. Each entry is
+//! folded into the ballot's running entropy hash, and no entry is accepted
+//! once registration closes, so the entropy is final before the draw. The
+//! organizer committed to a seed when opening the ballot and reveals it within
+//! `DRAW_REVEAL_PERIOD` of registration closing to run the draw.
+//!
+//! The seed alone would let the organizer, who knows it, add a last entry that
+//! produces the order they want. The draw therefore also mixes in the first
+//! round published by a `RandomnessBeacon` after registration closed, which
+//! nobody knows while entries can still be made: the seed, entropy and beacon
+//! output are hashed together and drive a Fisher-Yates shuffle, so anyone can
+//! recompute the drawn order from the stored entries, seed and beacon round.
+//! Revealing the seed returns the bond to the organizer. A ballot that is not
+//! drawn in time can be closed by anyone, returning the event to general sale;
+//! if a beacon round was available to draw with, the organizer withheld the
+//! seed and the bond is shared among the entrants, otherwise it is returned.
+//! Bonds and forfeited shares are credited to refund balances.
+//!
+//! Entries are stored one per position and allocated tickets in drawn order;
+//! each allocation can be bought through `purchase_ticket` until its claim
+//! deadline. Entries beyond the allocated ones form the ballot's waitlist:
+//! `roll_over` moves lapsed allocations to the next fans in line with a fresh
+//! deadline. While a ballot is open, only fans with a live allocation can buy
+//! and seats cannot be held or quoted; once every allocation is claimed or the
+//! waitlist is exhausted the ballot closes and general sale resumes.
+//!
+//! # Functions
+//! - `create_ballot` -- opens a bonded ballot for an event before sales start
+//! - `enter_ballot` -- registers a fan's interest during registration
+//! - `publish_beacon` -- records a randomness beacon round
+//! - `beacon_after` -- the first beacon round published at or after a time
+//! - `draw` -- checks the revealed seed and allocates tickets in drawn order
+//! - `close_undrawn` -- closes a ballot that was not drawn in time, settling its bond
+//! - `roll_over` -- offers lapsed allocations to the waitlist
+//! - `entries` -- a ballot's entries in stored order
+//! - `ensure_not_balloted` -- rejects holds and quotes for an event with an open ballot
+//! - `check_purchase` / `record_purchase` -- gate and record purchases against allocations
+
+use crate::storage::*;
+use crate::types::*;
+use ink::env::DefaultEnvironment;
+use ink::prelude::string::String;
+use ink::prelude::string::ToString;
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+/// Milliseconds after registration closes within which the organizer must reveal the seed (24 hours)
+pub const DRAW_REVEAL_PERIOD: u64 = 24 * 60 * 60 * 1000;
+/// Most entries a ballot accepts, keeping its draw and roll-over within one message
+pub const MAX_BALLOT_ENTRIES: u32 = 500;
+/// Smallest native bond an organizer posts when opening a ballot (1 DOT)
+pub const MIN_BALLOT_BOND: u128 = 10_000_000_000;
+
+/// Ballot management
+pub struct Ballots;
+
+#[allow(clippy::arithmetic_side_effects)]
+#[allow(clippy::cast_possible_truncation)]
+impl Ballots {
+    /// Open a ballot for an event that has not sold any tickets with the organizer's native `bond`, returning the ballot ID
+    pub fn create_ballot(
+        storage: &mut InkTixStorage,
+        organizer: AccountId,
+        event_id: u32,
+        terms: BallotTerms,
+        bond: u128,
+    ) -> Result<u32, String> {
+        let event = storage.events.get(event_id).ok_or("Event not found")?;
+        if !event.active { return Err("Event is not active".to_string()); }
+        if event.sold_tickets > 0 {
+            return Err("Ballot must open before sales start".to_string());
+        }
+        if storage.event_ballots.contains(event_id) {
+            return Err("Event already has a ballot".to_string());
+        }
+        if terms.tickets == 0 || terms.tickets > event.capacity || terms.max_per_entrant == 0 {
+            return Err("Invalid ballot ticket counts".to_string());
+        }
+        let now = ink::env::block_timestamp::<DefaultEnvironment>();
+        if terms.registration_ends_at <= now || terms.registration_ends_at >= event.date || terms.claim_window == 0 {
+            return Err("Invalid ballot schedule".to_string());
+        }
+        if bond < MIN_BALLOT_BOND {
+            return Err("Ballot bond is below the minimum".to_string());
+        }
+
+        let ballot_id = storage.get_next_id("ballot");
+        storage.ballots.insert(ballot_id, &Ballot {
+            id: ballot_id,
+            event_id,
+            organizer,
+            bond,
+            terms,
+            entries: 0,
+            entropy: [0u8; 32],
+            draw_seed: None,
+            beacon_round: None,
+            next_in_line: 0,
+            tickets_purchased: 0,
+            closed: false,
+        });
+        storage.event_ballots.insert(event_id, &ballot_id);
+        Ok(ballot_id)
+    }
+
+    /// Register interest in up to `max_per_entrant` tickets
+    ///
+    /// The ballot's entropy becomes the Blake2x256 hash of the SCALE-encoded
+    /// `(entropy, entrant, quantity, block_number)`.
+    pub fn enter_ballot(storage: &mut InkTixStorage, entrant: AccountId, ballot_id: u32, quantity: u32) -> Result<(), String> {
+        let mut ballot = storage.ballots.get(ballot_id).ok_or("Ballot not found")?;
+        if ink::env::block_timestamp::<DefaultEnvironment>() >= ballot.terms.registration_ends_at
+            || ballot.draw_seed.is_some()
+            || ballot.closed
+        {
+            return Err("Ballot registration has closed".to_string());
+        }
+        if let Some(kind) = ballot.terms.required_attestation {
+            if !super::attestations::Attestations::is_valid(storage, entrant, kind) {
+                return Err("Only attested fans can enter this ballot".to_string());
+            }
+        }
+        if quantity == 0 || quantity > ballot.terms.max_per_entrant {
+            return Err("Invalid ballot quantity".to_string());
+        }
+        if storage.ballot_positions.contains((ballot_id, entrant)) {
+            return Err("Already entered this ballot".to_string());
+        }
+        if ballot.entries >= MAX_BALLOT_ENTRIES {
+            return Err("Ballot is full".to_string());
+        }
+        let entered_block = ink::env::block_number::<DefaultEnvironment>();
+        let position = ballot.entries;
+        storage.ballot_entries.insert((ballot_id, position), &BallotEntry {
+            entrant, requested: quantity, entered_block, allocated: 0, purchased: 0, claim_deadline: 0,
+        });
+        storage.ballot_positions.insert((ballot_id, entrant), &position);
+        ballot.entries += 1;
+        ballot.entropy = Self::hash(&(ballot.entropy, entrant, quantity, entered_block));
+        storage.ballots.insert(ballot_id, &ballot);
+        Ok(())
+    }
+
+    /// Record a randomness beacon round (beacon publishers only), returning its round number
+    pub fn publish_beacon(storage: &mut InkTixStorage, publisher: AccountId, output: [u8; 32]) -> Result<u32, String> {
+        super::access_control::AccessControl::ensure_role(storage, publisher, Role::RandomnessBeacon, RoleScope::Global)?;
+        let round = storage.get_next_id("beacon_round");
+        let published_at = ink::env::block_timestamp::<DefaultEnvironment>();
+        storage.beacon_rounds.insert(round, &BeaconRound { output, published_at });
+        Ok(round)
+    }
+
+    /// The first beacon round published at or after `timestamp`, with its round number
+    pub fn beacon_after(storage: &InkTixStorage, timestamp: u64) -> Option<(u32, BeaconRound)> {
+        let (mut low, mut high) = (1, storage.total_beacon_rounds + 1);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if storage.beacon_rounds.get(mid).is_some_and(|round| round.published_at < timestamp) {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        storage.beacon_rounds.get(low).map(|round| (low, round))
+    }
+
+    /// Run the draw with the organizer's revealed seed, returning the draw seed
+    ///
+    /// The draw seed is the Blake2x256 hash of the SCALE-encoded
+    /// `(seed, entropy, beacon_output)`, taking the first beacon round published
+    /// at or after registration closed. Position `i` (from last to second) swaps
+    /// with position `r % (i + 1)`, where `r` is the little-endian `u64` prefix
+    /// of the hash of `(draw_seed, i)`. The organizer's bond is credited back.
+    pub fn draw(storage: &mut InkTixStorage, ballot_id: u32, seed: [u8; 32]) -> Result<[u8; 32], String> {
+        let mut ballot = storage.ballots.get(ballot_id).ok_or("Ballot not found")?;
+        let now = ink::env::block_timestamp::<DefaultEnvironment>();
+        if now < ballot.terms.registration_ends_at {
+            return Err("Ballot registration is still open".to_string());
+        }
+        if ballot.draw_seed.is_some() { return Err("Ballot already drawn".to_string()); }
+        if ballot.closed || now >= ballot.terms.registration_ends_at + DRAW_REVEAL_PERIOD {
+            return Err("Ballot draw deadline has passed".to_string());
+        }
+        let mut commitment = [0u8; 32];
+        ink::env::hash_bytes::<ink::env::hash::Blake2x256>(&seed, &mut commitment);
+        if commitment != ballot.terms.seed_commitment {
+            return Err("Seed does not match the commitment".to_string());
+        }
+        let (round, beacon) = Self::beacon_after(storage, ballot.terms.registration_ends_at)
+            .ok_or("No beacon round since registration closed")?;
+
+        let draw_seed = Self::hash(&(seed, ballot.entropy, beacon.output));
+        for i in (1..ballot.entries).rev() {
+            let digest = Self::hash(&(draw_seed, i));
+            let mut prefix = [0u8; 8];
+            prefix.copy_from_slice(&digest[..8]);
+            let j = (u64::from_le_bytes(prefix) % (i as u64 + 1)) as u32;
+            if j != i {
+                Self::swap(storage, ballot_id, i, j);
+            }
+        }
+
+        ballot.draw_seed = Some(draw_seed);
+        ballot.beacon_round = Some(round);
+        super::escrow::Escrow::credit_refund(storage, ballot.organizer, CurrencyId::DOT, ballot.bond);
+        let tickets = ballot.terms.tickets;
+        let unallocated = Self::allocate(storage, &mut ballot, tickets, now);
+        ballot.closed = unallocated == tickets;
+        storage.ballots.insert(ballot_id, &ballot);
+        Ok(draw_seed)
+    }
+
+    /// Close a ballot that was not drawn within `DRAW_REVEAL_PERIOD`, resuming general sale
+    ///
+    /// If a beacon round was published in time to draw with, the bond is split
+    /// equally among the entrants, any remainder going to the first; otherwise,
+    /// or with no entrants, it is credited back to the organizer.
+    pub fn close_undrawn(storage: &mut InkTixStorage, ballot_id: u32) -> Result<(), String> {
+        let mut ballot = storage.ballots.get(ballot_id).ok_or("Ballot not found")?;
+        if ballot.draw_seed.is_some() { return Err("Ballot already drawn".to_string()); }
+        if ballot.closed { return Err("Ballot is closed".to_string()); }
+        let now = ink::env::block_timestamp::<DefaultEnvironment>();
+        let deadline = ballot.terms.registration_ends_at + DRAW_REVEAL_PERIOD;
+        if now < deadline {
+            return Err("Ballot draw deadline has not passed".to_string());
+        }
+        ballot.closed = true;
+        storage.ballots.insert(ballot_id, &ballot);
+
+        let withheld = Self::beacon_after(storage, ballot.terms.registration_ends_at)
+            .is_some_and(|(_, beacon)| beacon.published_at < deadline);
+        if !withheld || ballot.entries == 0 {
+            super::escrow::Escrow::credit_refund(storage, ballot.organizer, CurrencyId::DOT, ballot.bond);
+            return Ok(());
+        }
+        let share = ballot.bond / ballot.entries as u128;
+        let remainder = ballot.bond - share * ballot.entries as u128;
+        for position in 0..ballot.entries {
+            let Some(entry) = storage.ballot_entries.get((ballot_id, position)) else { continue };
+            let amount = if position == 0 { share + remainder } else { share };
+            super::escrow::Escrow::credit_refund(storage, entry.entrant, CurrencyId::DOT, amount);
+        }
+        Ok(())
+    }
+
+    /// Move lapsed, unclaimed allocations to the next fans on the waitlist, returning the tickets released
+    pub fn roll_over(storage: &mut InkTixStorage, ballot_id: u32) -> Result<u32, String> {
+        let mut ballot = storage.ballots.get(ballot_id).ok_or("Ballot not found")?;
+        if ballot.draw_seed.is_none() { return Err("Ballot has not been drawn".to_string()); }
+        if ballot.closed { return Err("Ballot is closed".to_string()); }
+        let now = ink::env::block_timestamp::<DefaultEnvironment>();
+        let mut released = 0;
+        for position in 0..ballot.next_in_line {
+            let Some(mut entry) = storage.ballot_entries.get((ballot_id, position)) else { continue };
+            if entry.purchased < entry.allocated && now >= entry.claim_deadline {
+                released += entry.allocated - entry.purchased;
+                entry.allocated = entry.purchased;
+                storage.ballot_entries.insert((ballot_id, position), &entry);
+            }
+        }
+
+        let unallocated = Self::allocate(storage, &mut ballot, released, now);
+        let live = (0..ballot.next_in_line)
+            .filter_map(|position| storage.ballot_entries.get((ballot_id, position)))
+            .any(|entry| entry.purchased < entry.allocated && now < entry.claim_deadline);
+        if unallocated > 0 || !live {
+            ballot.closed = true;
+        }
+        storage.ballots.insert(ballot_id, &ballot);
+        Ok(released)
+    }
+
+    /// A ballot's entries, in drawn order once drawn
+    pub fn entries(storage: &InkTixStorage, ballot_id: u32) -> Vec<BallotEntry> {
+        let count = storage.ballots.get(ballot_id).map_or(0, |ballot| ballot.entries);
+        (0..count).filter_map(|position| storage.ballot_entries.get((ballot_id, position))).collect()
+    }
+
+    /// Reject holds and quotes while an event's ballot is open
+    pub fn ensure_not_balloted(storage: &InkTixStorage, event_id: u32) -> Result<(), String> {
+        match Self::open_ballot(storage, event_id) {
+            Some(_) => Err("Tickets are sold by ballot".to_string()),
+            None => Ok(()),
+        }
+    }
+
+    /// Check a buyer may purchase a ticket, returning the ballot entry position to record it against
+    ///
+    /// Events without an open ballot need no allocation.
+    pub fn check_purchase(storage: &InkTixStorage, buyer: AccountId, event_id: u32) -> Result<Option<(u32, u32)>, String> {
+        let Some(ballot) = Self::open_ballot(storage, event_id) else {
+            return Ok(None);
+        };
+        if ballot.draw_seed.is_none() { return Err("Tickets are sold by ballot".to_string()); }
+        let now = ink::env::block_timestamp::<DefaultEnvironment>();
+        storage.ballot_positions.get((ballot.id, buyer))
+            .filter(|&position| storage.ballot_entries.get((ballot.id, position))
+                .is_some_and(|entry| entry.purchased < entry.allocated && now < entry.claim_deadline))
+            .map(|position| Some((ballot.id, position)))
+            .ok_or_else(|| "No live ballot allocation".to_string())
+    }
+
+    /// Record a purchase against an allocation, closing the ballot once every ticket is bought
+    pub fn record_purchase(storage: &mut InkTixStorage, ballot_id: u32, position: u32) {
+        let Some(mut entry) = storage.ballot_entries.get((ballot_id, position)) else { return };
+        entry.purchased += 1;
+        storage.ballot_entries.insert((ballot_id, position), &entry);
+        if let Some(mut ballot) = storage.ballots.get(ballot_id) {
+            ballot.tickets_purchased += 1;
+            if ballot.tickets_purchased >= ballot.terms.tickets {
+                ballot.closed = true;
+            }
+            storage.ballots.insert(ballot_id, &ballot);
+        }
+    }
+
+    /// Allocate up to `tickets` to entries from `next_in_line` on, returning how many were left over
+    fn allocate(storage: &mut InkTixStorage, ballot: &mut Ballot, mut tickets: u32, now: u64) -> u32 {
+        while tickets > 0 {
+            let Some(mut entry) = storage.ballot_entries.get((ballot.id, ballot.next_in_line)) else { break };
+            let granted = entry.requested.min(tickets);
+            entry.allocated = granted;
+            entry.claim_deadline = now + ballot.terms.claim_window;
+            storage.ballot_entries.insert((ballot.id, ballot.next_in_line), &entry);
+            tickets -= granted;
+            ballot.next_in_line += 1;
+        }
+        tickets
+    }
+
+    /// Swap the entries at two positions, keeping their entrants' positions current
+    fn swap(storage: &mut InkTixStorage, ballot_id: u32, a: u32, b: u32) {
+        let (Some(first), Some(second)) = (storage.ballot_entries.get((ballot_id, a)), storage.ballot_entries.get((ballot_id, b))) else {
+            return;
+        };
+        storage.ballot_positions.insert((ballot_id, first.entrant), &b);
+        storage.ballot_positions.insert((ballot_id, second.entrant), &a);
+        storage.ballot_entries.insert((ballot_id, a), &second);
+        storage.ballot_entries.insert((ballot_id, b), &first);
+    }
+
+    /// An event's ballot, if it is still open
+    fn open_ballot(storage: &InkTixStorage, event_id: u32) -> Option<Ballot> {
+        storage.event_ballots.get(event_id)
+            .and_then(|ballot_id| storage.ballots.get(ballot_id))
+            .filter(|ballot| !ballot.closed)
+    }
+
+    /// Blake2x256 hash of a SCALE-encoded value
+    fn hash<T: ink::scale::Encode>(value: &T) -> [u8; 32] {
+        let mut output = [0u8; 32];
+        ink::env::hash_encoded::<ink::env::hash::Blake2x256, _>(value, &mut output);
+        output
+    }
+}
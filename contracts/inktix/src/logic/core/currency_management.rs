@@ -1,27 +1,107 @@
-//! Currency exchange rate management.
+//! Currency exchange rates, conversion and payment currencies.
 //!
-//! Provides the ability to update on-chain exchange rates for supported currencies.
+//! Ticket prices are set in DOT. A buyer may pay in any supported currency:
+//! DOT as native value, and every other currency through the PSP22 token
+//! contract registered for it, charged at the DOT price converted with the
+//! effective rate reported by the `RateOracle`.
 //!
 //! # Functions
-//! - `update_currency_rate` -- sets a new exchange rate for a given currency
+//! - `set_currency_token` -- registers (or clears) the PSP22 token contract for a currency
+//! - `charge_in` -- converts a DOT price into the amount charged in a currency
+//! - `ensure_payable` -- checks a charge can be paid in a currency with the given native value
+//! - `record_revenue` -- adds a payment to the currency's revenue total
+//!
+//! `CurrencyConverter` is implemented for `InkTixStorage` on top of
+//! `utils::conversion`.
 
 use crate::storage::*;
 use crate::types::*;
+use crate::utils::conversion::{convert_from_dot_equivalent, convert_to_dot_equivalent};
 use ink::prelude::string::String;
 use ink::prelude::string::ToString;
+use ink::primitives::AccountId;
 
 /// Currency management functionality
 pub struct CurrencyManagement;
 
 impl CurrencyManagement {
-    /// Update currency rate
-    pub fn update_currency_rate(
+    /// Register the PSP22 token contract payments in `currency` go through, or clear it with `None`
+    pub fn set_currency_token(
         storage: &mut InkTixStorage,
         currency: CurrencyId,
-        rate: u128,
+        token: Option<AccountId>,
     ) -> Result<(), String> {
-        if rate == 0 { return Err("Currency rate cannot be zero".to_string()); }
-        storage.currency_rates.insert(currency, &rate);
+        if currency == CurrencyId::DOT {
+            return Err("DOT is paid in native value".to_string());
+        }
+        if !storage.supported_currencies.contains(&currency) {
+            return Err(InkTixError::InvalidCurrency.into());
+        }
+        if let Some(token) = token {
+            storage.currency_tokens.insert(currency, &token);
+        } else {
+            storage.currency_tokens.remove(currency);
+        }
         Ok(())
     }
+
+    /// Amount charged in `currency` for a DOT price
+    ///
+    /// Fails while the currency's rate is stale or the currency is deactivated.
+    pub fn charge_in(storage: &InkTixStorage, dot_price: u128, currency: CurrencyId) -> Result<u128, String> {
+        if !storage.supported_currencies.contains(&currency) {
+            return Err(InkTixError::InvalidCurrency.into());
+        }
+        super::rate_oracle::RateOracle::ensure_rate_usable(storage, currency)?;
+        storage.convert_from_dot(dot_price, currency)
+    }
+
+    /// Check `charge` can be paid in `currency`
+    ///
+    /// DOT charges must be covered by the native `payment`; other currencies
+    /// need a registered token contract, from which the charge is pulled.
+    pub fn ensure_payable(
+        storage: &InkTixStorage,
+        currency: CurrencyId,
+        charge: u128,
+        payment: u128,
+    ) -> Result<(), String> {
+        match currency {
+            CurrencyId::DOT if payment < charge => Err(InkTixError::InsufficientPayment.into()),
+            CurrencyId::DOT => Ok(()),
+            _ if !storage.currency_tokens.contains(currency) => {
+                Err("No token contract registered for this currency".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Add a payment to the currency's revenue total
+    pub fn record_revenue(storage: &mut InkTixStorage, currency: CurrencyId, amount: u128) {
+        let revenue = storage.currency_revenue.get(currency).unwrap_or(0);
+        storage.currency_revenue.insert(currency, &revenue.saturating_add(amount));
+    }
+
+    /// Stored rates indexed by `CurrencyId` discriminant, as `utils::conversion` expects
+    fn rate_table(storage: &InkTixStorage) -> [u128; 6] {
+        let mut rates = [0u128; 6];
+        for currency in &storage.supported_currencies {
+            rates[*currency as usize] = storage.currency_rates.get(*currency).unwrap_or(0);
+        }
+        rates
+    }
+}
+
+impl CurrencyConverter for InkTixStorage {
+    fn convert_to_dot(&self, amount: u128, from_currency: CurrencyId) -> Result<u128, String> {
+        convert_to_dot_equivalent(amount, from_currency, &CurrencyManagement::rate_table(self)).map_err(Into::into)
+    }
+
+    fn convert_from_dot(&self, dot_amount: u128, to_currency: CurrencyId) -> Result<u128, String> {
+        convert_from_dot_equivalent(dot_amount, to_currency, &CurrencyManagement::rate_table(self)).map_err(Into::into)
+    }
+
+    fn get_exchange_rate(&self, currency: CurrencyId) -> Result<u128, String> {
+        self.currency_rates.get(currency).ok_or(InkTixError::InvalidCurrency.into())
+    }
 }
@@ -0,0 +1,112 @@
+//! Per-event escrow of ticket sale proceeds.
+//!
+//! Ticket payments are held against their event rather than paid out on
+//! purchase, in the currency they were paid in: native DOT in `event_escrow`
+//! and PSP22 token payments in `event_token_escrow`. Once the event reaches
+//! `EventStatus::Completed`, the organizer can withdraw the accumulated
+//! balance. A cancelled event's balance funds ticket refunds, and only what is
+//! left unclaimed after the refund window can be withdrawn.
+//!
+//! Refunds owed to accounts other than the caller, such as lapsed waitlist
+//! deposits, are credited to a per-account refund balance that the account
+//! withdraws itself, so one failing transfer cannot block anyone else.
+//!
+//! # Functions
+//! - `deposit` -- credits sale proceeds to an event's escrow balance
+//! - `release` -- debits an amount from an event's escrow balance
+//! - `balance` -- an event's escrow balance in one currency
+//! - `withdraw_proceeds` -- settles a completed event's balance to its organizer,
+//!   or a cancelled one's after refunds close
+//! - `credit_refund` -- adds to an account's withdrawable refund balance
+//! - `withdraw_refund` -- empties an account's refund balance in one currency
+
+use crate::storage::*;
+use crate::types::*;
+use ink::prelude::string::String;
+use ink::prelude::string::ToString;
+use ink::primitives::AccountId;
+
+/// Event escrow accounting
+pub struct Escrow;
+
+impl Escrow {
+    /// Credit sale proceeds to an event's escrow balance
+    pub fn deposit(storage: &mut InkTixStorage, event_id: u32, currency: CurrencyId, amount: u128) {
+        let balance = Self::balance(storage, event_id, currency);
+        Self::set_balance(storage, event_id, currency, balance.saturating_add(amount));
+    }
+
+    /// Debit an amount from an event's escrow balance
+    pub fn release(storage: &mut InkTixStorage, event_id: u32, currency: CurrencyId, amount: u128) -> Result<(), String> {
+        let balance = Self::balance(storage, event_id, currency);
+        if balance < amount {
+            return Err("Insufficient escrow balance".to_string());
+        }
+        Self::set_balance(storage, event_id, currency, balance - amount);
+        Ok(())
+    }
+
+    /// An event's escrow balance in one currency
+    pub fn balance(storage: &InkTixStorage, event_id: u32, currency: CurrencyId) -> u128 {
+        match currency {
+            CurrencyId::DOT => storage.event_escrow.get(event_id),
+            _ => storage.event_token_escrow.get((event_id, currency)),
+        }
+        .unwrap_or(0)
+    }
+
+    fn set_balance(storage: &mut InkTixStorage, event_id: u32, currency: CurrencyId, amount: u128) {
+        match currency {
+            CurrencyId::DOT => storage.event_escrow.insert(event_id, &amount),
+            _ => storage.event_token_escrow.insert((event_id, currency), &amount),
+        };
+    }
+
+    /// Settle a completed event's escrow in one currency to its organizer, returning the amount to pay out
+    pub fn withdraw_proceeds(
+        storage: &mut InkTixStorage,
+        caller: AccountId,
+        event_id: u32,
+        currency: CurrencyId,
+    ) -> Result<u128, String> {
+        let event = storage.events.get(event_id).ok_or(InkTixError::EventNotFound)?;
+        if event.organizer != caller {
+            return Err("Only the event organizer can withdraw proceeds".to_string());
+        }
+        match event.status {
+            EventStatus::Completed => {}
+            EventStatus::Cancelled => {
+                let deadline = storage.refund_deadlines.get(event_id).unwrap_or(0);
+                if ink::env::block_timestamp::<ink::env::DefaultEnvironment>() < deadline {
+                    return Err("Refund window is still open".to_string());
+                }
+            }
+            _ => return Err("Event has not completed".to_string()),
+        }
+        let amount = Self::balance(storage, event_id, currency);
+        if amount == 0 {
+            return Err("No proceeds to withdraw".to_string());
+        }
+        Self::set_balance(storage, event_id, currency, 0);
+        storage.proceeds_withdrawn.insert(event_id, &());
+        Ok(amount)
+    }
+
+    /// Add an amount to an account's withdrawable refund balance
+    pub fn credit_refund(storage: &mut InkTixStorage, account: AccountId, currency: CurrencyId, amount: u128) {
+        if amount == 0 {
+            return;
+        }
+        let balance = storage.refund_balances.get((account, currency)).unwrap_or(0);
+        storage.refund_balances.insert((account, currency), &balance.saturating_add(amount));
+    }
+
+    /// Empty an account's refund balance in one currency, returning the amount to pay out
+    pub fn withdraw_refund(storage: &mut InkTixStorage, account: AccountId, currency: CurrencyId) -> Result<u128, String> {
+        let amount = storage.refund_balances.take((account, currency)).unwrap_or(0);
+        if amount == 0 {
+            return Err("No refund balance to withdraw".to_string());
+        }
+        Ok(amount)
+    }
+}
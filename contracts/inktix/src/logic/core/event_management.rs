@@ -1,12 +1,16 @@
 //! Event creation and lifecycle management.
 //!
-//! Handles event creation with category-specific validation (sports rivalry,
-//! concert artist lookup), status updates, and analytics initialization.
+//! Handles event creation with category-specific validation (sports teams and
+//! season, concert artist lookup), status updates, cancellation refund windows, and
+//! analytics initialization. An event's rivalry multiplier is not stored; reads
+//! take it from the event's current effective pricing policy.
 //!
 //! # Functions
-//! - `create_event` -- creates a new event with venue and category validation
+//! - `create_event` -- creates a new event with venue and category validation, indexing sports events by home team and season
+//! - `get_event` -- returns an event with its current rivalry multiplier
 //! - `get_all_events` -- returns all registered events
-//! - `update_event_status` -- changes an event's active/inactive state
+//! - `update_event_status` -- records an event's lifecycle status, opening refunds on cancellation
+//! - `can_transition` -- the allowed event status transitions
 
 use ink::primitives::AccountId;
 use ink::prelude::string::String;
@@ -17,6 +21,9 @@ use ink::prelude::vec;
 use crate::storage::contract_storage::InkTixStorage;
 use crate::types::*;
 
+/// How long ticket holders can claim refunds after a cancellation, in milliseconds
+pub const REFUND_CLAIM_WINDOW: u64 = 90 * 24 * 60 * 60 * 1000;
+
 /// Event management logic for comprehensive event handling
 pub struct EventManagement;
 
@@ -24,8 +31,10 @@ pub struct EventManagement;
 #[allow(clippy::cast_possible_truncation)]
 impl EventManagement {
     /// Create a new event with EventCategory
+    #[allow(clippy::too_many_arguments)]
     pub fn create_event(
         storage: &mut InkTixStorage,
+        organizer: AccountId,
         name: String,
         venue_id: u32,
         date: u64,
@@ -40,7 +49,7 @@ impl EventManagement {
         let venue = storage.venues.get(venue_id).ok_or("Venue not found")?;
 
         // Validate sport-specific fields
-        let (rivalry_multiplier, season_pass_discount) = match &category {
+        let season_pass_discount = match &category {
             EventCategory::Sports { home_team_id, away_team_id, season_id, game_type, sport_type } => {
                 if *home_team_id == *away_team_id {
                     return Err("Home and away teams must be different".to_string());
@@ -49,17 +58,13 @@ impl EventManagement {
                 let _away_team = storage.teams.get(*away_team_id).ok_or("Away team not found")?;
                 let _season = storage.seasons.get(*season_id).ok_or("Season not found")?;
 
-                let rivalry = Self::calculate_rivalry_multiplier_by_ids(storage, *home_team_id, *away_team_id);
-                let discount = Self::get_season_pass_discount_for_game_type(game_type);
-                (rivalry, discount)
+                Self::get_season_pass_discount_for_game_type(game_type)
             }
             EventCategory::Concert { artist_id } => {
                 let _artist = storage.artists.get(*artist_id).ok_or("Artist not found")?;
-                (10000, 0) // 1.0x multiplier, no season pass discount
-            }
-            EventCategory::Generic => {
-                (10000, 0)
+                0 // No season pass discount
             }
+            EventCategory::Generic => 0,
         };
 
         let event_id = storage.get_next_id("event");
@@ -76,11 +81,23 @@ impl EventManagement {
             category,
             season_pass_discount,
             dynamic_pricing_enabled: true,
-            rivalry_multiplier,
+            rivalry_multiplier: 10000,
             revenue_generated: 0,
+            organizer,
+            status: EventStatus::OnSale,
         };
 
         storage.events.insert(event_id, &event);
+        if let EventCategory::Sports { home_team_id, season_id, .. } = event.category {
+            let mut games = storage.home_season_events.get((home_team_id, season_id)).unwrap_or_default();
+            games.push(event_id);
+            storage.home_season_events.insert((home_team_id, season_id), &games);
+        }
+
+        // Snapshot the venue's seat layout so later venue changes don't affect this event
+        if let Some(layout) = storage.venue_seat_layouts.get(venue_id) {
+            super::seat_inventory::SeatInventory::store_event_layout(storage, event_id, layout);
+        }
 
         // Create event analytics
         let analytics = EventAnalytics {
@@ -102,42 +119,78 @@ impl EventManagement {
         Ok(event_id)
     }
 
+    /// Get an event with its rivalry multiplier read from its current effective pricing policy
+    pub fn get_event(storage: &InkTixStorage, event_id: u32) -> Option<Event> {
+        let mut event = storage.events.get(event_id)?;
+        let policy = super::pricing_policy::PricingPolicies::effective(storage, &event);
+        event.rivalry_multiplier = super::pricing::DynamicPricing::rivalry_multiplier(&policy, &event);
+        Some(event)
+    }
+
     /// Get all events
     pub fn get_all_events(storage: &InkTixStorage) -> Vec<Event> {
         let mut events = Vec::new();
         for event_id in 1..=storage.total_events {
-            if let Some(event) = storage.events.get(event_id) {
+            if let Some(event) = Self::get_event(storage, event_id) {
                 events.push(event);
             }
         }
         events
     }
 
-    /// Update event status
+    /// Update event status along an allowed transition
+    ///
+    /// Cancelling an event opens a `REFUND_CLAIM_WINDOW` during which ticket
+    /// holders can claim their purchase price back, so it is refused once any
+    /// proceeds have been withdrawn. An event can only complete once its date
+    /// has passed. Cancellation and completion are both final.
     pub fn update_event_status(
         storage: &mut InkTixStorage,
         event_id: u32,
         status: EventStatus,
     ) -> Result<(), String> {
         let mut event = storage.events.get(event_id).ok_or("Event not found")?;
+        if event.status == EventStatus::Cancelled {
+            return Err("Cancelled events cannot change status".to_string());
+        }
+        if event.status == EventStatus::Completed {
+            return Err("Completed events cannot change status".to_string());
+        }
+        if !Self::can_transition(&event.status, &status) {
+            return Err("Invalid event status transition".to_string());
+        }
+        let now = ink::env::block_timestamp::<ink::env::DefaultEnvironment>();
+        if status == EventStatus::Completed && now < event.date {
+            return Err("Event has not taken place yet".to_string());
+        }
+        if status == EventStatus::Cancelled {
+            if storage.proceeds_withdrawn.contains(event_id) {
+                return Err("Proceeds have already been withdrawn".to_string());
+            }
+            storage.refund_deadlines.insert(event_id, &(now + REFUND_CLAIM_WINDOW));
+        }
         event.active = status == EventStatus::OnSale;
+        event.status = status;
         storage.events.insert(event_id, &event);
         Ok(())
     }
 
-    fn calculate_rivalry_multiplier_by_ids(storage: &InkTixStorage, home_id: u32, away_id: u32) -> u32 {
-        let home_team = match storage.teams.get(home_id) { Some(t) => t, None => return 10000 };
-        let away_team = match storage.teams.get(away_id) { Some(t) => t, None => return 10000 };
-
-        if home_team.city == away_team.city { return 12000; }
-        match (home_team.name.as_str(), away_team.name.as_str()) {
-            ("Lakers", "Celtics") | ("Celtics", "Lakers") => 15000,
-            ("Yankees", "Red Sox") | ("Red Sox", "Yankees") => 15000,
-            ("Cowboys", "Giants") | ("Giants", "Cowboys") => 14000,
-            ("Packers", "Bears") | ("Bears", "Packers") => 14000,
-            ("Real Madrid", "Barcelona") | ("Barcelona", "Real Madrid") => 16000,
-            _ => 10000,
-        }
+    /// Whether an event can move from one status to another
+    ///
+    /// Sales open from `Scheduled`, can toggle between `OnSale` and `SoldOut`,
+    /// and stop for good once the event is `InProgress`. Any unfinished event
+    /// can be cancelled; `Completed` and `Cancelled` are final.
+    pub fn can_transition(from: &EventStatus, to: &EventStatus) -> bool {
+        use EventStatus::*;
+        matches!(
+            (from, to),
+            (Scheduled, OnSale)
+                | (OnSale, SoldOut)
+                | (SoldOut, OnSale)
+                | (Scheduled | OnSale | SoldOut, InProgress)
+                | (OnSale | SoldOut | InProgress, Completed)
+                | (Scheduled | OnSale | SoldOut | InProgress, Cancelled)
+        )
     }
 
     fn get_season_pass_discount_for_game_type(game_type: &GameType) -> u8 {
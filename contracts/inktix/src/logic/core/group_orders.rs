@@ -0,0 +1,253 @@
+//! Group and corporate bulk purchases with split payment.
+//!
+//! A group organizer reserves a contiguous run of seats in one row and invites
+//! member accounts, each taking a number of those seats. Seats are priced at
+//! their current dynamic price less the event's group discount tier for the
+//! order size, and each member owes the price of their seats. Members pay their
+//! share (in one or several contributions) before the deadline, at most
+//! `MAX_GROUP_ORDER_DURATION_MS` away; the payment that funds the order rechecks
+//! every member's purchase limits and sale window, then issues their tickets at
+//! once. An order that is cancelled, or not funded by its deadline, releases its
+//! seats and credits every contribution to its member's refund balance. An organizer can have at most
+//! `MAX_LIVE_ORDERS_PER_ORGANIZER` orders reserving seats at a time.
+//!
+//! # Functions
+//! - `set_discount_tiers` -- configures an event's group discount tiers
+//! - `discount_for` -- the discount for an order of a given size
+//! - `create_order` -- reserves a seat block and assigns it to members
+//! - `contribute` -- credits a member's payment, completing a fully funded order
+//! - `cancel_order` -- releases a failed order's seats and credits contributions back
+//! - `is_order_live` -- whether an order still reserves its seats
+
+use crate::storage::*;
+use crate::types::*;
+use ink::env::DefaultEnvironment;
+use ink::prelude::string::String;
+use ink::prelude::string::ToString;
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+use super::seat_inventory::SeatInventory;
+use super::ticket_management::TicketManagement;
+
+/// Most seats a single group order can reserve
+pub const MAX_GROUP_SEATS: u32 = 50;
+/// Longest a group order can reserve its seats while funding (7 days)
+pub const MAX_GROUP_ORDER_DURATION_MS: u64 = 7 * 24 * 60 * 60 * 1000;
+/// Most live group orders one organizer can have
+pub const MAX_LIVE_ORDERS_PER_ORGANIZER: usize = 3;
+
+/// Group order management
+pub struct GroupOrders;
+
+#[allow(clippy::arithmetic_side_effects)]
+#[allow(clippy::cast_possible_truncation)]
+impl GroupOrders {
+    /// Store an event's group discount tiers (an empty list removes them)
+    pub fn set_discount_tiers(storage: &mut InkTixStorage, event_id: u32, tiers: Vec<GroupDiscountTier>) -> Result<(), String> {
+        if !storage.events.contains(event_id) {
+            return Err(InkTixError::EventNotFound.into());
+        }
+        if tiers.iter().any(|tier| tier.min_seats < 2 || tier.discount_bps >= 10000)
+            || tiers.windows(2).any(|pair| pair[1].min_seats <= pair[0].min_seats || pair[1].discount_bps < pair[0].discount_bps)
+        {
+            return Err("Group discount tiers must ascend by size and discount".to_string());
+        }
+        if tiers.is_empty() {
+            storage.event_group_discounts.remove(event_id);
+        } else {
+            storage.event_group_discounts.insert(event_id, &tiers);
+        }
+        Ok(())
+    }
+
+    /// Discount in basis points for a group order of `seats` seats
+    pub fn discount_for(storage: &InkTixStorage, event_id: u32, seats: u32) -> u32 {
+        storage.event_group_discounts.get(event_id).unwrap_or_default()
+            .iter()
+            .rev()
+            .find(|tier| tier.min_seats <= seats)
+            .map(|tier| tier.discount_bps)
+            .unwrap_or(0)
+    }
+
+    /// Reserve seats from `first_seat` along its row for the invited members, returning the order ID
+    ///
+    /// Members take the reserved seats in invitation order.
+    pub fn create_order(
+        storage: &mut InkTixStorage,
+        organizer: AccountId,
+        event_id: u32,
+        first_seat: Seat,
+        members: Vec<GroupInvite>,
+        deadline: u64,
+    ) -> Result<u32, String> {
+        let event = storage.events.get(event_id).ok_or("Event not found")?;
+        if !event.active { return Err("Event is not active".to_string()); }
+        if !storage.event_seat_layouts.contains(event_id) {
+            return Err("Event has no seat layout to reserve".to_string());
+        }
+        let quantity: u32 = members.iter().map(|(_, seats)| *seats).sum();
+        if members.iter().any(|(_, seats)| *seats == 0) || !(2..=MAX_GROUP_SEATS).contains(&quantity) {
+            return Err("Invalid group size".to_string());
+        }
+        if members.iter().enumerate().any(|(i, (account, _))| members[..i].iter().any(|(other, _)| other == account)) {
+            return Err("Member invited more than once".to_string());
+        }
+        let now = ink::env::block_timestamp::<DefaultEnvironment>();
+        if deadline <= now || deadline >= event.date || deadline - now > MAX_GROUP_ORDER_DURATION_MS {
+            return Err("Invalid group order deadline".to_string());
+        }
+        let live_orders = storage.user_group_orders.get(organizer).unwrap_or_default().into_iter()
+            .filter_map(|id| storage.group_orders.get(id))
+            .filter(|order| order.organizer == organizer && Self::is_order_live(order))
+            .count();
+        if live_orders >= MAX_LIVE_ORDERS_PER_ORGANIZER {
+            return Err("Too many live group orders".to_string());
+        }
+        if event.sold_tickets + quantity > event.capacity {
+            return Err(InkTixError::SoldOut.into());
+        }
+        super::ballots::Ballots::ensure_not_balloted(storage, event_id)?;
+        super::waitlist::Waitlists::ensure_unreserved(storage, &event, quantity)?;
+        let mut sale_window = None;
+        for (account, seats) in &members {
+            TicketManagement::check_purchase_limit(storage, *account, &event, *seats)?;
+            sale_window = super::sale_windows::SaleWindows::check(storage, *account, &event, *seats)?;
+        }
+
+        let discount_bps = Self::discount_for(storage, event_id, quantity);
+        let first_number: u32 = first_seat.seat_number.parse().map_err(|_| "Invalid seat number")?;
+        let mut reserved = Vec::new();
+        let mut keys: Vec<SeatKey> = Vec::new();
+        for seat_number in first_number..first_number + quantity {
+            let requested = Seat { seat_number: seat_number.to_string(), ..first_seat.clone() };
+            let (seat, seat_number, key) = SeatInventory::apply_layout(storage, event_id, requested)?;
+            let key = key.ok_or("Event has no seat layout to reserve")?;
+            SeatInventory::ensure_available(storage, &key)?;
+            let (price, performance_multiplier) = super::pricing::DynamicPricing::calculate_price(
+                storage, event_id, &seat, false
+            )?;
+            let price = price * (10000 - discount_bps as u128) / 10000;
+            reserved.push(HeldSeat { seat, seat_number, price, performance_multiplier });
+            keys.push(key);
+        }
+
+        let mut next_seat = 0;
+        let members: Vec<GroupMember> = members.into_iter().map(|(account, seats)| {
+            let share = reserved[next_seat..next_seat + seats as usize].iter().map(|seat| seat.price).sum();
+            next_seat += seats as usize;
+            GroupMember { account, seats, share, paid: 0 }
+        }).collect();
+
+        let order_id = storage.get_next_id("group_order");
+        for key in &keys {
+            SeatInventory::set_status(storage, key, SeatStatus::GroupReserved { order_id });
+        }
+        SeatInventory::track_group_order(storage, event_id, order_id);
+        for account in members.iter().map(|member| member.account).chain(::core::iter::once(organizer)) {
+            let mut orders = storage.user_group_orders.get(account).unwrap_or_default();
+            if !orders.contains(&order_id) {
+                orders.push(order_id);
+                storage.user_group_orders.insert(account, &orders);
+            }
+        }
+        storage.group_orders.insert(order_id, &GroupOrder {
+            id: order_id,
+            event_id,
+            organizer,
+            seats: reserved,
+            members,
+            discount_bps,
+            deadline,
+            sale_window,
+            status: GroupOrderStatus::Funding,
+            ticket_ids: Vec::new(),
+        });
+        Ok(order_id)
+    }
+
+    /// Credit a member's native DOT payment towards their share
+    ///
+    /// When the payment funds the whole order, every member's purchase limits and
+    /// sale window are checked again and their tickets issued; if a member no
+    /// longer qualifies the payment is rejected.
+    /// Returns the order with its new status and the part of `payment` beyond the
+    /// member's outstanding share, left for the caller to refund.
+    pub fn contribute(
+        storage: &mut InkTixStorage,
+        member: AccountId,
+        order_id: u32,
+        payment: u128,
+    ) -> Result<(GroupOrder, u128), String> {
+        let mut order = storage.group_orders.get(order_id).ok_or("Group order not found")?;
+        if !Self::is_order_live(&order) { return Err("Group order is not open".to_string()); }
+        let position = order.members.iter().position(|m| m.account == member).ok_or("Not a member of this group order")?;
+        let event = storage.events.get(order.event_id).ok_or("Event not found")?;
+        if !event.active { return Err("Event is not active".to_string()); }
+        let owed = order.members[position].share - order.members[position].paid;
+        if owed == 0 { return Err("Group order share already paid".to_string()); }
+        if payment == 0 { return Err(InkTixError::InsufficientPayment.into()); }
+
+        let credited = payment.min(owed);
+        order.members[position].paid += credited;
+        let funded = order.members.iter().all(|m| m.paid >= m.share);
+        if funded && event.sold_tickets + order.seats.len() as u32 > event.capacity {
+            return Err(InkTixError::SoldOut.into());
+        }
+
+        if funded {
+            for member in &order.members {
+                TicketManagement::check_purchase_limit(storage, member.account, &event, member.seats)?;
+                order.sale_window = super::sale_windows::SaleWindows::check(storage, member.account, &event, member.seats)?;
+            }
+            let mut seats = order.seats.iter();
+            for member in &order.members {
+                for seat in seats.by_ref().take(member.seats as usize) {
+                    let ticket_id = TicketManagement::issue_ticket(
+                        storage, member.account, order.event_id, seat, CurrencyId::DOT, seat.price
+                    );
+                    order.ticket_ids.push(ticket_id);
+                }
+                if let Some(window_index) = order.sale_window {
+                    super::sale_windows::SaleWindows::record(storage, order.event_id, window_index, member.account, member.seats);
+                }
+            }
+            order.status = GroupOrderStatus::Completed;
+        }
+        storage.group_orders.insert(order_id, &order);
+        Ok((order, payment - credited))
+    }
+
+    /// Cancel an unfunded order, releasing its seats and crediting contributions to refund balances
+    ///
+    /// The organizer can cancel at any time; anyone can once the deadline has
+    /// passed or the event is no longer active.
+    pub fn cancel_order(storage: &mut InkTixStorage, caller: AccountId, order_id: u32) -> Result<GroupOrder, String> {
+        let mut order = storage.group_orders.get(order_id).ok_or("Group order not found")?;
+        if order.status != GroupOrderStatus::Funding { return Err("Group order is not open".to_string()); }
+        let event_active = storage.events.get(order.event_id).is_some_and(|event| event.active);
+        if caller != order.organizer && Self::is_order_live(&order) && event_active {
+            return Err("Only the organizer can cancel a group order before its deadline".to_string());
+        }
+
+        order.status = GroupOrderStatus::Cancelled;
+        storage.group_orders.insert(order_id, &order);
+        for seat in &order.seats {
+            let key = (order.event_id, seat.seat.section.clone(), seat.seat.row.clone(), seat.seat_number);
+            if storage.seat_inventory.get(&key) == Some(SeatStatus::GroupReserved { order_id }) {
+                SeatInventory::set_status(storage, &key, SeatStatus::Available);
+            }
+        }
+        for member in order.members.iter().filter(|member| member.paid > 0) {
+            super::escrow::Escrow::credit_refund(storage, member.account, CurrencyId::DOT, member.paid);
+        }
+        Ok(order)
+    }
+
+    /// An order reserves its seats while funding and before its deadline
+    pub fn is_order_live(order: &GroupOrder) -> bool {
+        order.status == GroupOrderStatus::Funding
+            && ink::env::block_timestamp::<DefaultEnvironment>() < order.deadline
+    }
+}
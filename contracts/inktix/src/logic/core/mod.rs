@@ -1,19 +1,50 @@
 //! Core business logic modules.
 //!
-//! Contains event, ticket, venue, currency, anti-scalping, dynamic pricing,
-//! NFT, and XCM management logic used by all contract features.
+//! Contains access control, identity attestations, event, ticket, venue, seat inventory, holds, group orders, auctions, ballots, waitlists and sale windows, currency,
+//! escrow, anti-scalping and purchase limits, dynamic pricing with pricing policies and locked quotes, NFT
+//! and PSP34 collections, the currency rate oracle, and XCM management logic used by
+//! all contract features.
 
+pub mod access_control;
 pub mod anti_scalping;
+pub mod attestations;
+pub mod auctions;
+pub mod ballots;
 pub mod currency_management;
+pub mod escrow;
+pub mod group_orders;
 pub mod event_management;
 pub mod nft_management;
 pub mod ticket_management;
 pub mod venue_management;
+pub mod waitlist;
+pub mod price_locks;
 pub mod pricing;
+pub mod pricing_policy;
+pub mod psp34;
+pub mod purchase_limits;
+pub mod rate_oracle;
+pub mod sale_windows;
+pub mod seat_holds;
+pub mod seat_inventory;
 pub mod xcm_management;
 
+pub use access_control::*;
 pub use anti_scalping::*;
+pub use attestations::*;
+pub use auctions::*;
+pub use ballots::*;
 pub use currency_management::*;
+pub use escrow::*;
+pub use group_orders::*;
 pub use event_management::*;
 pub use ticket_management::*;
 pub use venue_management::*;
+pub use waitlist::*;
+pub use price_locks::*;
+pub use psp34::*;
+pub use purchase_limits::*;
+pub use rate_oracle::*;
+pub use sale_windows::*;
+pub use seat_holds::*;
+pub use seat_inventory::*;
@@ -1,16 +1,29 @@
 //! NFT ticket minting, verification, usage, and transfer logic.
 //!
 //! Manages the lifecycle of ticket NFTs: minting from purchased tickets,
-//! verifying ownership for event entry, marking tickets as used (with attendance
-//! token issuance), and transferring NFTs between accounts.
+//! verifying ownership for event entry, gate check-in against a holder-signed
+//! challenge, marking tickets as used (with attendance token issuance), and
+//! transferring NFTs between accounts.
+//!
+//! # Check-in challenge
+//! The holder signs `check_in_message(token_id, verification_hash, issued_at)`:
+//! the bytes `b"inktix:check-in"`, then `token_id` and `issued_at` (little-endian)
+//! around the 32-byte verification hash. sr25519 signatures cover the message
+//! itself; ECDSA signatures cover its Blake2x256 hash, and the recovered key's
+//! Blake2x256 hash must equal the holder's account.
 //!
 //! # Functions
 //! - `mint_ticket_nft` -- creates an NFT for an owned ticket with a Blake2 verification hash
+//! - `verification_hash` -- derives the hash binding an NFT to its ticket and current holder
+//! - `validate_ticket_nft` -- checks the NFT's ticket, holder and event are still valid for entry
 //! - `verify_ticket_nft` -- returns verification details for a token ID
-//! - `use_ticket_nft` -- marks a ticket NFT as used and mints an attendance token
+//! - `check_in_message` -- builds the challenge a holder signs to check in
+//! - `check_in` -- verifies a scanned, signed challenge and admits the holder
+//! - `use_ticket_nft` -- admin override marking a ticket NFT used and minting an attendance token
 //! - `get_user_nft_tickets` -- returns all NFT tickets owned by a user
 //! - `get_nft_by_ticket` -- looks up the NFT for a specific ticket ID
-//! - `transfer_nft` -- transfers NFT ownership to a new account
+//! - `transfer_nft` -- transfers an NFT and its ticket to a new account
+//! - `move_nft` -- reassigns an NFT's owner and clears its PSP34 approval
 
 use crate::storage::contract_storage::InkTixStorage;
 use crate::types::*;
@@ -19,6 +32,15 @@ use ink::prelude::string::ToString;
 use ink::prelude::vec::Vec;
 use ink::primitives::AccountId;
 
+/// Check-in opens this long before the event date, in milliseconds
+pub const CHECK_IN_OPENS_BEFORE: u64 = 6 * 60 * 60 * 1000;
+/// Check-in closes this long after the event date, in milliseconds
+pub const CHECK_IN_CLOSES_AFTER: u64 = 12 * 60 * 60 * 1000;
+/// How long a signed check-in challenge stays valid, in milliseconds
+pub const CHECK_IN_CHALLENGE_TTL: u64 = 2 * 60 * 1000;
+
+const CHECK_IN_DOMAIN: &[u8] = b"inktix:check-in";
+
 /// NFT lifecycle management for ticket tokenization
 pub struct NftManagement;
 
@@ -52,17 +74,7 @@ impl NftManagement {
             .unwrap_or_else(|| "Unknown Venue".to_string());
 
         let token_id = storage.get_next_nft_token_id();
-
-        // Generate verification hash from ticket data
-        let mut hash_input: Vec<u8> = Vec::new();
-        hash_input.extend_from_slice(&ticket_id.to_le_bytes());
-        hash_input.extend_from_slice(&ticket.event_id.to_le_bytes());
-        hash_input.extend_from_slice(&ticket.seat_number.to_le_bytes());
-        hash_input.extend_from_slice(caller.as_ref());
-        hash_input.extend_from_slice(&token_id.to_le_bytes());
-
-        let mut verification_hash = [0u8; 32];
-        ink::env::hash_bytes::<ink::env::hash::Blake2x256>(&hash_input, &mut verification_hash);
+        let verification_hash = Self::verification_hash(&ticket, caller, token_id);
 
         let nft = TicketNft {
             token_id,
@@ -81,6 +93,7 @@ impl NftManagement {
             metadata_uri: "".to_string(),
             verification_hash,
             is_used: false,
+            is_valid: true,
         };
 
         storage.nft_tickets.insert(token_id, &nft);
@@ -93,6 +106,44 @@ impl NftManagement {
         Ok(token_id)
     }
 
+    /// Hash binding an NFT to its ticket and holder, shown in the holder's QR code
+    ///
+    /// Changes whenever the ticket moves to a new holder, so codes issued to a
+    /// previous holder stop scanning.
+    pub fn verification_hash(ticket: &Ticket, holder: AccountId, token_id: u64) -> [u8; 32] {
+        let mut hash_input: Vec<u8> = Vec::new();
+        hash_input.extend_from_slice(&ticket.id.to_le_bytes());
+        hash_input.extend_from_slice(&ticket.event_id.to_le_bytes());
+        hash_input.extend_from_slice(&ticket.seat_number.to_le_bytes());
+        hash_input.extend_from_slice(holder.as_ref());
+        hash_input.extend_from_slice(&token_id.to_le_bytes());
+
+        let mut verification_hash = [0u8; 32];
+        ink::env::hash_bytes::<ink::env::hash::Blake2x256>(&hash_input, &mut verification_hash);
+        verification_hash
+    }
+
+    /// Check that an NFT's ticket is still valid for entry
+    ///
+    /// Fails for refunded tickets, cancelled events, and NFTs whose holder or
+    /// verification hash no longer match the ticket (e.g. transferred after minting).
+    pub fn validate_ticket_nft(storage: &InkTixStorage, nft: &TicketNft) -> Result<(), String> {
+        let ticket = storage.tickets.get(nft.ticket_id).ok_or("Ticket not found")?;
+        if !nft.is_valid || ticket.refunded {
+            return Err("Ticket has been refunded".to_string());
+        }
+        let event = storage.events.get(nft.event_id).ok_or("Event not found")?;
+        if event.status == EventStatus::Cancelled {
+            return Err("Event has been cancelled".to_string());
+        }
+        if ticket.owner != nft.owner
+            || nft.verification_hash != Self::verification_hash(&ticket, nft.owner, nft.token_id)
+        {
+            return Err("Ticket holder has changed since minting".to_string());
+        }
+        Ok(())
+    }
+
     /// Verify a ticket NFT by its token_id
     pub fn verify_ticket_nft(
         storage: &InkTixStorage,
@@ -100,9 +151,11 @@ impl NftManagement {
     ) -> Result<TicketVerification, String> {
         let nft = storage.nft_tickets.get(token_id)
             .ok_or("NFT not found".to_string())?;
+        let invalid_reason = Self::validate_ticket_nft(storage, &nft).err();
 
         Ok(TicketVerification {
-            is_valid: true,
+            is_valid: invalid_reason.is_none(),
+            invalid_reason,
             is_used: nft.is_used,
             owner: nft.owner,
             event_id: nft.event_id,
@@ -113,22 +166,107 @@ impl NftManagement {
         })
     }
 
-    /// Mark ticket as used (for event entry)
-    pub fn use_ticket_nft(
+    /// Build the challenge a holder signs to check in with a ticket NFT
+    pub fn check_in_message(token_id: u64, verification_hash: &[u8; 32], issued_at: u64) -> Vec<u8> {
+        let mut message = Vec::with_capacity(CHECK_IN_DOMAIN.len() + 48);
+        message.extend_from_slice(CHECK_IN_DOMAIN);
+        message.extend_from_slice(&token_id.to_le_bytes());
+        message.extend_from_slice(verification_hash);
+        message.extend_from_slice(&issued_at.to_le_bytes());
+        message
+    }
+
+    /// Check a holder in at the gate, returning the attendance token ID
+    ///
+    /// The scanner must be a gate scanner for the event (or its venue). The
+    /// scanned hash must be the NFT's current verification hash, the challenge
+    /// must be fresh and signed by the holder, the ticket must still be valid and
+    /// unused, and the event must be inside its check-in window.
+    #[allow(clippy::arithmetic_side_effects)]
+    pub fn check_in(
         storage: &mut InkTixStorage,
-        _caller: AccountId,
+        scanner: AccountId,
         token_id: u64,
+        verification_hash: [u8; 32],
+        issued_at: u64,
+        signature: CheckInSignature,
     ) -> Result<u64, String> {
-        let mut nft = storage.nft_tickets.get(token_id)
+        let nft = storage.nft_tickets.get(token_id)
             .ok_or("NFT not found".to_string())?;
+        super::access_control::AccessControl::ensure_role(
+            storage, scanner, Role::GateScanner, RoleScope::Event(nft.event_id),
+        )?;
+        if verification_hash != nft.verification_hash {
+            return Err("Verification hash does not match".to_string());
+        }
+        Self::validate_ticket_nft(storage, &nft)?;
+        if nft.is_used {
+            return Err("Ticket already used".to_string());
+        }
+
+        let now = ink::env::block_timestamp::<ink::env::DefaultEnvironment>();
+        let event = storage.events.get(nft.event_id).ok_or("Event not found")?;
+        if now < event.date.saturating_sub(CHECK_IN_OPENS_BEFORE) {
+            return Err("Check-in has not opened".to_string());
+        }
+        if now > event.date.saturating_add(CHECK_IN_CLOSES_AFTER) {
+            return Err("Check-in has closed".to_string());
+        }
+        if issued_at > now || now - issued_at > CHECK_IN_CHALLENGE_TTL {
+            return Err("Check-in challenge has expired".to_string());
+        }
 
+        let message = Self::check_in_message(token_id, &verification_hash, issued_at);
+        if !Self::is_signed_by(&message, &signature, nft.owner) {
+            return Err("Invalid check-in signature".to_string());
+        }
+
+        Ok(Self::mark_used(storage, nft))
+    }
+
+    /// Mark ticket as used without a signed challenge (global admins only)
+    ///
+    /// A manual override for gate failures; gate scanners admit holders through
+    /// `check_in`, and the caller's message records which admin used the ticket.
+    pub fn use_ticket_nft(
+        storage: &mut InkTixStorage,
+        caller: AccountId,
+        token_id: u64,
+    ) -> Result<u64, String> {
+        super::access_control::AccessControl::ensure_role(storage, caller, Role::Admin, RoleScope::Global)?;
+        let nft = storage.nft_tickets.get(token_id)
+            .ok_or("NFT not found".to_string())?;
+        Self::validate_ticket_nft(storage, &nft)?;
         if nft.is_used {
             return Err("Ticket already used".to_string());
         }
+        Ok(Self::mark_used(storage, nft))
+    }
+
+    /// Whether `signature` over `message` was produced by `signer`
+    fn is_signed_by(message: &[u8], signature: &CheckInSignature, signer: AccountId) -> bool {
+        match signature {
+            CheckInSignature::Sr25519(signature) => {
+                ink::env::sr25519_verify(signature, message, signer.as_ref()).is_ok()
+            }
+            CheckInSignature::Ecdsa(signature) => {
+                let mut message_hash = [0u8; 32];
+                ink::env::hash_bytes::<ink::env::hash::Blake2x256>(message, &mut message_hash);
+                let mut public_key = [0u8; 33];
+                if ink::env::ecdsa_recover(signature, &message_hash, &mut public_key).is_err() {
+                    return false;
+                }
+                let mut account = [0u8; 32];
+                ink::env::hash_bytes::<ink::env::hash::Blake2x256>(&public_key, &mut account);
+                AccountId::from(account) == signer
+            }
+        }
+    }
 
-        // Mark as used
+    /// Mark an NFT used and mint the holder's attendance token
+    fn mark_used(storage: &mut InkTixStorage, mut nft: TicketNft) -> u64 {
         nft.is_used = true;
-        storage.nft_tickets.insert(token_id, &nft);
+        storage.nft_tickets.insert(nft.token_id, &nft);
 
         // Mint attendance token
         let attendance_id = storage.get_next_attendance_token_id();
@@ -145,7 +283,7 @@ impl NftManagement {
         user_attendance.push(attendance_id);
         storage.user_attendance_tokens.insert(nft.owner, &user_attendance);
 
-        Ok(attendance_id)
+        attendance_id
     }
 
     /// Get all NFT tokens for a user
@@ -168,32 +306,43 @@ impl NftManagement {
             .and_then(|token_id| storage.nft_tickets.get(token_id))
     }
 
-    /// Transfer NFT to new owner (follows ticket transfer)
+    /// Transfer NFT to new owner, moving its ticket with it as a gift
     pub fn transfer_nft(
         storage: &mut InkTixStorage,
         caller: AccountId,
         token_id: u64,
         to: AccountId,
     ) -> Result<(), String> {
-        let mut nft = storage.nft_tickets.get(token_id)
+        let nft = storage.nft_tickets.get(token_id)
             .ok_or("NFT not found".to_string())?;
 
         if nft.owner != caller {
             return Err("Not NFT owner".to_string());
         }
+        if !nft.is_valid {
+            return Err("Ticket has been refunded".to_string());
+        }
+
+        super::ticket_management::TicketManagement::transfer_ticket(storage, caller, nft.ticket_id, to)
+    }
+
+    /// Move an NFT to a new owner, clearing its PSP34 token approval
+    ///
+    /// Only called by `TicketManagement::reassign_ticket`, which keeps the ticket in step.
+    pub fn move_nft(storage: &mut InkTixStorage, mut nft: TicketNft, to: AccountId) {
+        let token_id = nft.token_id;
 
         // Remove from old owner
-        let mut old_tokens = storage.user_nft_tokens.get(caller).unwrap_or_default();
+        let mut old_tokens = storage.user_nft_tokens.get(nft.owner).unwrap_or_default();
         old_tokens.retain(|&id| id != token_id);
-        storage.user_nft_tokens.insert(caller, &old_tokens);
+        storage.user_nft_tokens.insert(nft.owner, &old_tokens);
 
         // Add to new owner
         nft.owner = to;
         storage.nft_tickets.insert(token_id, &nft);
+        storage.nft_token_approvals.remove(token_id);
         let mut new_tokens = storage.user_nft_tokens.get(to).unwrap_or_default();
         new_tokens.push(token_id);
         storage.user_nft_tokens.insert(to, &new_tokens);
-
-        Ok(())
     }
 }
@@ -0,0 +1,120 @@
+//! Price-locked quotes.
+//!
+//! A buyer can lock the current dynamic price of one seat for a short window
+//! and redeem it with `purchase_ticket`, so demand or time multipliers moving in
+//! between do not change what they pay. A lock does not reserve the seat: the
+//! seat must still be free when the quote is redeemed. Each lock is redeemable
+//! once, by its holder, for the seat it was issued for.
+//!
+//! # Functions
+//! - `lock_quote` -- quotes a seat and locks the price for `QUOTE_LOCK_DURATION`
+//! - `locked_price` -- checks a quote can be redeemed for a seat and returns the locked price
+//! - `redeem` -- marks a quote as used
+//! - `is_lock_live` -- whether a quote is unredeemed and unexpired
+//! - `active_locks` -- returns an account's live quote IDs
+
+use crate::storage::*;
+use crate::types::*;
+use ink::env::DefaultEnvironment;
+use ink::prelude::string::String;
+use ink::prelude::string::ToString;
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+use super::pricing::DynamicPricing;
+use super::pricing_policy::PricingPolicies;
+use super::seat_inventory::SeatInventory;
+
+/// How long a locked quote can be redeemed, in milliseconds
+pub const QUOTE_LOCK_DURATION: u64 = 5 * 60 * 1000;
+/// Most live locked quotes an account can have at once
+pub const MAX_ACTIVE_QUOTES_PER_ACCOUNT: usize = 5;
+
+/// Price-locked quote management
+pub struct PriceLocks;
+
+#[allow(clippy::arithmetic_side_effects)]
+impl PriceLocks {
+    /// Quote a seat and lock its price for the holder, returning the quote ID
+    pub fn lock_quote(
+        storage: &mut InkTixStorage,
+        holder: AccountId,
+        event_id: u32,
+        seat: Seat,
+    ) -> Result<u64, String> {
+        let event = storage.events.get(event_id).ok_or("Event not found")?;
+        if !event.active { return Err("Event is not active".to_string()); }
+        if event.sold_tickets >= event.capacity {
+            return Err(InkTixError::SoldOut.into());
+        }
+        super::ballots::Ballots::ensure_not_balloted(storage, event_id)?;
+        let (seat, seat_number, seat_key) = SeatInventory::apply_layout(storage, event_id, seat)?;
+        if let Some(key) = &seat_key {
+            SeatInventory::ensure_available(storage, key)?;
+        }
+        let mut active = Self::active_locks(storage, holder);
+        if active.len() >= MAX_ACTIVE_QUOTES_PER_ACCOUNT {
+            return Err("Too many locked quotes".to_string());
+        }
+
+        let (price, performance_multiplier) = DynamicPricing::calculate_price(storage, event_id, &seat, false)?;
+        let now = ink::env::block_timestamp::<DefaultEnvironment>();
+        let quote_id = storage.get_next_price_lock_id();
+        storage.price_locks.insert(quote_id, &PriceLock {
+            id: quote_id,
+            event_id,
+            holder,
+            priced: HeldSeat { seat, seat_number, price, performance_multiplier },
+            policy_version: PricingPolicies::effective(storage, &event).version,
+            locked_at: now,
+            expires_at: now + QUOTE_LOCK_DURATION,
+            redeemed: false,
+        });
+        active.push(quote_id);
+        storage.user_price_locks.insert(holder, &active);
+        Ok(quote_id)
+    }
+
+    /// Check `buyer` can redeem a quote for a resolved seat, returning the locked seat and price
+    pub fn locked_price(
+        storage: &InkTixStorage,
+        buyer: AccountId,
+        quote_id: u64,
+        event_id: u32,
+        seat: &Seat,
+        seat_number: u32,
+    ) -> Result<HeldSeat, String> {
+        let lock = storage.price_locks.get(quote_id).ok_or("Quote not found")?;
+        if lock.holder != buyer { return Err("Only the holder can redeem a quote".to_string()); }
+        if lock.redeemed { return Err("Quote has already been redeemed".to_string()); }
+        if !Self::is_lock_live(&lock) { return Err("Quote has expired".to_string()); }
+        if lock.event_id != event_id || lock.priced.seat != *seat || lock.priced.seat_number != seat_number {
+            return Err("Quote was issued for a different seat".to_string());
+        }
+        Ok(lock.priced)
+    }
+
+    /// Mark a quote as redeemed
+    pub fn redeem(storage: &mut InkTixStorage, quote_id: u64) {
+        if let Some(mut lock) = storage.price_locks.get(quote_id) {
+            lock.redeemed = true;
+            storage.price_locks.insert(quote_id, &lock);
+            let mut locks = storage.user_price_locks.get(lock.holder).unwrap_or_default();
+            locks.retain(|&id| id != quote_id);
+            storage.user_price_locks.insert(lock.holder, &locks);
+        }
+    }
+
+    /// A quote is live until it is redeemed or its window closes
+    pub fn is_lock_live(lock: &PriceLock) -> bool {
+        !lock.redeemed && ink::env::block_timestamp::<DefaultEnvironment>() < lock.expires_at
+    }
+
+    /// Get an account's live quote IDs
+    pub fn active_locks(storage: &InkTixStorage, holder: AccountId) -> Vec<u64> {
+        storage.user_price_locks.get(holder).unwrap_or_default()
+            .into_iter()
+            .filter(|id| storage.price_locks.get(*id).is_some_and(|lock| Self::is_lock_live(&lock)))
+            .collect()
+    }
+}
@@ -0,0 +1,167 @@
+//! Pricing policy storage, validation and lookup.
+//!
+//! A `PricingPolicy` can be set for a venue and for an individual event. An
+//! event is priced with its own policy, else its venue's, else the built-in
+//! default. Every stored policy receives a new version number so quotes can
+//! report exactly which curves were applied.
+//!
+//! Policies must be monotonic: demand multipliers may not fall as more of the
+//! event sells, and time multipliers may not rise further from the event date.
+//!
+//! # Functions
+//! - `validate` -- checks a policy's bands, tables, floor and cap
+//! - `set_for_venue` / `set_for_event` -- stores or clears a policy, returning its version
+//! - `effective` / `for_venue` -- resolves the policy applying to an event or a venue
+//! - `demand_multiplier`, `time_multiplier`, `seat_multiplier`, `rivalry_multiplier` -- curve lookups
+
+use crate::storage::*;
+use crate::types::*;
+use ink::prelude::string::String;
+use ink::prelude::string::ToString;
+use ink::prelude::vec::Vec;
+
+/// Milliseconds in a day
+const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+/// Pricing policy management
+pub struct PricingPolicies;
+
+impl PricingPolicies {
+    /// Check a policy's bands are ascending and monotonic, and its tables, floor and cap are sane
+    pub fn validate(policy: &PricingPolicy) -> Result<(), String> {
+        Self::validate_bands(&policy.demand_bands)?;
+        if policy.demand_bands.windows(2).any(|pair| pair[1].1 < pair[0].1) {
+            return Err("Demand multipliers must not decrease as demand rises".to_string());
+        }
+        Self::validate_bands(&policy.time_bands)?;
+        if policy.time_bands.windows(2).any(|pair| pair[1].1 > pair[0].1) {
+            return Err("Time multipliers must not increase further from the event".to_string());
+        }
+
+        let mut seat_types = Vec::new();
+        for (seat_type, multiplier) in &policy.seat_multipliers {
+            if seat_types.contains(seat_type) {
+                return Err("Seat type listed more than once".to_string());
+            }
+            if *multiplier == 0 {
+                return Err("Pricing multipliers must be greater than 0".to_string());
+            }
+            seat_types.push(*seat_type);
+        }
+
+        if policy.floor_bps > 10000 {
+            return Err("Price floor cannot exceed the base price".to_string());
+        }
+        if policy.cap_bps < 10000 {
+            return Err("Price cap cannot be below the base price".to_string());
+        }
+
+        let mut pairs: Vec<(u32, u32)> = Vec::new();
+        for (team_a, team_b, multiplier) in &policy.rivalries {
+            let pair = ((*team_a).min(*team_b), (*team_a).max(*team_b));
+            if team_a == team_b {
+                return Err("A rivalry needs two different teams".to_string());
+            }
+            if pairs.contains(&pair) {
+                return Err("Rivalry listed more than once".to_string());
+            }
+            if *multiplier == 0 {
+                return Err("Pricing multipliers must be greater than 0".to_string());
+            }
+            pairs.push(pair);
+        }
+        Ok(())
+    }
+
+    /// Store or clear a venue's pricing policy, returning the version assigned (0 when cleared)
+    pub fn set_for_venue(storage: &mut InkTixStorage, venue_id: u32, policy: Option<PricingPolicy>) -> Result<u32, String> {
+        if !storage.venues.contains(venue_id) {
+            return Err(InkTixError::VenueNotFound.into());
+        }
+        let Some(mut policy) = policy else {
+            storage.venue_pricing_policies.remove(venue_id);
+            return Ok(0);
+        };
+        Self::validate(&policy)?;
+        policy.version = storage.get_next_id("pricing_policy");
+        storage.venue_pricing_policies.insert(venue_id, &policy);
+        Ok(policy.version)
+    }
+
+    /// Store or clear an event's pricing policy, returning the version assigned (0 when cleared)
+    pub fn set_for_event(storage: &mut InkTixStorage, event_id: u32, policy: Option<PricingPolicy>) -> Result<u32, String> {
+        if !storage.events.contains(event_id) {
+            return Err(InkTixError::EventNotFound.into());
+        }
+        let Some(mut policy) = policy else {
+            storage.event_pricing_policies.remove(event_id);
+            return Ok(0);
+        };
+        Self::validate(&policy)?;
+        policy.version = storage.get_next_id("pricing_policy");
+        storage.event_pricing_policies.insert(event_id, &policy);
+        Ok(policy.version)
+    }
+
+    /// Policy applying to an event: its own, else its venue's, else the default
+    pub fn effective(storage: &InkTixStorage, event: &Event) -> PricingPolicy {
+        storage.event_pricing_policies.get(event.id)
+            .unwrap_or_else(|| Self::for_venue(storage, event.venue_id))
+    }
+
+    /// Policy applying to events at a venue that have no policy of their own
+    pub fn for_venue(storage: &InkTixStorage, venue_id: u32) -> PricingPolicy {
+        storage.venue_pricing_policies.get(venue_id).unwrap_or_default()
+    }
+
+    /// Demand multiplier for the share of the event sold
+    pub fn demand_multiplier(policy: &PricingPolicy, sold: u32, capacity: u32) -> u32 {
+        if capacity == 0 { return 10000; }
+        let pct = (sold as u64 * 100 / capacity as u64) as u32;
+        Self::band(&policy.demand_bands, pct)
+    }
+
+    /// Time multiplier for the whole days left before the event; past events price at 1.0x
+    pub fn time_multiplier(policy: &PricingPolicy, event_date: u64, now: u64) -> u32 {
+        if event_date <= now { return 10000; }
+        Self::band(&policy.time_bands, (event_date - now) / DAY_MS)
+    }
+
+    /// Multiplier for a seat type
+    pub fn seat_multiplier(policy: &PricingPolicy, seat_type: &SeatType) -> u32 {
+        policy.seat_multipliers.iter()
+            .find(|(listed, _)| listed == seat_type)
+            .map(|(_, multiplier)| *multiplier)
+            .unwrap_or(10000)
+    }
+
+    /// Rivalry multiplier for a game between two teams
+    pub fn rivalry_multiplier(policy: &PricingPolicy, home_team_id: u32, away_team_id: u32) -> u32 {
+        policy.rivalries.iter()
+            .find(|(a, b, _)| (*a, *b) == (home_team_id, away_team_id) || (*b, *a) == (home_team_id, away_team_id))
+            .map(|(_, _, multiplier)| *multiplier)
+            .unwrap_or(10000)
+    }
+
+    /// Multiplier of the last band whose threshold `value` has reached
+    fn band<T: PartialOrd + Copy>(bands: &[(T, u32)], value: T) -> u32 {
+        bands.iter()
+            .take_while(|(threshold, _)| *threshold <= value)
+            .last()
+            .map(|(_, multiplier)| *multiplier)
+            .unwrap_or(10000)
+    }
+
+    /// Bands must be non-empty, start at 0, strictly ascend and have non-zero multipliers
+    fn validate_bands<T: PartialOrd + Copy + Default>(bands: &[(T, u32)]) -> Result<(), String> {
+        if bands.first().map(|(threshold, _)| *threshold) != Some(T::default())
+            || bands.windows(2).any(|pair| pair[1].0 <= pair[0].0)
+        {
+            return Err("Pricing bands must start at 0 and ascend".to_string());
+        }
+        if bands.iter().any(|(_, multiplier)| *multiplier == 0) {
+            return Err("Pricing multipliers must be greater than 0".to_string());
+        }
+        Ok(())
+    }
+}
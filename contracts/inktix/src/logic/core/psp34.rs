@@ -0,0 +1,198 @@
+//! PSP34 collection logic for ticket NFTs.
+//!
+//! `TicketCollection` exposes `TicketNft`s as a PSP34 collection with approvals,
+//! enumeration and metadata attributes; approved operators (including the
+//! contract itself, acting as the resale marketplace) can move tokens.
+//! Attendance tokens are not part of it: they are minted into the separate,
+//! soulbound `attendance_token` contract.
+//!
+//! Token IDs are the contract's `u64` token IDs; any integer `Id` variant that
+//! fits in a `u64` is accepted, and tokens are reported as `Id::U64`.
+//!
+//! # Functions
+//! - `TicketCollection::collection_id` -- collection identifier
+//! - `token_id` -- converts a PSP34 `Id` into a contract token ID
+//! - `balance_of`, `owner_of`, `total_supply` -- ownership queries
+//! - `allowance` / `is_approved` -- approval queries
+//! - `approve` -- approves an operator for one token or all of the caller's tokens
+//! - `transfer` -- moves a ticket NFT and its ticket as the owner or an approved operator
+//! - `get_attribute` -- collection and token metadata attributes
+//! - `token_by_index`, `owners_token_by_index` -- enumeration
+
+use super::ticket_management::TicketManagement;
+use crate::storage::contract_storage::InkTixStorage;
+use crate::types::*;
+use ink::prelude::string::ToString;
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+/// Convert a PSP34 `Id` into a contract token ID
+pub fn token_id(id: &Id) -> Option<u64> {
+    match id {
+        Id::U8(n) => Some(*n as u64),
+        Id::U16(n) => Some(*n as u64),
+        Id::U32(n) => Some(*n as u64),
+        Id::U64(n) => Some(*n),
+        Id::U128(n) => u64::try_from(*n).ok(),
+        Id::Bytes(_) => None,
+    }
+}
+
+/// PSP34 view of the `TicketNft` collection
+pub struct TicketCollection;
+
+impl TicketCollection {
+    /// Identifier of the ticket collection
+    pub fn collection_id() -> Id {
+        Id::Bytes(b"inktix:tickets".to_vec())
+    }
+
+    /// Number of ticket NFTs held by `owner`
+    pub fn balance_of(storage: &InkTixStorage, owner: AccountId) -> u32 {
+        storage.user_nft_tokens.get(owner).unwrap_or_default().len() as u32
+    }
+
+    /// Current owner of a ticket NFT
+    pub fn owner_of(storage: &InkTixStorage, id: &Id) -> Option<AccountId> {
+        token_id(id)
+            .and_then(|token_id| storage.nft_tickets.get(token_id))
+            .map(|nft| nft.owner)
+    }
+
+    /// Number of ticket NFTs ever minted
+    pub fn total_supply(storage: &InkTixStorage) -> u128 {
+        storage.total_nft_tokens as u128
+    }
+
+    /// Whether `operator` may move `owner`'s token, or all of `owner`'s tokens when `id` is `None`
+    pub fn allowance(storage: &InkTixStorage, owner: AccountId, operator: AccountId, id: Option<&Id>) -> bool {
+        if storage.nft_operator_approvals.contains((owner, operator)) {
+            return true;
+        }
+        match id.map(token_id) {
+            Some(Some(token_id)) => Self::owner_of(storage, &Id::U64(token_id)) == Some(owner)
+                && storage.nft_token_approvals.get(token_id) == Some(operator),
+            _ => false,
+        }
+    }
+
+    /// Whether `operator` may move token `token_id` on behalf of its current owner
+    pub fn is_approved(storage: &InkTixStorage, token_id: u64, operator: AccountId) -> bool {
+        match storage.nft_tickets.get(token_id) {
+            Some(nft) => nft.owner == operator
+                || Self::allowance(storage, nft.owner, operator, Some(&Id::U64(token_id))),
+            None => false,
+        }
+    }
+
+    /// Approve or revoke `operator` for one of the caller's tokens, or for all of them
+    ///
+    /// A token has at most one approved operator; approvals are cleared when it moves.
+    pub fn approve(
+        storage: &mut InkTixStorage,
+        caller: AccountId,
+        operator: AccountId,
+        id: Option<&Id>,
+        approved: bool,
+    ) -> Result<(), PSP34Error> {
+        if operator == caller {
+            return Err(PSP34Error::SelfApprove);
+        }
+        let Some(id) = id else {
+            if approved {
+                storage.nft_operator_approvals.insert((caller, operator), &());
+            } else {
+                storage.nft_operator_approvals.remove((caller, operator));
+            }
+            return Ok(());
+        };
+
+        let token_id = token_id(id).ok_or(PSP34Error::TokenNotExists)?;
+        let nft = storage.nft_tickets.get(token_id).ok_or(PSP34Error::TokenNotExists)?;
+        if nft.owner != caller {
+            return Err(PSP34Error::NotApproved);
+        }
+        if approved {
+            storage.nft_token_approvals.insert(token_id, &operator);
+        } else if storage.nft_token_approvals.get(token_id) == Some(operator) {
+            storage.nft_token_approvals.remove(token_id);
+        }
+        Ok(())
+    }
+
+    /// Transfer a ticket NFT, and its ticket, as its owner or an approved operator, returning the previous owner
+    pub fn transfer(
+        storage: &mut InkTixStorage,
+        caller: AccountId,
+        to: AccountId,
+        id: &Id,
+    ) -> Result<AccountId, PSP34Error> {
+        let token_id = token_id(id).ok_or(PSP34Error::TokenNotExists)?;
+        let nft = storage.nft_tickets.get(token_id).ok_or(PSP34Error::TokenNotExists)?;
+        if !Self::is_approved(storage, token_id, caller) {
+            return Err(PSP34Error::NotApproved);
+        }
+        if !nft.is_valid {
+            return Err(PSP34Error::Custom("Ticket has been refunded".to_string()));
+        }
+
+        let ticket = storage.tickets.get(nft.ticket_id).ok_or(PSP34Error::TokenNotExists)?;
+        TicketManagement::ensure_transferable(storage, &ticket, &TransferReason::Gift).map_err(PSP34Error::Custom)?;
+        super::anti_scalping::AntiScalping::ensure_allowed(storage, nft.owner, ticket.event_id).map_err(PSP34Error::Custom)?;
+        super::anti_scalping::AntiScalping::ensure_allowed(storage, to, ticket.event_id).map_err(PSP34Error::Custom)?;
+        super::purchase_limits::PurchaseLimits::check_transfer(storage, nft.owner, to, &ticket).map_err(PSP34Error::Custom)?;
+
+        TicketManagement::reassign_ticket(storage, nft.ticket_id, to, TransferReason::Gift, None);
+        Ok(nft.owner)
+    }
+
+    /// Metadata attribute of the collection or of a ticket NFT
+    ///
+    /// The collection has `name` and `symbol`; tokens have `name`, `event_id`,
+    /// `event_name`, `venue_name`, `event_date`, `section`, `row`, `seat_number`,
+    /// `seat_type`, `access_level` and `uri`.
+    pub fn get_attribute(storage: &InkTixStorage, id: &Id, key: &[u8]) -> Option<Vec<u8>> {
+        if *id == Self::collection_id() {
+            return match key {
+                b"name" => Some(b"InkTix Tickets".to_vec()),
+                b"symbol" => Some(b"TIX".to_vec()),
+                _ => None,
+            };
+        }
+
+        let nft = storage.nft_tickets.get(token_id(id)?)?;
+        let value = match key {
+            b"name" => ink::prelude::format!("{} - {} {} {}", nft.event_name, nft.section, nft.row, nft.seat_number),
+            b"event_id" => nft.event_id.to_string(),
+            b"event_name" => nft.event_name,
+            b"venue_name" => nft.venue_name,
+            b"event_date" => nft.event_date.to_string(),
+            b"section" => nft.section,
+            b"row" => nft.row,
+            b"seat_number" => nft.seat_number.to_string(),
+            b"seat_type" => nft.seat_type,
+            b"access_level" => nft.access_level,
+            b"uri" if !nft.metadata_uri.is_empty() => nft.metadata_uri,
+            _ => return None,
+        };
+        Some(value.into_bytes())
+    }
+
+    /// The `index`-th ticket NFT held by `owner`
+    pub fn owners_token_by_index(storage: &InkTixStorage, owner: AccountId, index: u128) -> Result<Id, PSP34Error> {
+        let tokens = storage.user_nft_tokens.get(owner).unwrap_or_default();
+        usize::try_from(index).ok()
+            .and_then(|index| tokens.get(index))
+            .map(|token_id| Id::U64(*token_id))
+            .ok_or(PSP34Error::TokenNotExists)
+    }
+
+    /// The `index`-th ticket NFT minted
+    pub fn token_by_index(storage: &InkTixStorage, index: u128) -> Result<Id, PSP34Error> {
+        if index < Self::total_supply(storage) {
+            Ok(Id::U64(index as u64 + 1))
+        } else {
+            Err(PSP34Error::TokenNotExists)
+        }
+    }
+}
@@ -0,0 +1,169 @@
+//! Ticket holding limits across events, seasons, teams and linked accounts.
+//!
+//! Limits count holdings rather than purchases: the valid tickets an account
+//! owns, so received transfers count and tickets sold or given away don't.
+//! Season-pass tickets are not counted. An event's limit is the
+//! `max_tickets_per_user` of its `AntiScalpingConfig` (4 for concerts without
+//! one), together with any limit set for the event, its season or its home team
+//! through `PurchaseLimitScope`; every applicable limit must allow the tickets.
+//!
+//! Admins can link accounts that belong to one household or entity; linked
+//! accounts share their limits, counting the holdings of every member.
+//!
+//! # Functions
+//! - `set_limit` -- sets or clears the limit for an event, season or team
+//! - `check` -- enforces every applicable limit for new tickets
+//! - `check_transfer` -- enforces the recipient's limits for a transferred ticket
+//! - `holdings` -- valid tickets held by accounts within a scope
+//! - `link_accounts` / `unlink_account` -- declare accounts that share limits
+//! - `household` -- an account and the accounts linked to it
+
+use crate::storage::*;
+use crate::types::*;
+use ink::prelude::string::String;
+use ink::prelude::string::ToString;
+use ink::prelude::vec;
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+/// Default per-event limit for concerts without an anti-scalping config
+pub const DEFAULT_CONCERT_LIMIT: u32 = 4;
+
+/// Purchase limit management
+pub struct PurchaseLimits;
+
+#[allow(clippy::arithmetic_side_effects)]
+#[allow(clippy::cast_possible_truncation)]
+impl PurchaseLimits {
+    /// Set the holding limit for an event, season or team, or clear it with `None`
+    pub fn set_limit(storage: &mut InkTixStorage, scope: PurchaseLimitScope, limit: Option<u32>) -> Result<(), String> {
+        let exists = match scope {
+            PurchaseLimitScope::Event(id) => storage.events.contains(id),
+            PurchaseLimitScope::Season(id) => storage.seasons.contains(id),
+            PurchaseLimitScope::Team(id) => storage.teams.contains(id),
+        };
+        if !exists { return Err("Purchase limit target not found".to_string()); }
+        match limit {
+            Some(0) => return Err("Purchase limit must be greater than 0".to_string()),
+            Some(limit) => storage.purchase_limits.insert(scope, &limit),
+            None => {
+                storage.purchase_limits.remove(scope);
+                None
+            }
+        };
+        Ok(())
+    }
+
+    /// Check `account` and its linked accounts can hold `quantity` more tickets for an event
+    pub fn check(storage: &InkTixStorage, account: AccountId, event: &Event, quantity: u32) -> Result<(), String> {
+        let household = Self::household(storage, account);
+        for (scope, limit) in Self::applicable_limits(storage, event) {
+            if Self::holdings(storage, &household, scope) + quantity > limit {
+                return Err(match scope {
+                    PurchaseLimitScope::Event(_) => InkTixError::PurchaseLimitReached.into(),
+                    PurchaseLimitScope::Season(_) => "Season purchase limit reached".to_string(),
+                    PurchaseLimitScope::Team(_) => "Team purchase limit reached".to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Check the recipient of a transferred ticket stays within its limits
+    ///
+    /// Transfers within a household leave its holdings unchanged.
+    pub fn check_transfer(storage: &InkTixStorage, from: AccountId, to: AccountId, ticket: &Ticket) -> Result<(), String> {
+        if ticket.is_season_pass_ticket || Self::household(storage, from).contains(&to) {
+            return Ok(());
+        }
+        let event = storage.events.get(ticket.event_id).ok_or("Event not found")?;
+        Self::check(storage, to, &event, 1)
+    }
+
+    /// Valid tickets, other than season-pass tickets, held by `accounts` within a scope
+    pub fn holdings(storage: &InkTixStorage, accounts: &[AccountId], scope: PurchaseLimitScope) -> u32 {
+        accounts.iter()
+            .flat_map(|account| storage.user_tickets.get(account).unwrap_or_default())
+            .filter_map(|ticket_id| storage.tickets.get(ticket_id))
+            .filter(|ticket| !ticket.refunded && !ticket.is_season_pass_ticket)
+            .filter(|ticket| match scope {
+                PurchaseLimitScope::Event(event_id) => ticket.event_id == event_id,
+                _ => storage.events.get(ticket.event_id).is_some_and(|event| Self::in_scope(&event, scope)),
+            })
+            .count() as u32
+    }
+
+    /// Link accounts so they share purchase limits, returning the link ID
+    pub fn link_accounts(storage: &mut InkTixStorage, accounts: Vec<AccountId>) -> Result<u32, String> {
+        if accounts.len() < 2 { return Err("At least two accounts are needed to link".to_string()); }
+        if accounts.iter().enumerate().any(|(i, account)| accounts[..i].contains(account)) {
+            return Err("Account listed more than once".to_string());
+        }
+        if accounts.iter().any(|account| storage.account_links.contains(account)) {
+            return Err("Account is already linked".to_string());
+        }
+        let link_id = storage.get_next_id("account_link");
+        for account in &accounts {
+            storage.account_links.insert(account, &link_id);
+        }
+        storage.linked_accounts.insert(link_id, &accounts);
+        Ok(link_id)
+    }
+
+    /// Remove an account from its link; a link left with one account is dissolved
+    pub fn unlink_account(storage: &mut InkTixStorage, account: AccountId) -> Result<(), String> {
+        let link_id = storage.account_links.get(account).ok_or("Account is not linked")?;
+        let mut accounts = storage.linked_accounts.get(link_id).unwrap_or_default();
+        accounts.retain(|linked| *linked != account);
+        storage.account_links.remove(account);
+        if accounts.len() < 2 {
+            for linked in &accounts {
+                storage.account_links.remove(linked);
+            }
+            storage.linked_accounts.remove(link_id);
+        } else {
+            storage.linked_accounts.insert(link_id, &accounts);
+        }
+        Ok(())
+    }
+
+    /// An account together with the accounts linked to it
+    pub fn household(storage: &InkTixStorage, account: AccountId) -> Vec<AccountId> {
+        storage.account_links.get(account)
+            .and_then(|link_id| storage.linked_accounts.get(link_id))
+            .unwrap_or_else(|| vec![account])
+    }
+
+    /// Every limit that applies to an event's tickets
+    fn applicable_limits(storage: &InkTixStorage, event: &Event) -> Vec<(PurchaseLimitScope, u32)> {
+        let event_scope = PurchaseLimitScope::Event(event.id);
+        let mut limits = Vec::new();
+        match storage.anti_scalping_configs.get(event.id) {
+            Some(config) => limits.push((event_scope, config.max_tickets_per_user)),
+            None if matches!(event.category, EventCategory::Concert { .. }) => {
+                limits.push((event_scope, DEFAULT_CONCERT_LIMIT));
+            }
+            None => {}
+        }
+        let mut scopes = vec![event_scope];
+        if let EventCategory::Sports { season_id, home_team_id, .. } = event.category {
+            scopes.push(PurchaseLimitScope::Season(season_id));
+            scopes.push(PurchaseLimitScope::Team(home_team_id));
+        }
+        for scope in scopes {
+            if let Some(limit) = storage.purchase_limits.get(scope) {
+                limits.push((scope, limit));
+            }
+        }
+        limits
+    }
+
+    fn in_scope(event: &Event, scope: PurchaseLimitScope) -> bool {
+        match (scope, &event.category) {
+            (PurchaseLimitScope::Event(event_id), _) => event.id == event_id,
+            (PurchaseLimitScope::Season(id), EventCategory::Sports { season_id, .. }) => *season_id == id,
+            (PurchaseLimitScope::Team(id), EventCategory::Sports { home_team_id, .. }) => *home_team_id == id,
+            _ => false,
+        }
+    }
+}
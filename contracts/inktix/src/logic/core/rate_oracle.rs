@@ -0,0 +1,141 @@
+//! Currency rate oracle.
+//!
+//! Accounts holding the global `OracleFeeder` role report rates for non-DOT
+//! currencies. Each feeder's latest report is kept, and the effective rate is
+//! the median of the fresh reports from current feeders. A report may not move
+//! more than `max_deviation_bps` away from a fresh effective rate, and an
+//! effective rate older than `max_rate_age` is stale. Payments in a currency
+//! are refused until it has a fresh rate, and while it is deactivated.
+//!
+//! DOT is the unit of account and its rate is fixed at 1:1.
+//!
+//! # Functions
+//! - `submit_rate` -- records a feeder's report and recomputes the effective rate
+//! - `set_currency_active` -- activates or deactivates a currency for payments
+//! - `set_config` -- updates the staleness and deviation limits
+//! - `ensure_rate_usable` -- checks a currency's rate is active and fresh
+//! - `median` -- median of a set of rates
+
+use crate::storage::*;
+use crate::types::*;
+use ink::env::DefaultEnvironment;
+use ink::prelude::string::String;
+use ink::prelude::string::ToString;
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+use super::access_control::AccessControl;
+
+/// Effective rate changes kept per currency
+pub const MAX_RATE_HISTORY: usize = 100;
+
+/// Currency rate oracle
+pub struct RateOracle;
+
+#[allow(clippy::arithmetic_side_effects)]
+impl RateOracle {
+    /// Record a feeder's rate report and return the new effective rate
+    pub fn submit_rate(
+        storage: &mut InkTixStorage,
+        feeder: AccountId,
+        currency: CurrencyId,
+        rate: u128,
+    ) -> Result<u128, String> {
+        AccessControl::ensure_role(storage, feeder, Role::OracleFeeder, RoleScope::Global)?;
+        if currency == CurrencyId::DOT { return Err("DOT rate is fixed".to_string()); }
+        if !storage.supported_currencies.contains(&currency) {
+            return Err(InkTixError::InvalidCurrency.into());
+        }
+        if rate == 0 { return Err("Currency rate cannot be zero".to_string()); }
+
+        let now = ink::env::block_timestamp::<DefaultEnvironment>();
+        let config = storage.rate_oracle_config.clone();
+        let current = storage.currency_rate_info.get(currency);
+        if let Some(current) = current.as_ref().filter(|info| Self::is_fresh(&config, info.last_updated, now)) {
+            let deviation = rate.abs_diff(current.rate_to_dot).saturating_mul(10_000) / current.rate_to_dot;
+            if deviation > config.max_deviation_bps as u128 {
+                return Err("Rate deviates too far from the current rate".to_string());
+            }
+        }
+
+        let mut submissions = storage.rate_submissions.get(currency).unwrap_or_default();
+        submissions.retain(|s| {
+            s.feeder != feeder
+                && Self::is_fresh(&config, s.submitted_at, now)
+                && AccessControl::has_role(storage, s.feeder, Role::OracleFeeder, RoleScope::Global)
+        });
+        submissions.push(RateSubmission { feeder, rate, submitted_at: now });
+        let effective = Self::median(submissions.iter().map(|s| s.rate).collect());
+        storage.rate_submissions.insert(currency, &submissions);
+
+        storage.currency_rate_info.insert(currency, &CurrencyRates {
+            currency,
+            rate_to_dot: effective,
+            last_updated: now,
+            is_active: current.map(|info| info.is_active).unwrap_or(true),
+        });
+        storage.currency_rates.insert(currency, &effective);
+
+        let mut history = storage.rate_history.get(currency).unwrap_or_default();
+        if history.len() >= MAX_RATE_HISTORY {
+            history.remove(0);
+        }
+        history.push((now, effective));
+        storage.rate_history.insert(currency, &history);
+        Ok(effective)
+    }
+
+    /// Activate or deactivate a currency for payments
+    pub fn set_currency_active(storage: &mut InkTixStorage, currency: CurrencyId, active: bool) -> Result<(), String> {
+        if currency == CurrencyId::DOT { return Err("DOT cannot be deactivated".to_string()); }
+        let mut info = storage.currency_rate_info.get(currency)
+            .ok_or("Currency rate has not been reported")?;
+        info.is_active = active;
+        storage.currency_rate_info.insert(currency, &info);
+        Ok(())
+    }
+
+    /// Update the staleness and deviation limits
+    pub fn set_config(storage: &mut InkTixStorage, config: RateOracleConfig) -> Result<(), String> {
+        if config.max_rate_age == 0 || config.max_deviation_bps == 0 {
+            return Err("Rate oracle limits must be greater than 0".to_string());
+        }
+        storage.rate_oracle_config = config;
+        Ok(())
+    }
+
+    /// Check payments can be made in a currency: DOT always can, others need an active, fresh rate
+    pub fn ensure_rate_usable(storage: &InkTixStorage, currency: CurrencyId) -> Result<(), String> {
+        if currency == CurrencyId::DOT {
+            return Ok(());
+        }
+        let info = storage.currency_rate_info.get(currency)
+            .ok_or("Currency rate has not been reported")?;
+        if !info.is_active {
+            return Err("Currency is not active".to_string());
+        }
+        let now = ink::env::block_timestamp::<DefaultEnvironment>();
+        if !Self::is_fresh(&storage.rate_oracle_config, info.last_updated, now) {
+            return Err("Currency rate is stale".to_string());
+        }
+        Ok(())
+    }
+
+    /// Median of a set of rates, averaging the middle pair for an even count
+    pub fn median(mut rates: Vec<u128>) -> u128 {
+        if rates.is_empty() {
+            return 0;
+        }
+        rates.sort_unstable();
+        let mid = rates.len() / 2;
+        if rates.len().is_multiple_of(2) {
+            rates[mid - 1] / 2 + rates[mid] / 2 + (rates[mid - 1] % 2 + rates[mid] % 2) / 2
+        } else {
+            rates[mid]
+        }
+    }
+
+    fn is_fresh(config: &RateOracleConfig, timestamp: u64, now: u64) -> bool {
+        now.saturating_sub(timestamp) <= config.max_rate_age
+    }
+}
@@ -0,0 +1,144 @@
+//! Staged on-sale windows and presale access codes.
+//!
+//! An event without sale windows is on sale whenever it is active. With
+//! windows, tickets can only be bought or held while a window is open and only
+//! by the fans it admits: fan-club presales by platform or team loyalty tier
+//! (team tiers grow only from attendance recorded by gate scanners),
+//! season-pass holders, fans who redeemed an access code, and finally the
+//! general on-sale, which must be the last window. Windows ascend without
+//! overlapping and each has its own per-user purchase limit.
+//!
+//! Access codes are issued to specific accounts and stored as the Blake2x256
+//! hash of the SCALE-encoded `(code, account)`, so a code seen in a pending
+//! redemption is useless to anyone else: redeeming hashes the code with the
+//! caller's account. Redeeming spends the code and unlocks the event's
+//! access-code presales for that account.
+//!
+//! # Functions
+//! - `set_windows` -- validates and stores an event's sale windows before sales start
+//! - `access_code_hash` -- the hash an access code is stored under for an account
+//! - `add_access_codes` -- stores account-bound access code hashes
+//! - `redeem_access_code` -- spends an account's code and unlocks access-code presales for it
+//! - `current_window` -- the window open at a given time
+//! - `check` / `record` -- enforce and count purchases against the open window
+
+use crate::storage::*;
+use crate::types::*;
+use ink::env::DefaultEnvironment;
+use ink::prelude::string::String;
+use ink::prelude::string::ToString;
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+/// Sale window management
+pub struct SaleWindows;
+
+#[allow(clippy::arithmetic_side_effects)]
+#[allow(clippy::cast_possible_truncation)]
+impl SaleWindows {
+    /// Store an event's sale windows (an empty list removes them); only before sales start
+    pub fn set_windows(storage: &mut InkTixStorage, event_id: u32, windows: Vec<SaleWindow>) -> Result<(), String> {
+        let event = storage.events.get(event_id).ok_or("Event not found")?;
+        if event.sold_tickets > 0 {
+            return Err("Sale windows cannot change after sales have started".to_string());
+        }
+        if windows.is_empty() {
+            storage.event_sale_windows.remove(event_id);
+            return Ok(());
+        }
+        if windows.iter().any(|w| w.starts_at >= w.ends_at || w.ends_at > event.date)
+            || windows.windows(2).any(|pair| pair[1].starts_at < pair[0].ends_at)
+        {
+            return Err("Sale windows must ascend without overlapping and end by the event".to_string());
+        }
+        let general = windows.iter().position(|w| w.access == SaleWindowAccess::General);
+        if general != Some(windows.len() - 1) {
+            return Err("The general on-sale must be the last window".to_string());
+        }
+        storage.event_sale_windows.insert(event_id, &windows);
+        Ok(())
+    }
+
+    /// The Blake2x256 hash of the SCALE-encoded `(code, account)` an access code issued to `account` is stored under
+    pub fn access_code_hash(code: &[u8], account: AccountId) -> [u8; 32] {
+        let mut code_hash = [0u8; 32];
+        ink::env::hash_encoded::<ink::env::hash::Blake2x256, _>(&(code, account), &mut code_hash);
+        code_hash
+    }
+
+    /// Store account-bound access code hashes for an event, each redeemable once by its account
+    pub fn add_access_codes(storage: &mut InkTixStorage, event_id: u32, code_hashes: Vec<[u8; 32]>) -> Result<(), String> {
+        if !storage.events.contains(event_id) {
+            return Err(InkTixError::EventNotFound.into());
+        }
+        for code_hash in code_hashes {
+            storage.event_access_codes.insert((event_id, code_hash), &());
+        }
+        Ok(())
+    }
+
+    /// Spend the access code issued to `account`, unlocking the event's access-code presales for it
+    pub fn redeem_access_code(storage: &mut InkTixStorage, account: AccountId, event_id: u32, code: Vec<u8>) -> Result<(), String> {
+        if storage.access_code_unlocks.contains((event_id, account)) {
+            return Err("Presale access already unlocked".to_string());
+        }
+        let code_hash = Self::access_code_hash(&code, account);
+        if storage.event_access_codes.take((event_id, code_hash)).is_none() {
+            return Err("Invalid access code".to_string());
+        }
+        storage.access_code_unlocks.insert((event_id, account), &true);
+        Ok(())
+    }
+
+    /// The window open at `now`, with its index
+    pub fn current_window(storage: &InkTixStorage, event_id: u32, now: u64) -> Option<(u32, SaleWindow)> {
+        storage.event_sale_windows.get(event_id)?
+            .into_iter()
+            .enumerate()
+            .find(|(_, w)| w.starts_at <= now && now < w.ends_at)
+            .map(|(index, w)| (index as u32, w))
+    }
+
+    /// Check `buyer` can buy `quantity` tickets in the open window, returning its index
+    ///
+    /// Events without sale windows need no check.
+    pub fn check(storage: &InkTixStorage, buyer: AccountId, event: &Event, quantity: u32) -> Result<Option<u32>, String> {
+        if !storage.event_sale_windows.contains(event.id) {
+            return Ok(None);
+        }
+        let now = ink::env::block_timestamp::<DefaultEnvironment>();
+        let (index, window) = Self::current_window(storage, event.id, now).ok_or("No sale window is open")?;
+        if !Self::admits(storage, buyer, event.id, &window.access) {
+            return Err("Not eligible for the current sale window".to_string());
+        }
+        let bought = storage.sale_window_purchases.get((event.id, index, buyer)).unwrap_or(0);
+        if window.per_user_limit > 0 && bought + quantity > window.per_user_limit {
+            return Err("Sale window purchase limit reached".to_string());
+        }
+        Ok(Some(index))
+    }
+
+    /// Count tickets bought by `buyer` in a window
+    pub fn record(storage: &mut InkTixStorage, event_id: u32, window_index: u32, buyer: AccountId, quantity: u32) {
+        let key = (event_id, window_index, buyer);
+        let bought = storage.sale_window_purchases.get(key).unwrap_or(0);
+        storage.sale_window_purchases.insert(key, &(bought + quantity));
+    }
+
+    /// Whether a window's access rule admits an account
+    fn admits(storage: &InkTixStorage, account: AccountId, event_id: u32, access: &SaleWindowAccess) -> bool {
+        match access {
+            SaleWindowAccess::LoyaltyTier(tier) => storage.loyalty_profiles.get(account)
+                .is_some_and(|profile| profile.current_tier >= *tier),
+            SaleWindowAccess::TeamLoyaltyTier { team_id, tier } => storage.team_loyalty_profiles.get((account, *team_id))
+                .is_some_and(|profile| profile.loyalty_tier >= *tier),
+            SaleWindowAccess::SeasonPassHolder { team_id } => storage.user_season_passes.get(account)
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|pass_id| storage.season_passes.get(pass_id))
+                .any(|pass| pass.team_id == *team_id && pass.status == SeasonPassStatus::Active),
+            SaleWindowAccess::AccessCode => storage.access_code_unlocks.contains((event_id, account)),
+            SaleWindowAccess::General => true,
+        }
+    }
+}
@@ -0,0 +1,198 @@
+//! Temporary seat holds for checkout flows.
+//!
+//! A hold locks specific seats in an event's layout for a bounded number of
+//! blocks or milliseconds and records the price quoted for each seat. The holder
+//! can confirm the hold into tickets at those prices or release it early; a
+//! lapsed hold stops blocking its seats without any further call.
+//!
+//! # Functions
+//! - `hold_seats` -- reserves layout seats at their current quoted prices
+//! - `confirm_hold` -- converts a live hold into tickets at the quoted prices
+//! - `release_hold` -- releases a hold's seats before it lapses
+//! - `is_hold_live` -- whether a hold is still active and unexpired
+//! - `active_holds` -- returns an account's live hold IDs
+
+use crate::storage::*;
+use crate::types::*;
+use ink::env::DefaultEnvironment;
+use ink::prelude::string::String;
+use ink::prelude::string::ToString;
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+use super::currency_management::CurrencyManagement;
+use super::seat_inventory::SeatInventory;
+use super::ticket_management::TicketManagement;
+
+/// Longest hold that can be requested in milliseconds
+pub const MAX_HOLD_DURATION_MS: u64 = 15 * 60 * 1000;
+/// Longest hold that can be requested in blocks
+pub const MAX_HOLD_BLOCKS: u32 = 150;
+/// Most seats a single hold can lock
+pub const MAX_SEATS_PER_HOLD: usize = 8;
+/// Most live holds an account can have at once
+pub const MAX_ACTIVE_HOLDS_PER_ACCOUNT: usize = 3;
+
+/// Seat hold management
+pub struct SeatHolds;
+
+#[allow(clippy::arithmetic_side_effects)]
+#[allow(clippy::cast_possible_truncation)]
+impl SeatHolds {
+    /// Hold seats for an account, returning the hold ID
+    ///
+    /// Seats must belong to the event's layout and be free. Each seat's dynamic
+    /// price is quoted now and honoured when the hold is confirmed.
+    pub fn hold_seats(
+        storage: &mut InkTixStorage,
+        holder: AccountId,
+        event_id: u32,
+        seats: Vec<Seat>,
+        duration: HoldDuration,
+    ) -> Result<u64, String> {
+        let event = storage.events.get(event_id).ok_or("Event not found")?;
+        if !event.active { return Err("Event is not active".to_string()); }
+        if seats.is_empty() { return Err("No seats requested".to_string()); }
+        if seats.len() > MAX_SEATS_PER_HOLD { return Err("Too many seats in one hold".to_string()); }
+        super::ballots::Ballots::ensure_not_balloted(storage, event_id)?;
+
+        let expiry = match duration {
+            HoldDuration::Blocks(blocks) if blocks > 0 && blocks <= MAX_HOLD_BLOCKS => {
+                HoldExpiry::AtBlock(ink::env::block_number::<DefaultEnvironment>() + blocks)
+            }
+            HoldDuration::Milliseconds(ms) if ms > 0 && ms <= MAX_HOLD_DURATION_MS => {
+                HoldExpiry::AtTimestamp(ink::env::block_timestamp::<DefaultEnvironment>() + ms)
+            }
+            _ => return Err("Invalid hold duration".to_string()),
+        };
+
+        let mut active = Self::active_holds(storage, holder);
+        if active.len() >= MAX_ACTIVE_HOLDS_PER_ACCOUNT {
+            return Err("Too many active seat holds".to_string());
+        }
+        let quantity = seats.len() as u32;
+        if event.sold_tickets + quantity > event.capacity {
+            return Err(InkTixError::SoldOut.into());
+        }
+        super::waitlist::Waitlists::ensure_unreserved(storage, &event, quantity)?;
+        TicketManagement::check_purchase_limit(storage, holder, &event, quantity)?;
+        super::sale_windows::SaleWindows::check(storage, holder, &event, quantity)?;
+
+        let mut held = Vec::new();
+        let mut keys: Vec<SeatKey> = Vec::new();
+        for seat in seats {
+            let (seat, seat_number, key) = SeatInventory::apply_layout(storage, event_id, seat)?;
+            let key = key.ok_or("Event has no seat layout to hold")?;
+            if keys.contains(&key) { return Err("Seat requested more than once".to_string()); }
+            SeatInventory::ensure_available(storage, &key)?;
+            let (price, performance_multiplier) = super::pricing::DynamicPricing::calculate_price(
+                storage, event_id, &seat, false
+            )?;
+            held.push(HeldSeat { seat, seat_number, price, performance_multiplier });
+            keys.push(key);
+        }
+
+        let hold_id = storage.get_next_seat_hold_id();
+        for key in &keys {
+            SeatInventory::set_status(storage, key, SeatStatus::Held { hold_id });
+        }
+        SeatInventory::track_hold(storage, event_id, hold_id);
+        let hold = SeatHold {
+            id: hold_id,
+            event_id,
+            holder,
+            seats: held,
+            created_at: ink::env::block_timestamp::<DefaultEnvironment>(),
+            expiry,
+            is_active: true,
+        };
+        storage.seat_holds.insert(hold_id, &hold);
+        active.push(hold_id);
+        storage.user_seat_holds.insert(holder, &active);
+        Ok(hold_id)
+    }
+
+    /// Confirm a live hold, issuing one ticket per held seat at its quoted price
+    ///
+    /// The quoted DOT prices are charged in `currency` at the current rate; a
+    /// native `payment` must cover a DOT total. Returns the ticket IDs and the
+    /// total charged in `currency`; refunding excess native value and pulling a
+    /// token charge are left to the caller.
+    pub fn confirm_hold(
+        storage: &mut InkTixStorage,
+        caller: AccountId,
+        hold_id: u64,
+        currency: CurrencyId,
+        payment: u128,
+    ) -> Result<(Vec<u64>, u128), String> {
+        let mut hold = storage.seat_holds.get(hold_id).ok_or("Seat hold not found")?;
+        if hold.holder != caller { return Err("Only the holder can confirm a seat hold".to_string()); }
+        if !Self::is_hold_live(&hold) { return Err("Seat hold has expired".to_string()); }
+        let event = storage.events.get(hold.event_id).ok_or("Event not found")?;
+        if !event.active { return Err("Event is not active".to_string()); }
+        let quantity = hold.seats.len() as u32;
+        if event.sold_tickets + quantity > event.capacity {
+            return Err(InkTixError::SoldOut.into());
+        }
+        TicketManagement::check_purchase_limit(storage, caller, &event, quantity)?;
+        let sale_window = super::sale_windows::SaleWindows::check(storage, caller, &event, quantity)?;
+        let charges = hold.seats.iter()
+            .map(|seat| CurrencyManagement::charge_in(storage, seat.price, currency))
+            .collect::<Result<Vec<u128>, String>>()?;
+        let total: u128 = charges.iter().sum();
+        CurrencyManagement::ensure_payable(storage, currency, total, payment)?;
+
+        hold.is_active = false;
+        storage.seat_holds.insert(hold_id, &hold);
+        Self::forget_hold(storage, caller, hold_id);
+        if let Some(window_index) = sale_window {
+            super::sale_windows::SaleWindows::record(storage, hold.event_id, window_index, caller, quantity);
+        }
+
+        let ticket_ids = hold.seats.iter().zip(charges)
+            .map(|(seat, charge)| TicketManagement::issue_ticket(storage, caller, hold.event_id, seat, currency, charge))
+            .collect();
+        Ok((ticket_ids, total))
+    }
+
+    /// Release a live hold early, freeing its seats (holder only)
+    pub fn release_hold(storage: &mut InkTixStorage, caller: AccountId, hold_id: u64) -> Result<(), String> {
+        let mut hold = storage.seat_holds.get(hold_id).ok_or("Seat hold not found")?;
+        if hold.holder != caller { return Err("Only the holder can release a seat hold".to_string()); }
+        if !hold.is_active { return Err("Seat hold is not active".to_string()); }
+
+        hold.is_active = false;
+        storage.seat_holds.insert(hold_id, &hold);
+        Self::forget_hold(storage, caller, hold_id);
+
+        for held in &hold.seats {
+            let key = (hold.event_id, held.seat.section.clone(), held.seat.row.clone(), held.seat_number);
+            if storage.seat_inventory.get(&key) == Some(SeatStatus::Held { hold_id }) {
+                SeatInventory::set_status(storage, &key, SeatStatus::Available);
+            }
+        }
+        Ok(())
+    }
+
+    /// A hold is live while active and before its expiry block or timestamp
+    pub fn is_hold_live(hold: &SeatHold) -> bool {
+        hold.is_active && match hold.expiry {
+            HoldExpiry::AtBlock(block) => ink::env::block_number::<DefaultEnvironment>() < block,
+            HoldExpiry::AtTimestamp(time) => ink::env::block_timestamp::<DefaultEnvironment>() < time,
+        }
+    }
+
+    /// Get an account's live hold IDs
+    pub fn active_holds(storage: &InkTixStorage, holder: AccountId) -> Vec<u64> {
+        storage.user_seat_holds.get(holder).unwrap_or_default()
+            .into_iter()
+            .filter(|id| storage.seat_holds.get(*id).is_some_and(|h| Self::is_hold_live(&h)))
+            .collect()
+    }
+
+    fn forget_hold(storage: &mut InkTixStorage, holder: AccountId, hold_id: u64) {
+        let mut holds = storage.user_seat_holds.get(holder).unwrap_or_default();
+        holds.retain(|&id| id != hold_id);
+        storage.user_seat_holds.insert(holder, &holds);
+    }
+}
@@ -0,0 +1,298 @@
+//! Per-event seat inventory and double-booking prevention.
+//!
+//! Seat layouts are defined per venue as `SeatBlock`s and snapshotted onto each
+//! event at creation; an event may override its layout until sales start. Every
+//! seat in a layout is tracked as Available, Held, GroupReserved or Sold, keyed
+//! by (event, section, row, seat number), so a seat can only ever be sold once.
+//! A seat whose hold or group order has lapsed reads as Available again, and
+//! `release_lapsed` returns it to the event's available-seat count. A seat
+//! assigned to a non-flex season pass is recorded as SeasonPassReserved in
+//! every game the pass covers until the pass claims it. Flex passes do not
+//! hold their seat; it stays on general sale until claimed.
+//!
+//! # Functions
+//! - `set_venue_layout` -- validates and stores a venue's default seat layout
+//! - `set_event_layout` -- validates and stores an event-specific layout before sales start
+//! - `store_event_layout` -- stores an event's layout and resets its available-seat count
+//! - `apply_layout` -- resolves a requested seat against the event layout
+//! - `ensure_available` -- rejects seats that are held, reserved, sold or being auctioned
+//! - `seat_status` -- returns a seat's availability, releasing lapsed holds
+//! - `season_pass_covers` -- whether a season pass covers an event
+//! - `set_status` -- records a seat's availability state
+//! - `track_hold` / `track_group_order` -- index a hold or group order under its event
+//! - `release_lapsed` -- releases the seats of an event's lapsed holds and group orders
+//! - `available_seats` -- returns the running count of an event's unrecorded seats
+//! - `get_seat_map` -- returns every seat in an event's layout with its state
+
+use crate::storage::*;
+use crate::types::*;
+use ink::prelude::string::String;
+use ink::prelude::string::ToString;
+use ink::prelude::vec::Vec;
+
+/// Seat inventory management
+pub struct SeatInventory;
+
+#[allow(clippy::arithmetic_side_effects)]
+impl SeatInventory {
+    /// Store the default seat layout for a venue
+    pub fn set_venue_layout(
+        storage: &mut InkTixStorage,
+        venue_id: u32,
+        blocks: Vec<SeatBlock>,
+    ) -> Result<(), String> {
+        let venue = storage.venues.get(venue_id).ok_or("Venue not found")?;
+        Self::validate_layout(&venue, &blocks)?;
+        storage.venue_seat_layouts.insert(venue_id, &blocks);
+        Ok(())
+    }
+
+    /// Override the seat layout for a single event (only before any sales)
+    pub fn set_event_layout(
+        storage: &mut InkTixStorage,
+        event_id: u32,
+        blocks: Vec<SeatBlock>,
+    ) -> Result<(), String> {
+        let event = storage.events.get(event_id).ok_or("Event not found")?;
+        if event.sold_tickets > 0 {
+            return Err("Seat layout cannot change after sales have started".to_string());
+        }
+        let layout_seats: u32 = storage.event_seat_layouts.get(event_id).unwrap_or_default()
+            .iter().map(|block| block.seat_count).sum();
+        if Self::available_seats(storage, event_id) != layout_seats {
+            return Err("Seat layout cannot change while seats are held or reserved".to_string());
+        }
+        if storage.event_auctions.contains(event_id) {
+            return Err("Seat layout cannot change once an auction is configured".to_string());
+        }
+        let venue = storage.venues.get(event.venue_id).ok_or("Venue not found")?;
+        Self::validate_layout(&venue, &blocks)?;
+        Self::store_event_layout(storage, event_id, blocks);
+        Ok(())
+    }
+
+    /// Store an event's layout and reset its count of seats with no recorded state
+    pub fn store_event_layout(storage: &mut InkTixStorage, event_id: u32, blocks: Vec<SeatBlock>) {
+        let seats: u32 = blocks.iter().map(|block| block.seat_count).sum();
+        storage.event_seat_layouts.insert(event_id, &blocks);
+        storage.event_available_seats.insert(event_id, &seats);
+    }
+
+    /// Resolve a requested seat against the event's layout
+    ///
+    /// Returns the seat with its layout-defined type, access level and pricing
+    /// tier multiplier, its numeric seat number, and its inventory key. Events
+    /// without a layout are general admission: the requested seat type, access
+    /// level and multiplier are ignored, and there is no inventory key.
+    pub fn apply_layout(
+        storage: &InkTixStorage,
+        event_id: u32,
+        seat: Seat,
+    ) -> Result<(Seat, u32, Option<SeatKey>), String> {
+        let Some(blocks) = storage.event_seat_layouts.get(event_id) else {
+            let seat_number = seat.seat_number.parse().unwrap_or(0);
+            let general_admission = Seat {
+                seat_type: SeatType::GeneralAdmission, access_level: AccessLevel::Standard, price_multiplier: 0, ..seat
+            };
+            return Ok((general_admission, seat_number, None));
+        };
+        let seat_number: u32 = seat.seat_number.parse().map_err(|_| "Invalid seat number")?;
+        let block = blocks.iter()
+            .find(|b| b.section == seat.section && b.row == seat.row
+                && seat_number >= b.first_seat && seat_number < b.first_seat + b.seat_count)
+            .ok_or("Seat not found in event layout")?;
+
+        let price_multiplier = match block.pricing_tier_id {
+            Some(tier_id) => {
+                let event = storage.events.get(event_id).ok_or("Event not found")?;
+                storage.venues.get(event.venue_id)
+                    .and_then(|v| v.pricing_tiers.into_iter().find(|t| t.id == tier_id))
+                    .map(|t| t.base_price_multiplier)
+                    .unwrap_or(0)
+            }
+            None => 0,
+        };
+
+        let key = (event_id, block.section.clone(), block.row.clone(), seat_number);
+        let resolved = Seat {
+            section: block.section.clone(),
+            row: block.row.clone(),
+            seat_number: seat.seat_number,
+            seat_type: block.seat_type,
+            access_level: block.access_level,
+            price_multiplier,
+        };
+        Ok((resolved, seat_number, Some(key)))
+    }
+
+    /// Reject a seat that is already sold, under a live hold, reserved for a group order or season pass, or being auctioned
+    pub fn ensure_available(storage: &InkTixStorage, key: &SeatKey) -> Result<(), String> {
+        super::auctions::Auctions::ensure_posted_price(storage, key)?;
+        match Self::seat_status(storage, key) {
+            SeatStatus::Available => Ok(()),
+            SeatStatus::Held { .. } => Err("Seat is currently held".to_string()),
+            SeatStatus::GroupReserved { .. } => Err("Seat is reserved for a group order".to_string()),
+            SeatStatus::SeasonPassReserved { .. } => Err("Seat is reserved for a season pass holder".to_string()),
+            SeatStatus::Sold { .. } => Err(InkTixError::SoldOut.into()),
+        }
+    }
+
+    /// Get a seat's current availability, treating lapsed holds and group orders as available
+    pub fn seat_status(storage: &InkTixStorage, key: &SeatKey) -> SeatStatus {
+        match storage.seat_inventory.get(key) {
+            Some(SeatStatus::Held { hold_id }) => {
+                let live = storage.seat_holds.get(hold_id)
+                    .is_some_and(|hold| super::seat_holds::SeatHolds::is_hold_live(&hold));
+                if live { SeatStatus::Held { hold_id } } else { SeatStatus::Available }
+            }
+            Some(SeatStatus::GroupReserved { order_id }) => {
+                let live = storage.group_orders.get(order_id)
+                    .is_some_and(|order| super::group_orders::GroupOrders::is_order_live(&order));
+                if live { SeatStatus::GroupReserved { order_id } } else { SeatStatus::Available }
+            }
+            Some(status) => status,
+            None => SeatStatus::Available,
+        }
+    }
+
+    /// Whether an active season pass covers an event: a home game of its season on its package's game list
+    ///
+    /// Flex packages without a game list cover every home game of the season.
+    pub fn season_pass_covers(storage: &InkTixStorage, pass: &SeasonPass, event: &Event) -> bool {
+        let EventCategory::Sports { home_team_id, season_id, .. } = event.category else {
+            return false;
+        };
+        if pass.status != SeasonPassStatus::Active || home_team_id != pass.team_id || season_id != pass.season_id {
+            return false;
+        }
+        storage.season_pass_packages.get(pass.package_id)
+            .is_some_and(|package| package.game_ids.is_empty() || package.game_ids.contains(&event.id))
+    }
+
+    /// Record a seat's availability state, keeping the event's available-seat count in step
+    pub fn set_status(storage: &mut InkTixStorage, key: &SeatKey, status: SeatStatus) {
+        let was_recorded = storage.seat_inventory.contains(key);
+        let available = storage.event_available_seats.get(key.0).unwrap_or_default();
+        if status == SeatStatus::Available {
+            storage.seat_inventory.remove(key);
+            if was_recorded {
+                storage.event_available_seats.insert(key.0, &(available + 1));
+            }
+        } else {
+            storage.seat_inventory.insert(key, &status);
+            if !was_recorded {
+                storage.event_available_seats.insert(key.0, &available.saturating_sub(1));
+            }
+        }
+    }
+
+    /// Index a new hold under its event, first releasing the event's lapsed holds and orders
+    pub fn track_hold(storage: &mut InkTixStorage, event_id: u32, hold_id: u64) {
+        Self::release_lapsed(storage, event_id);
+        let mut holds = storage.event_seat_holds.get(event_id).unwrap_or_default();
+        holds.push(hold_id);
+        storage.event_seat_holds.insert(event_id, &holds);
+    }
+
+    /// Index a new group order under its event, first releasing the event's lapsed holds and orders
+    pub fn track_group_order(storage: &mut InkTixStorage, event_id: u32, order_id: u32) {
+        Self::release_lapsed(storage, event_id);
+        let mut orders = storage.event_group_orders.get(event_id).unwrap_or_default();
+        orders.push(order_id);
+        storage.event_group_orders.insert(event_id, &orders);
+    }
+
+    /// Release the seats still recorded for an event's holds and group orders that are no longer live
+    ///
+    /// Released holds and orders are dropped from the event's index, so each is
+    /// visited until it stops being live and once more.
+    pub fn release_lapsed(storage: &mut InkTixStorage, event_id: u32) {
+        let mut lapsed_keys: Vec<(SeatKey, SeatStatus)> = Vec::new();
+        let mut holds = storage.event_seat_holds.get(event_id).unwrap_or_default();
+        holds.retain(|&hold_id| {
+            let Some(hold) = storage.seat_holds.get(hold_id) else { return false };
+            if super::seat_holds::SeatHolds::is_hold_live(&hold) { return true; }
+            lapsed_keys.extend(hold.seats.iter().map(|seat| (Self::held_key(event_id, seat), SeatStatus::Held { hold_id })));
+            false
+        });
+        let mut orders = storage.event_group_orders.get(event_id).unwrap_or_default();
+        orders.retain(|&order_id| {
+            let Some(order) = storage.group_orders.get(order_id) else { return false };
+            if super::group_orders::GroupOrders::is_order_live(&order) { return true; }
+            lapsed_keys.extend(order.seats.iter().map(|seat| (Self::held_key(event_id, seat), SeatStatus::GroupReserved { order_id })));
+            false
+        });
+        for (key, status) in lapsed_keys {
+            if storage.seat_inventory.get(&key) == Some(status) {
+                Self::set_status(storage, &key, SeatStatus::Available);
+            }
+        }
+        storage.event_seat_holds.insert(event_id, &holds);
+        storage.event_group_orders.insert(event_id, &orders);
+    }
+
+    /// Seats of an event's layout with no recorded hold, group reservation, season pass reservation or sale
+    ///
+    /// Kept as a running count by `set_status`, so seats of a lapsed hold or group
+    /// order stay counted as taken until `release_lapsed` returns them.
+    pub fn available_seats(storage: &InkTixStorage, event_id: u32) -> u32 {
+        storage.event_available_seats.get(event_id).unwrap_or_default()
+    }
+
+    /// Get every seat in an event's layout with its availability
+    pub fn get_seat_map(storage: &InkTixStorage, event_id: u32) -> Vec<SeatAvailability> {
+        let mut seats = Vec::new();
+        for block in storage.event_seat_layouts.get(event_id).unwrap_or_default() {
+            for seat_number in block.first_seat..block.first_seat + block.seat_count {
+                let key = (event_id, block.section.clone(), block.row.clone(), seat_number);
+                seats.push(SeatAvailability {
+                    section: block.section.clone(),
+                    row: block.row.clone(),
+                    seat_number,
+                    seat_type: block.seat_type,
+                    access_level: block.access_level,
+                    status: Self::seat_status(storage, &key),
+                });
+            }
+        }
+        seats
+    }
+
+    /// Inventory key of a held or group-reserved seat
+    fn held_key(event_id: u32, seat: &HeldSeat) -> SeatKey {
+        (event_id, seat.seat.section.clone(), seat.seat.row.clone(), seat.seat_number)
+    }
+
+    fn validate_layout(venue: &Venue, blocks: &[SeatBlock]) -> Result<(), String> {
+        let mut total_seats: u64 = 0;
+        for (i, block) in blocks.iter().enumerate() {
+            if block.section.is_empty() || block.row.is_empty() {
+                return Err("Seat block section and row cannot be empty".to_string());
+            }
+            if block.seat_count == 0 {
+                return Err("Seat block must contain at least one seat".to_string());
+            }
+            if block.first_seat.checked_add(block.seat_count).is_none() {
+                return Err("Seat block numbering overflows".to_string());
+            }
+            if let Some(tier_id) = block.pricing_tier_id {
+                if !venue.pricing_tiers.iter().any(|t| t.id == tier_id && t.active) {
+                    return Err("Pricing tier not found".to_string());
+                }
+            }
+            let overlaps = blocks[..i].iter().any(|other| {
+                other.section == block.section && other.row == block.row
+                    && block.first_seat < other.first_seat + other.seat_count
+                    && other.first_seat < block.first_seat + block.seat_count
+            });
+            if overlaps {
+                return Err("Seat blocks overlap".to_string());
+            }
+            total_seats += block.seat_count as u64;
+        }
+        if total_seats > venue.capacity as u64 {
+            return Err("Seat layout exceeds venue capacity".to_string());
+        }
+        Ok(())
+    }
+}
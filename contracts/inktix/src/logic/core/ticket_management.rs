@@ -1,13 +1,28 @@
 //! Ticket purchase, transfer, and resale logic.
 //!
-//! Manages the full ticket lifecycle including dynamic price calculation at purchase,
-//! anti-scalping enforcement for concert events, ownership transfers, and resale listings.
+//! Manages the full ticket lifecycle including dynamic price calculation and payment
+//! checks at purchase, anti-scalping enforcement and behavior scoring, ownership transfers,
+//! and the secondary resale marketplace.
 //!
 //! # Functions
-//! - `purchase_ticket` -- buys a ticket with dynamic pricing and anti-scalping checks
+//! - `purchase_ticket` -- buys a ticket with seat inventory, dynamic or locked pricing, payment and anti-scalping checks
+//! - `check_purchase_limit` -- enforces anti-scalping lists, behavior status, required attestations and holding limits
+//! - `issue_ticket` -- records a paid ticket, its seat, analytics and escrow deposit
 //! - `transfer_ticket` -- transfers ticket ownership between accounts
-//! - `resell_ticket` -- lists a ticket on the resale marketplace
+//! - `ensure_transferable` -- applies listing, transfer restriction and lock period checks
+//! - `reassign_ticket` -- moves a ticket and its linked NFT together and records the transfer history
+//! - `resell_ticket` -- lists a ticket on the resale marketplace within the event's price cap
+//!   (a minted NFT must first be PSP34-approved to the contract)
+//! - `buy_resale_listing` -- buys a live resale listing
+//! - `settle_listing` -- sells a listed ticket, splitting the price between seller and fee
+//! - `cancel_resale_listing` -- withdraws a listing from the marketplace, returning it to any waitlist
+//! - `get_active_listings` -- returns an event's unexpired listings
+//! - `claim_refund` -- refunds a cancelled event's ticket from escrow and invalidates it
+//! - `refund_ticket` -- refunds a live event's ticket from escrow, returning its seat to sale
 
+use super::anti_scalping::AntiScalping;
+use super::currency_management::CurrencyManagement;
+use super::price_locks::PriceLocks;
 use crate::storage::*;
 use crate::types::*;
 use ink::env::DefaultEnvironment;
@@ -16,6 +31,9 @@ use ink::prelude::string::String;
 use ink::prelude::vec::Vec;
 use ink::prelude::string::ToString;
 
+/// How long a resale listing stays open, in milliseconds (capped at the event start)
+pub const RESALE_LISTING_DURATION: u64 = 7 * 24 * 60 * 60 * 1000;
+
 #[allow(clippy::arithmetic_side_effects)]
 #[allow(clippy::cast_possible_truncation)]
 
@@ -25,59 +43,135 @@ pub struct TicketManagement;
 #[allow(clippy::arithmetic_side_effects)]
 #[allow(clippy::cast_possible_truncation)]
 impl TicketManagement {
-    /// Purchase a ticket for an event
+    /// Purchase a ticket for an event, holding the payment in the event's escrow
+    ///
+    /// The price is the seat's current dynamic price, or the price locked by
+    /// `quote_id`. It is set in DOT and charged in `currency` at the stored
+    /// rate, and the purchase fails if the charge exceeds `max_price`.
+    /// `payment` is the native value transferred with the call; for DOT it
+    /// must cover the charge, and any excess (or, for token currencies, all of it)
+    /// is left for the caller to refund. Pulling a token charge, the ticket's
+    /// `purchase_price`, is also left to the caller.
+    #[allow(clippy::too_many_arguments)]
     pub fn purchase_ticket(
         storage: &mut InkTixStorage,
         buyer: AccountId,
         event_id: u32,
         seat: Seat,
         currency: CurrencyId,
+        payment: u128,
+        quote_id: Option<u64>,
+        max_price: Option<u128>,
     ) -> Result<u64, String> {
         let event = storage.events.get(event_id).ok_or("Event not found")?;
         if !event.active { return Err("Event is not active".to_string()); }
+        if event.sold_tickets >= event.capacity {
+            return Err(InkTixError::SoldOut.into());
+        }
+        super::waitlist::Waitlists::ensure_unreserved(storage, &event, 1)?;
+
+        // Resolve the seat against the event layout and make sure it is still free
+        let (seat, seat_number, seat_key) =
+            super::seat_inventory::SeatInventory::apply_layout(storage, event_id, seat)?;
+        if let Some(key) = &seat_key {
+            super::seat_inventory::SeatInventory::ensure_available(storage, key)?;
+        }
 
-        // Check anti-scalping per-event purchase count for concert events
-        if let EventCategory::Concert { .. } = &event.category {
-            let key = (event_id, buyer);
-            let count = storage.per_event_purchase_count.get(key).unwrap_or(0);
-            // Check anti-scalping config, default to 4 for concerts
-            let max_tickets = storage.anti_scalping_configs.get(event_id)
-                .map(|c| c.max_tickets_per_user)
-                .unwrap_or(4);
-            if count >= max_tickets {
-                return Err("Purchase limit reached".to_string());
+        Self::check_purchase_limit(storage, buyer, &event, 1)?;
+        let ballot_allocation = super::ballots::Ballots::check_purchase(storage, buyer, event_id)?;
+        let sale_window = super::sale_windows::SaleWindows::check(storage, buyer, &event, 1)?;
+
+        // Use the locked price, or calculate the dynamic price
+        let priced = match quote_id {
+            Some(quote_id) => PriceLocks::locked_price(storage, buyer, quote_id, event_id, &seat, seat_number)?,
+            None => {
+                let (dynamic_price, multiplier) = super::pricing::DynamicPricing::calculate_price(
+                    storage, event_id, &seat, false
+                )?;
+                HeldSeat { seat, seat_number, price: dynamic_price, performance_multiplier: multiplier }
             }
-            storage.per_event_purchase_count.insert(key, &(count + 1));
+        };
+        let charge = CurrencyManagement::charge_in(storage, priced.price, currency)?;
+        if max_price.is_some_and(|max_price| charge > max_price) {
+            return Err("Price exceeds the maximum price".to_string());
         }
+        CurrencyManagement::ensure_payable(storage, currency, charge, payment)?;
 
-        // Calculate dynamic price
-        let (dynamic_price, multiplier) = super::pricing::DynamicPricing::calculate_price(
-            storage, event_id, &seat, false
-        )?;
+        if let Some(quote_id) = quote_id {
+            PriceLocks::redeem(storage, quote_id);
+        }
+        if let Some((ballot_id, position)) = ballot_allocation {
+            super::ballots::Ballots::record_purchase(storage, ballot_id, position);
+        }
+        if let Some(window_index) = sale_window {
+            super::sale_windows::SaleWindows::record(storage, event_id, window_index, buyer, 1);
+        }
+        Ok(Self::issue_ticket(storage, buyer, event_id, &priced, currency, charge))
+    }
 
+    /// Check the buyer may buy `quantity` more tickets
+    ///
+    /// Applies the event's anti-scalping lists and the buyer's behavior status,
+    /// the attestations the event requires, then the event, season and team
+    /// holding limits.
+    pub fn check_purchase_limit(
+        storage: &InkTixStorage,
+        buyer: AccountId,
+        event: &Event,
+        quantity: u32,
+    ) -> Result<(), String> {
+        AntiScalping::ensure_allowed(storage, buyer, event.id)?;
+        super::attestations::Attestations::ensure_attested(storage, buyer, event.id)?;
+        super::purchase_limits::PurchaseLimits::check(storage, buyer, event, quantity)
+    }
+
+    /// Issue a paid ticket for a resolved, priced seat
+    ///
+    /// Records ownership, marks the seat sold, updates sold counts and
+    /// analytics (in DOT), and credits the `charged` amount to the event's
+    /// escrow and revenue in `currency`. Callers must have already validated
+    /// availability, limits and payment.
+    pub fn issue_ticket(
+        storage: &mut InkTixStorage,
+        buyer: AccountId,
+        event_id: u32,
+        priced: &HeldSeat,
+        currency: CurrencyId,
+        charged: u128,
+    ) -> u64 {
+        let price = priced.price;
+        let seat = &priced.seat;
         let ticket_id = storage.get_next_ticket_id();
         let ticket = Ticket {
             id: ticket_id,
             event_id,
             owner: buyer,
-            purchase_price: dynamic_price,
+            purchase_price: charged,
             purchase_currency: currency,
             purchase_date: ink::env::block_timestamp::<DefaultEnvironment>(),
-            seat_number: 1,
+            acquired_at: ink::env::block_timestamp::<DefaultEnvironment>(),
+            seat_number: priced.seat_number,
             transferable: true,
-            section: seat.section,
-            row: seat.row,
+            section: seat.section.clone(),
+            row: seat.row.clone(),
             seat_type: seat.seat_type,
             access_level: seat.access_level,
-            loyalty_points_earned: Self::calculate_loyalty_points(dynamic_price),
+            loyalty_points_earned: Self::calculate_loyalty_points(price),
             season_pass_discount_applied: false,
             is_season_pass_ticket: false,
-            dynamic_price_paid: dynamic_price,
-            performance_multiplier_applied: multiplier,
-            dot_equivalent_paid: dynamic_price,
+            dynamic_price_paid: price,
+            performance_multiplier_applied: priced.performance_multiplier,
+            dot_equivalent_paid: price,
+            refunded: false,
         };
 
         storage.tickets.insert(ticket_id, &ticket);
+        if storage.event_seat_layouts.contains(event_id) {
+            let key = (event_id, seat.section.clone(), seat.row.clone(), priced.seat_number);
+            super::seat_inventory::SeatInventory::set_status(
+                storage, &key, SeatStatus::Sold { ticket_id }
+            );
+        }
 
         // Update user tickets
         let mut user_tickets = storage.user_tickets.get(buyer).unwrap_or_default();
@@ -88,20 +182,25 @@ impl TicketManagement {
         if let Some(analytics) = storage.event_analytics.get(event_id) {
             let mut updated_analytics = analytics;
             updated_analytics.tickets_sold += 1;
-            updated_analytics.revenue_generated += dynamic_price;
+            updated_analytics.revenue_generated += price;
             storage.event_analytics.insert(event_id, &updated_analytics);
         }
 
         // Update event sold tickets
-        let mut updated_event = event;
-        updated_event.sold_tickets += 1;
-        updated_event.revenue_generated += dynamic_price;
-        storage.events.insert(event_id, &updated_event);
+        if let Some(mut event) = storage.events.get(event_id) {
+            event.sold_tickets += 1;
+            event.revenue_generated += price;
+            storage.events.insert(event_id, &event);
+        }
 
         storage.platform_stats.total_tickets_sold += 1;
-        storage.platform_stats.total_revenue += dynamic_price;
+        storage.platform_stats.total_revenue += price;
+        AntiScalping::record_purchase(storage, buyer, event_id);
+
+        CurrencyManagement::record_revenue(storage, currency, charged);
+        super::escrow::Escrow::deposit(storage, event_id, currency, charged);
 
-        Ok(ticket_id)
+        ticket_id
     }
 
     /// Transfer ticket to another user
@@ -111,52 +210,384 @@ impl TicketManagement {
         ticket_id: u64,
         to: AccountId,
     ) -> Result<(), String> {
-        let mut ticket = storage.tickets.get(ticket_id).ok_or("Ticket not found")?;
+        let ticket = storage.tickets.get(ticket_id).ok_or("Ticket not found")?;
         if ticket.owner != caller { return Err("Only ticket owner can transfer".to_string()); }
+        Self::ensure_transferable(storage, &ticket, &TransferReason::Gift)?;
+        AntiScalping::ensure_allowed(storage, caller, ticket.event_id)?;
+        AntiScalping::ensure_allowed(storage, to, ticket.event_id)?;
+        super::purchase_limits::PurchaseLimits::check_transfer(storage, caller, to, &ticket)?;
+
+        Self::reassign_ticket(storage, ticket_id, to, TransferReason::Gift, None);
+        Ok(())
+    }
+
+    /// Check a ticket may move to a new holder for the given reason
+    ///
+    /// Gifts and resales are subject to the event's `AntiScalpingConfig`: a
+    /// restricted event only allows transfers through the resale marketplace,
+    /// and neither is allowed within `transfer_lock_period` of the current owner
+    /// acquiring the ticket.
+    /// Admin and emergency transfers skip these checks.
+    pub fn ensure_transferable(
+        storage: &InkTixStorage,
+        ticket: &Ticket,
+        reason: &TransferReason,
+    ) -> Result<(), String> {
         if !ticket.transferable { return Err("Ticket is not transferable".to_string()); }
+        if *reason == TransferReason::Gift && Self::active_listing_for_ticket(storage, ticket.id).is_some() {
+            return Err("Ticket is listed for resale".to_string());
+        }
+        if !matches!(reason, TransferReason::Gift | TransferReason::Resale) {
+            return Ok(());
+        }
+
+        if let Some(config) = storage.anti_scalping_configs.get(ticket.event_id) {
+            if config.transfer_restricted && *reason == TransferReason::Gift {
+                return Err("Transfers are restricted for this event".to_string());
+            }
+            let now = ink::env::block_timestamp::<DefaultEnvironment>();
+            if now < ticket.acquired_at.saturating_add(config.transfer_lock_period) {
+                return Err("Ticket is within its transfer lock period".to_string());
+            }
+        }
+        Ok(())
+    }
 
+    /// Move a ticket and its linked NFT, if any, to a new owner
+    ///
+    /// The single routine behind ticket, NFT and resale transfers: it updates
+    /// `tickets`, `user_tickets`, `nft_tickets` and `user_nft_tokens` together and
+    /// records the move in the ticket's `TicketTransferHistory`. The NFT's
+    /// verification hash is reissued so the previous holder's QR code no longer
+    /// scans. Callers check `ensure_transferable` first.
+    pub fn reassign_ticket(
+        storage: &mut InkTixStorage,
+        ticket_id: u64,
+        to: AccountId,
+        reason: TransferReason,
+        price: Option<u128>,
+    ) {
+        let Some(mut ticket) = storage.tickets.get(ticket_id) else { return };
+        let from = ticket.owner;
+        match (&reason, price) {
+            (TransferReason::Gift, _) => AntiScalping::record_transfer(storage, from, &ticket),
+            (TransferReason::Resale, Some(price)) => AntiScalping::record_resale(storage, from, &ticket, price),
+            _ => {}
+        }
         ticket.owner = to;
+        ticket.acquired_at = ink::env::block_timestamp::<DefaultEnvironment>();
         storage.tickets.insert(ticket_id, &ticket);
 
-        let mut from_tickets = storage.user_tickets.get(&caller).unwrap_or_default();
+        let mut from_tickets = storage.user_tickets.get(from).unwrap_or_default();
         from_tickets.retain(|&id| id != ticket_id);
-        storage.user_tickets.insert(&caller, &from_tickets);
+        storage.user_tickets.insert(from, &from_tickets);
 
-        let mut to_tickets = storage.user_tickets.get(&to).unwrap_or_default();
+        let mut to_tickets = storage.user_tickets.get(to).unwrap_or_default();
         to_tickets.push(ticket_id);
-        storage.user_tickets.insert(&to, &to_tickets);
+        storage.user_tickets.insert(to, &to_tickets);
 
-        Ok(())
+        if let Some(token_id) = storage.ticket_to_nft.get(ticket_id) {
+            if let Some(mut nft) = storage.nft_tickets.get(token_id) {
+                nft.verification_hash = super::nft_management::NftManagement::verification_hash(&ticket, to, token_id);
+                super::nft_management::NftManagement::move_nft(storage, nft, to);
+            }
+        }
+
+        let now = ink::env::block_timestamp::<DefaultEnvironment>();
+        let mut history = storage.ticket_transfer_history.get(ticket_id).unwrap_or(TicketTransferHistory {
+            ticket_id,
+            original_owner: from,
+            current_owner: from,
+            transfer_count: 0,
+            first_transfer_time: now,
+            last_transfer_time: now,
+            transfer_reasons: Vec::new(),
+            price_history: Vec::new(),
+        });
+        history.current_owner = to;
+        history.transfer_count += 1;
+        history.last_transfer_time = now;
+        history.transfer_reasons.push(reason);
+        if let Some(price) = price {
+            history.price_history.push((now, price));
+        }
+        storage.ticket_transfer_history.insert(ticket_id, &history);
     }
 
-    /// Resell ticket
+    /// List a ticket on the resale marketplace, returning the listing ID
+    ///
+    /// Listings are priced and settled in DOT. The asking price is checked against
+    /// the event's `AntiScalpingConfig`, and the listing expires after
+    /// `RESALE_LISTING_DURATION` or at the event start.
     pub fn resell_ticket(
         storage: &mut InkTixStorage,
         caller: AccountId,
         ticket_id: u64,
         price: u128,
-        _currency: CurrencyId,
-    ) -> Result<(), String> {
+        currency: CurrencyId,
+    ) -> Result<u64, String> {
+        if currency != CurrencyId::DOT { return Err("Resale listings are settled in DOT".to_string()); }
         let ticket = storage.tickets.get(ticket_id).ok_or("Ticket not found")?;
         if ticket.owner != caller { return Err("Only ticket owner can resell".to_string()); }
         if !ticket.transferable { return Err("Ticket is not transferable".to_string()); }
+        if price == 0 { return Err("Resale price must be greater than 0".to_string()); }
+        if Self::active_listing_for_ticket(storage, ticket_id).is_some() {
+            return Err("Ticket is already listed for resale".to_string());
+        }
+        let event = storage.events.get(ticket.event_id).ok_or("Event not found")?;
+        Self::ensure_marketplace_approved(storage, ticket_id)?;
+        AntiScalping::ensure_allowed(storage, caller, ticket.event_id)?;
 
-        let resale_id = storage.get_next_id("resale");
+        if let Some(config) = storage.anti_scalping_configs.get(ticket.event_id) {
+            if !config.resale_allowed {
+                return Err("Resale is not allowed for this event".to_string());
+            }
+            let max_price = ticket.dot_equivalent_paid * config.max_resale_price_multiplier as u128 / 100;
+            if price > max_price {
+                return Err("Resale price exceeds the allowed cap".to_string());
+            }
+        }
+        Self::ensure_transferable(storage, &ticket, &TransferReason::Resale)?;
+
+        let now = ink::env::block_timestamp::<DefaultEnvironment>();
+        let resale_id = storage.get_next_resale_listing_id();
         let resale = ResaleListing {
-            listing_id: resale_id as u64,
+            listing_id: resale_id,
             ticket_id,
             seller: caller,
             asking_price: price,
-            original_price: ticket.purchase_price,
-            listing_time: ink::env::block_timestamp::<DefaultEnvironment>(),
-            expiry_time: ink::env::block_timestamp::<DefaultEnvironment>() + 86400,
+            original_price: ticket.dot_equivalent_paid,
+            listing_time: now,
+            expiry_time: (now + RESALE_LISTING_DURATION).min(event.date),
             is_active: true,
-            approved: false,
+            approved: true,
         };
-        storage.resale_listings.insert(resale_id as u64, &resale);
+        storage.resale_listings.insert(resale_id, &resale);
+        storage.ticket_resale_listing.insert(ticket_id, &resale_id);
+
+        let mut event_listings = storage.event_resale_listings.get(ticket.event_id).unwrap_or_default();
+        event_listings.push(resale_id);
+        storage.event_resale_listings.insert(ticket.event_id, &event_listings);
+        Ok(resale_id)
+    }
+
+    /// Buy an active resale listing
+    ///
+    /// The resale fee is credited to the event's escrow and the ticket (with its
+    /// NFT) moves to the buyer. Returns the settled listing and the amount owed to
+    /// the seller; any overpayment is left for the caller to refund.
+    pub fn buy_resale_listing(
+        storage: &mut InkTixStorage,
+        buyer: AccountId,
+        listing_id: u64,
+        payment: u128,
+    ) -> Result<(ResaleListing, u128), String> {
+        let listing = storage.resale_listings.get(listing_id).ok_or("Resale listing not found")?;
+        if !Self::is_listing_live(storage, &listing) {
+            return Err("Resale listing is not active".to_string());
+        }
+        if payment < listing.asking_price {
+            return Err(InkTixError::InsufficientPayment.into());
+        }
+        let seller_proceeds = Self::settle_listing(storage, buyer, listing.clone())?;
+        Ok((listing, seller_proceeds))
+    }
+
+    /// Sell a listed ticket to `buyer` at its asking price, returning the amount owed to the seller
+    ///
+    /// Used by live listings and by listings returned to the event's waitlist;
+    /// the seller must still hold the ticket.
+    pub fn settle_listing(
+        storage: &mut InkTixStorage,
+        buyer: AccountId,
+        mut listing: ResaleListing,
+    ) -> Result<u128, String> {
+        if listing.seller == buyer { return Err("Cannot buy your own listing".to_string()); }
+        let ticket = storage.tickets.get(listing.ticket_id).ok_or("Ticket not found")?;
+        if ticket.owner != listing.seller || ticket.refunded {
+            return Err("Seller no longer holds the ticket".to_string());
+        }
+        Self::ensure_transferable(storage, &ticket, &TransferReason::Resale)?;
+        Self::ensure_marketplace_approved(storage, listing.ticket_id)?;
+        AntiScalping::ensure_allowed(storage, listing.seller, ticket.event_id)?;
+        AntiScalping::ensure_allowed(storage, buyer, ticket.event_id)?;
+        super::attestations::Attestations::ensure_attested(storage, buyer, ticket.event_id)?;
+        super::purchase_limits::PurchaseLimits::check_transfer(storage, listing.seller, buyer, &ticket)?;
+        let fee_percentage = storage.anti_scalping_configs.get(ticket.event_id)
+            .map(|c| c.resale_fee_percentage)
+            .unwrap_or(0);
+        let fee = listing.asking_price * fee_percentage.min(100) as u128 / 100;
+
+        listing.is_active = false;
+        storage.resale_listings.insert(listing.listing_id, &listing);
+        if storage.ticket_resale_listing.get(listing.ticket_id) == Some(listing.listing_id) {
+            storage.ticket_resale_listing.remove(listing.ticket_id);
+        }
+
+        Self::reassign_ticket(storage, listing.ticket_id, buyer, TransferReason::Resale, Some(listing.asking_price));
+        super::escrow::Escrow::deposit(storage, ticket.event_id, CurrencyId::DOT, fee);
+
+        Ok(listing.asking_price.saturating_sub(fee))
+    }
+
+    /// Check the contract, acting as the marketplace, may move the ticket's NFT, if one was minted
+    ///
+    /// Sellers grant this with a PSP34 `approve` of the contract account for the token.
+    fn ensure_marketplace_approved(storage: &InkTixStorage, ticket_id: u64) -> Result<(), String> {
+        let Some(token_id) = storage.ticket_to_nft.get(ticket_id) else { return Ok(()) };
+        let marketplace = ink::env::account_id::<DefaultEnvironment>();
+        if !super::psp34::TicketCollection::is_approved(storage, token_id, marketplace) {
+            return Err("Marketplace is not approved to transfer the ticket NFT".to_string());
+        }
         Ok(())
     }
 
+    /// Cancel an active resale listing (seller only), returning the ticket's event ID
+    ///
+    /// While fans wait for the event the listing is returned to its waitlist
+    /// rather than withdrawn outright.
+    pub fn cancel_resale_listing(
+        storage: &mut InkTixStorage,
+        caller: AccountId,
+        listing_id: u64,
+    ) -> Result<u32, String> {
+        let mut listing = storage.resale_listings.get(listing_id).ok_or("Resale listing not found")?;
+        if listing.seller != caller { return Err("Only the seller can cancel a listing".to_string()); }
+        if !listing.is_active { return Err("Resale listing is not active".to_string()); }
+        let event_id = storage.tickets.get(listing.ticket_id).ok_or("Ticket not found")?.event_id;
+
+        listing.is_active = false;
+        storage.resale_listings.insert(listing_id, &listing);
+        storage.ticket_resale_listing.remove(listing.ticket_id);
+        super::waitlist::Waitlists::return_listing(storage, event_id, listing_id);
+        Ok(event_id)
+    }
+
+    /// Get all live resale listings for an event
+    pub fn get_active_listings(storage: &InkTixStorage, event_id: u32) -> Vec<ResaleListing> {
+        storage.event_resale_listings.get(event_id).unwrap_or_default()
+            .iter()
+            .filter_map(|id| storage.resale_listings.get(*id))
+            .filter(|listing| Self::is_listing_live(storage, listing))
+            .collect()
+    }
+
+    /// Claim a refund for a ticket to a cancelled event
+    ///
+    /// Releases the purchase price from the event's escrow and invalidates the
+    /// ticket and its NFT. A season-pass ticket gives a flex pass its game back;
+    /// other passes cover that game only, so the pass owner's refund balance is
+    /// credited the pass's per-game share instead. Returns the refunded ticket;
+    /// paying out its `purchase_price` is left to the caller.
+    pub fn claim_refund(
+        storage: &mut InkTixStorage,
+        caller: AccountId,
+        ticket_id: u64,
+    ) -> Result<Ticket, String> {
+        let mut ticket = storage.tickets.get(ticket_id).ok_or("Ticket not found")?;
+        if ticket.owner != caller { return Err("Only ticket owner can claim a refund".to_string()); }
+        if ticket.refunded { return Err("Ticket already refunded".to_string()); }
+        let event = storage.events.get(ticket.event_id).ok_or("Event not found")?;
+        if event.status != EventStatus::Cancelled {
+            return Err("Event has not been cancelled".to_string());
+        }
+        let deadline = storage.refund_deadlines.get(ticket.event_id).unwrap_or(0);
+        if ink::env::block_timestamp::<DefaultEnvironment>() >= deadline {
+            return Err("Refund window has closed".to_string());
+        }
+
+        super::escrow::Escrow::release(storage, ticket.event_id, ticket.purchase_currency, ticket.purchase_price)?;
+        Self::void_ticket(storage, &mut ticket);
+
+        if ticket.is_season_pass_ticket {
+            if let Some(pass_id) = storage.season_pass_tickets.get(ticket_id) {
+                if let Some(mut pass) = storage.season_passes.get(pass_id) {
+                    pass.games_attended = pass.games_attended.saturating_sub(1);
+                    if matches!(pass.pass_type, SeasonPassType::Flex(_)) {
+                        pass.games_remaining += 1;
+                    } else if let Some(package) = storage.season_pass_packages.get(pass.package_id) {
+                        let share = pass.purchase_price / package.total_games.max(1) as u128;
+                        super::escrow::Escrow::credit_refund(storage, pass.owner, package.currency, share);
+                    }
+                    pass.loyalty_points = pass.loyalty_points.saturating_sub(pass.points_per_game());
+                    storage.season_passes.insert(pass_id, &pass);
+                    storage.season_pass_usage.remove((pass_id, ticket.event_id));
+                }
+            }
+        }
+
+        Ok(ticket)
+    }
+
+    /// Refund a ticket to an event that has not been cancelled, returning its seat to sale
+    ///
+    /// Releases the purchase price from the event's escrow, invalidates the ticket
+    /// and its NFT, and withdraws any resale listing. Returns the refunded ticket;
+    /// crediting its owner is left to the caller.
+    pub fn refund_ticket(storage: &mut InkTixStorage, ticket_id: u64) -> Result<Ticket, String> {
+        let mut ticket = storage.tickets.get(ticket_id).ok_or("Ticket not found")?;
+        if ticket.refunded { return Err("Ticket already refunded".to_string()); }
+        if ticket.is_season_pass_ticket {
+            return Err("Season pass tickets cannot be refunded individually".to_string());
+        }
+        let event = storage.events.get(ticket.event_id).ok_or("Event not found")?;
+        if event.status == EventStatus::Cancelled {
+            return Err("Refunds for a cancelled event are claimed by the ticket owner".to_string());
+        }
+
+        super::escrow::Escrow::release(storage, ticket.event_id, ticket.purchase_currency, ticket.purchase_price)?;
+        Self::void_ticket(storage, &mut ticket);
+        Ok(ticket)
+    }
+
+    /// Invalidate a refunded ticket and its NFT, withdraw its listing, and return its seat to inventory
+    fn void_ticket(storage: &mut InkTixStorage, ticket: &mut Ticket) {
+        ticket.refunded = true;
+        ticket.transferable = false;
+        storage.tickets.insert(ticket.id, ticket);
+
+        if let Some(token_id) = storage.ticket_to_nft.get(ticket.id) {
+            if let Some(mut nft) = storage.nft_tickets.get(token_id) {
+                nft.is_valid = false;
+                storage.nft_tickets.insert(token_id, &nft);
+            }
+        }
+        if let Some(listing_id) = storage.ticket_resale_listing.take(ticket.id) {
+            if let Some(mut listing) = storage.resale_listings.get(listing_id) {
+                listing.is_active = false;
+                storage.resale_listings.insert(listing_id, &listing);
+            }
+        }
+
+        if storage.event_seat_layouts.contains(ticket.event_id) {
+            let key = (ticket.event_id, ticket.section.clone(), ticket.row.clone(), ticket.seat_number);
+            super::seat_inventory::SeatInventory::set_status(storage, &key, SeatStatus::Available);
+        }
+        if let Some(mut event) = storage.events.get(ticket.event_id) {
+            event.sold_tickets = event.sold_tickets.saturating_sub(1);
+            storage.events.insert(ticket.event_id, &event);
+        }
+    }
+
+    /// Return the live listing ID for a ticket, if any
+    fn active_listing_for_ticket(storage: &InkTixStorage, ticket_id: u64) -> Option<u64> {
+        let listing_id = storage.ticket_resale_listing.get(ticket_id)?;
+        let listing = storage.resale_listings.get(listing_id)?;
+        Self::is_listing_live(storage, &listing).then_some(listing_id)
+    }
+
+    /// A listing is live while active, unexpired, still held by its seller and
+    /// its event has not been cancelled
+    fn is_listing_live(storage: &InkTixStorage, listing: &ResaleListing) -> bool {
+        let now = ink::env::block_timestamp::<DefaultEnvironment>();
+        listing.is_active
+            && now < listing.expiry_time
+            && storage.tickets.get(listing.ticket_id).is_some_and(|t| {
+                t.owner == listing.seller
+                    && storage.events.get(t.event_id).is_some_and(|e| e.status != EventStatus::Cancelled)
+            })
+    }
+
     #[allow(clippy::cast_possible_truncation)]
     fn calculate_loyalty_points(price: u128) -> u32 {
         (price / 1_000_000_000_000_000) as u32
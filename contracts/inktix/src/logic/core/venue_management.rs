@@ -7,6 +7,7 @@
 //! - `register_venue` -- creates a venue with default parking, concession, and merchandise info
 //! - `get_all_venues` -- returns all registered venues
 //! - `update_venue_capacity` -- adjusts venue capacity and recalculates availability
+//! - `add_pricing_tier` -- adds a seat pricing tier that seat layouts can reference
 //! - `purchase_parking_pass` -- issues a parking pass for a venue
 //! - `purchase_concession_credits` -- issues concession credits for a venue
 //! - `purchase_merchandise_bundle` -- purchases a merchandise bundle from a venue
@@ -112,6 +113,25 @@ impl VenueManagement {
         Ok(())
     }
 
+    /// Add a seat pricing tier to a venue, returning its tier id
+    pub fn add_pricing_tier(
+        storage: &mut InkTixStorage,
+        venue_id: u32,
+        mut tier: VenuePricingTier,
+    ) -> Result<u32, String> {
+        let mut v = storage.venues.get(venue_id).ok_or("Venue not found")?;
+        if tier.name.is_empty() { return Err("Pricing tier name cannot be empty".to_string()); }
+        if tier.base_price_multiplier == 0 {
+            return Err("Pricing tier multiplier must be greater than 0".to_string());
+        }
+        let tier_id = v.pricing_tiers.iter().map(|t| t.id).max().unwrap_or(0) + 1;
+        tier.id = tier_id;
+        v.pricing_tiers.push(tier);
+        v.updated_at = Self::get_current_timestamp();
+        storage.venues.insert(venue_id, &v);
+        Ok(tier_id)
+    }
+
     /// Purchase parking pass
     pub fn purchase_parking_pass(
         storage: &mut InkTixStorage,
@@ -0,0 +1,324 @@
+//! Waitlists for sold-out events.
+//!
+//! Fans queue for a sold-out event with a quantity, the highest price they will
+//! pay per ticket and an optional deposit held in escrow. Whenever inventory is
+//! released, through refunds or lapsed or released seat holds, it is offered to
+//! the queue in FIFO order. An offer reserves the tickets for
+//! `WAITLIST_CLAIM_WINDOW`; if it is not claimed in time the fan leaves the queue
+//! with their deposit credited to their refund balance and the tickets move on
+//! to the next fan. Reserved tickets cannot be bought or held by anyone else.
+//! A waitlist holds at most `MAX_WAITLIST_ENTRIES` fans.
+//!
+//! A resale listing cancelled, or lapsed before the event, while fans are
+//! waiting is returned to the queue: it is offered, one fan at a time, to the
+//! first fan without an offer whose maximum price covers the asking price, and
+//! the fan buys it through the resale path. The listing is dropped, leaving
+//! the seller with the ticket, once no fan in the queue will pay its price.
+//!
+//! # Functions
+//! - `join` -- queues a fan for a sold-out event
+//! - `leave` -- removes a fan from the queue, returning their deposit
+//! - `process` -- expires lapsed offers and offers free inventory in FIFO order
+//! - `claim` -- buys offered tickets at the current dynamic price, using the deposit
+//! - `claim_listing` -- buys an offered resale listing at its asking price, using the deposit
+//! - `return_listing` -- returns a withdrawn resale listing to the queue
+//! - `ensure_unreserved` -- rejects purchases and holds that would take offered tickets
+//! - `free_capacity` -- tickets of an event that are neither sold, held nor reserved
+
+use crate::storage::*;
+use crate::types::*;
+use ink::env::DefaultEnvironment;
+use ink::prelude::string::String;
+use ink::prelude::string::ToString;
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+use super::seat_inventory::SeatInventory;
+use super::ticket_management::TicketManagement;
+
+/// How long a waitlist offer can be claimed, in milliseconds
+pub const WAITLIST_CLAIM_WINDOW: u64 = 30 * 60 * 1000;
+/// Most tickets a fan can queue for
+pub const MAX_WAITLIST_QUANTITY: u32 = 4;
+/// Most fans an event's waitlist can hold
+pub const MAX_WAITLIST_ENTRIES: usize = 100;
+
+/// Waitlist management
+pub struct Waitlists;
+
+#[allow(clippy::arithmetic_side_effects)]
+#[allow(clippy::cast_possible_truncation)]
+impl Waitlists {
+    /// Queue a fan for a sold-out event, holding `deposit` towards the tickets
+    pub fn join(
+        storage: &mut InkTixStorage,
+        account: AccountId,
+        event_id: u32,
+        quantity: u32,
+        max_price: u128,
+        deposit: u128,
+    ) -> Result<(), String> {
+        let event = storage.events.get(event_id).ok_or("Event not found")?;
+        if !event.active { return Err("Event is not active".to_string()); }
+        SeatInventory::release_lapsed(storage, event_id);
+        if quantity == 0 || quantity > MAX_WAITLIST_QUANTITY {
+            return Err("Invalid waitlist quantity".to_string());
+        }
+        if max_price == 0 { return Err("Maximum price must be greater than 0".to_string()); }
+        if deposit > max_price.saturating_mul(quantity as u128) {
+            return Err("Deposit exceeds the maximum total".to_string());
+        }
+        let mut entries = storage.event_waitlists.get(event_id).unwrap_or_default();
+        if entries.iter().any(|entry| entry.account == account) {
+            return Err("Already on the waitlist".to_string());
+        }
+        if entries.len() >= MAX_WAITLIST_ENTRIES {
+            return Err("Waitlist is full".to_string());
+        }
+        if Self::free_capacity(storage, &event) > Self::reserved(storage, event_id) {
+            return Err("Event is not sold out".to_string());
+        }
+        entries.push(WaitlistEntry {
+            account,
+            quantity,
+            max_price,
+            deposit,
+            joined_at: ink::env::block_timestamp::<DefaultEnvironment>(),
+            offer: None,
+        });
+        storage.event_waitlists.insert(event_id, &entries);
+        Ok(())
+    }
+
+    /// Remove a fan from an event's waitlist, returning their deposit to refund
+    pub fn leave(storage: &mut InkTixStorage, account: AccountId, event_id: u32) -> Result<u128, String> {
+        let mut entries = storage.event_waitlists.get(event_id).unwrap_or_default();
+        let position = entries.iter().position(|entry| entry.account == account).ok_or("Not on the waitlist")?;
+        let entry = entries.remove(position);
+        storage.event_waitlists.insert(event_id, &entries);
+        Ok(entry.deposit)
+    }
+
+    /// Drop fans whose offers lapsed and offer returned listings and free inventory down the queue
+    ///
+    /// Seats of lapsed holds and group orders are released, and listings that
+    /// lapsed unsold are returned, first. Dropped fans' deposits are credited to
+    /// their refund balances. Returns the fans newly made an offer.
+    pub fn process(storage: &mut InkTixStorage, event_id: u32) -> Result<Vec<AccountId>, String> {
+        let event = storage.events.get(event_id).ok_or("Event not found")?;
+        SeatInventory::release_lapsed(storage, event_id);
+        let now = ink::env::block_timestamp::<DefaultEnvironment>();
+        let mut entries = storage.event_waitlists.get(event_id).unwrap_or_default();
+        let mut refunds = Vec::new();
+        entries.retain(|entry| {
+            let lapsed = entry.offer.is_some_and(|offer| now >= offer.expires_at);
+            if lapsed {
+                refunds.push((entry.account, entry.deposit));
+            }
+            !lapsed
+        });
+        for (account, deposit) in refunds {
+            super::escrow::Escrow::credit_refund(storage, account, CurrencyId::DOT, deposit);
+        }
+
+        let mut offered = Vec::new();
+        if event.active && !entries.is_empty() {
+            Self::return_lapsed_listings(storage, &event, now);
+            let mut returned = storage.event_returned_listings.get(event_id).unwrap_or_default();
+            returned.retain(|&listing_id| {
+                let Some(listing) = Self::returned_listing(storage, listing_id) else { return false };
+                if entries.iter().any(|entry| entry.offer.is_some_and(|offer| offer.listing_id == Some(listing_id))) {
+                    return true;
+                }
+                let next = entries.iter_mut()
+                    .find(|entry| entry.offer.is_none() && entry.max_price >= listing.asking_price && entry.account != listing.seller);
+                let Some(entry) = next else { return false };
+                entry.offer = Some(WaitlistOffer { quantity: 1, expires_at: now + WAITLIST_CLAIM_WINDOW, listing_id: Some(listing_id) });
+                offered.push(entry.account);
+                true
+            });
+            storage.event_returned_listings.insert(event_id, &returned);
+        }
+
+        let reserved: u32 = entries.iter().filter_map(|entry| entry.offer)
+            .filter(|offer| offer.listing_id.is_none())
+            .map(|offer| offer.quantity)
+            .sum();
+        let mut free = if event.active { Self::free_capacity(storage, &event).saturating_sub(reserved) } else { 0 };
+        for entry in entries.iter_mut().filter(|entry| entry.offer.is_none()) {
+            if free == 0 { break; }
+            let quantity = entry.quantity.min(free);
+            entry.offer = Some(WaitlistOffer { quantity, expires_at: now + WAITLIST_CLAIM_WINDOW, listing_id: None });
+            free -= quantity;
+            offered.push(entry.account);
+        }
+        storage.event_waitlists.insert(event_id, &entries);
+        Ok(offered)
+    }
+
+    /// Return a withdrawn resale listing to the event's waitlist, if any fans are waiting
+    pub fn return_listing(storage: &mut InkTixStorage, event_id: u32, listing_id: u64) {
+        if storage.event_waitlists.get(event_id).unwrap_or_default().is_empty() {
+            return;
+        }
+        let mut returned = storage.event_returned_listings.get(event_id).unwrap_or_default();
+        returned.push(listing_id);
+        storage.event_returned_listings.insert(event_id, &returned);
+    }
+
+    /// Buy the returned resale listing offered to the fan at its asking price in DOT
+    ///
+    /// The fan's deposit and native `payment` together must cover the price. The
+    /// fan leaves the queue. Returns the listing, the amount owed to the seller
+    /// and the excess to refund.
+    pub fn claim_listing(
+        storage: &mut InkTixStorage,
+        buyer: AccountId,
+        event_id: u32,
+        payment: u128,
+    ) -> Result<(ResaleListing, u128, u128), String> {
+        let mut entries = storage.event_waitlists.get(event_id).unwrap_or_default();
+        let position = entries.iter().position(|entry| entry.account == buyer).ok_or("Not on the waitlist")?;
+        let now = ink::env::block_timestamp::<DefaultEnvironment>();
+        let listing_id = entries[position].offer
+            .filter(|offer| now < offer.expires_at)
+            .and_then(|offer| offer.listing_id)
+            .ok_or("No live resale listing offer")?;
+        let event = storage.events.get(event_id).ok_or("Event not found")?;
+        if !event.active { return Err("Event is not active".to_string()); }
+        let listing = Self::returned_listing(storage, listing_id).ok_or("Seller no longer holds the ticket")?;
+        let funds = payment + entries[position].deposit;
+        if funds < listing.asking_price {
+            return Err(InkTixError::InsufficientPayment.into());
+        }
+
+        let seller_proceeds = TicketManagement::settle_listing(storage, buyer, listing.clone())?;
+        entries.remove(position);
+        storage.event_waitlists.insert(event_id, &entries);
+        let mut returned = storage.event_returned_listings.get(event_id).unwrap_or_default();
+        returned.retain(|&id| id != listing_id);
+        storage.event_returned_listings.insert(event_id, &returned);
+        Ok((listing.clone(), seller_proceeds, funds - listing.asking_price))
+    }
+
+    /// Buy offered tickets for the given seats at their current dynamic prices in DOT
+    ///
+    /// The fan's deposit and native `payment` together must cover the total. The
+    /// fan leaves the queue; any unclaimed part of the offer is released. Returns
+    /// the ticket IDs, the total charged and the excess to refund.
+    pub fn claim(
+        storage: &mut InkTixStorage,
+        buyer: AccountId,
+        event_id: u32,
+        seats: Vec<Seat>,
+        payment: u128,
+    ) -> Result<(Vec<u64>, u128, u128), String> {
+        let mut entries = storage.event_waitlists.get(event_id).unwrap_or_default();
+        let position = entries.iter().position(|entry| entry.account == buyer).ok_or("Not on the waitlist")?;
+        let entry = entries[position].clone();
+        let now = ink::env::block_timestamp::<DefaultEnvironment>();
+        let offer = entry.offer.filter(|offer| now < offer.expires_at).ok_or("No live waitlist offer")?;
+        if offer.listing_id.is_some() { return Err("Offer is for a resale listing".to_string()); }
+        let event = storage.events.get(event_id).ok_or("Event not found")?;
+        if !event.active { return Err("Event is not active".to_string()); }
+        let quantity = seats.len() as u32;
+        if quantity == 0 || quantity > offer.quantity {
+            return Err("Invalid number of seats for the offer".to_string());
+        }
+        if event.sold_tickets + quantity > event.capacity {
+            return Err(InkTixError::SoldOut.into());
+        }
+        TicketManagement::check_purchase_limit(storage, buyer, &event, quantity)?;
+        let sale_window = super::sale_windows::SaleWindows::check(storage, buyer, &event, quantity)?;
+
+        let mut priced = Vec::new();
+        let mut keys: Vec<SeatKey> = Vec::new();
+        for seat in seats {
+            let (seat, seat_number, key) = SeatInventory::apply_layout(storage, event_id, seat)?;
+            if let Some(key) = key {
+                if keys.contains(&key) { return Err("Seat requested more than once".to_string()); }
+                SeatInventory::ensure_available(storage, &key)?;
+                keys.push(key);
+            }
+            let (price, performance_multiplier) = super::pricing::DynamicPricing::calculate_price(
+                storage, event_id, &seat, false
+            )?;
+            if price > entry.max_price {
+                return Err("Price exceeds the waitlist maximum price".to_string());
+            }
+            priced.push(HeldSeat { seat, seat_number, price, performance_multiplier });
+        }
+        let total: u128 = priced.iter().map(|seat| seat.price).sum();
+        let funds = payment + entry.deposit;
+        if funds < total {
+            return Err(InkTixError::InsufficientPayment.into());
+        }
+
+        entries.remove(position);
+        storage.event_waitlists.insert(event_id, &entries);
+        let ticket_ids = priced.iter()
+            .map(|seat| TicketManagement::issue_ticket(storage, buyer, event_id, seat, CurrencyId::DOT, seat.price))
+            .collect();
+        if let Some(window_index) = sale_window {
+            super::sale_windows::SaleWindows::record(storage, event_id, window_index, buyer, quantity);
+        }
+        Ok((ticket_ids, total, funds - total))
+    }
+
+    /// Reject a purchase or hold of `quantity` tickets that would take tickets offered to the waitlist
+    pub fn ensure_unreserved(storage: &InkTixStorage, event: &Event, quantity: u32) -> Result<(), String> {
+        let reserved = Self::reserved(storage, event.id);
+        if reserved > 0 && Self::free_capacity(storage, event) < reserved + quantity {
+            return Err("Remaining tickets are offered to the waitlist".to_string());
+        }
+        Ok(())
+    }
+
+    /// Tickets of an event that are neither sold nor, for seated events, held or reserved
+    ///
+    /// Seated events use the inventory's running count of available seats rather
+    /// than walking the layout, so seats reserved for season passes are deducted
+    /// and seats of lapsed holds count once `release_lapsed` has returned them.
+    pub fn free_capacity(storage: &InkTixStorage, event: &Event) -> u32 {
+        let unsold = event.capacity.saturating_sub(event.sold_tickets);
+        if !storage.event_seat_layouts.contains(event.id) {
+            return unsold;
+        }
+        unsold.min(SeatInventory::available_seats(storage, event.id))
+    }
+
+    /// Tickets reserved by live waitlist offers
+    fn reserved(storage: &InkTixStorage, event_id: u32) -> u32 {
+        let now = ink::env::block_timestamp::<DefaultEnvironment>();
+        storage.event_waitlists.get(event_id).unwrap_or_default()
+            .iter()
+            .filter_map(|entry| entry.offer)
+            .filter(|offer| now < offer.expires_at && offer.listing_id.is_none())
+            .map(|offer| offer.quantity)
+            .sum()
+    }
+
+    /// Return an event's listings that lapsed unsold before the event, deactivating them
+    fn return_lapsed_listings(storage: &mut InkTixStorage, event: &Event, now: u64) {
+        for listing_id in storage.event_resale_listings.get(event.id).unwrap_or_default() {
+            let Some(mut listing) = storage.resale_listings.get(listing_id) else { continue };
+            if !listing.is_active || now < listing.expiry_time || listing.expiry_time >= event.date {
+                continue;
+            }
+            listing.is_active = false;
+            storage.resale_listings.insert(listing_id, &listing);
+            if storage.ticket_resale_listing.get(listing.ticket_id) == Some(listing_id) {
+                storage.ticket_resale_listing.remove(listing.ticket_id);
+            }
+            Self::return_listing(storage, event.id, listing_id);
+        }
+    }
+
+    /// A returned listing whose seller still holds the ticket
+    fn returned_listing(storage: &InkTixStorage, listing_id: u64) -> Option<ResaleListing> {
+        let listing = storage.resale_listings.get(listing_id)?;
+        storage.tickets.get(listing.ticket_id)
+            .is_some_and(|ticket| ticket.owner == listing.seller && !ticket.refunded)
+            .then_some(listing)
+    }
+}
@@ -2,16 +2,24 @@
 //!
 //! Provides per-team loyalty profiles, token staking on teams, and
 //! attendance recording with automatic tier promotion based on engagement.
+//! Attendance is recorded by the event's gate scanners, once per fan, team and
+//! game, for ticket holders at a game the team plays; checking a ticket in
+//! records it for each playing team the holder has a profile with.
+//! Attendance points are boosted by the team's active `TeamPerformanceReward`s,
+//! which the game result oracle grants for notable wins.
 //!
 //! # Functions
 //! - `create_team_loyalty_profile` -- initializes a loyalty profile for a user-team pair
 //! - `stake_on_team` -- stakes tokens on a team for loyalty rewards
 //! - `record_attendance` -- records event attendance and awards loyalty points
+//! - `record_check_in` -- records attendance for a checked-in holder's teams
+//! - `points_multiplier` -- best active performance reward multiplier for a team
 
 use crate::storage::*;
 use crate::types::*;
 use ink::primitives::AccountId;
 use ink::prelude::string::String;
+use ink::prelude::string::ToString;
 use ink::prelude::vec;
 
 /// Advanced team loyalty functionality
@@ -55,21 +63,66 @@ impl AdvancedTeamLoyalty {
         Ok(stake_id)
     }
 
-    /// Record a user's attendance at a team event and update loyalty tier
-    pub fn record_attendance(storage: &mut InkTixStorage, user: AccountId, team_id: u32, event_id: u32) -> Result<u32, String> {
+    /// Record a ticket holder's attendance at a team's game and update their loyalty tier
+    ///
+    /// Only the event's gate scanners can record attendance.
+    pub fn record_attendance(
+        storage: &mut InkTixStorage, scanner: AccountId, user: AccountId, team_id: u32, event_id: u32,
+    ) -> Result<u32, String> {
         let _team = storage.teams.get(team_id).ok_or("Team not found")?;
-        let _event = storage.events.get(event_id).ok_or("Event not found")?;
+        let event = storage.events.get(event_id).ok_or("Event not found")?;
+        crate::logic::core::access_control::AccessControl::ensure_role(
+            storage, scanner, Role::GateScanner, RoleScope::Event(event_id),
+        )?;
+        let EventCategory::Sports { home_team_id, away_team_id, .. } = event.category else {
+            return Err("Team is not playing this event".to_string());
+        };
+        if team_id != home_team_id && team_id != away_team_id {
+            return Err("Team is not playing this event".to_string());
+        }
+        let holds_ticket = storage.user_tickets.get(user).unwrap_or_default().into_iter()
+            .filter_map(|ticket_id| storage.tickets.get(ticket_id))
+            .any(|ticket| ticket.event_id == event_id && !ticket.refunded);
+        if !holds_ticket { return Err("No ticket for this event".to_string()); }
+        if storage.recorded_team_attendance.contains((user, team_id, event_id)) {
+            return Err("Attendance already recorded".to_string());
+        }
+        Ok(Self::credit_attendance(storage, user, team_id, event_id))
+    }
+
+    /// Record attendance for a checked-in holder at a sports event
+    ///
+    /// Credits each playing team the holder has a loyalty profile with, skipping
+    /// teams whose attendance is already recorded.
+    pub fn record_check_in(storage: &mut InkTixStorage, user: AccountId, event_id: u32) {
+        let Some(EventCategory::Sports { home_team_id, away_team_id, .. }) = storage.events.get(event_id).map(|e| e.category) else {
+            return;
+        };
+        for team_id in [home_team_id, away_team_id] {
+            if storage.team_loyalty_profiles.contains((user, team_id))
+                && !storage.recorded_team_attendance.contains((user, team_id, event_id))
+            {
+                Self::credit_attendance(storage, user, team_id, event_id);
+            }
+        }
+    }
+
+    /// Store an attendance record and award its points, returning the attendance ID
+    fn credit_attendance(storage: &mut InkTixStorage, user: AccountId, team_id: u32, event_id: u32) -> u32 {
+        let now = ink::env::block_timestamp::<ink::env::DefaultEnvironment>();
+        let points_earned = 100 * Self::points_multiplier(storage, team_id, now) / 10000;
         let attendance_id = storage.get_next_id("attendance");
         let attendance = TeamAttendance {
             user_id: user, team_id, event_id,
-            attendance_date: ink::env::block_timestamp::<ink::env::DefaultEnvironment>(),
-            points_earned: 100, streak_bonus: 0, total_streak: 0,
+            attendance_date: now,
+            points_earned, streak_bonus: 0, total_streak: 0,
         };
         storage.team_attendance.insert(attendance_id, &attendance);
+        storage.recorded_team_attendance.insert((user, team_id, event_id), &attendance_id);
         if let Some(mut profile) = storage.team_loyalty_profiles.get((user, team_id)) {
             profile.total_events_attended += 1;
             profile.attendance_streak += 1;
-            profile.loyalty_points += 100;
+            profile.loyalty_points += points_earned;
             profile.last_attendance = attendance.attendance_date;
             profile.loyalty_tier = if profile.loyalty_points >= 10000 { TeamLoyaltyTier::LegendaryFan }
                 else if profile.loyalty_points >= 2000 { TeamLoyaltyTier::UltraFan }
@@ -78,6 +131,16 @@ impl AdvancedTeamLoyalty {
                 else { TeamLoyaltyTier::Rookie };
             storage.team_loyalty_profiles.insert((user, team_id), &profile);
         }
-        Ok(attendance_id)
+        attendance_id
+    }
+
+    /// Best points multiplier among a team's active performance rewards, in basis points
+    pub fn points_multiplier(storage: &InkTixStorage, team_id: u32, now: u64) -> u32 {
+        storage.team_performance_reward_ids.get(team_id).unwrap_or_default()
+            .into_iter()
+            .filter_map(|id| storage.team_performance_rewards.get(id))
+            .filter(|reward| reward.is_active && reward.start_date <= now && now < reward.end_date)
+            .map(|reward| reward.points_multiplier)
+            .fold(10000, u32::max)
     }
 }
@@ -1,12 +1,18 @@
 //! Fantasy sports league and team management.
 //!
 //! Handles creation of fantasy leagues, joining leagues, and creating fantasy teams
-//! as part of the ticket-holder engagement system.
+//! as part of the ticket-holder engagement system. Fantasy players belong to real
+//! teams and score from that team's finalized game results: 3 points for a win,
+//! 1 for a tie, plus one point per 10 points the team scored.
 //!
 //! # Functions
 //! - `create_fantasy_league` -- creates a new fantasy league with entry fee and prize pool
 //! - `join_fantasy_league` -- enrolls a user in an existing league
 //! - `create_fantasy_team` -- creates a named fantasy team for a user in a league
+//! - `register_player` -- registers a fantasy player on a real team
+//! - `add_player_to_team` -- adds a player to a fantasy team's roster
+//! - `score_result` -- scores the players of both teams in a finalized game result
+//! - `refresh_leaderboard` -- totals roster points and re-ranks a league's fantasy teams
 
 use crate::storage::*;
 use crate::types::*;
@@ -61,4 +67,83 @@ impl FantasySportsManagement {
         storage.user_fantasy_teams.insert(&user, &user_teams);
         Ok(team_id)
     }
+
+    /// Register a fantasy player on a real team
+    pub fn register_player(storage: &mut InkTixStorage, name: String, position: PlayerPosition, team_id: u32) -> Result<u32, String> {
+        if !storage.teams.contains(team_id) { return Err("Team not found".to_string()); }
+        let player_id = storage.get_next_id("fantasy_player");
+        storage.fantasy_players.insert(player_id, &PlayerStats {
+            player_id, name, position, team_id, points: 0, games_played: 0, touchdowns: 0, yards: 0,
+            completion_percentage: None, field_goal_percentage: None,
+            last_updated: ink::env::block_timestamp::<ink::env::DefaultEnvironment>(),
+        });
+        let mut players = storage.team_fantasy_players.get(team_id).unwrap_or_default();
+        players.push(player_id);
+        storage.team_fantasy_players.insert(team_id, &players);
+        Ok(player_id)
+    }
+
+    /// Add a player to the roster of a fantasy team owned by `user`
+    pub fn add_player_to_team(storage: &mut InkTixStorage, user: AccountId, fantasy_team_id: u32, player_id: u32) -> Result<(), String> {
+        let mut team = storage.fantasy_teams.get(fantasy_team_id).ok_or("Fantasy team not found")?;
+        if team.owner != user { return Err("Not the fantasy team owner".to_string()); }
+        if !storage.fantasy_players.contains(player_id) { return Err("Player not found".to_string()); }
+        if team.players.contains(&player_id) { return Err("Player already on roster".to_string()); }
+        team.players.push(player_id);
+        team.last_updated = ink::env::block_timestamp::<ink::env::DefaultEnvironment>();
+        storage.fantasy_teams.insert(fantasy_team_id, &team);
+        Ok(())
+    }
+
+    /// Score the players of both teams in a finalized game result
+    pub fn score_result(storage: &mut InkTixStorage, result: &GameResult) {
+        let sides = [
+            (result.home_team_id, result.home_score, result.away_score),
+            (result.away_team_id, result.away_score, result.home_score),
+        ];
+        for (team_id, scored, allowed) in sides {
+            let outcome_points = if scored > allowed { 3 } else if scored == allowed { 1 } else { 0 };
+            let game_points = outcome_points + scored / 10;
+            for player_id in storage.team_fantasy_players.get(team_id).unwrap_or_default() {
+                if let Some(mut player) = storage.fantasy_players.get(player_id) {
+                    player.points += game_points;
+                    player.games_played += 1;
+                    player.last_updated = result.finalized_at;
+                    storage.fantasy_players.insert(player_id, &player);
+                }
+            }
+        }
+    }
+
+    /// Total each fantasy team's roster points and re-rank the league's leaderboard
+    pub fn refresh_leaderboard(storage: &mut InkTixStorage, league_id: u32) -> Result<FantasyLeaderboard, String> {
+        let league = storage.fantasy_leagues.get(league_id).ok_or("League not found")?;
+        let now = ink::env::block_timestamp::<ink::env::DefaultEnvironment>();
+        let mut teams: Vec<FantasyTeam> = (1..=storage.total_fantasy_teams)
+            .filter_map(|team_id| storage.fantasy_teams.get(team_id))
+            .filter(|team| team.league_id == league_id)
+            .collect();
+        let mut games_played = Vec::new();
+        for team in teams.iter_mut() {
+            let players: Vec<PlayerStats> = team.players.iter().filter_map(|id| storage.fantasy_players.get(id)).collect();
+            team.total_points = players.iter().map(|player| player.points).sum();
+            games_played.push((team.team_id, players.iter().map(|player| player.games_played).max().unwrap_or(0)));
+        }
+        teams.sort_by_key(|team| ::core::cmp::Reverse(team.total_points));
+
+        let mut entries = Vec::new();
+        for (index, mut team) in teams.into_iter().enumerate() {
+            team.rank = index as u32 + 1;
+            team.last_updated = now;
+            entries.push(FantasyLeaderboardEntry {
+                user_id: team.owner, team_name: team.name.clone(), total_points: team.total_points, rank: team.rank,
+                games_played: games_played.iter().find(|(id, _)| *id == team.team_id).map(|(_, games)| *games).unwrap_or(0),
+                win_streak: 0, bonus_points: 0, loyalty_multiplier: 10000,
+            });
+            storage.fantasy_teams.insert(team.team_id, &team);
+        }
+        let leaderboard = FantasyLeaderboard { league_id, season_id: league.season_id, entries, last_updated: now };
+        storage.fantasy_leaderboards.insert(league_id, &leaderboard);
+        Ok(leaderboard)
+    }
 }
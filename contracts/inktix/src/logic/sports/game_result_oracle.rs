@@ -0,0 +1,214 @@
+//! Game result oracle and the team records derived from it.
+//!
+//! Accounts holding the `ResultReporter` role (globally, or for the event's
+//! venue or the event itself) report the final score of a sports event once it
+//! has been played, while it is active, in progress or completed. Reports that
+//! disagree are kept side by side, and reporting again replaces the reporter's
+//! pending report. Pending reports from accounts that no longer hold the role
+//! for the event are dropped before counting, so the first score reported by
+//! `result_quorum` current reporters becomes the event's `GameResult`, and
+//! later reports for it are refused.
+//!
+//! A finalized result updates both teams' `TeamPerformance` for the event's
+//! season, re-ranks the season's teams, grants the winner any
+//! `TeamPerformanceReward` the game earns, and scores the fantasy players of
+//! both teams. Dynamic pricing, team loyalty and fantasy scoring all read
+//! from these records.
+//!
+//! # Functions
+//! - `report_result` -- records a reporter's final score and finalizes it once a quorum agrees
+//! - `set_quorum` -- sets how many matching reports finalize a result
+
+use crate::storage::*;
+use crate::types::*;
+use ink::env::DefaultEnvironment;
+use ink::prelude::string::String;
+use ink::prelude::string::ToString;
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+use super::fantasy_sports_management::FantasySportsManagement;
+use crate::logic::core::access_control::AccessControl;
+use crate::logic::core::pricing_policy::PricingPolicies;
+
+/// Reward granted for winning a championship game: 2.0x loyalty points for 30 days
+const CHAMPIONSHIP_REWARD: (u32, u64) = (20000, 30 * 86_400_000);
+/// Reward granted for winning a rivalry game: 1.5x loyalty points for 7 days
+const RIVALRY_REWARD: (u32, u64) = (15000, 7 * 86_400_000);
+/// Reward granted for a win that ends a losing streak of 3 or more: 1.25x for 7 days
+const COMEBACK_REWARD: (u32, u64) = (12500, 7 * 86_400_000);
+
+/// Game result oracle
+pub struct GameResultOracle;
+
+#[allow(clippy::arithmetic_side_effects)]
+#[allow(clippy::cast_possible_truncation)]
+impl GameResultOracle {
+    /// Record a reporter's final score, returning the result if this report finalized it
+    pub fn report_result(
+        storage: &mut InkTixStorage,
+        reporter: AccountId,
+        event_id: u32,
+        home_score: u32,
+        away_score: u32,
+    ) -> Result<Option<GameResult>, String> {
+        let event = storage.events.get(event_id).ok_or("Event not found")?;
+        let EventCategory::Sports { home_team_id, away_team_id, season_id, game_type, .. } = event.category else {
+            return Err("Event is not a sports event".to_string());
+        };
+        let reportable = event.active || matches!(event.status, EventStatus::InProgress | EventStatus::Completed);
+        if !reportable {
+            return Err("Event is not active or completed".to_string());
+        }
+        let now = ink::env::block_timestamp::<DefaultEnvironment>();
+        if now < event.date {
+            return Err("Game has not been played yet".to_string());
+        }
+        if storage.game_results.contains(event_id) {
+            return Err("Game result already finalized".to_string());
+        }
+        let mut reports = storage.game_result_reports.get(event_id).unwrap_or_default();
+        reports.retain(|report| {
+            report.reporter != reporter
+                && AccessControl::has_role(storage, report.reporter, Role::ResultReporter, RoleScope::Event(event_id))
+        });
+        reports.push(GameResultReport { reporter, home_score, away_score, reported_at: now });
+        let confirmations = reports.iter()
+            .filter(|report| report.home_score == home_score && report.away_score == away_score)
+            .count() as u32;
+        if confirmations < storage.result_quorum {
+            storage.game_result_reports.insert(event_id, &reports);
+            return Ok(None);
+        }
+
+        storage.game_result_reports.remove(event_id);
+        let result = GameResult {
+            event_id, season_id, home_team_id, away_team_id, home_score, away_score,
+            confirmations, finalized_at: now,
+        };
+        storage.game_results.insert(event_id, &result);
+
+        let home_streak = Self::record(storage, home_team_id, season_id, home_score, away_score, true, now);
+        let away_streak = Self::record(storage, away_team_id, season_id, away_score, home_score, false, now);
+        Self::rank_season(storage, season_id);
+        if home_score != away_score {
+            let (winner, previous_streak) = if home_score > away_score {
+                (home_team_id, home_streak)
+            } else {
+                (away_team_id, away_streak)
+            };
+            let policy = PricingPolicies::effective(storage, &event);
+            let rivalry_multiplier = PricingPolicies::rivalry_multiplier(&policy, home_team_id, away_team_id);
+            Self::grant_rewards(storage, winner, game_type, rivalry_multiplier, previous_streak, now);
+        }
+        FantasySportsManagement::score_result(storage, &result);
+        Ok(Some(result))
+    }
+
+    /// Set how many matching reports finalize a result
+    pub fn set_quorum(storage: &mut InkTixStorage, quorum: u32) -> Result<(), String> {
+        if quorum == 0 {
+            return Err("Result quorum must be greater than 0".to_string());
+        }
+        storage.result_quorum = quorum;
+        Ok(())
+    }
+
+    /// Add one game to a team's season record, returning its streak before the game
+    fn record(
+        storage: &mut InkTixStorage,
+        team_id: u32,
+        season_id: u32,
+        scored: u32,
+        allowed: u32,
+        is_home: bool,
+        now: u64,
+    ) -> i32 {
+        let mut perf = storage.team_performance.get((team_id, season_id)).unwrap_or_else(|| {
+            let mut teams = storage.season_teams.get(season_id).unwrap_or_default();
+            teams.push(team_id);
+            storage.season_teams.insert(season_id, &teams);
+            TeamPerformance {
+                team_id, season_id, wins: 0, losses: 0, ties: 0, win_percentage: 0, streak: 0,
+                playoff_probability: 0, last_updated: now, performance_rank: 0,
+                home_record_wins: 0, home_record_losses: 0, points_scored_total: 0,
+                points_allowed_total: 0, points_scored_avg: 0, points_allowed_avg: 0,
+            }
+        });
+        let previous_streak = perf.streak;
+
+        if scored > allowed {
+            perf.wins += 1;
+            perf.streak = if perf.streak > 0 { perf.streak + 1 } else { 1 };
+            if is_home { perf.home_record_wins += 1; }
+        } else if scored < allowed {
+            perf.losses += 1;
+            perf.streak = if perf.streak < 0 { perf.streak - 1 } else { -1 };
+            if is_home { perf.home_record_losses += 1; }
+        } else {
+            perf.ties += 1;
+            perf.streak = 0;
+        }
+
+        let games = perf.wins + perf.losses + perf.ties;
+        perf.win_percentage = (perf.wins * 1000 + perf.ties * 500) / games;
+        perf.playoff_probability = perf.win_percentage / 10;
+        perf.points_scored_total = perf.points_scored_total.saturating_add(scored);
+        perf.points_allowed_total = perf.points_allowed_total.saturating_add(allowed);
+        perf.points_scored_avg = perf.points_scored_total / games;
+        perf.points_allowed_avg = perf.points_allowed_total / games;
+        perf.last_updated = now;
+        storage.team_performance.insert((team_id, season_id), &perf);
+        previous_streak
+    }
+
+    /// Rank a season's teams by win percentage, then by wins
+    fn rank_season(storage: &mut InkTixStorage, season_id: u32) {
+        let mut records: Vec<TeamPerformance> = storage.season_teams.get(season_id).unwrap_or_default()
+            .into_iter()
+            .filter_map(|team_id| storage.team_performance.get((team_id, season_id)))
+            .collect();
+        records.sort_by(|a, b| b.win_percentage.cmp(&a.win_percentage).then(b.wins.cmp(&a.wins)));
+        for (index, mut record) in records.into_iter().enumerate() {
+            record.performance_rank = index as u32 + 1;
+            storage.team_performance.insert((record.team_id, season_id), &record);
+        }
+    }
+
+    /// Grant the winner the loyalty rewards the game earns
+    fn grant_rewards(
+        storage: &mut InkTixStorage,
+        team_id: u32,
+        game_type: GameType,
+        rivalry_multiplier: u32,
+        previous_streak: i32,
+        now: u64,
+    ) {
+        if game_type == GameType::Championship {
+            Self::grant_reward(storage, team_id, TeamPerformanceRewardType::ChampionshipWin, CHAMPIONSHIP_REWARD, now);
+        }
+        if rivalry_multiplier > 10000 {
+            Self::grant_reward(storage, team_id, TeamPerformanceRewardType::RivalryGameVictory, RIVALRY_REWARD, now);
+        }
+        if previous_streak <= -3 {
+            Self::grant_reward(storage, team_id, TeamPerformanceRewardType::ComebackVictory, COMEBACK_REWARD, now);
+        }
+    }
+
+    fn grant_reward(
+        storage: &mut InkTixStorage,
+        team_id: u32,
+        reward_type: TeamPerformanceRewardType,
+        (points_multiplier, duration): (u32, u64),
+        now: u64,
+    ) {
+        let id = storage.get_next_id("team_performance_reward");
+        storage.team_performance_rewards.insert(id, &TeamPerformanceReward {
+            id, team_id, reward_type, points_multiplier,
+            start_date: now, end_date: now + duration, is_active: true,
+        });
+        let mut ids = storage.team_performance_reward_ids.get(team_id).unwrap_or_default();
+        ids.push(id);
+        storage.team_performance_reward_ids.insert(team_id, &ids);
+    }
+}
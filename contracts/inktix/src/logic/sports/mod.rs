@@ -1,12 +1,13 @@
 //! Sports-specific business logic modules.
 //!
-//! Contains team, season, season pass, fantasy sports, loyalty, analytics,
-//! and cross-chain management logic gated behind the "sports" feature flag.
+//! Contains team, season, season pass, fantasy sports, game result oracle,
+//! loyalty, analytics, and cross-chain management logic gated behind the "sports" feature flag.
 
 pub mod advanced_team_loyalty;
 pub mod analytics;
 pub mod cross_chain_management;
 pub mod fantasy_sports_management;
+pub mod game_result_oracle;
 pub mod loyalty;
 pub mod season_management;
 pub mod season_pass_management;
@@ -16,6 +17,7 @@ pub use advanced_team_loyalty::*;
 pub use analytics::*;
 pub use cross_chain_management::*;
 pub use fantasy_sports_management::*;
+pub use game_result_oracle::*;
 pub use loyalty::*;
 pub use season_management::*;
 pub use season_pass_management::*;
@@ -1,15 +1,57 @@
-//! Season pass package creation, purchasing, and event redemption logic.
+//! Season pass package creation, purchasing, renewal, upgrades and event redemption logic.
 //!
 //! Manages the full lifecycle of season passes: creating package configurations,
 //! processing purchases with staking requirements, and redeeming passes for individual events.
 //!
+//! A package has a pass type and a game list: full, half-season and playoff
+//! passes cover every listed game, while a `Flex(n)` pass covers any `n` of
+//! them (or of the season's games when the list is empty). Organizers open
+//! renewals from one season's package to the next: holders get a priority
+//! window, before the general sale, to renew at the price they paid, moving
+//! the loyalty points and staking accrued on their pass to the new one. The
+//! window is recorded once for the package and each holder's offer is worked
+//! out from it when they renew. The renewed pass stays usable for its remaining
+//! games. A pass can be upgraded to a higher tier of the same team and season,
+//! paying only the price difference.
+//!
+//! Packages are bound to a team's season: their games are home games of that
+//! season. Every pass is issued with a fixed seat; renewing keeps the seat.
+//! Non-flex passes record it as reserved in each game they cover, while a flex
+//! pass's seat stays on general sale and must be free when the game is claimed.
+//! Each covered game can be claimed once per pass, issuing a ticket for the
+//! assigned seat and recording the claim as the pass's usage for that game.
+//! Claims count against the game's capacity like any other sale.
+//!
+//! Pass payments, renewals and upgrade differences are held against the
+//! package they buy and released to its organizer game by game: each completed
+//! game of the package releases an equal share of everything paid for it. An
+//! open flex package's share is fixed by the season's home games when the
+//! package is created. The share of games still scheduled stays held. When a
+//! game of a non-flex package is cancelled, each holder that has not claimed it
+//! is credited their pass's per-game share to their refund balance.
+//!
 //! # Functions
 //! - `create_season_pass_package` -- defines a purchasable season pass offering
-//! - `purchase_season_pass` -- issues a season pass to a buyer
-//! - `use_season_pass_for_event` -- redeems a pass for entry to a specific event
+//! - `purchase_season_pass` -- issues a season pass to a buyer during the package's sale
+//! - `open_renewals` -- offers a package's holders a priority renewal window at a locked price
+//! - `renewal_option` -- the renewal a pass is currently offered, if any
+//! - `renew_season_pass` -- renews a pass into the next package, carrying over loyalty and staking
+//! - `upgrade_season_pass` -- moves a pass to a higher-priced package for the difference
+//! - `use_season_pass_for_event` -- claims a covered game with a pass, issuing a ticket for its seat
+//! - `covered_events` -- the scheduled games a pass covers
+//! - `refund_cancelled_game` -- credits holders their per-game share of a cancelled game
+//! - `withdraw_proceeds` -- settles the proceeds released by a package's completed games to its organizer
 
+use crate::logic::core::currency_management::CurrencyManagement;
+use crate::logic::core::escrow::Escrow;
+use crate::logic::core::seat_inventory::SeatInventory;
+use crate::logic::core::waitlist::Waitlists;
 use crate::storage::contract_storage::InkTixStorage;
+use crate::types::core::error::InkTixError;
+use crate::types::core::event::EventStatus;
+use crate::types::core::seat::{Seat, SeatStatus};
 use crate::types::sports::season_pass::*;
+use crate::utils::conversion::mul_div;
 use ink::env::DefaultEnvironment;
 use ink::primitives::AccountId;
 use ink::prelude::string::String;
@@ -22,16 +64,47 @@ pub struct SeasonPassManagement;
 #[allow(clippy::arithmetic_side_effects)]
 impl SeasonPassManagement {
     /// Create a new season pass package with pricing and benefits
+    ///
+    /// `game_ids` must be home games of the team's season. Flex passes cover
+    /// `n` games of `game_ids`, or of the season when it is empty; every other
+    /// pass type covers each game in `game_ids`.
+    #[allow(clippy::too_many_arguments)]
     pub fn create_season_pass_package(
-        storage: &mut InkTixStorage, name: String, team_id: u32, season_id: u32,
+        storage: &mut InkTixStorage, organizer: AccountId, name: String, team_id: u32, season_id: u32,
+        pass_type: SeasonPassType, game_ids: Vec<u32>,
         price: u128, currency: crate::types::core::currency::CurrencyId,
         staking_requirement: u128, benefits: Vec<SeasonPassBenefits>,
     ) -> Result<u32, String> {
+        let benefits = benefits.into_iter().next().ok_or("Package needs benefits")?;
+        if !storage.teams.contains(team_id) { return Err("Team not found".to_string()); }
+        if !storage.seasons.contains(season_id) { return Err("Season not found".to_string()); }
+        let home_games = storage.home_season_events.get((team_id, season_id)).unwrap_or_default();
+        for (index, event_id) in game_ids.iter().enumerate() {
+            if !home_games.contains(event_id) { return Err("Game is not a home game of the team's season".to_string()); }
+            if game_ids[..index].contains(event_id) { return Err("Game listed more than once".to_string()); }
+        }
+        let total_games = match pass_type {
+            SeasonPassType::Flex(0) => return Err("Flex pass needs at least one game".to_string()),
+            SeasonPassType::Flex(games) if !game_ids.is_empty() && games as usize > game_ids.len() => {
+                return Err("Flex pass covers more games than listed".to_string());
+            }
+            SeasonPassType::Flex(games) => games,
+            _ if game_ids.is_empty() => return Err("Package needs a game list".to_string()),
+            _ => game_ids.len() as u32,
+        };
+        let proceeds_games = if game_ids.is_empty() { (home_games.len() as u32).max(total_games) } else { game_ids.len() as u32 };
         let package_id = storage.get_next_season_pass_package_id();
+        if !matches!(pass_type, SeasonPassType::Flex(_)) {
+            for &event_id in &game_ids {
+                let mut packages = storage.game_season_pass_packages.get(event_id).unwrap_or_default();
+                packages.push(package_id);
+                storage.game_season_pass_packages.insert(event_id, &packages);
+            }
+        }
         let package = SeasonPassPackage {
-            id: package_id, team_id, season_id, package_name: name,
-            pass_type: SeasonPassType::FullSeason, total_games: 41, base_price: price, currency,
-            max_quantity: 1000, sold_quantity: 0, benefits: benefits[0].clone(),
+            id: package_id, organizer, team_id, season_id, package_name: name,
+            pass_type, game_ids, total_games, proceeds_games, base_price: price, currency,
+            max_quantity: 1000, sold_quantity: 0, benefits,
             staking_required: staking_requirement > 0, min_staking_amount: staking_requirement,
             staking_reward_rate: 500, active: true,
             sale_start_date: ink::env::block_timestamp::<DefaultEnvironment>(),
@@ -42,58 +115,377 @@ impl SeasonPassManagement {
         Ok(package_id)
     }
 
-    /// Purchase a season pass from an active package
-    pub fn purchase_season_pass(storage: &mut InkTixStorage, user: AccountId, package_id: u32) -> Result<u32, String> {
+    /// Purchase a season pass with a fixed `seat` from an active package, checking `payment` covers its price
+    pub fn purchase_season_pass(
+        storage: &mut InkTixStorage, user: AccountId, package_id: u32, seat: Seat, payment: u128,
+    ) -> Result<u32, String> {
         let package = storage.season_pass_packages.get(package_id).ok_or("Package not found")?;
-        if !package.active { return Err("Package is not active".to_string()); }
-        let pass_id = storage.get_next_season_pass_id();
-        let current_time = ink::env::block_timestamp::<DefaultEnvironment>();
-        let season_pass = SeasonPass {
-            id: pass_id, owner: user, team_id: package.team_id, season_id: package.season_id,
-            pass_type: package.pass_type.clone(), status: SeasonPassStatus::Active,
-            purchase_date: current_time, activation_date: current_time,
-            expiry_date: current_time + 365 * 24 * 60 * 60 * 1000,
-            total_games: package.total_games, games_attended: 0, games_remaining: package.total_games,
-            purchase_price: package.base_price, purchase_currency: package.currency,
-            benefits: package.benefits.clone(), staking_amount: 0, staking_rewards_earned: 0,
-            last_staking_update: current_time, transferable: true,
-            transfer_cooldown_until: current_time + 30 * 24 * 60 * 60 * 1000,
-        };
-        storage.season_passes.insert(pass_id, &season_pass);
-        let mut user_passes = storage.user_season_passes.get(user).unwrap_or(Vec::new());
-        user_passes.push(pass_id);
-        storage.user_season_passes.insert(user, &user_passes);
-        let mut updated_package = package;
-        updated_package.sold_quantity += 1;
-        storage.season_pass_packages.insert(package_id, &updated_package);
-        Ok(pass_id)
+        Self::ensure_on_sale(&package)?;
+        let now = ink::env::block_timestamp::<DefaultEnvironment>();
+        if now < package.sale_start_date { return Err("Season pass sale has not started".to_string()); }
+        let seat_key = Self::check_seat(storage, &package, &seat, None)?;
+        CurrencyManagement::ensure_payable(storage, package.currency, package.base_price, payment)?;
+        let price = package.base_price;
+        Self::credit(storage, package_id, package.currency, price);
+        Ok(Self::issue_pass(storage, user, package, seat, seat_key, price))
+    }
+
+    /// Offer every holder of `from_package_id` a renewal into `renewal_package_id`
+    ///
+    /// Holders can renew at the lower of what they paid and the package price
+    /// until `window_ends_at`, when the package's general sale starts.
+    pub fn open_renewals(
+        storage: &mut InkTixStorage, caller: AccountId, from_package_id: u32, renewal_package_id: u32, window_ends_at: u64,
+    ) -> Result<(), String> {
+        let from = storage.season_pass_packages.get(from_package_id).ok_or("Package not found")?;
+        let mut renewal = storage.season_pass_packages.get(renewal_package_id).ok_or("Package not found")?;
+        if from.organizer != caller || renewal.organizer != caller {
+            return Err("Only the organizer of both packages can open renewals".to_string());
+        }
+        if from.team_id != renewal.team_id || from.season_id == renewal.season_id {
+            return Err("Renewal package must be for another season of the same team".to_string());
+        }
+        if from.currency != renewal.currency {
+            return Err("Renewal package must be in the same currency".to_string());
+        }
+        if !renewal.active { return Err("Package is not active".to_string()); }
+        let now = ink::env::block_timestamp::<DefaultEnvironment>();
+        if window_ends_at <= now || window_ends_at >= renewal.sale_end_date || renewal.sold_quantity > 0 {
+            return Err("Invalid renewal window".to_string());
+        }
+
+        storage.package_renewals.insert(from_package_id, &PackageRenewal { renewal_package_id, window_ends_at });
+        renewal.sale_start_date = renewal.sale_start_date.max(window_ends_at);
+        storage.season_pass_packages.insert(renewal_package_id, &renewal);
+        Ok(())
+    }
+
+    /// The renewal a pass is offered through its package, unless it was already renewed
+    pub fn renewal_option(storage: &InkTixStorage, pass_id: u32) -> Option<RenewalOption> {
+        let pass = storage.season_passes.get(pass_id)?;
+        if !matches!(pass.status, SeasonPassStatus::Active | SeasonPassStatus::Expired)
+            || storage.renewed_season_passes.contains(pass_id)
+        {
+            return None;
+        }
+        let renewal = storage.package_renewals.get(pass.package_id)?;
+        let package = storage.season_pass_packages.get(renewal.renewal_package_id)?;
+        Some(RenewalOption {
+            current_pass_id: pass_id,
+            renewal_package_id: renewal.renewal_package_id,
+            locked_price: pass.purchase_price.min(package.base_price),
+            window_ends_at: renewal.window_ends_at,
+        })
     }
 
-    /// Redeem a season pass for entry to a specific event, issuing a free ticket
+    /// Renew a pass during its priority window, returning the new pass ID and the locked price charged
+    ///
+    /// The new pass keeps the seat and takes over the loyalty points and
+    /// staking of the old one. The old pass stays active for the rest of its
+    /// own season's games and is recorded as renewed so it is not offered
+    /// another renewal.
+    pub fn renew_season_pass(storage: &mut InkTixStorage, user: AccountId, pass_id: u32, payment: u128) -> Result<(u32, u128), String> {
+        let mut pass = storage.season_passes.get(pass_id).ok_or("Season pass not found")?;
+        if pass.owner != user { return Err("Not the owner of this season pass".to_string()); }
+        let option = Self::renewal_option(storage, pass_id).ok_or("No renewal offer for this season pass")?;
+        if ink::env::block_timestamp::<DefaultEnvironment>() >= option.window_ends_at {
+            return Err("Renewal window has closed".to_string());
+        }
+        let package = storage.season_pass_packages.get(option.renewal_package_id).ok_or("Package not found")?;
+        Self::ensure_on_sale(&package)?;
+        let seat_key = Self::check_seat(storage, &package, &pass.seat, None)?;
+        CurrencyManagement::ensure_payable(storage, package.currency, option.locked_price, payment)?;
+
+        Self::credit(storage, package.id, package.currency, option.locked_price);
+        let renewed_id = Self::issue_pass(storage, user, package, pass.seat.clone(), seat_key, option.locked_price);
+        if let Some(mut renewed) = storage.season_passes.get(renewed_id) {
+            renewed.loyalty_points = pass.loyalty_points;
+            renewed.staking_amount = pass.staking_amount;
+            renewed.staking_rewards_earned = pass.staking_rewards_earned;
+            renewed.last_staking_update = pass.last_staking_update;
+            storage.season_passes.insert(renewed_id, &renewed);
+        }
+        storage.renewed_season_passes.insert(pass_id, &renewed_id);
+        pass.loyalty_points = 0;
+        pass.staking_amount = 0;
+        pass.staking_rewards_earned = 0;
+        storage.season_passes.insert(pass_id, &pass);
+        Ok((renewed_id, option.locked_price))
+    }
+
+    /// Move a pass to a higher-priced package of the same team and season, returning the difference charged
+    ///
+    /// Games already attended count against the new package's games, and the
+    /// pass's seat must be free at any games the new package adds.
+    pub fn upgrade_season_pass(
+        storage: &mut InkTixStorage, user: AccountId, pass_id: u32, package_id: u32, payment: u128,
+    ) -> Result<u128, String> {
+        let mut pass = storage.season_passes.get(pass_id).ok_or("Season pass not found")?;
+        if pass.owner != user { return Err("Not the owner of this season pass".to_string()); }
+        if pass.status != SeasonPassStatus::Active { return Err("Season pass is not active".to_string()); }
+        let mut package = storage.season_pass_packages.get(package_id).ok_or("Package not found")?;
+        Self::ensure_on_sale(&package)?;
+        if package.team_id != pass.team_id || package.season_id != pass.season_id {
+            return Err("Package is for a different team or season".to_string());
+        }
+        if package.currency != pass.purchase_currency || package.base_price <= pass.purchase_price {
+            return Err("Package is not a higher tier".to_string());
+        }
+        let difference = package.base_price - pass.purchase_price;
+        Self::check_seat(storage, &package, &pass.seat, Some(pass_id))?;
+        CurrencyManagement::ensure_payable(storage, package.currency, difference, payment)?;
+
+        if let Some(mut previous) = storage.season_pass_packages.get(pass.package_id) {
+            Self::reserve_seat(storage, &pass, &previous, false);
+            previous.sold_quantity = previous.sold_quantity.saturating_sub(1);
+            storage.season_pass_packages.insert(pass.package_id, &previous);
+        }
+        let mut previous_passes = storage.package_season_passes.get(pass.package_id).unwrap_or_default();
+        previous_passes.retain(|&id| id != pass_id);
+        storage.package_season_passes.insert(pass.package_id, &previous_passes);
+        let mut package_passes = storage.package_season_passes.get(package_id).unwrap_or_default();
+        package_passes.push(pass_id);
+        storage.package_season_passes.insert(package_id, &package_passes);
+        pass.package_id = package_id;
+        pass.pass_type = package.pass_type.clone();
+        pass.benefits = package.benefits.clone();
+        pass.total_games = package.total_games;
+        pass.games_remaining = package.total_games.saturating_sub(pass.games_attended);
+        pass.purchase_price = package.base_price;
+        storage.season_passes.insert(pass_id, &pass);
+        Self::reserve_seat(storage, &pass, &package, true);
+        package.sold_quantity += 1;
+        storage.season_pass_packages.insert(package_id, &package);
+        Self::credit(storage, package_id, package.currency, difference);
+        Ok(difference)
+    }
+
+    /// Claim a game the pass covers, issuing a free ticket for the pass's seat
+    ///
+    /// Each game can be claimed once per pass; the claim is recorded as the
+    /// pass's usage for the event and counts as a sold ticket, so it fails
+    /// once the game is sold out.
     pub fn use_season_pass_for_event(storage: &mut InkTixStorage, user: AccountId, season_pass_id: u32, event_id: u32) -> Result<u64, String> {
         let mut season_pass = storage.season_passes.get(season_pass_id).ok_or("Season pass not found")?;
         if season_pass.owner != user { return Err("Not the owner of this season pass".to_string()); }
         if season_pass.status != SeasonPassStatus::Active { return Err("Season pass is not active".to_string()); }
         if season_pass.games_remaining == 0 { return Err("No games remaining on this season pass".to_string()); }
+        let event = storage.events.get(event_id).ok_or("Event not found")?;
+        if !event.active { return Err("Event is not active".to_string()); }
+        if !SeatInventory::season_pass_covers(storage, &season_pass, &event) {
+            return Err("Event is not covered by this season pass".to_string());
+        }
+        if storage.season_pass_usage.contains((season_pass_id, event_id)) {
+            return Err("Game already claimed with this season pass".to_string());
+        }
+        if event.sold_tickets >= event.capacity {
+            return Err(InkTixError::SoldOut.into());
+        }
+        let (seat, seat_number, seat_key) = SeatInventory::apply_layout(storage, event_id, season_pass.seat.clone())?;
+        let reserved = seat_key.as_ref().is_some_and(|key| {
+            SeatInventory::seat_status(storage, key) == (SeatStatus::SeasonPassReserved { pass_id: season_pass_id })
+        });
+        if !reserved {
+            Waitlists::ensure_unreserved(storage, &event, 1)?;
+            if let Some(key) = &seat_key {
+                SeatInventory::ensure_available(storage, key)?;
+            }
+        }
+
+        let points = season_pass.points_per_game();
         season_pass.games_attended += 1;
         season_pass.games_remaining -= 1;
+        season_pass.loyalty_points += points;
         storage.season_passes.insert(season_pass_id, &season_pass);
+        let now = ink::env::block_timestamp::<DefaultEnvironment>();
         let ticket_id = storage.get_next_ticket_id();
         let ticket = crate::types::core::ticket::Ticket {
             id: ticket_id, event_id, owner: user, purchase_price: 0,
             purchase_currency: season_pass.purchase_currency,
-            purchase_date: ink::env::block_timestamp::<DefaultEnvironment>(),
-            seat_number: 1, section: "Season Pass".to_string(), row: "N/A".to_string(),
-            seat_type: crate::types::core::seat::SeatType::GeneralAdmission,
-            access_level: crate::types::core::seat::AccessLevel::Standard,
+            purchase_date: now, acquired_at: now,
+            seat_number, section: seat.section, row: seat.row,
+            seat_type: seat.seat_type,
+            access_level: seat.access_level,
             transferable: true, loyalty_points_earned: 0, season_pass_discount_applied: true,
             is_season_pass_ticket: true, dynamic_price_paid: 0, performance_multiplier_applied: 0,
-            dot_equivalent_paid: 0,
+            dot_equivalent_paid: 0, refunded: false,
         };
         storage.tickets.insert(ticket_id, &ticket);
-        let mut user_tickets = storage.user_tickets.get(user).unwrap_or(Vec::new());
+        storage.season_pass_tickets.insert(ticket_id, &season_pass_id);
+        let mut event = event;
+        event.sold_tickets += 1;
+        storage.events.insert(event_id, &event);
+        if let Some(key) = seat_key {
+            SeatInventory::set_status(storage, &key, SeatStatus::Sold { ticket_id });
+        }
+        storage.season_pass_usage.insert((season_pass_id, event_id), &SeasonPassUsage {
+            pass_id: season_pass_id, event_id, usage_date: now, entry_time: event.date, exit_time: None,
+            benefits_used: Vec::new(), loyalty_points_earned: points, staking_rewards_earned: 0,
+        });
+        let mut user_tickets = storage.user_tickets.get(user).unwrap_or_default();
         user_tickets.push(ticket_id);
         storage.user_tickets.insert(user, &user_tickets);
         Ok(ticket_id)
     }
+
+    /// The scheduled games a pass's package covers
+    pub fn covered_events(storage: &InkTixStorage, pass: &SeasonPass) -> Vec<u32> {
+        storage.season_pass_packages.get(pass.package_id)
+            .map(|package| Self::package_games(storage, &package))
+            .unwrap_or_default()
+    }
+
+    /// A package's game list, or every scheduled home game of its season for an open flex package
+    fn package_games(storage: &InkTixStorage, package: &SeasonPassPackage) -> Vec<u32> {
+        if !package.game_ids.is_empty() {
+            return package.game_ids.clone();
+        }
+        storage.home_season_events.get((package.team_id, package.season_id)).unwrap_or_default()
+    }
+
+    /// Check `seat` can be assigned to a pass of `package`, returning its season seat key
+    ///
+    /// The seat must not be assigned to another active pass for the season and
+    /// must be in the layout of, and free at, every scheduled game of the package
+    /// that `own_pass` (the pass being upgraded, if any) has not claimed.
+    fn check_seat(
+        storage: &InkTixStorage, package: &SeasonPassPackage, seat: &Seat, own_pass: Option<u32>,
+    ) -> Result<SeasonSeatKey, String> {
+        let seat_number: u32 = seat.seat_number.parse().map_err(|_| "Invalid seat number")?;
+        let key = (package.team_id, package.season_id, seat.section.clone(), seat.row.clone(), seat_number);
+        if let Some(assigned) = storage.season_pass_seats.get(&key) {
+            let live = storage.season_passes.get(assigned).is_some_and(|pass| pass.status == SeasonPassStatus::Active);
+            if live && Some(assigned) != own_pass {
+                return Err("Seat is assigned to another season pass".to_string());
+            }
+        }
+        if matches!(package.pass_type, SeasonPassType::Flex(_)) {
+            return Ok(key);
+        }
+        for event_id in Self::package_games(storage, package) {
+            if own_pass.is_some_and(|pass_id| storage.season_pass_usage.contains((pass_id, event_id))) {
+                continue;
+            }
+            let (_, _, Some(seat_key)) = SeatInventory::apply_layout(storage, event_id, seat.clone())? else { continue };
+            let reserved = own_pass.is_some_and(|pass_id| {
+                SeatInventory::seat_status(storage, &seat_key) == SeatStatus::SeasonPassReserved { pass_id }
+            });
+            if !reserved {
+                SeatInventory::ensure_available(storage, &seat_key)?;
+            }
+        }
+        Ok(key)
+    }
+
+    /// Reserve a non-flex pass's seat at each game of `package` it has not claimed, or release those reservations
+    fn reserve_seat(storage: &mut InkTixStorage, pass: &SeasonPass, package: &SeasonPassPackage, reserve: bool) {
+        if matches!(package.pass_type, SeasonPassType::Flex(_)) {
+            return;
+        }
+        let reserved = SeatStatus::SeasonPassReserved { pass_id: pass.id };
+        for event_id in Self::package_games(storage, package) {
+            if storage.season_pass_usage.contains((pass.id, event_id)) {
+                continue;
+            }
+            let Ok((_, _, Some(key))) = SeatInventory::apply_layout(storage, event_id, pass.seat.clone()) else { continue };
+            if reserve {
+                SeatInventory::set_status(storage, &key, reserved.clone());
+            } else if storage.seat_inventory.get(&key).as_ref() == Some(&reserved) {
+                SeatInventory::set_status(storage, &key, SeatStatus::Available);
+            }
+        }
+    }
+
+    /// Credit each holder of a non-flex package listing a cancelled game their pass's per-game share
+    ///
+    /// Passes that claimed the game are refunded through their ticket instead.
+    pub fn refund_cancelled_game(storage: &mut InkTixStorage, event_id: u32) {
+        for package_id in storage.game_season_pass_packages.get(event_id).unwrap_or_default() {
+            let Some(package) = storage.season_pass_packages.get(package_id) else { continue };
+            for pass_id in storage.package_season_passes.get(package_id).unwrap_or_default() {
+                if storage.season_pass_usage.contains((pass_id, event_id)) {
+                    continue;
+                }
+                let Some(pass) = storage.season_passes.get(pass_id) else { continue };
+                let share = pass.purchase_price / package.total_games.max(1) as u128;
+                Escrow::credit_refund(storage, pass.owner, package.currency, share);
+            }
+        }
+    }
+
+    /// Settle the proceeds released by a package's completed games to its organizer
+    ///
+    /// Once `k` of the package's `n` proceeds games have completed, `k / n` of
+    /// everything paid for the package has been released; this pays out what was
+    /// released and not yet withdrawn. Returns the amount and currency to pay out.
+    pub fn withdraw_proceeds(
+        storage: &mut InkTixStorage, caller: AccountId, package_id: u32,
+    ) -> Result<(u128, crate::types::core::currency::CurrencyId), String> {
+        let package = storage.season_pass_packages.get(package_id).ok_or("Package not found")?;
+        if package.organizer != caller {
+            return Err("Only the package organizer can withdraw proceeds".to_string());
+        }
+        let completed = Self::package_games(storage, &package).iter()
+            .filter(|&&event_id| storage.events.get(event_id).is_some_and(|event| event.status == EventStatus::Completed))
+            .count() as u32;
+        if completed == 0 { return Err("No games of the package have completed".to_string()); }
+        let received = storage.season_pass_proceeds.get(package_id).unwrap_or(0);
+        let games = package.proceeds_games.max(1);
+        let released = mul_div(received, completed.min(games) as u128, games as u128).ok_or("Proceeds overflow")?;
+        let withdrawn = storage.season_pass_proceeds_withdrawn.get(package_id).unwrap_or(0);
+        let amount = released.saturating_sub(withdrawn);
+        if amount == 0 { return Err("No proceeds to withdraw".to_string()); }
+        storage.season_pass_proceeds_withdrawn.insert(package_id, &(withdrawn + amount));
+        Ok((amount, package.currency))
+    }
+
+    /// Hold a payment against a package and add it to the currency's revenue
+    fn credit(storage: &mut InkTixStorage, package_id: u32, currency: crate::types::core::currency::CurrencyId, amount: u128) {
+        let balance = storage.season_pass_proceeds.get(package_id).unwrap_or(0);
+        storage.season_pass_proceeds.insert(package_id, &balance.saturating_add(amount));
+        CurrencyManagement::record_revenue(storage, currency, amount);
+    }
+
+    /// Reject packages that are inactive, sold out or past their sale
+    fn ensure_on_sale(package: &SeasonPassPackage) -> Result<(), String> {
+        if !package.active { return Err("Package is not active".to_string()); }
+        if ink::env::block_timestamp::<DefaultEnvironment>() >= package.sale_end_date {
+            return Err("Season pass sale has ended".to_string());
+        }
+        if package.sold_quantity >= package.max_quantity { return Err("Package is sold out".to_string()); }
+        Ok(())
+    }
+
+    /// Issue a pass with its assigned seat from a package at `price`, returning its ID
+    fn issue_pass(
+        storage: &mut InkTixStorage, user: AccountId, package: SeasonPassPackage, seat: Seat, seat_key: SeasonSeatKey, price: u128,
+    ) -> u32 {
+        let pass_id = storage.get_next_season_pass_id();
+        let current_time = ink::env::block_timestamp::<DefaultEnvironment>();
+        let season_pass = SeasonPass {
+            id: pass_id, owner: user, team_id: package.team_id, season_id: package.season_id,
+            pass_type: package.pass_type.clone(), status: SeasonPassStatus::Active,
+            purchase_date: current_time, activation_date: current_time,
+            expiry_date: current_time + 365 * 24 * 60 * 60 * 1000,
+            total_games: package.total_games, games_attended: 0, games_remaining: package.total_games,
+            purchase_price: price, purchase_currency: package.currency,
+            benefits: package.benefits.clone(), staking_amount: 0, staking_rewards_earned: 0,
+            last_staking_update: current_time, transferable: true,
+            transfer_cooldown_until: current_time + 30 * 24 * 60 * 60 * 1000,
+            package_id: package.id, loyalty_points: 0, seat,
+        };
+        storage.season_passes.insert(pass_id, &season_pass);
+        storage.season_pass_seats.insert(seat_key, &pass_id);
+        Self::reserve_seat(storage, &season_pass, &package, true);
+        let mut user_passes = storage.user_season_passes.get(user).unwrap_or_default();
+        user_passes.push(pass_id);
+        storage.user_season_passes.insert(user, &user_passes);
+        let mut package_passes = storage.package_season_passes.get(package.id).unwrap_or_default();
+        package_passes.push(pass_id);
+        storage.package_season_passes.insert(package.id, &package_passes);
+        let mut updated_package = package;
+        updated_package.sold_quantity += 1;
+        storage.season_pass_packages.insert(updated_package.id, &updated_package);
+        pass_id
+    }
 }
@@ -1,9 +1,10 @@
 //! Primary contract storage definition.
 //!
 //! Contains `InkTixStorage`, the single ink! storage item holding all on-chain state:
-//! core entity mappings, currency rates, analytics, anti-scalping configs, loyalty,
-//! season passes, fantasy sports, team loyalty, venue services, cross-chain data,
-//! XCM messaging, NFTs, and concert-specific fields.
+//! ownership and roles, core entity mappings, seat inventory, holds, locked quotes, group orders, auctions, ballots, waitlists, sale windows, currency rates and their oracle,
+//! game results and team records, analytics, anti-scalping configs and behavior scoring, purchase limits and linked accounts, identity attestations, loyalty, season passes and their seat assignments, fantasy sports, team loyalty,
+//! venue services, cross-chain data, XCM messaging, NFTs, payment escrow, and
+//! concert-specific fields.
 //!
 //! # Functions
 //! - `initialize_currency_rates` -- seeds default exchange rates for supported currencies
@@ -12,6 +13,9 @@
 //! - `get_next_report_id` -- allocates the next analytics report ID
 //! - `get_next_season_pass_id` -- allocates the next season pass ID
 //! - `get_next_season_pass_package_id` -- allocates the next season pass package ID
+//! - `get_next_resale_listing_id` -- allocates the next resale listing ID
+//! - `get_next_seat_hold_id` -- allocates the next seat hold ID
+//! - `get_next_price_lock_id` -- allocates the next locked quote ID
 //! - `get_next_nft_token_id` -- allocates the next NFT token ID
 //! - `get_next_attendance_token_id` -- allocates the next attendance token ID
 //! - `get_next_artist_id` -- allocates the next artist ID
@@ -35,6 +39,9 @@ pub struct InkTixStorage {
     // CORE CONTRACT STATE
     // =========================================================================
     pub owner: AccountId,
+    /// Proposed new owner awaiting `accept_ownership`
+    pub pending_owner: Option<AccountId>,
+    pub account_roles: Mapping<AccountId, Vec<RoleGrant>>,
     pub total_teams: u32,
     pub total_venues: u32,
     pub total_events: u32,
@@ -50,17 +57,105 @@ pub struct InkTixStorage {
     pub venues: Mapping<u32, Venue>,
     pub seasons: Mapping<u32, Season>,
     pub events: Mapping<u32, Event>,
+    /// Sports events by (home team, season)
+    pub home_season_events: Mapping<HomeSeasonKey, Vec<u32>>,
     pub tickets: Mapping<u64, Ticket>,
     pub user_tickets: Mapping<AccountId, Vec<u64>>,
 
+    // Seat inventory
+    pub venue_seat_layouts: Mapping<u32, Vec<SeatBlock>>,
+    pub event_seat_layouts: Mapping<u32, Vec<SeatBlock>>,
+    pub seat_inventory: Mapping<SeatKey, SeatStatus>,
+    pub event_available_seats: Mapping<u32, u32>,
+    pub total_seat_holds: u64,
+    pub seat_holds: Mapping<u64, SeatHold>,
+    pub user_seat_holds: Mapping<AccountId, Vec<u64>>,
+    /// Holds per event that may still have seats recorded as held
+    pub event_seat_holds: Mapping<u32, Vec<u64>>,
+    pub total_price_locks: u64,
+    pub price_locks: Mapping<u64, PriceLock>,
+    pub user_price_locks: Mapping<AccountId, Vec<u64>>,
+
+    // Group orders
+    pub total_group_orders: u32,
+    pub group_orders: Mapping<u32, GroupOrder>,
+    /// Group order IDs per organizer and member
+    pub user_group_orders: Mapping<AccountId, Vec<u32>>,
+    /// Group orders per event that may still have seats recorded as reserved
+    pub event_group_orders: Mapping<u32, Vec<u32>>,
+    /// Group discount tiers per event, ascending by size
+    pub event_group_discounts: Mapping<u32, Vec<GroupDiscountTier>>,
+
+    // Auctions
+    pub total_auctions: u32,
+    pub auctions: Mapping<u32, Auction>,
+    /// Auction IDs per event
+    pub event_auctions: Mapping<u32, Vec<u32>>,
+    /// Unsettled auction selling each `(event_id, block_index)`
+    pub block_auctions: Mapping<(u32, u32), u32>,
+    /// Sealed bids per auction, in commit order
+    pub auction_bids: Mapping<u32, Vec<SealedBid>>,
+
+    // Ballots
+    pub total_ballots: u32,
+    pub ballots: Mapping<u32, Ballot>,
+    /// Ballot selling each event
+    pub event_ballots: Mapping<u32, u32>,
+    /// Entries by (ballot, position), in registration order until drawn and in drawn order after
+    pub ballot_entries: Mapping<(u32, u32), BallotEntry>,
+    /// Position of each entrant's entry by (ballot, entrant)
+    pub ballot_positions: Mapping<(u32, AccountId), u32>,
+    pub total_beacon_rounds: u32,
+    /// Randomness beacon rounds in publication order
+    pub beacon_rounds: Mapping<u32, BeaconRound>,
+
+    // Waitlists
+    /// Fans queued for each event, in FIFO order
+    pub event_waitlists: Mapping<u32, Vec<WaitlistEntry>>,
+    /// Resale listings withdrawn or lapsed while fans were waiting, offered down each event's waitlist
+    pub event_returned_listings: Mapping<u32, Vec<u64>>,
+
+    // Sale windows and presale access
+    pub event_sale_windows: Mapping<u32, Vec<SaleWindow>>,
+    /// Tickets bought per `(event_id, window_index, account)`
+    pub sale_window_purchases: Mapping<SaleWindowPurchaseKey, u32>,
+    /// Unredeemed account-bound access codes per `(event_id, access code hash)`
+    pub event_access_codes: Mapping<AccessCodeKey, ()>,
+    /// Accounts that redeemed an access code for an event
+    pub access_code_unlocks: Mapping<(u32, AccountId), bool>,
+
     // Currency and pricing
     pub supported_currencies: Vec<CurrencyId>,
     pub currency_rates: Mapping<CurrencyId, u128>,
+    /// Oracle-reported effective rate, freshness and status for each currency
+    pub currency_rate_info: Mapping<CurrencyId, CurrencyRates>,
+    /// Each feeder's latest submission, per currency
+    pub rate_submissions: Mapping<CurrencyId, Vec<RateSubmission>>,
+    /// Effective rate changes per currency as (timestamp, rate), oldest first
+    pub rate_history: Mapping<CurrencyId, Vec<RateHistoryEntry>>,
+    pub rate_oracle_config: RateOracleConfig,
     pub currency_revenue: Mapping<CurrencyId, u128>,
+    /// PSP22 token contract that payments in a non-DOT currency are made through
+    pub currency_tokens: Mapping<CurrencyId, AccountId>,
 
     // Team performance and pricing
-    pub team_performance: Mapping<u32, TeamPerformance>,
+    /// Season records keyed by `(team_id, season_id)`, derived from game results
+    pub team_performance: Mapping<(u32, u32), TeamPerformance>,
+    /// Teams with a record in each season
+    pub season_teams: Mapping<u32, Vec<u32>>,
+    /// Finalized results keyed by event ID
+    pub game_results: Mapping<u32, GameResult>,
+    /// Reports received for events whose result is not yet finalized
+    pub game_result_reports: Mapping<u32, Vec<GameResultReport>>,
+    /// Matching reports needed to finalize a game result
+    pub result_quorum: u32,
     pub pricing_multipliers: Mapping<u32, PricingMultiplier>,
+    /// Pricing policies set for a venue, applying to its events without their own policy
+    pub venue_pricing_policies: Mapping<u32, PricingPolicy>,
+    /// Pricing policies set for a single event
+    pub event_pricing_policies: Mapping<u32, PricingPolicy>,
+    /// Last pricing policy version assigned
+    pub total_pricing_policies: u32,
 
     // Analytics storage
     pub platform_stats: PlatformStats,
@@ -73,7 +168,26 @@ pub struct InkTixStorage {
     pub anti_scalping_configs: Mapping<u32, AntiScalpingConfig>,
     pub ticket_transfer_history: Mapping<u64, TicketTransferHistory>,
     pub user_behavior_profiles: Mapping<AccountId, UserBehaviorProfile>,
+    /// Current rapid-purchase run by (buyer, event)
+    pub purchase_bursts: Mapping<(AccountId, u32), PurchaseBurst>,
     pub resale_listings: Mapping<u64, ResaleListing>,
+    pub total_resale_listings: u64,
+    pub ticket_resale_listing: Mapping<u64, u64>,
+    pub event_resale_listings: Mapping<u32, Vec<u64>>,
+
+    // Purchase limits
+    pub purchase_limits: Mapping<PurchaseLimitScope, u32>,
+    pub total_account_links: u32,
+    /// Link each linked account belongs to
+    pub account_links: Mapping<AccountId, u32>,
+    /// Accounts sharing purchase limits, per link
+    pub linked_accounts: Mapping<u32, Vec<AccountId>>,
+
+    // Identity attestations
+    /// Latest attestation of each kind per account
+    pub attestations: Mapping<(AccountId, AttestationKind), Attestation>,
+    /// Attestation kinds buyers of each event need
+    pub event_required_attestations: Mapping<u32, Vec<AttestationKind>>,
 
     // Loyalty and rewards
     pub loyalty_profiles: Mapping<AccountId, LoyaltyProfile>,
@@ -85,11 +199,27 @@ pub struct InkTixStorage {
     // Season pass management
     pub season_passes: Mapping<u32, SeasonPass>,
     pub season_pass_packages: Mapping<u32, SeasonPassPackage>,
-    pub season_pass_usage: Mapping<u32, SeasonPassUsage>,
-    pub renewal_options: Mapping<u32, RenewalOption>,
+    /// Games claimed with a season pass, by (pass, event)
+    pub season_pass_usage: Mapping<(u32, u32), SeasonPassUsage>,
+    /// Renewal window opened for each package's holders, by the package renewed from
+    pub package_renewals: Mapping<u32, PackageRenewal>,
+    /// Pass each renewed pass was renewed into, by renewed pass
+    pub renewed_season_passes: Mapping<u32, u32>,
     pub season_pass_analytics: Mapping<u32, SeasonPassAnalytics>,
     pub user_season_passes: Mapping<AccountId, Vec<u32>>,
     pub team_season_passes: Mapping<u32, Vec<u32>>,
+    /// Passes currently held under each package (at most its `max_quantity`)
+    pub package_season_passes: Mapping<u32, Vec<u32>>,
+    /// Season pass each season-pass ticket was issued from
+    pub season_pass_tickets: Mapping<u64, u32>,
+    /// Season pass each seat is assigned to for a home team's season
+    pub season_pass_seats: Mapping<SeasonSeatKey, u32>,
+    /// Sale proceeds received by each season pass package, in the package currency
+    pub season_pass_proceeds: Mapping<u32, u128>,
+    /// Proceeds of each season pass package already paid out to its organizer
+    pub season_pass_proceeds_withdrawn: Mapping<u32, u128>,
+    /// Non-flex packages listing each game
+    pub game_season_pass_packages: Mapping<u32, Vec<u32>>,
 
     // Fantasy sports management
     pub total_fantasy_leagues: u32,
@@ -98,6 +228,7 @@ pub struct InkTixStorage {
     pub total_fantasy_rewards: u32,
     pub total_fantasy_game_weeks: u32,
     pub total_fantasy_transfers: u32,
+    pub total_fantasy_players: u32,
     pub fantasy_leagues: Mapping<u32, FantasyLeague>,
     pub fantasy_teams: Mapping<u32, FantasyTeam>,
     pub fantasy_participations: Mapping<u32, FantasyLeagueParticipation>,
@@ -106,6 +237,9 @@ pub struct InkTixStorage {
     pub fantasy_game_weeks: Mapping<u32, FantasyGameWeek>,
     pub fantasy_transfers: Mapping<u32, FantasyTransfer>,
     pub fantasy_settings: Mapping<u32, FantasySettings>,
+    pub fantasy_players: Mapping<u32, PlayerStats>,
+    /// Fantasy players registered for each real team
+    pub team_fantasy_players: Mapping<u32, Vec<u32>>,
     pub user_fantasy_leagues: Mapping<AccountId, Vec<u32>>,
     pub user_fantasy_teams: Mapping<AccountId, Vec<u32>>,
     pub league_participants: Mapping<u32, Vec<AccountId>>,
@@ -119,8 +253,11 @@ pub struct InkTixStorage {
     pub team_loyalty_profiles: Mapping<(AccountId, u32), TeamLoyaltyProfile>,
     pub team_stakings: Mapping<u32, TeamStaking>,
     pub team_attendance: Mapping<u32, TeamAttendance>,
+    /// Attendance record by (fan, team, event), so each game counts once
+    pub recorded_team_attendance: Mapping<AttendanceKey, u32>,
     pub team_attendances: Mapping<u32, TeamAttendance>,
     pub team_performance_rewards: Mapping<u32, TeamPerformanceReward>,
+    pub team_performance_reward_ids: Mapping<u32, Vec<u32>>,
     pub team_loyalty_challenges: Mapping<u32, TeamLoyaltyChallenge>,
     pub team_loyalty_analytics: Mapping<u32, TeamLoyaltyAnalytics>,
     pub user_team_loyalty: Mapping<AccountId, Vec<u32>>,
@@ -176,12 +313,31 @@ pub struct InkTixStorage {
     pub user_nft_tokens: Mapping<AccountId, Vec<u64>>,
     pub attendance_tokens: Mapping<u64, AttendanceToken>,
     pub user_attendance_tokens: Mapping<AccountId, Vec<u64>>,
+    /// Soulbound PSP34 contract attendance tokens are minted into, once configured
+    pub attendance_token_contract: Option<AccountId>,
+    /// PSP34 per-token approvals: token ID -> approved operator
+    pub nft_token_approvals: Mapping<u64, AccountId>,
+    /// PSP34 approvals for all of an owner's tokens: (owner, operator)
+    pub nft_operator_approvals: Mapping<(AccountId, AccountId), ()>,
+
+    // =========================================================================
+    // PAYMENTS AND SETTLEMENT
+    // =========================================================================
+    /// Native (DOT) proceeds held for each event
+    pub event_escrow: Mapping<u32, u128>,
+    /// PSP22 token proceeds held for each event, by payment currency
+    pub event_token_escrow: Mapping<(u32, CurrencyId), u128>,
+    /// Refund claim deadline for each cancelled event
+    pub refund_deadlines: Mapping<u32, u64>,
+    /// Events whose organizer has withdrawn any escrowed proceeds
+    pub proceeds_withdrawn: Mapping<u32, ()>,
+    /// Refunds owed to each account by currency, withdrawn by the account itself
+    pub refund_balances: Mapping<(AccountId, CurrencyId), u128>,
 
     // =========================================================================
     // CONCERT-SPECIFIC FIELDS
     // =========================================================================
     pub artists: Mapping<u32, Artist>,
-    pub per_event_purchase_count: Mapping<(u32, AccountId), u32>,
     pub total_artists: u32,
     pub next_artist_id: u32,
 }
@@ -190,6 +346,8 @@ impl Default for InkTixStorage {
     fn default() -> Self {
         Self {
             owner: AccountId::from([0u8; 32]),
+            pending_owner: None,
+            account_roles: Mapping::default(),
             total_teams: 0,
             total_venues: 0,
             total_events: 0,
@@ -203,6 +361,7 @@ impl Default for InkTixStorage {
             total_fantasy_rewards: 0,
             total_fantasy_game_weeks: 0,
             total_fantasy_transfers: 0,
+            total_fantasy_players: 0,
             total_team_loyalty_profiles: 0,
             total_team_stakings: 0,
             total_team_attendance: 0,
@@ -219,19 +378,67 @@ impl Default for InkTixStorage {
             teams: Mapping::default(),
             venues: Mapping::default(),
             seasons: Mapping::default(),
+            home_season_events: Mapping::default(),
             events: Mapping::default(),
             tickets: Mapping::default(),
             user_tickets: Mapping::default(),
 
+            venue_seat_layouts: Mapping::default(),
+            event_seat_layouts: Mapping::default(),
+            seat_inventory: Mapping::default(),
+            event_available_seats: Mapping::default(),
+            total_seat_holds: 0,
+            seat_holds: Mapping::default(),
+            user_seat_holds: Mapping::default(),
+            event_seat_holds: Mapping::default(),
+            total_price_locks: 0,
+            price_locks: Mapping::default(),
+            user_price_locks: Mapping::default(),
+            total_group_orders: 0,
+            group_orders: Mapping::default(),
+            user_group_orders: Mapping::default(),
+            event_group_orders: Mapping::default(),
+            event_group_discounts: Mapping::default(),
+            total_auctions: 0,
+            auctions: Mapping::default(),
+            event_auctions: Mapping::default(),
+            block_auctions: Mapping::default(),
+            auction_bids: Mapping::default(),
+            total_ballots: 0,
+            ballots: Mapping::default(),
+            event_ballots: Mapping::default(),
+            ballot_entries: Mapping::default(),
+            ballot_positions: Mapping::default(),
+            total_beacon_rounds: 0,
+            beacon_rounds: Mapping::default(),
+            event_waitlists: Mapping::default(),
+            event_returned_listings: Mapping::default(),
+            event_sale_windows: Mapping::default(),
+            sale_window_purchases: Mapping::default(),
+            event_access_codes: Mapping::default(),
+            access_code_unlocks: Mapping::default(),
+
             supported_currencies: vec![
                 CurrencyId::DOT, CurrencyId::ACA, CurrencyId::AUSD,
                 CurrencyId::LDOT, CurrencyId::KSM,
             ],
             currency_rates: Mapping::default(),
+            currency_rate_info: Mapping::default(),
+            rate_submissions: Mapping::default(),
+            rate_history: Mapping::default(),
+            rate_oracle_config: RateOracleConfig::default(),
             currency_revenue: Mapping::default(),
+            currency_tokens: Mapping::default(),
 
             team_performance: Mapping::default(),
+            season_teams: Mapping::default(),
+            game_results: Mapping::default(),
+            game_result_reports: Mapping::default(),
+            result_quorum: 2,
             pricing_multipliers: Mapping::default(),
+            venue_pricing_policies: Mapping::default(),
+            event_pricing_policies: Mapping::default(),
+            total_pricing_policies: 0,
 
             platform_stats: PlatformStats {
                 total_events: 0, total_tickets_sold: 0, total_revenue: 0,
@@ -246,7 +453,17 @@ impl Default for InkTixStorage {
             anti_scalping_configs: Mapping::default(),
             ticket_transfer_history: Mapping::default(),
             user_behavior_profiles: Mapping::default(),
+            purchase_bursts: Mapping::default(),
             resale_listings: Mapping::default(),
+            total_resale_listings: 0,
+            ticket_resale_listing: Mapping::default(),
+            event_resale_listings: Mapping::default(),
+            purchase_limits: Mapping::default(),
+            total_account_links: 0,
+            account_links: Mapping::default(),
+            linked_accounts: Mapping::default(),
+            attestations: Mapping::default(),
+            event_required_attestations: Mapping::default(),
 
             loyalty_profiles: Mapping::default(),
             reward_redemptions: Mapping::default(),
@@ -257,10 +474,17 @@ impl Default for InkTixStorage {
             season_passes: Mapping::default(),
             season_pass_packages: Mapping::default(),
             season_pass_usage: Mapping::default(),
-            renewal_options: Mapping::default(),
+            package_renewals: Mapping::default(),
+            renewed_season_passes: Mapping::default(),
             season_pass_analytics: Mapping::default(),
             user_season_passes: Mapping::default(),
             team_season_passes: Mapping::default(),
+            package_season_passes: Mapping::default(),
+            season_pass_tickets: Mapping::default(),
+            season_pass_seats: Mapping::default(),
+            season_pass_proceeds: Mapping::default(),
+            season_pass_proceeds_withdrawn: Mapping::default(),
+            game_season_pass_packages: Mapping::default(),
 
             fantasy_leagues: Mapping::default(),
             fantasy_teams: Mapping::default(),
@@ -270,6 +494,8 @@ impl Default for InkTixStorage {
             fantasy_game_weeks: Mapping::default(),
             fantasy_transfers: Mapping::default(),
             fantasy_settings: Mapping::default(),
+            fantasy_players: Mapping::default(),
+            team_fantasy_players: Mapping::default(),
             user_fantasy_leagues: Mapping::default(),
             user_fantasy_teams: Mapping::default(),
             league_participants: Mapping::default(),
@@ -277,8 +503,10 @@ impl Default for InkTixStorage {
             team_loyalty_profiles: Mapping::default(),
             team_stakings: Mapping::default(),
             team_attendance: Mapping::default(),
+            recorded_team_attendance: Mapping::default(),
             team_attendances: Mapping::default(),
             team_performance_rewards: Mapping::default(),
+            team_performance_reward_ids: Mapping::default(),
             team_loyalty_challenges: Mapping::default(),
             team_loyalty_analytics: Mapping::default(),
             user_team_loyalty: Mapping::default(),
@@ -324,10 +552,19 @@ impl Default for InkTixStorage {
             user_nft_tokens: Mapping::default(),
             attendance_tokens: Mapping::default(),
             user_attendance_tokens: Mapping::default(),
+            attendance_token_contract: None,
+            nft_token_approvals: Mapping::default(),
+            nft_operator_approvals: Mapping::default(),
+
+            // Payments and settlement
+            event_escrow: Mapping::default(),
+            event_token_escrow: Mapping::default(),
+            refund_deadlines: Mapping::default(),
+            proceeds_withdrawn: Mapping::default(),
+            refund_balances: Mapping::default(),
 
             // Concert-specific
             artists: Mapping::default(),
-            per_event_purchase_count: Mapping::default(),
             total_artists: 0,
             next_artist_id: 1,
         }
@@ -367,6 +604,13 @@ impl InkTixStorage {
             "cross_chain_transaction" => { self.total_cross_chain_transactions += 1; self.total_cross_chain_transactions }
             "fantasy_league" => { self.total_fantasy_leagues += 1; self.total_fantasy_leagues }
             "fantasy_team" => { self.total_fantasy_teams += 1; self.total_fantasy_teams }
+            "pricing_policy" => { self.total_pricing_policies += 1; self.total_pricing_policies }
+            "fantasy_player" => { self.total_fantasy_players += 1; self.total_fantasy_players }
+            "auction" => { self.total_auctions += 1; self.total_auctions }
+            "ballot" => { self.total_ballots += 1; self.total_ballots }
+            "beacon_round" => { self.total_beacon_rounds += 1; self.total_beacon_rounds }
+            "group_order" => { self.total_group_orders += 1; self.total_group_orders }
+            "account_link" => { self.total_account_links += 1; self.total_account_links }
             _ => 0,
         }
     }
@@ -392,6 +636,24 @@ impl InkTixStorage {
     /// Allocate and return the next fantasy transfer ID
     pub fn get_next_fantasy_transfer_id(&mut self) -> u32 { self.total_fantasy_transfers += 1; self.total_fantasy_transfers }
 
+    /// Allocate and return the next resale listing ID
+    pub fn get_next_resale_listing_id(&mut self) -> u64 {
+        self.total_resale_listings += 1;
+        self.total_resale_listings
+    }
+
+    /// Allocate and return the next seat hold ID
+    pub fn get_next_seat_hold_id(&mut self) -> u64 {
+        self.total_seat_holds += 1;
+        self.total_seat_holds
+    }
+
+    /// Allocate and return the next locked quote ID
+    pub fn get_next_price_lock_id(&mut self) -> u64 {
+        self.total_price_locks += 1;
+        self.total_price_locks
+    }
+
     /// Allocate and return the next NFT token ID
     pub fn get_next_nft_token_id(&mut self) -> u64 {
         self.total_nft_tokens += 1;
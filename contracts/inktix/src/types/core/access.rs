@@ -0,0 +1,44 @@
+//! Access control type definitions.
+//!
+//! Defines the operational roles that can be granted on the contract and the
+//! scope (global, one venue, or one event) each grant applies to.
+
+/// Operational roles that gate privileged messages
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub enum Role {
+    /// Passes every role check; can grant and revoke all roles except Admin
+    Admin,
+    EventOrganizer,
+    VenueOperator,
+    GateScanner,
+    OracleFeeder,
+    ArtistManager,
+    /// Reports final scores of sports events
+    ResultReporter,
+    /// Records identity attestations against accounts
+    Attester,
+    /// Publishes randomness beacon rounds that ballot draws mix in
+    RandomnessBeacon,
+}
+
+/// What a role grant applies to
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub enum RoleScope {
+    Global,
+    /// A venue and every event held there
+    Venue(u32),
+    Event(u32),
+}
+
+/// A role held by an account within a scope
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct RoleGrant {
+    pub role: Role,
+    pub scope: RoleScope,
+}
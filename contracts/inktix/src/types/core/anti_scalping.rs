@@ -12,15 +12,22 @@ use ink::prelude::vec::Vec;
 #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
 pub struct AntiScalpingConfig {
     pub event_id: u32,
+    /// Only allow transfers through the resale marketplace
     pub transfer_restricted: bool,
     pub max_tickets_per_user: u32,
     pub resale_allowed: bool,
+    /// Maximum resale price as a percentage of the original purchase price (100 = face value)
     pub max_resale_price_multiplier: u8,
+    /// Share of each resale price credited to the event's escrow
     pub resale_fee_percentage: u8,
+    /// Milliseconds after its holder acquires a ticket during which it can't be gifted or resold
     pub transfer_lock_period: u64,
+    /// Accounts that cannot buy, receive, transfer or resell the event's tickets
     pub blacklisted_addresses: Vec<AccountId>,
+    /// If non-empty, only these accounts can buy, receive, transfer or resell the event's tickets
     pub whitelisted_addresses: Vec<AccountId>,
     pub dynamic_pricing_enabled: bool,
+    /// Weight rapid repeat purchases more heavily in behavior scoring
     pub anti_bot_measures: bool,
 }
 
@@ -35,7 +42,7 @@ impl AntiScalpingConfig {
             resale_allowed: false,
             max_resale_price_multiplier: 100,
             resale_fee_percentage: 0,
-            transfer_lock_period: 86400,
+            transfer_lock_period: 86_400_000,
             blacklisted_addresses: Vec::new(),
             whitelisted_addresses: Vec::new(),
             dynamic_pricing_enabled: false,
@@ -80,17 +87,31 @@ pub struct UserBehaviorProfile {
     pub total_tickets_purchased: u32,
     pub total_tickets_resold: u32,
     pub average_hold_time: u64,
+    /// 0-100; escalates `blacklist_status` as it rises
     pub suspicious_activity_score: u8,
     pub last_purchase_time: u64,
     pub last_resale_time: u64,
+    /// When the score last decayed or rose
+    pub score_updated_at: u64,
     pub blacklist_status: BlacklistStatus,
+    /// Times the status has escalated
     pub warning_count: u8,
 }
 
-/// Blacklist status levels
+/// A buyer's run of rapid purchases for one event
 #[derive(Debug, PartialEq, Eq, Clone)]
 #[ink::scale_derive(Encode, Decode, TypeInfo)]
 #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct PurchaseBurst {
+    /// Purchases in the run, each within `RAPID_PURCHASE_INTERVAL` of the last
+    pub purchases: u32,
+    pub last_purchase_time: u64,
+}
+
+/// Blacklist status levels, in escalating order
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
 pub enum BlacklistStatus {
     Clean,
     Warning,
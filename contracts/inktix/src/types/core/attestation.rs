@@ -0,0 +1,34 @@
+//! Identity attestation type definitions.
+//!
+//! Models a time-limited verification a trusted attester records against an
+//! account (`Attestation`) and what was verified (`AttestationKind`). Only a
+//! hash of the verified claim is stored; the underlying data stays off-chain.
+
+use ink::primitives::AccountId;
+
+/// What an attestation verifies
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub enum AttestationKind {
+    /// Lightweight identity check
+    KycLite,
+    /// Established social media presence
+    SocialProof,
+    /// Verified phone number
+    Phone,
+}
+
+/// A verification recorded against an account by a trusted attester
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct Attestation {
+    pub kind: AttestationKind,
+    pub attester: AccountId,
+    /// Hash of the verified claim, computed off-chain; never the claim itself
+    pub claim_hash: [u8; 32],
+    pub issued_at: u64,
+    pub expires_at: u64,
+    pub revoked: bool,
+}
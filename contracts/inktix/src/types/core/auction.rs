@@ -0,0 +1,62 @@
+//! Auction type definitions.
+//!
+//! Premium seat blocks can be sold by auction instead of at the posted dynamic
+//! price. Models the sale mode of a block (`SaleMode`), the auction formats
+//! (`AuctionFormat`), an auction over one block (`Auction`), and the committed
+//! and revealed bids of a sealed-bid auction (`SealedBid`).
+
+use ink::primitives::AccountId;
+
+/// How an auction sells its seats
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub enum AuctionFormat {
+    /// Price falls linearly from `start_price` to `floor_price` between
+    /// `starts_at` and `ends_at`; each buyer pays the price when they buy
+    Dutch { start_price: u128, floor_price: u128, starts_at: u64, ends_at: u64 },
+    /// Bids are committed until `commit_ends_at` and revealed until
+    /// `reveal_ends_at`; every winner pays the same clearing price
+    SealedBid { reserve_price: u128, commit_ends_at: u64, reveal_ends_at: u64 },
+}
+
+/// Sale mode of one block in an event's seat layout
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub enum SaleMode {
+    PostedPrice,
+    Auction { auction_id: u32, format: AuctionFormat },
+}
+
+/// Auction of the seats in one block of an event's layout, settled in DOT
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct Auction {
+    pub id: u32,
+    pub event_id: u32,
+    /// Index of the auctioned block in the event's seat layout
+    pub block_index: u32,
+    pub format: AuctionFormat,
+    pub seats_sold: u32,
+    /// Uniform price paid by sealed-bid winners, set at settlement
+    pub clearing_price: Option<u128>,
+    /// Set once the auction is over; unsold seats return to posted-price sale
+    pub settled: bool,
+}
+
+/// A sealed bid: the commitment and deposit, then the revealed price and quantity
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct SealedBid {
+    pub bidder: AccountId,
+    /// Blake2x256 hash of the SCALE-encoded `(bidder, price, quantity, salt)`
+    pub commitment: [u8; 32],
+    /// Native value held against the bid; must cover `price * quantity`
+    pub deposit: u128,
+    /// Price per seat, once revealed
+    pub price: Option<u128>,
+    pub quantity: u32,
+}
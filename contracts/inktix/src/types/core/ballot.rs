@@ -0,0 +1,80 @@
+//! Ballot sale type definitions.
+//!
+//! Models a ballot phase for an oversubscribed event (`Ballot`, opened with
+//! `BallotTerms`), each fan's registration of interest and resulting
+//! allocation (`BallotEntry`), and the randomness beacon rounds draws mix in
+//! (`BeaconRound`).
+
+use ink::primitives::AccountId;
+
+use super::attestation::AttestationKind;
+
+/// Terms an organizer sets when opening a ballot
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct BallotTerms {
+    /// Tickets allocated by the draw
+    pub tickets: u32,
+    pub max_per_entrant: u32,
+    pub registration_ends_at: u64,
+    /// Milliseconds an allocation can be claimed for once granted
+    pub claim_window: u64,
+    /// Attestation kind an entrant must hold a valid attestation of, if any
+    pub required_attestation: Option<AttestationKind>,
+    /// Blake2x256 hash of the organizer's seed, fixed before registration opens
+    pub seed_commitment: [u8; 32],
+}
+
+/// Ballot selling an event's tickets to registered fans by a verifiable draw
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct Ballot {
+    pub id: u32,
+    pub event_id: u32,
+    /// Account that opened the ballot and posted its bond
+    pub organizer: AccountId,
+    /// Native bond returned when the seed is revealed and forfeited to the entrants if it is withheld
+    pub bond: u128,
+    pub terms: BallotTerms,
+    /// Number of entries
+    pub entries: u32,
+    /// Running Blake2x256 hash folded from every entry, unknown to the organizer when committing
+    pub entropy: [u8; 32],
+    /// Seed the draw shuffled entries with: the revealed seed hashed with the entry entropy and beacon output
+    pub draw_seed: Option<[u8; 32]>,
+    /// Beacon round the draw mixed in
+    pub beacon_round: Option<u32>,
+    /// Position in the drawn entry order of the next fan on the waitlist
+    pub next_in_line: u32,
+    /// Allocated tickets bought so far
+    pub tickets_purchased: u32,
+    /// Set once every allocation is claimed or the waitlist is exhausted; general sale resumes
+    pub closed: bool,
+}
+
+/// A fan's ballot entry; after the draw, entries are stored in drawn order
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct BallotEntry {
+    pub entrant: AccountId,
+    pub requested: u32,
+    /// Block the entry was made in, folded into the ballot's entropy
+    pub entered_block: u32,
+    /// Tickets the entrant has the right to buy
+    pub allocated: u32,
+    pub purchased: u32,
+    /// Time until which the unpurchased allocation can be claimed
+    pub claim_deadline: u64,
+}
+
+/// Output of a randomness beacon round, such as a relayed drand round
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct BeaconRound {
+    pub output: [u8; 32],
+    pub published_at: u64,
+}
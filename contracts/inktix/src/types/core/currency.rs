@@ -1,9 +1,12 @@
 //! Currency type definitions for multi-chain payment support.
 //!
 //! Defines supported currency identifiers (DOT, ACA, aUSD, LDOT, KSM),
-//! exchange rate tracking, and a conversion trait for flexible implementations.
+//! exchange rate tracking and the rate oracle's feeder submissions and limits,
+//! a conversion trait for flexible implementations,
+//! and the `PSP22Error` returned by the token contracts non-DOT currencies are paid through.
 
 use ink::prelude::string::String;
+use ink::primitives::AccountId;
 
 /// Multi-currency support for Acala integration
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -23,12 +26,61 @@ pub enum CurrencyId {
 #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
 pub struct CurrencyRates {
     pub currency: CurrencyId,
+    /// Effective rate: the median of the feeders' fresh submissions
     pub rate_to_dot: u128,
     pub last_updated: u64,
+    /// Purchases in an inactive currency are refused
     pub is_active: bool,
 }
 
+/// An effective rate and when it took effect: `(timestamp, rate)`
+pub type RateHistoryEntry = (u64, u128);
+
+/// A feeder's latest rate report for a currency
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct RateSubmission {
+    pub feeder: AccountId,
+    pub rate: u128,
+    pub submitted_at: u64,
+}
+
+/// Limits the rate oracle enforces on reported rates
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct RateOracleConfig {
+    /// Milliseconds after which an effective rate (or a submission) is stale
+    pub max_rate_age: u64,
+    /// Largest move a submission may make from a fresh effective rate, in basis points
+    pub max_deviation_bps: u32,
+}
+
+impl Default for RateOracleConfig {
+    fn default() -> Self {
+        Self {
+            max_rate_age: 60 * 60 * 1000,
+            max_deviation_bps: 1000,
+        }
+    }
+}
+
+/// Error returned by a PSP22 token contract
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+pub enum PSP22Error {
+    Custom(String),
+    InsufficientBalance,
+    InsufficientAllowance,
+    ZeroRecipientAddress,
+    ZeroSenderAddress,
+    SafeTransferCheckFailed(String),
+}
+
 /// Currency conversion trait for flexible implementation
+///
+/// Implemented for `InkTixStorage` using its stored `currency_rates`.
 pub trait CurrencyConverter {
     fn convert_to_dot(&self, amount: u128, from_currency: CurrencyId) -> Result<u128, String>;
     fn convert_from_dot(&self, dot_amount: u128, to_currency: CurrencyId) -> Result<u128, String>;
@@ -1,13 +1,17 @@
 //! Error types for the InkTix contract.
 //!
 //! Provides a unified error enum and a convenience result type alias used
-//! across utility and validation functions.
+//! across utility and validation functions. Contract messages surface errors
+//! as `String`, so `InkTixError` converts into its human-readable message.
+
+use ink::prelude::string::{String, ToString};
 
 /// Unified error type combining InkTixError and String-based errors
 #[derive(Debug, PartialEq, Eq)]
 #[ink::scale_derive(Encode, Decode, TypeInfo)]
 pub enum InkTixError {
     NotOwner,
+    MissingRole,
     InsufficientPayment,
     InvalidData,
     NotFound,
@@ -25,8 +29,44 @@ pub enum InkTixError {
     PurchaseLimitReached,
     NotTicketOwner,
     NotTransferable,
+    TransferFailed,
     Custom(ink::prelude::string::String),
 }
 
+impl InkTixError {
+    /// Human-readable message for this error
+    pub fn message(&self) -> String {
+        match self {
+            InkTixError::NotOwner => "Only the owner can call this function".to_string(),
+            InkTixError::MissingRole => "Caller does not have the required role".to_string(),
+            InkTixError::InsufficientPayment => "Insufficient payment".to_string(),
+            InkTixError::InvalidData => "Invalid data".to_string(),
+            InkTixError::NotFound => "Not found".to_string(),
+            InkTixError::IdOverflow => "ID overflow".to_string(),
+            InkTixError::InvalidCurrency => "Invalid currency".to_string(),
+            InkTixError::CurrencyConversionFailed => "Currency conversion failed".to_string(),
+            InkTixError::EventNotFound => "Event not found".to_string(),
+            InkTixError::TeamNotFound => "Team not found".to_string(),
+            InkTixError::VenueNotFound => "Venue not found".to_string(),
+            InkTixError::TicketNotFound => "Ticket not found".to_string(),
+            InkTixError::SeasonNotFound => "Season not found".to_string(),
+            InkTixError::ArtistNotFound => "Artist not found".to_string(),
+            InkTixError::NotActive => "Event is not active".to_string(),
+            InkTixError::SoldOut => "Event is sold out".to_string(),
+            InkTixError::PurchaseLimitReached => "Purchase limit reached".to_string(),
+            InkTixError::NotTicketOwner => "Not the ticket owner".to_string(),
+            InkTixError::NotTransferable => "Ticket is not transferable".to_string(),
+            InkTixError::TransferFailed => "Transfer failed".to_string(),
+            InkTixError::Custom(message) => message.clone(),
+        }
+    }
+}
+
+impl From<InkTixError> for String {
+    fn from(error: InkTixError) -> Self {
+        error.message()
+    }
+}
+
 /// Common result type
 pub type InkTixResult<T> = Result<T, InkTixError>;
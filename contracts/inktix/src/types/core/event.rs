@@ -4,6 +4,10 @@
 //! game types, sport types, and event lifecycle statuses.
 
 use ink::prelude::string::String;
+use ink::primitives::AccountId;
+
+/// Home season key: (home team, season)
+pub type HomeSeasonKey = (u32, u32);
 
 #[allow(clippy::cast_possible_truncation)]
 
@@ -70,8 +74,12 @@ pub struct Event {
     pub category: EventCategory,
     pub season_pass_discount: u8,
     pub dynamic_pricing_enabled: bool,
+    /// Rivalry multiplier from the event's effective pricing policy, filled in when the event is read
     pub rivalry_multiplier: u32,
     pub revenue_generated: u128,
+    /// Account that created the event and receives its settled proceeds
+    pub organizer: AccountId,
+    pub status: EventStatus,
 }
 
 /// Event status
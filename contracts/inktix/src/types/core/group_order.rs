@@ -0,0 +1,67 @@
+//! Group order type definitions.
+//!
+//! Models a group or corporate purchase of a contiguous seat block paid for by
+//! several members (`GroupOrder`, `GroupMember`) and the per-event discount
+//! tiers that price it (`GroupDiscountTier`).
+
+use super::hold::HeldSeat;
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+/// Member invitation: (account, seats the member takes)
+pub type GroupInvite = (AccountId, u32);
+
+/// Discount applied to group orders of at least `min_seats` seats
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct GroupDiscountTier {
+    pub min_seats: u32,
+    /// Discount in basis points (10000 = 100%)
+    pub discount_bps: u32,
+}
+
+/// Lifecycle of a group order
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub enum GroupOrderStatus {
+    /// Seats are reserved while members pay their shares
+    Funding,
+    /// Fully funded; every member holds their tickets
+    Completed,
+    /// Cancelled or expired; seats released and contributions refunded
+    Cancelled,
+}
+
+/// A member's part of a group order
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct GroupMember {
+    pub account: AccountId,
+    pub seats: u32,
+    /// DOT the member owes for their seats at the group price
+    pub share: u128,
+    pub paid: u128,
+}
+
+/// Group purchase of a contiguous seat block, completed once every member has paid
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct GroupOrder {
+    pub id: u32,
+    pub event_id: u32,
+    pub organizer: AccountId,
+    /// Reserved seats at their discounted prices, assigned to members in order
+    pub seats: Vec<HeldSeat>,
+    pub members: Vec<GroupMember>,
+    pub discount_bps: u32,
+    /// Time by which every member must have paid
+    pub deadline: u64,
+    /// Sale window the order was placed in, replaced on completion by the window it completed in
+    pub sale_window: Option<u32>,
+    pub status: GroupOrderStatus,
+    pub ticket_ids: Vec<u64>,
+}
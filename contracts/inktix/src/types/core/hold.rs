@@ -0,0 +1,70 @@
+//! Seat hold type definitions.
+//!
+//! Models temporary seat reservations taken during checkout: how long a hold
+//! lasts (`HoldDuration`/`HoldExpiry`), the seats and prices it locks
+//! (`HeldSeat`), and the hold itself (`SeatHold`). Also defines `PriceLock`,
+//! a quote whose price is locked without reserving the seat.
+
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+/// Requested hold length, measured in blocks or milliseconds
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub enum HoldDuration {
+    Blocks(u32),
+    Milliseconds(u64),
+}
+
+/// Point at which a hold lapses
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub enum HoldExpiry {
+    AtBlock(u32),
+    AtTimestamp(u64),
+}
+
+/// A seat locked by a hold, with the price quoted when the hold was taken
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct HeldSeat {
+    pub seat: super::seat::Seat,
+    pub seat_number: u32,
+    pub price: u128,
+    pub performance_multiplier: u32,
+}
+
+/// Temporary reservation of seats while the holder completes payment
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct SeatHold {
+    pub id: u64,
+    pub event_id: u32,
+    pub holder: AccountId,
+    pub seats: Vec<HeldSeat>,
+    pub created_at: u64,
+    pub expiry: HoldExpiry,
+    /// Cleared when the hold is confirmed or released
+    pub is_active: bool,
+}
+
+/// A price quote locked for one seat, redeemable by its holder until it expires
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct PriceLock {
+    pub id: u64,
+    pub event_id: u32,
+    pub holder: AccountId,
+    /// The resolved seat and its locked DOT price
+    pub priced: HeldSeat,
+    /// Version of the pricing policy the price was quoted under
+    pub policy_version: u32,
+    pub locked_at: u64,
+    pub expires_at: u64,
+    pub redeemed: bool,
+}
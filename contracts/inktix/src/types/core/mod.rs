@@ -1,31 +1,54 @@
 //! Core domain types shared across all InkTix features.
 //!
 //! Contains fundamental data structures for events, tickets, venues, currencies,
-//! seating, anti-scalping, NFTs, cross-chain operations, and user profiles.
+//! seating, seat holds, group orders, auctions, ballots, waitlists, sale windows, pricing policies, purchase limits, access roles, identity attestations, anti-scalping, NFTs and
+//! their PSP34 interface, cross-chain operations, and user profiles.
 
+pub mod access;
 #[allow(clippy::cast_possible_truncation)]
 pub mod anti_scalping;
+pub mod attestation;
+pub mod auction;
+pub mod ballot;
 pub mod cross_chain;
 pub mod currency;
 pub mod error;
 pub mod event;
+pub mod group_order;
+pub mod hold;
+pub mod pricing_policy;
+pub mod purchase_limit;
+pub mod sale_window;
 pub mod search;
 pub mod seat;
 pub mod ticket;
 pub mod user;
 pub mod venue;
+pub mod waitlist;
 pub mod nft;
+pub mod psp34;
 pub mod xcm;
 
+pub use access::*;
 pub use anti_scalping::*;
+pub use attestation::*;
+pub use auction::*;
+pub use ballot::*;
 pub use cross_chain::*;
 pub use currency::*;
 pub use error::*;
 pub use event::*;
+pub use group_order::*;
+pub use hold::*;
+pub use pricing_policy::*;
+pub use purchase_limit::*;
+pub use sale_window::*;
 pub use search::*;
 pub use seat::*;
 pub use ticket::*;
 pub use user::*;
 pub use venue::*;
+pub use waitlist::*;
 pub use nft::*;
+pub use psp34::*;
 pub use xcm::*;
@@ -1,7 +1,8 @@
 //! NFT type definitions for ticket tokenization.
 //!
 //! Defines `TicketNft` for on-chain ticket representation, `AttendanceToken`
-//! for proof-of-attendance, and `TicketVerification` for QR-based entry validation.
+//! for proof-of-attendance, `TicketVerification` for QR-based entry validation,
+//! and `CheckInSignature` for holder-signed check-in challenges.
 
 use ink::prelude::string::String;
 use ink::primitives::AccountId;
@@ -28,6 +29,8 @@ pub struct TicketNft {
     /// Hash of ticket data for QR verification
     pub verification_hash: [u8; 32],
     pub is_used: bool,
+    /// Cleared when the underlying ticket is refunded
+    pub is_valid: bool,
 }
 
 /// Proof of attendance token - awarded after event
@@ -42,11 +45,23 @@ pub struct AttendanceToken {
     pub attended_at: u64,
 }
 
+/// Holder's signature over a check-in challenge, by key type
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+pub enum CheckInSignature {
+    /// Signed with the sr25519 key behind the holder's account
+    Sr25519([u8; 64]),
+    /// Recoverable secp256k1 signature from an ECDSA account
+    Ecdsa([u8; 65]),
+}
+
 /// Result of ticket verification
 #[derive(Debug, PartialEq, Eq, Clone)]
 #[ink::scale_derive(Encode, Decode, TypeInfo)]
 pub struct TicketVerification {
     pub is_valid: bool,
+    /// Why the ticket is not valid for entry, when `is_valid` is false
+    pub invalid_reason: Option<String>,
     pub is_used: bool,
     pub owner: AccountId,
     pub event_id: u32,
@@ -0,0 +1,57 @@
+//! Pricing policy type definitions.
+//!
+//! A `PricingPolicy` holds the curves the dynamic pricing engine applies: demand
+//! and time bands, the seat-type table, the price floor and cap, and rivalry
+//! multipliers keyed by team pairs. Policies can be set per venue and per
+//! event; the default policy reproduces the engine's original curves.
+//!
+//! All multipliers are in basis points (10000 = 1.0x).
+
+use ink::prelude::vec;
+use ink::prelude::vec::Vec;
+
+use super::seat::SeatType;
+
+/// Pricing curves applied by the dynamic pricing engine
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct PricingPolicy {
+    /// Assigned when the policy is stored; 0 for the built-in default
+    pub version: u32,
+    /// `(min_sold_percentage, multiplier)` bands, thresholds ascending from 0
+    pub demand_bands: Vec<(u32, u32)>,
+    /// `(min_days_before_event, multiplier)` bands, thresholds ascending from 0
+    pub time_bands: Vec<(u64, u32)>,
+    /// Multiplier per seat type; seat types not listed price at 1.0x
+    pub seat_multipliers: Vec<(SeatType, u32)>,
+    /// Lowest price as a share of the base price
+    pub floor_bps: u32,
+    /// Highest price as a share of the base price
+    pub cap_bps: u32,
+    /// `(team_id, team_id, multiplier)` for games between the two teams, in either order
+    pub rivalries: Vec<(u32, u32, u32)>,
+}
+
+impl Default for PricingPolicy {
+    fn default() -> Self {
+        Self {
+            version: 0,
+            demand_bands: vec![(0, 8000), (20, 9000), (40, 10000), (60, 11000), (75, 12500), (90, 15000), (95, 18000)],
+            time_bands: vec![(0, 13000), (1, 11500), (7, 10000), (30, 9000)],
+            seat_multipliers: vec![
+                (SeatType::GeneralAdmission, 10000),
+                (SeatType::Reserved, 11000),
+                (SeatType::PremiumReserved, 13000),
+                (SeatType::Club, 15000),
+                (SeatType::Suite, 20000),
+                (SeatType::FieldLevel, 18000),
+                (SeatType::Courtside, 25000),
+                (SeatType::StudentSection, 7000),
+            ],
+            floor_bps: 5000,
+            cap_bps: 30000,
+            rivalries: Vec::new(),
+        }
+    }
+}
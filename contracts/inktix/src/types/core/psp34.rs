@@ -0,0 +1,88 @@
+//! PSP34 (non-fungible token) type and interface definitions.
+//!
+//! Defines the PSP34 token `Id` and `PSP34Error`, plus the `PSP34`,
+//! `PSP34Metadata` and `PSP34Enumerable` trait definitions the contract
+//! implements for its `TicketNft` collection, so Substrate wallets and
+//! marketplaces can discover tickets through the standard selectors.
+
+use ink::prelude::string::String;
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+/// PSP34 token identifier
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub enum Id {
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    Bytes(Vec<u8>),
+}
+
+/// PSP34 error codes
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+pub enum PSP34Error {
+    /// Contract-specific failure, e.g. a refunded ticket or a soulbound token
+    Custom(String),
+    SelfApprove,
+    NotApproved,
+    TokenExists,
+    TokenNotExists,
+    SafeTransferCheckFailed(String),
+}
+
+/// PSP34 core interface
+#[ink::trait_definition]
+pub trait PSP34 {
+    /// Identifier of the collection
+    #[ink(message)]
+    fn collection_id(&self) -> Id;
+
+    /// Number of tokens held by `owner`
+    #[ink(message)]
+    fn balance_of(&self, owner: AccountId) -> u32;
+
+    /// Current owner of a token
+    #[ink(message)]
+    fn owner_of(&self, id: Id) -> Option<AccountId>;
+
+    /// Whether `operator` may move `owner`'s token `id`, or all their tokens when `id` is `None`
+    #[ink(message)]
+    fn allowance(&self, owner: AccountId, operator: AccountId, id: Option<Id>) -> bool;
+
+    /// Approve or revoke `operator` for one of the caller's tokens, or for all of them
+    #[ink(message)]
+    fn approve(&mut self, operator: AccountId, id: Option<Id>, approved: bool) -> Result<(), PSP34Error>;
+
+    /// Transfer a token owned by, or approved to, the caller
+    #[ink(message)]
+    fn transfer(&mut self, to: AccountId, id: Id, data: Vec<u8>) -> Result<(), PSP34Error>;
+
+    /// Number of tokens ever minted
+    #[ink(message)]
+    fn total_supply(&self) -> u128;
+}
+
+/// PSP34 metadata extension
+#[ink::trait_definition]
+pub trait PSP34Metadata {
+    /// Attribute `key` of a token, or of the collection when `id` is the collection ID
+    #[ink(message)]
+    fn get_attribute(&self, id: Id, key: Vec<u8>) -> Option<Vec<u8>>;
+}
+
+/// PSP34 enumerable extension
+#[ink::trait_definition]
+pub trait PSP34Enumerable {
+    /// The `index`-th token held by `owner`
+    #[ink(message)]
+    fn owners_token_by_index(&self, owner: AccountId, index: u128) -> Result<Id, PSP34Error>;
+
+    /// The `index`-th token of the collection
+    #[ink(message)]
+    fn token_by_index(&self, index: u128) -> Result<Id, PSP34Error>;
+}
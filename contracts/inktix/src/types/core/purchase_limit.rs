@@ -0,0 +1,16 @@
+//! Purchase limit type definitions.
+//!
+//! Defines what a ticket holding limit applies to (`PurchaseLimitScope`).
+
+/// What a purchase limit counts holdings across
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub enum PurchaseLimitScope {
+    /// One event
+    Event(u32),
+    /// Every game of a season
+    Season(u32),
+    /// Every home game of a team
+    Team(u32),
+}
@@ -0,0 +1,42 @@
+//! Staged on-sale window type definitions.
+//!
+//! Models the windows an event's tickets go on sale in (`SaleWindow`) and who
+//! each window admits (`SaleWindowAccess`): fan-club presales by loyalty tier,
+//! season-pass holders, access-code holders, and the general on-sale.
+
+use crate::types::sports::loyalty::{LoyaltyTier, TeamLoyaltyTier};
+use ink::primitives::AccountId;
+
+/// Access code storage key: (event_id, Blake2x256 hash of the SCALE-encoded `(code, account)`)
+pub type AccessCodeKey = (u32, [u8; 32]);
+/// Sale window purchase counter key: (event_id, window index, account)
+pub type SaleWindowPurchaseKey = (u32, u32, AccountId);
+
+/// Who can buy during a sale window
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub enum SaleWindowAccess {
+    /// Fans with at least this platform loyalty tier
+    LoyaltyTier(LoyaltyTier),
+    /// Fans with at least this loyalty tier with a team
+    TeamLoyaltyTier { team_id: u32, tier: TeamLoyaltyTier },
+    /// Holders of an active season pass for a team
+    SeasonPassHolder { team_id: u32 },
+    /// Fans who redeemed one of the event's access codes
+    AccessCode,
+    /// Everyone
+    General,
+}
+
+/// A period during which tickets are sold to the fans a window admits
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct SaleWindow {
+    pub access: SaleWindowAccess,
+    pub starts_at: u64,
+    pub ends_at: u64,
+    /// Most tickets one account can buy in this window (0 = no limit)
+    pub per_user_limit: u32,
+}
@@ -1,7 +1,8 @@
 //! Seat and access-level type definitions.
 //!
 //! Models seating categories (general admission through courtside), access levels,
-//! and the composite `Seat` struct used during ticket purchase.
+//! the composite `Seat` struct used during ticket purchase, and the seat layout
+//! and availability types backing per-event seat inventory.
 
 use ink::prelude::string::String;
 
@@ -43,3 +44,46 @@ pub struct Seat {
     pub access_level: AccessLevel,
     pub price_multiplier: u32,
 }
+
+/// Contiguous run of seats in one row, used to define venue and event seat layouts
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct SeatBlock {
+    pub section: String,
+    pub row: String,
+    pub first_seat: u32,
+    pub seat_count: u32,
+    pub seat_type: SeatType,
+    pub access_level: AccessLevel,
+    /// Venue `VenuePricingTier` whose multiplier prices these seats
+    pub pricing_tier_id: Option<u32>,
+}
+
+/// Seat inventory key: (event_id, section, row, seat_number)
+pub type SeatKey = (u32, String, String, u32);
+
+/// Availability state of a seat in an event's inventory
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub enum SeatStatus {
+    Available,
+    Held { hold_id: u64 },
+    GroupReserved { order_id: u32 },
+    /// Assigned to a season pass covering the event
+    SeasonPassReserved { pass_id: u32 },
+    Sold { ticket_id: u64 },
+}
+
+/// Seat map entry returned for rendering an event's availability
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+pub struct SeatAvailability {
+    pub section: String,
+    pub row: String,
+    pub seat_number: u32,
+    pub seat_type: SeatType,
+    pub access_level: AccessLevel,
+    pub status: SeatStatus,
+}
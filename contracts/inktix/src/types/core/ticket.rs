@@ -17,6 +17,8 @@ pub struct Ticket {
     pub purchase_price: u128,
     pub purchase_currency: super::currency::CurrencyId,
     pub purchase_date: u64,
+    /// When the current owner acquired the ticket, by purchase, gift or resale
+    pub acquired_at: u64,
     pub seat_number: u32,
     pub transferable: bool,
     pub section: String,
@@ -29,4 +31,6 @@ pub struct Ticket {
     pub dynamic_price_paid: u128,
     pub performance_multiplier_applied: u32,
     pub dot_equivalent_paid: u128,
+    /// Set once the purchase price has been refunded; the ticket is no longer valid
+    pub refunded: bool,
 }
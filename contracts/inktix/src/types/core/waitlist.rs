@@ -0,0 +1,33 @@
+//! Waitlist type definitions.
+//!
+//! Models a fan queued for a sold-out event (`WaitlistEntry`) and the released
+//! tickets or returned resale listing offered to them for a limited time
+//! (`WaitlistOffer`).
+
+use ink::primitives::AccountId;
+
+/// Tickets offered to a waitlisted fan, claimable until `expires_at`
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct WaitlistOffer {
+    pub quantity: u32,
+    pub expires_at: u64,
+    /// Returned resale listing on offer, or `None` for released inventory
+    pub listing_id: Option<u64>,
+}
+
+/// A fan queued for a sold-out event
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct WaitlistEntry {
+    pub account: AccountId,
+    pub quantity: u32,
+    /// Highest DOT price per ticket the fan will pay
+    pub max_price: u128,
+    /// Native value held in escrow and put towards the tickets when claimed
+    pub deposit: u128,
+    pub joined_at: u64,
+    pub offer: Option<WaitlistOffer>,
+}
@@ -0,0 +1,33 @@
+//! Game result oracle type definitions.
+//!
+//! Contains `GameResultReport`, a reporter's final score for a sports event,
+//! and `GameResult`, the score a quorum of reporters agreed on.
+
+use ink::primitives::AccountId;
+
+/// A result reporter's final score for a sports event
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct GameResultReport {
+    pub reporter: AccountId,
+    pub home_score: u32,
+    pub away_score: u32,
+    pub reported_at: u64,
+}
+
+/// Final score of a sports event, agreed on by a quorum of reporters
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct GameResult {
+    pub event_id: u32,
+    pub season_id: u32,
+    pub home_team_id: u32,
+    pub away_team_id: u32,
+    pub home_score: u32,
+    pub away_score: u32,
+    /// Number of reports that agreed on this score
+    pub confirmations: u32,
+    pub finalized_at: u64,
+}
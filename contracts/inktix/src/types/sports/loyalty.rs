@@ -125,6 +125,9 @@ pub struct TeamAttendance {
     pub attendance_date: u64, pub points_earned: u32, pub streak_bonus: u32, pub total_streak: u32,
 }
 
+/// Attendance key: (fan, team, event)
+pub type AttendanceKey = (AccountId, u32, u32);
+
 /// Team performance-based loyalty rewards
 #[derive(Debug, PartialEq, Eq, Clone)]
 #[ink::scale_derive(Encode, Decode, TypeInfo)]
@@ -1,10 +1,12 @@
 //! Sports-specific type definitions.
 //!
 //! Contains data structures for teams, seasons, season passes, fantasy sports,
-//! loyalty programs, analytics, and dynamic pricing related to sporting events.
+//! loyalty programs, analytics, game results, and dynamic pricing related to
+//! sporting events.
 
 pub mod analytics;
 pub mod fantasy_sports;
+pub mod game_result;
 pub mod loyalty;
 pub mod pricing;
 pub mod season;
@@ -13,6 +15,7 @@ pub mod team;
 
 pub use analytics::*;
 pub use fantasy_sports::*;
+pub use game_result::*;
 pub use loyalty::*;
 pub use pricing::*;
 pub use season::*;
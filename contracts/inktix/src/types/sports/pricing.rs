@@ -1,17 +1,24 @@
 //! Dynamic pricing type definitions.
 //!
-//! Contains `TeamPerformance` for win/loss tracking, `PriceQuote` for presenting
+//! Contains `TeamPerformance` for per-season records derived from game results, `PriceQuote` for presenting
 //! calculated prices to users, and `PricingMultiplier` for factor-based price adjustments.
 
-/// Team performance for dynamic pricing
+/// A team's record for one season, derived from finalized game results
+///
+/// `win_percentage` is in tenths of a percent (a tie counts as half a win),
+/// `streak` is positive for consecutive wins and negative for consecutive
+/// losses, and `playoff_probability` is a percentage estimated from the win
+/// percentage. `performance_rank` is the team's position among the season's
+/// teams, 1 being the best record.
 #[derive(Debug, PartialEq, Eq, Clone)]
 #[ink::scale_derive(Encode, Decode, TypeInfo)]
 #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
 pub struct TeamPerformance {
-    pub team_id: u32, pub season_id: u32, pub wins: u32, pub losses: u32,
+    pub team_id: u32, pub season_id: u32, pub wins: u32, pub losses: u32, pub ties: u32,
     pub win_percentage: u32, pub streak: i32, pub playoff_probability: u32,
     pub last_updated: u64, pub performance_rank: u32,
     pub home_record_wins: u32, pub home_record_losses: u32,
+    pub points_scored_total: u32, pub points_allowed_total: u32,
     pub points_scored_avg: u32, pub points_allowed_avg: u32,
 }
 
@@ -28,6 +35,8 @@ pub struct PriceQuote {
     pub seat_multiplier: u32,
     pub rivalry_multiplier: u32,
     pub season_pass_discount: u8,
+    /// Version of the pricing policy applied, 0 for the built-in default
+    pub policy_version: u32,
 }
 
 /// Pricing multiplier based on various factors
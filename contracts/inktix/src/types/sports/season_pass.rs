@@ -1,12 +1,19 @@
 //! Season pass type definitions.
 //!
 //! Covers season pass types, statuses, benefits, packages, usage tracking,
-//! renewal options, and analytics for recurring ticket holders.
+//! renewal options, and analytics for recurring ticket holders. A package
+//! covers a list of games: every game for full, half-season and playoff
+//! passes, or any N of them for a flex pass. Each pass holds a fixed seat that
+//! is reserved for it in every home game of its season the pass covers.
 
+use crate::types::core::seat::{AccessLevel, Seat, SeatType};
 use ink::primitives::AccountId;
 use ink::prelude::string::String;
 use ink::prelude::vec::Vec;
 
+/// Season seat assignment key: (home team, season, section, row, seat number)
+pub type SeasonSeatKey = (u32, u32, String, String, u32);
+
 /// Season pass types
 #[derive(Debug, PartialEq, Eq, Clone)]
 #[ink::scale_derive(Encode, Decode, TypeInfo)]
@@ -18,6 +25,8 @@ pub enum SeasonPassType {
     Premium,
     Corporate,
     Alumni,
+    /// Any N games of the package's game list
+    Flex(u32),
 }
 
 /// Season pass status
@@ -30,6 +39,8 @@ pub enum SeasonPassStatus {
     Expired,
     Cancelled,
     PendingActivation,
+    /// Renewed into a later season's pass, which took over its loyalty and staking
+    Renewed,
 }
 
 /// Season pass benefits and perks
@@ -74,6 +85,24 @@ pub struct SeasonPass {
     pub last_staking_update: u64,
     pub transferable: bool,
     pub transfer_cooldown_until: u64,
+    /// Package the pass was bought, renewed or upgraded from
+    pub package_id: u32,
+    /// Loyalty points earned at games, carried over on renewal
+    pub loyalty_points: u32,
+    /// Seat reserved for the pass at each covered game, kept on renewal
+    pub seat: Seat,
+}
+
+/// Loyalty points a pass earns per game before its benefits' multiplier
+pub const SEASON_PASS_GAME_POINTS: u32 = 10;
+
+#[allow(clippy::arithmetic_side_effects)]
+#[allow(clippy::cast_possible_truncation)]
+impl SeasonPass {
+    /// Loyalty points the pass earns per game, scaled by its loyalty multiplier (basis points)
+    pub fn points_per_game(&self) -> u32 {
+        (SEASON_PASS_GAME_POINTS as u64 * self.benefits.loyalty_multiplier as u64 / 10_000) as u32
+    }
 }
 
 /// Season pass package configuration
@@ -82,11 +111,17 @@ pub struct SeasonPass {
 #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
 pub struct SeasonPassPackage {
     pub id: u32,
+    /// Account that created the package and withdraws its proceeds
+    pub organizer: AccountId,
     pub team_id: u32,
     pub season_id: u32,
     pub package_name: String,
     pub pass_type: SeasonPassType,
+    /// Events the package covers; empty for a flex pass valid at any of the season's games
+    pub game_ids: Vec<u32>,
     pub total_games: u32,
+    /// Games the package's proceeds are released over, fixed when it is created
+    pub proceeds_games: u32,
     pub base_price: u128,
     pub currency: crate::types::core::currency::CurrencyId,
     pub max_quantity: u32,
@@ -100,7 +135,7 @@ pub struct SeasonPassPackage {
     pub sale_end_date: u64,
 }
 
-/// Season pass usage tracking
+/// A game claimed with a season pass
 #[derive(Debug, PartialEq, Eq, Clone)]
 #[ink::scale_derive(Encode, Decode, TypeInfo)]
 #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
@@ -122,11 +157,19 @@ pub struct SeasonPassUsage {
 pub struct RenewalOption {
     pub current_pass_id: u32,
     pub renewal_package_id: u32,
-    pub discount_percentage: u8,
-    pub early_bird_discount: u8,
-    pub early_bird_deadline: u64,
-    pub loyalty_tier_discount: u8,
-    pub staking_bonus: u32,
+    /// Price the holder renews at: the lower of what they paid and the renewal package's price
+    pub locked_price: u128,
+    /// End of the priority window; the package's general sale starts here
+    pub window_ends_at: u64,
+}
+
+/// Priority renewal window offered to every holder of a package
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct PackageRenewal {
+    pub renewal_package_id: u32,
+    pub window_ends_at: u64,
 }
 
 /// Season pass analytics
@@ -185,6 +228,16 @@ impl Default for SeasonPass {
             last_staking_update: 0,
             transferable: true,
             transfer_cooldown_until: 0,
+            package_id: 0,
+            loyalty_points: 0,
+            seat: Seat {
+                section: String::new(),
+                row: String::new(),
+                seat_number: String::new(),
+                seat_type: SeatType::GeneralAdmission,
+                access_level: AccessLevel::Standard,
+                price_multiplier: 0,
+            },
         }
     }
 }
@@ -193,11 +246,14 @@ impl Default for SeasonPassPackage {
     fn default() -> Self {
         Self {
             id: 0,
+            organizer: AccountId::from([0u8; 32]),
             team_id: 0,
             season_id: 0,
             package_name: String::new(),
             pass_type: SeasonPassType::FullSeason,
+            game_ids: Vec::new(),
             total_games: 0,
+            proceeds_games: 0,
             base_price: 0,
             currency: crate::types::core::currency::CurrencyId::DOT,
             max_quantity: 0,
@@ -1,15 +1,24 @@
 //! Currency conversion utilities.
 //!
 //! Provides bidirectional conversion between DOT and other supported currencies
-//! using pre-stored exchange rate arrays.
+//! using pre-stored exchange rate arrays, indexed by `CurrencyId` discriminant.
+//! Rates are the DOT value of one unit of the currency, scaled by `RATE_PRECISION`.
+//! Conversions that overflow fail with `CurrencyConversionFailed` rather than saturating.
+//! Amounts converted from DOT round up, so a charge in another currency is never
+//! worth less than its DOT price.
 //!
 //! # Functions
 //! - `convert_to_dot_equivalent` -- converts a currency amount to its DOT equivalent
 //! - `convert_from_dot_equivalent` -- converts a DOT amount to a target currency
+//! - `mul_div` -- overflow-checked multiply-then-divide, rounded down
+//! - `mul_div_ceil` -- overflow-checked multiply-then-divide, rounded up
 
 use crate::types::core::currency::CurrencyId;
 use crate::types::core::error::{InkTixError, InkTixResult};
 
+/// Fixed-point scale of exchange rates (a rate of `RATE_PRECISION` is 1:1 with DOT)
+pub const RATE_PRECISION: u128 = 1_000_000_000_000_000_000;
+
 /// Convert amount to DOT equivalent
 pub fn convert_to_dot_equivalent(
     amount: u128,
@@ -23,7 +32,7 @@ pub fn convert_to_dot_equivalent(
             if rate == 0 {
                 return Err(InkTixError::InvalidCurrency);
             }
-            let dot_amount = amount.saturating_mul(rate) / 1_000_000_000_000;
+            let dot_amount = mul_div(amount, rate, RATE_PRECISION).ok_or(InkTixError::CurrencyConversionFailed)?;
             if dot_amount == 0 && amount > 0 {
                 return Err(InkTixError::CurrencyConversionFailed);
             }
@@ -32,7 +41,7 @@ pub fn convert_to_dot_equivalent(
     }
 }
 
-/// Convert from DOT equivalent to target currency
+/// Convert from DOT equivalent to target currency, rounding up
 pub fn convert_from_dot_equivalent(
     dot_amount: u128,
     target_currency: CurrencyId,
@@ -45,9 +54,27 @@ pub fn convert_from_dot_equivalent(
             if rate == 0 {
                 return Err(InkTixError::InvalidCurrency);
             }
-            #[allow(clippy::arithmetic_side_effects)]
-            let target_amount = dot_amount.saturating_mul(1_000_000_000_000) / rate;
-            Ok(target_amount)
+            mul_div_ceil(dot_amount, RATE_PRECISION, rate).ok_or(InkTixError::CurrencyConversionFailed)
         }
     }
 }
+
+/// `amount * numerator / denominator` rounded down, or `None` if the result or an intermediate overflows
+///
+/// When the product overflows, `amount` is split into whole multiples of the
+/// denominator and a remainder, so the result stays exact without 256-bit math.
+pub fn mul_div(amount: u128, numerator: u128, denominator: u128) -> Option<u128> {
+    if let Some(product) = amount.checked_mul(numerator) {
+        return product.checked_div(denominator);
+    }
+    let whole = amount.checked_div(denominator)?.checked_mul(numerator)?;
+    let remainder = amount.checked_rem(denominator)?.checked_mul(numerator)?.checked_div(denominator)?;
+    whole.checked_add(remainder)
+}
+
+/// `amount * numerator / denominator` rounded up, or `None` if the result or an intermediate overflows
+pub fn mul_div_ceil(amount: u128, numerator: u128, denominator: u128) -> Option<u128> {
+    let rounded_down = mul_div(amount, numerator, denominator)?;
+    let remainder = amount.checked_rem(denominator)?.checked_mul(numerator)?.checked_rem(denominator)?;
+    if remainder == 0 { Some(rounded_down) } else { rounded_down.checked_add(1) }
+}
@@ -124,13 +124,26 @@ export class ContractProvider implements InkTixSDK {
   private async tx<T>(
     method: string,
     ...args: any[]
+  ): Promise<ContractCallResult<T>> {
+    return this.payableTx(method, undefined, ...args);
+  }
+
+  /**
+   * Send a transaction transferring `value` (in plancks) with the call.
+   * The dry-run carries the same value, so payable messages are checked
+   * against the payment they will actually receive.
+   */
+  private async payableTx<T>(
+    method: string,
+    value: bigint | undefined,
+    ...args: any[]
   ): Promise<ContractCallResult<T>> {
     try {
       // Dry-run to estimate gas and check for errors
       const { result: dryResult, output, gasRequired } =
         await this.contract.query[method](
           this.callerAddress,
-          { gasLimit: QUERY_GAS_LIMIT, storageDepositLimit: QUERY_STORAGE_DEPOSIT },
+          { gasLimit: QUERY_GAS_LIMIT, storageDepositLimit: QUERY_STORAGE_DEPOSIT, value },
           ...args
         );
 
@@ -147,7 +160,7 @@ export class ContractProvider implements InkTixSDK {
       // Send actual transaction with estimated gas
       return new Promise((resolve) => {
         this.contract.tx[method](
-          { gasLimit: gasRequired, storageDepositLimit: QUERY_STORAGE_DEPOSIT },
+          { gasLimit: gasRequired, storageDepositLimit: QUERY_STORAGE_DEPOSIT, value },
           ...args
         )
           .signAndSend(
@@ -312,13 +325,21 @@ export class ContractProvider implements InkTixSDK {
     row = "1"
   ): Promise<ContractCallResult<number>> {
     const seat = {
-      seat_number: seatNumber,
+      seat_number: String(seatNumber),
       section,
       row,
       seat_type: { GeneralAdmission: null },
       access_level: { Standard: null },
+      price_multiplier: 0,
     };
-    return this.tx("purchase_ticket", eventId, seat, { DOT: null });
+    // purchase_ticket is payable: send the seat's current DOT price with the call
+    const quote = await this.query<PriceQuote>("get_price_quote", eventId, seat, false);
+    if (!quote.success || !quote.data) {
+      return { success: false, error: quote.error ?? "Could not price the ticket" };
+    }
+    const price = BigInt(quote.data.finalPrice);
+    // No locked quote and no max price
+    return this.payableTx("purchase_ticket", price, eventId, seat, { DOT: null }, null, null);
   }
 
   async getTicket(ticketId: number): Promise<ContractCallResult<Ticket>> {